@@ -0,0 +1,65 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// 言語エラー
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("言語({0})に対応していません。jaまたはenを指定してください。")]
+pub struct LanguageError(pub String);
+
+/// コード表の名前の言語
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// 日本語（コード表の名前）
+    #[default]
+    Ja,
+    /// 英語
+    En,
+}
+
+impl Language {
+    /// 言語を表現するISO 639-1の言語コードを返す。
+    ///
+    /// # 戻り値
+    ///
+    /// 言語コード
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ja => "ja",
+            Self::En => "en",
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = LanguageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "ja" => Ok(Self::Ja),
+            "en" => Ok(Self::En),
+            _ => Err(LanguageError(s.to_string())),
+        }
+    }
+}
+
+impl Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_from_str_ok() {
+        assert_eq!("ja".parse::<Language>(), Ok(Language::Ja));
+        assert_eq!("EN".parse::<Language>(), Ok(Language::En));
+        assert!("fr".parse::<Language>().is_err());
+        assert_eq!(Language::En.to_string(), "en");
+    }
+}
@@ -0,0 +1,12 @@
+//! 交通事故統計情報のコード表
+//!
+//! コード表の行を登録するマイグレーションのSQLから構築したコード表と、
+//! コード表ごとのコードを表現する型を提供する。
+
+pub mod language;
+pub mod tables;
+pub mod types;
+
+pub use language::Language;
+pub use tables::CodeTables;
+pub use types::*;
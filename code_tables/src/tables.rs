@@ -0,0 +1,515 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::language::Language;
+
+/// コード表の行を登録するマイグレーションのSQL
+const CODE_TABLE_ROWS_SQL: &str =
+    include_str!("../../migrations/20231104060354_insert_code_table_rows.up.sql");
+
+/// コード表に英語の名前を登録するマイグレーションのSQL
+const CODE_TABLE_ENGLISH_NAMES_SQL: &str =
+    include_str!("../../migrations/20231202090000_add_code_table_english_names.up.sql");
+
+/// 高速票のコード表を作成して、行を登録するマイグレーションのSQL
+const HIGHWAY_CODE_TABLES_SQL: &str =
+    include_str!("../../migrations/20231209090000_create_highway_code_tables.up.sql");
+
+/// コード表
+///
+/// データベースに接続せずに本票などのコードを検査するため、コード表の行を登録する
+/// マイグレーションのSQLからコード表を構築する。
+#[derive(Debug, Clone, Default)]
+pub struct CodeTables {
+    /// テーブル名と、コードと名前の対応を記録したハッシュマップの対応を記録したハッシュマップ
+    ///
+    /// 警察署テーブルのコードは、都道府県コードと警察署コードを連結した文字列とする。
+    tables: HashMap<String, HashMap<String, String>>,
+    /// 本票の都道府県コードとJIS規格の都道府県コードの対応を記録したハッシュマップ
+    prefectures: HashMap<String, String>,
+    /// 路線コードの範囲（下限、上限及び路線名）
+    routes: Vec<(String, String, String)>,
+    /// テーブル名と、コードと英語の名前の対応を記録したハッシュマップの対応を記録したハッシュマップ
+    ///
+    /// 路線テーブルのコードは、路線コードの範囲の下限とする。
+    english_names: HashMap<String, HashMap<String, String>>,
+}
+
+impl CodeTables {
+    /// マイグレーションのSQLから構築したコード表を返す。
+    ///
+    /// コード表は最初に呼び出されたときに構築して、以後は構築したコード表を返す。
+    ///
+    /// # 戻り値
+    ///
+    /// コード表
+    pub fn global() -> &'static Self {
+        static CODE_TABLES: OnceLock<CodeTables> = OnceLock::new();
+
+        CODE_TABLES.get_or_init(|| {
+            Self::load().expect("コード表の行を登録するマイグレーションのSQLを解析できません。")
+        })
+    }
+
+    /// マイグレーションのSQLからコード表を構築する。
+    ///
+    /// # 戻り値
+    ///
+    /// コード表
+    pub fn load() -> anyhow::Result<Self> {
+        let mut code_tables = Self::parse(CODE_TABLE_ROWS_SQL)?;
+        code_tables.parse_english_names(CODE_TABLE_ENGLISH_NAMES_SQL)?;
+        code_tables.parse_rows(HIGHWAY_CODE_TABLES_SQL)?;
+
+        Ok(code_tables)
+    }
+
+    /// INSERT文を解析してコード表を構築する。
+    ///
+    /// # 引数
+    ///
+    /// * `sql` - コード表の行を登録するINSERT文
+    ///
+    /// # 戻り値
+    ///
+    /// コード表
+    pub fn parse(sql: &str) -> anyhow::Result<Self> {
+        let mut code_tables = Self::default();
+        code_tables.parse_rows(sql)?;
+
+        Ok(code_tables)
+    }
+
+    /// INSERT文を解析して、コード表に行を登録する。
+    ///
+    /// 英語の名前（`name_en`列）を持つ行は、英語の名前も登録する。
+    ///
+    /// # 引数
+    ///
+    /// * `sql` - コード表の行を登録するINSERT文
+    ///
+    /// # 戻り値
+    ///
+    /// `()`
+    pub fn parse_rows(&mut self, sql: &str) -> anyhow::Result<()> {
+        for (line_index, line) in sql.lines().enumerate() {
+            let line = line.trim();
+            if !line.starts_with("INSERT INTO ") {
+                continue;
+            }
+            let (table, row) = parse_insert_statement(line).ok_or(anyhow::anyhow!(
+                "{}行目: コード表のINSERT文を解析できません。",
+                line_index + 1
+            ))?;
+            let (code, name) = match table {
+                "prefectures" => {
+                    self.prefectures
+                        .insert(row_value(&row, "code")?, row_value(&row, "jis_code")?);
+                    (row_value(&row, "code")?, row_value(&row, "name")?)
+                }
+                "police_stations" => (
+                    format!(
+                        "{}{}",
+                        row_value(&row, "prefecture_code")?,
+                        row_value(&row, "police_station_code")?
+                    ),
+                    row_value(&row, "police_station_name")?,
+                ),
+                "cities" => (
+                    row_value(&row, "city_jis_code")?,
+                    row_value(&row, "city_name")?,
+                ),
+                // 路線テーブルはコードの範囲を記録しているため、範囲を別に記録
+                "routes" => {
+                    self.routes.push((
+                        row_value(&row, "lower_code")?,
+                        row_value(&row, "upper_code")?,
+                        row_value(&row, "name")?,
+                    ));
+                    continue;
+                }
+                _ => (row_value(&row, "code")?, row_value(&row, "name")?),
+            };
+            if let Some(name_en) = row.get("name_en") {
+                self.english_names
+                    .entry(table.to_string())
+                    .or_default()
+                    .insert(code.clone(), name_en.clone());
+            }
+            self.tables
+                .entry(table.to_string())
+                .or_default()
+                .insert(code, name);
+        }
+
+        Ok(())
+    }
+
+    /// UPDATE文を解析して、コード表に英語の名前を登録する。
+    ///
+    /// # 引数
+    ///
+    /// * `sql` - コード表の行に英語の名前を設定するUPDATE文
+    ///
+    /// # 戻り値
+    ///
+    /// `()`
+    pub fn parse_english_names(&mut self, sql: &str) -> anyhow::Result<()> {
+        for (line_index, line) in sql.lines().enumerate() {
+            let line = line.trim();
+            if !line.starts_with("UPDATE ") {
+                continue;
+            }
+            let (table, column, name, code) =
+                parse_update_statement(line).ok_or(anyhow::anyhow!(
+                    "{}行目: コード表のUPDATE文を解析できません。",
+                    line_index + 1
+                ))?;
+            if column != "name_en" {
+                anyhow::bail!(
+                    "{}行目: コード表の{}列は英語の名前ではありません。",
+                    line_index + 1,
+                    column
+                );
+            }
+            self.english_names
+                .entry(table.to_string())
+                .or_default()
+                .insert(code, name);
+        }
+
+        Ok(())
+    }
+
+    /// コード表にコードが存在するか確認する。
+    ///
+    /// # 引数
+    ///
+    /// * `table` - コード表のテーブル名
+    /// * `code` - コード
+    ///
+    /// # 戻り値
+    ///
+    /// コードが存在する場合は`true`
+    pub fn contains(&self, table: &str, code: &str) -> bool {
+        self.tables
+            .get(table)
+            .map(|codes| codes.contains_key(code))
+            .unwrap_or(false)
+    }
+
+    /// コードに対応する名前を返す。
+    ///
+    /// 路線テーブル（`routes`）の場合は、路線コードを含む範囲の路線名を返す。
+    ///
+    /// # 引数
+    ///
+    /// * `table` - コード表のテーブル名
+    /// * `code` - コード
+    ///
+    /// # 戻り値
+    ///
+    /// コードに対応する名前、コードが存在しない場合は`None`
+    pub fn name(&self, table: &str, code: &str) -> Option<&str> {
+        if table == "routes" {
+            return self.route_name(code);
+        }
+
+        self.tables
+            .get(table)
+            .and_then(|codes| codes.get(code))
+            .map(String::as_str)
+    }
+
+    /// コードに対応する言語の名前を返す。
+    ///
+    /// 英語の名前が登録されていないコード（市区町村や警察署など）は、日本語の名前を返す。
+    ///
+    /// # 引数
+    ///
+    /// * `table` - コード表のテーブル名
+    /// * `code` - コード
+    /// * `language` - 名前の言語
+    ///
+    /// # 戻り値
+    ///
+    /// コードに対応する名前、コードが存在しない場合は`None`
+    pub fn label(&self, table: &str, code: &str, language: Language) -> Option<&str> {
+        match language {
+            Language::Ja => self.name(table, code),
+            Language::En => self
+                .english_name(table, code)
+                .or_else(|| self.name(table, code)),
+        }
+    }
+
+    /// コードに対応する英語の名前を返す。
+    ///
+    /// # 引数
+    ///
+    /// * `table` - コード表のテーブル名
+    /// * `code` - コード
+    ///
+    /// # 戻り値
+    ///
+    /// コードに対応する英語の名前、英語の名前が登録されていない場合は`None`
+    pub fn english_name(&self, table: &str, code: &str) -> Option<&str> {
+        let code = if table == "routes" {
+            self.route_range(code).map(|(lower, _, _)| lower.as_str())?
+        } else {
+            code
+        };
+
+        self.english_names
+            .get(table)
+            .and_then(|codes| codes.get(code))
+            .map(String::as_str)
+    }
+
+    /// 路線テーブルに路線コードを含む範囲が存在するか確認する。
+    ///
+    /// # 引数
+    ///
+    /// * `route_code` - 4桁の路線コード
+    ///
+    /// # 戻り値
+    ///
+    /// 路線コードを含む範囲が存在する場合は`true`
+    pub fn contains_route(&self, route_code: &str) -> bool {
+        self.route_name(route_code).is_some()
+    }
+
+    /// 路線コードを含む範囲の路線名を返す。
+    ///
+    /// # 引数
+    ///
+    /// * `route_code` - 4桁の路線コード
+    ///
+    /// # 戻り値
+    ///
+    /// 路線名、路線コードを含む範囲が存在しない場合は`None`
+    pub fn route_name(&self, route_code: &str) -> Option<&str> {
+        self.route_range(route_code)
+            .map(|(_, _, name)| name.as_str())
+    }
+
+    /// 路線コードを含む範囲を返す。
+    fn route_range(&self, route_code: &str) -> Option<&(String, String, String)> {
+        if route_code.len() != 4 || !route_code.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        self.routes
+            .iter()
+            .find(|(lower, upper, _)| lower.as_str() <= route_code && route_code <= upper.as_str())
+    }
+
+    /// JIS規格の都道府県コードに対応する本票の都道府県コードが存在するか確認する。
+    ///
+    /// # 引数
+    ///
+    /// * `jis_code` - JIS規格の都道府県コード
+    ///
+    /// # 戻り値
+    ///
+    /// JIS規格の都道府県コードに対応する本票の都道府県コードが存在する場合は`true`
+    pub fn contains_prefecture_jis_code(&self, jis_code: &str) -> bool {
+        self.prefectures.values().any(|code| code == jis_code)
+    }
+
+    /// 本票の都道府県コードとJIS規格の都道府県コードの対応を記録したハッシュマップを返す。
+    ///
+    /// # 戻り値
+    ///
+    /// 本票の都道府県コードとJIS規格の都道府県コードの対応を記録したハッシュマップ
+    pub fn prefectures(&self) -> &HashMap<String, String> {
+        &self.prefectures
+    }
+}
+
+/// `INSERT INTO table (column, ...) VALUES ('value', ...);`形式のINSERT文を解析する。
+fn parse_insert_statement(line: &str) -> Option<(&str, HashMap<&str, String>)> {
+    let rest = line.strip_prefix("INSERT INTO ")?;
+    let (table, rest) = rest.split_once(" (")?;
+    let (columns, rest) = rest.split_once(") VALUES (")?;
+    let values = parse_string_literals(rest.strip_suffix(");")?)?;
+    let columns = columns.split(',').map(str::trim).collect::<Vec<_>>();
+    if columns.len() != values.len() {
+        return None;
+    }
+
+    Some((table, columns.into_iter().zip(values).collect()))
+}
+
+/// `UPDATE table SET column = 'value' WHERE key = 'code';`形式のUPDATE文を解析する。
+///
+/// テーブル名、更新する列名、更新する値及びコードを返す。
+fn parse_update_statement(line: &str) -> Option<(&str, &str, String, String)> {
+    let rest = line.strip_prefix("UPDATE ")?;
+    let (table, rest) = rest.split_once(" SET ")?;
+    let (column, rest) = rest.split_once(" = ")?;
+    let (value, rest) = rest.rsplit_once(" WHERE ")?;
+    let (_, code) = rest.strip_suffix(';')?.split_once(" = ")?;
+    let mut values = parse_string_literals(&format!("{}, {}", value, code))?;
+    if values.len() != 2 {
+        return None;
+    }
+    let code = values.pop()?;
+    let value = values.pop()?;
+
+    Some((table, column.trim(), value, code))
+}
+
+/// カンマで区切られたSQLの文字列リテラルを解析する。
+fn parse_string_literals(values: &str) -> Option<Vec<String>> {
+    let mut literals = Vec::new();
+    let mut chars = values.chars().peekable();
+    loop {
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+        if chars.next()? != '\'' {
+            return None;
+        }
+        let mut literal = String::new();
+        loop {
+            match chars.next()? {
+                '\'' if chars.peek() == Some(&'\'') => {
+                    chars.next();
+                    literal.push('\'');
+                }
+                '\'' => break,
+                c => literal.push(c),
+            }
+        }
+        literals.push(literal);
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+        match chars.next() {
+            Some(',') => continue,
+            None => return Some(literals),
+            Some(_) => return None,
+        }
+    }
+}
+
+fn row_value(row: &HashMap<&str, String>, column: &str) -> anyhow::Result<String> {
+    row.get(column)
+        .cloned()
+        .ok_or(anyhow::anyhow!("コード表に{}列がありません。", column))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_insert_statement_ok() {
+        let (table, row) = parse_insert_statement(
+            "INSERT INTO prefectures (code, name, jis_code) VALUES ('10', '北海道（札幌方面）', '01');",
+        )
+        .unwrap();
+
+        assert_eq!(table, "prefectures");
+        assert_eq!(row["code"], "10");
+        assert_eq!(row["name"], "北海道（札幌方面）");
+        assert_eq!(row["jis_code"], "01");
+    }
+
+    #[test]
+    fn parse_update_statement_ok() {
+        let (table, column, value, code) = parse_update_statement(
+            "UPDATE routes SET name_en = 'Road''s name WHERE' WHERE lower_code = '0001';",
+        )
+        .unwrap();
+
+        assert_eq!(table, "routes");
+        assert_eq!(column, "name_en");
+        assert_eq!(value, "Road's name WHERE");
+        assert_eq!(code, "0001");
+        assert!(parse_update_statement("UPDATE routes SET name_en = 'a';").is_none());
+    }
+
+    #[test]
+    fn parse_string_literals_ok() {
+        assert_eq!(
+            parse_string_literals("'a', 'b,c', 'd''e'"),
+            Some(vec![
+                String::from("a"),
+                String::from("b,c"),
+                String::from("d'e")
+            ])
+        );
+        assert_eq!(parse_string_literals("'a', b"), None);
+        assert_eq!(parse_string_literals("'a"), None);
+    }
+
+    #[test]
+    fn load_ok() {
+        let code_tables = CodeTables::load().unwrap();
+
+        assert_eq!(code_tables.prefectures()["30"], "13");
+        assert!(code_tables.contains("police_stations", "10101"));
+        assert!(code_tables.contains("cities", "01101"));
+        assert!(code_tables.contains("weathers", "1"));
+        assert!(!code_tables.contains("weathers", "99"));
+        assert!(!code_tables.contains("unknown_table", "1"));
+        assert_eq!(code_tables.name("weathers", "1"), Some("晴"));
+        assert_eq!(code_tables.name("police_stations", "10101"), Some("中央"));
+        assert_eq!(code_tables.name("weathers", "99"), None);
+        assert!(code_tables.contains_route("4001"));
+        assert_eq!(code_tables.route_name("4001"), Some("高速自動車国道"));
+        assert_eq!(code_tables.name("routes", "4001"), Some("高速自動車国道"));
+        assert!(!code_tables.contains_route("ABCD"));
+        assert_eq!(code_tables.name("lanes", "5"), Some("路肩"));
+        assert!(!code_tables.contains("lanes", "9"));
+        assert_eq!(
+            code_tables.label("road_structures", "2", Language::En),
+            Some("Ramp")
+        );
+    }
+
+    #[test]
+    fn label_ok() {
+        let code_tables = CodeTables::load().unwrap();
+
+        assert_eq!(code_tables.label("weathers", "1", Language::Ja), Some("晴"));
+        assert_eq!(
+            code_tables.label("weathers", "1", Language::En),
+            Some("Fine")
+        );
+        assert_eq!(
+            code_tables.label("prefectures", "30", Language::En),
+            Some("Tokyo")
+        );
+        assert_eq!(
+            code_tables.label("routes", "4001", Language::En),
+            Some("National expressway")
+        );
+        // 英語の名前がないコードは日本語の名前
+        assert_eq!(
+            code_tables.label("police_stations", "10101", Language::En),
+            Some("中央")
+        );
+        assert_eq!(code_tables.label("weathers", "99", Language::En), None);
+        assert_eq!(code_tables.english_name("cities", "01101"), None);
+
+        // 市区町村と警察署以外のコードは、すべて英語の名前を持つ
+        for (table, codes) in &code_tables.tables {
+            if table == "cities" || table == "police_stations" {
+                continue;
+            }
+            for code in codes.keys() {
+                assert!(
+                    code_tables.english_name(table, code).is_some(),
+                    "{table} {code}"
+                );
+            }
+        }
+        for (lower, upper, _) in &code_tables.routes {
+            assert!(code_tables.english_name("routes", lower).is_some());
+            assert!(code_tables.english_name("routes", upper).is_some());
+        }
+    }
+}
@@ -0,0 +1,540 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::language::Language;
+use crate::tables::CodeTables;
+
+/// コードエラー
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{table}テーブルにコード({code})が存在しません。")]
+pub struct CodeError {
+    /// コード表のテーブル名
+    pub table: &'static str,
+    /// コード
+    pub code: String,
+}
+
+/// コード表のコードを表現する型を定義する。
+///
+/// 定義した型は、コード表に存在するコードのみから構築できる。
+/// データベースから読み込む場合は、外部参照制約でコードが保証されているため検査しない。
+///
+/// `@define`はデータベースとの変換（`sqlx::Type`）とコード表の名前を返すメソッドを
+/// 除いた型を定義して、`@names`はコード表の名前を返すメソッドを定義する。
+macro_rules! code_type {
+    ($(#[$meta:meta])* $name:ident, $table:literal) => {
+        code_type!($(#[$meta])* $name, $table, |code_tables: &CodeTables, code: &str| {
+            code_tables.contains($table, code)
+        });
+    };
+    ($(#[$meta:meta])* $name:ident, $table:literal, $is_valid:expr) => {
+        code_type!(
+            @define
+            $(#[$meta])*
+            #[derive(sqlx::Type)]
+            #[sqlx(transparent)]
+            $name,
+            $table,
+            $is_valid
+        );
+        code_type!(@names $name, $table);
+    };
+    (@names $name:ident, $table:literal) => {
+        impl $name {
+            /// コードに対応するコード表の名前を返す。
+            ///
+            /// # 戻り値
+            ///
+            /// コード表の名前
+            pub fn name(&self) -> Option<&'static str> {
+                CodeTables::global().name($table, &self.0)
+            }
+
+            /// コードに対応するコード表の言語の名前を返す。
+            ///
+            /// # 引数
+            ///
+            /// * `language` - 名前の言語
+            ///
+            /// # 戻り値
+            ///
+            /// コード表の名前
+            pub fn label(&self, language: Language) -> Option<&'static str> {
+                CodeTables::global().label($table, &self.0, language)
+            }
+        }
+    };
+    (@define $(#[$meta:meta])* $name:ident, $table:literal, $is_valid:expr) => {
+        $(#[$meta])*
+        #[derive(
+            Debug,
+            Clone,
+            PartialEq,
+            Eq,
+            Hash,
+            PartialOrd,
+            Ord,
+            serde::Serialize,
+            serde::Deserialize,
+        )]
+        #[serde(try_from = "String", into = "String")]
+        pub struct $name(String);
+
+        impl $name {
+            /// コード表のテーブル名
+            pub const TABLE: &'static str = $table;
+
+            /// コードを文字列で返す。
+            ///
+            /// # 戻り値
+            ///
+            /// コード
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = CodeError;
+
+            fn try_from(code: String) -> Result<Self, Self::Error> {
+                let is_valid = $is_valid;
+                if is_valid(CodeTables::global(), code.as_str()) {
+                    Ok(Self(code))
+                } else {
+                    Err(CodeError {
+                        table: $table,
+                        code,
+                    })
+                }
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = CodeError;
+
+            fn try_from(code: &str) -> Result<Self, Self::Error> {
+                Self::try_from(code.to_string())
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = CodeError;
+
+            fn from_str(code: &str) -> Result<Self, Self::Err> {
+                Self::try_from(code)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(code: $name) -> Self {
+                code.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+code_type!(
+    /// 都道府県コード（本票の都道府県コード）
+    PrefectureCode,
+    "prefectures"
+);
+code_type!(
+    @define
+    /// 都道府県コード（JIS規格）
+    ///
+    /// 北海道の方面のように、複数の本票の都道府県コードが同じJIS規格の都道府県コードに
+    /// 対応するため、コード表の名前は返さない。
+    #[derive(sqlx::Type)]
+    #[sqlx(transparent)]
+    PrefectureJisCode,
+    "prefectures",
+    |code_tables: &CodeTables, code: &str| code_tables.contains_prefecture_jis_code(code)
+);
+code_type!(
+    @define
+    /// 警察署コード（本票の都道府県コードと警察署コードを連結したコード）
+    ///
+    /// 交通事故テーブルは都道府県コードと警察署コードを別の列に記録しているため、
+    /// データベースから読み込む場合は連結したコードの桁数を検査する。
+    PoliceStationCode,
+    "police_stations",
+    |code_tables: &CodeTables, code: &str| code_tables.contains("police_stations", code)
+);
+code_type!(@names PoliceStationCode, "police_stations");
+code_type!(
+    /// 事故内容コード
+    AccidentDetailCode,
+    "accident_details"
+);
+code_type!(
+    /// 路線区分コード
+    RouteClassCode,
+    "route_classes"
+);
+code_type!(
+    /// 市区町村コード
+    CityCode,
+    "cities"
+);
+code_type!(
+    /// 昼夜コード
+    DayNightCode,
+    "day_nights"
+);
+code_type!(
+    /// 天候コード
+    WeatherCode,
+    "weathers"
+);
+code_type!(
+    /// 地形（地区）コード
+    DistrictCode,
+    "districts"
+);
+code_type!(
+    /// 路面状態コード
+    SurfaceConditionCode,
+    "surface_conditions"
+);
+code_type!(
+    /// 道路形状コード
+    RoadModelCode,
+    "road_models"
+);
+code_type!(
+    /// 信号機コード
+    TrafficSignalCode,
+    "traffic_signals"
+);
+code_type!(
+    /// 一時停止規制標識コード
+    StopRegulationSignCode,
+    "stop_regulation_signs"
+);
+code_type!(
+    /// 一時停止規制表示コード
+    StopRegulationDisplayCode,
+    "stop_regulation_displays"
+);
+code_type!(
+    /// 車道幅員コード
+    RoadWidthCode,
+    "road_widths"
+);
+code_type!(
+    /// 道路線形コード
+    RoadAlignmentCode,
+    "road_alignments"
+);
+code_type!(
+    /// 衝突地点コード
+    CollisionPointCode,
+    "collision_points"
+);
+code_type!(
+    /// ゾーン規制コード
+    ZoneRegulationCode,
+    "zone_regulations"
+);
+code_type!(
+    /// 中央分離帯施設コード
+    CentralSeparationCode,
+    "central_separations"
+);
+code_type!(
+    /// 歩車道区分コード
+    RoadSegmentationCode,
+    "road_segmentations"
+);
+code_type!(
+    /// 事故類型コード
+    AccidentTypeCode,
+    "accident_types"
+);
+code_type!(
+    /// 年齢コード
+    AgeCode,
+    "ages"
+);
+code_type!(
+    /// 当事者種別コード
+    PartyCode,
+    "parties"
+);
+code_type!(
+    /// 用途コード
+    PurposeCode,
+    "purposes"
+);
+code_type!(
+    /// 車両形状コード
+    VehicleTypeCode,
+    "vehicle_types"
+);
+code_type!(
+    /// オートマチック車コード
+    AutomaticCode,
+    "automatics"
+);
+code_type!(
+    /// サポカーコード
+    SupportCarCode,
+    "support_cars"
+);
+code_type!(
+    /// 速度規制（指定のみ）コード
+    SpeedRegulationCode,
+    "speed_regulations"
+);
+code_type!(
+    /// 車両の損壊程度コード
+    VehicleDamageCode,
+    "vehicle_damages"
+);
+code_type!(
+    /// エアバッグの装備コード
+    AirbagCode,
+    "airbags"
+);
+code_type!(
+    /// サイドエアバッグの装備コード
+    SideAirbagCode,
+    "side_airbags"
+);
+code_type!(
+    /// 人身損傷程度コード
+    InjuryCode,
+    "injuries"
+);
+code_type!(
+    /// 曜日コード
+    WeekCode,
+    "weeks"
+);
+code_type!(
+    /// 祝日コード
+    HolidayCode,
+    "holidays"
+);
+code_type!(
+    /// 運転練習の方法コード
+    DrivingPracticeCode,
+    "driving_practices"
+);
+code_type!(
+    /// 乗車別コード
+    RidingTypeCode,
+    "riding_types"
+);
+code_type!(
+    /// 乗車等の区分コード
+    RidingClassCode,
+    "riding_classes"
+);
+code_type!(
+    /// 上下線コード
+    UpDownCode,
+    "up_downs"
+);
+code_type!(
+    /// 道路構造コード
+    RoadStructureCode,
+    "road_structures"
+);
+code_type!(
+    /// 車線コード
+    LaneCode,
+    "lanes"
+);
+code_type!(
+    /// 路線コード
+    ///
+    /// 路線テーブルはコードの範囲を記録しているため、範囲に含まれる4桁のコードを有効とする。
+    RouteCode,
+    "routes",
+    |code_tables: &CodeTables, code: &str| code_tables.contains_route(code)
+);
+
+impl PoliceStationCode {
+    /// 本票の都道府県コードと警察署コードから警察署コードを構築する。
+    ///
+    /// # 引数
+    ///
+    /// * `prefecture_code` - 都道府県コード
+    /// * `police_station_code` - 本票の警察署コード（3桁）
+    ///
+    /// # 戻り値
+    ///
+    /// 警察署コード
+    pub fn new(
+        prefecture_code: &PrefectureCode,
+        police_station_code: &str,
+    ) -> Result<Self, CodeError> {
+        Self::try_from(format!("{}{}", prefecture_code, police_station_code))
+    }
+
+    /// 都道府県コードを除いた、本票の警察署コード（3桁）を返す。
+    ///
+    /// # 戻り値
+    ///
+    /// 本票の警察署コード
+    pub fn station_code(&self) -> &str {
+        self.0.get(2..).unwrap_or_default()
+    }
+
+    /// データベースから読み込んだ警察署コードの桁数を検査する。
+    ///
+    /// # 引数
+    ///
+    /// * `code` - データベースから読み込んだ警察署コード
+    ///
+    /// # 戻り値
+    ///
+    /// 警察署コード
+    fn from_database(code: String) -> Result<Self, String> {
+        if code.len() == POLICE_STATION_CODE_LEN && code.bytes().all(|b| b.is_ascii_digit()) {
+            Ok(Self(code))
+        } else {
+            Err(format!(
+                "警察署コード({})が、都道府県コードと警察署コードを連結した{}桁のコードではありません。",
+                code, POLICE_STATION_CODE_LEN
+            ))
+        }
+    }
+}
+
+/// 警察署コード（本票の都道府県コードと警察署コードを連結したコード）の桁数
+const POLICE_STATION_CODE_LEN: usize = 5;
+
+impl<DB: sqlx::Database> sqlx::Type<DB> for PoliceStationCode
+where
+    String: sqlx::Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as sqlx::Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as sqlx::Type<DB>>::compatible(ty)
+    }
+}
+
+impl sqlx::postgres::PgHasArrayType for PoliceStationCode {
+    fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+        <String as sqlx::postgres::PgHasArrayType>::array_type_info()
+    }
+}
+
+impl<'q, DB: sqlx::Database> sqlx::Encode<'q, DB> for PoliceStationCode
+where
+    String: sqlx::Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as sqlx::database::HasArguments<'q>>::ArgumentBuffer,
+    ) -> sqlx::encode::IsNull {
+        <String as sqlx::Encode<'q, DB>>::encode_by_ref(&self.0, buf)
+    }
+}
+
+impl<'r, DB: sqlx::Database> sqlx::Decode<'r, DB> for PoliceStationCode
+where
+    String: sqlx::Decode<'r, DB>,
+{
+    fn decode(
+        value: <DB as sqlx::database::HasValueRef<'r>>::ValueRef,
+    ) -> Result<Self, sqlx::error::BoxDynError> {
+        let code = <String as sqlx::Decode<'r, DB>>::decode(value)?;
+
+        Ok(Self::from_database(code)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_type_ok() {
+        let weather = WeatherCode::try_from("1").unwrap();
+        assert_eq!(weather, "1");
+        assert_eq!(weather.as_str(), "1");
+        assert_eq!(weather.name(), Some("晴"));
+        assert_eq!(weather.to_string(), "1");
+        assert_eq!(WeatherCode::TABLE, "weathers");
+        assert_eq!(
+            PoliceStationCode::try_from("10101").unwrap().name(),
+            Some("中央")
+        );
+        let prefecture = PrefectureCode::try_from("10").unwrap();
+        let police_station = PoliceStationCode::new(&prefecture, "101").unwrap();
+        assert_eq!(police_station, "10101");
+        assert_eq!(police_station.station_code(), "101");
+        assert!(PoliceStationCode::new(&prefecture, "999").is_err());
+        assert_eq!(PrefectureJisCode::try_from("01").unwrap(), "01");
+        assert_eq!(
+            RouteCode::try_from("4001").unwrap().name(),
+            Some("高速自動車国道")
+        );
+        assert!("0500".parse::<RouteCode>().is_ok());
+    }
+
+    #[test]
+    fn code_type_fail() {
+        assert_eq!(
+            WeatherCode::try_from("99"),
+            Err(CodeError {
+                table: "weathers",
+                code: String::from("99")
+            })
+        );
+        // 1桁のコードを記録するコード表に2桁のコードを指定
+        assert!(WeatherCode::try_from("01").is_err());
+        assert!(RouteCode::try_from("ABCD").is_err());
+        assert!(RouteCode::try_from("40010").is_err());
+        // 本票の都道府県コード（沖縄）はJIS規格の都道府県コードではない
+        assert!(PrefectureJisCode::try_from("97").is_err());
+        assert!(PrefectureJisCode::try_from("48").is_err());
+        // データベースから読み込んだ警察署コードが連結したコードでない場合
+        assert!(PoliceStationCode::from_database(String::from("059")).is_err());
+        assert_eq!(
+            PoliceStationCode::from_database(String::from("10059"))
+                .unwrap()
+                .station_code(),
+            "059"
+        );
+    }
+
+    #[test]
+    fn code_type_serde() {
+        let weather: WeatherCode = serde_json::from_str(r#""2""#).unwrap();
+        assert_eq!(weather, "2");
+        assert_eq!(serde_json::to_string(&weather).unwrap(), r#""2""#);
+        assert!(serde_json::from_str::<WeatherCode>(r#""9""#).is_err());
+    }
+}
@@ -1,6 +1,10 @@
 use geo_types::Geometry;
 use geozero::wkb;
 
+pub mod srid;
+
+pub use srid::Srid;
+
 pub type GeometryF64 = Geometry<f64>;
 
 pub type WkbGeometryF64 = wkb::Decode<GeometryF64>;
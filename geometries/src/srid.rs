@@ -0,0 +1,247 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// SRIDエラー
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("SRID({0})に対応していません。4326、3857、6668または6669から6687を指定してください。")]
+pub struct SridError(pub String);
+
+/// 平面直角座標系の系番号（ローマ数字）
+const PLANE_RECTANGULAR_ZONE_NUMERALS: [&str; 19] = [
+    "I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X", "XI", "XII", "XIII", "XIV", "XV",
+    "XVI", "XVII", "XVIII", "XIX",
+];
+
+/// 平面直角座標系の原点（緯度の度、経度の度、経度の分）
+const PLANE_RECTANGULAR_ORIGINS: [(u32, u32, u32); 19] = [
+    (33, 129, 30),
+    (33, 131, 0),
+    (36, 132, 10),
+    (33, 133, 30),
+    (36, 134, 20),
+    (36, 136, 0),
+    (36, 137, 10),
+    (36, 138, 30),
+    (36, 139, 50),
+    (40, 140, 50),
+    (44, 140, 15),
+    (44, 142, 15),
+    (44, 144, 15),
+    (26, 142, 0),
+    (26, 127, 30),
+    (26, 124, 0),
+    (26, 131, 0),
+    (20, 136, 0),
+    (26, 154, 0),
+];
+
+/// 平面直角座標系の縮尺係数
+const PLANE_RECTANGULAR_SCALE_FACTOR: f64 = 0.9999;
+
+/// JGD2011の地理座標系を表現するWKT
+const JGD2011_WKT: &str = r#"GEOGCS["JGD2011",DATUM["Japanese_Geodetic_Datum_2011",SPHEROID["GRS 1980",6378137,298.257222101,AUTHORITY["EPSG","7019"]],AUTHORITY["EPSG","1128"]],PRIMEM["Greenwich",0,AUTHORITY["EPSG","8901"]],UNIT["degree",0.0174532925199433,AUTHORITY["EPSG","9122"]],AUTHORITY["EPSG","6668"]]"#;
+
+/// WGS84の地理座標系を表現するWKT
+const WGS84_WKT: &str = r#"GEOGCS["WGS 84",DATUM["WGS_1984",SPHEROID["WGS 84",6378137,298.257223563,AUTHORITY["EPSG","7030"]],AUTHORITY["EPSG","6326"]],PRIMEM["Greenwich",0,AUTHORITY["EPSG","8901"]],UNIT["degree",0.0174532925199433,AUTHORITY["EPSG","9122"]],AUTHORITY["EPSG","4326"]]"#;
+
+/// JGD2011の地理座標系を表現するESRI形式のWKT
+const JGD2011_ESRI_WKT: &str = r#"GEOGCS["GCS_JGD_2011",DATUM["D_JGD_2011",SPHEROID["GRS_1980",6378137.0,298.257222101]],PRIMEM["Greenwich",0.0],UNIT["Degree",0.0174532925199433]]"#;
+
+/// WGS84の地理座標系を表現するESRI形式のWKT
+const WGS84_ESRI_WKT: &str = r#"GEOGCS["GCS_WGS_1984",DATUM["D_WGS_1984",SPHEROID["WGS_1984",6378137.0,298.257223563]],PRIMEM["Greenwich",0.0],UNIT["Degree",0.0174532925199433]]"#;
+
+/// 座標の空間参照系を表現するSRID（EPSGコード）
+///
+/// 交通事故の発生場所はJGD2011（EPSG:6668）で記録しているため、出力する座標を変換できる
+/// WGS84（EPSG:4326）、WGS84 / 擬似メルカトル（EPSG:3857）及びJGD2011の平面直角座標系
+/// （EPSG:6669からEPSG:6687）に限定する。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Srid(u32);
+
+impl Srid {
+    /// 日本測地系2011（交通事故の発生場所を記録する座標系）
+    pub const JGD2011: Self = Self(6668);
+
+    /// WGS84
+    pub const WGS84: Self = Self(4326);
+
+    /// WGS84 / 擬似メルカトル
+    pub const WEB_MERCATOR: Self = Self(3857);
+
+    /// SRIDを返す。
+    ///
+    /// # 戻り値
+    ///
+    /// SRID
+    pub fn code(&self) -> u32 {
+        self.0
+    }
+
+    /// 経度と緯度で座標を表現する地理座標系であるかを返す。
+    ///
+    /// # 戻り値
+    ///
+    /// 地理座標系の場合は`true`、投影座標系の場合は`false`
+    pub fn is_geographic(&self) -> bool {
+        *self == Self::JGD2011 || *self == Self::WGS84
+    }
+
+    /// 空間参照系の名前を返す。
+    ///
+    /// # 戻り値
+    ///
+    /// 空間参照系の名前
+    pub fn name(&self) -> String {
+        match self.plane_rectangular_zone() {
+            Some(zone) => format!(
+                "JGD2011 / Japan Plane Rectangular CS {}",
+                PLANE_RECTANGULAR_ZONE_NUMERALS[zone]
+            ),
+            None if *self == Self::WGS84 => String::from("WGS 84"),
+            None if *self == Self::WEB_MERCATOR => String::from("WGS 84 / Pseudo-Mercator"),
+            None => String::from("JGD2011"),
+        }
+    }
+
+    /// 空間参照系を表現するOGC形式のWKTを返す。
+    ///
+    /// # 戻り値
+    ///
+    /// WKT
+    pub fn wkt(&self) -> String {
+        if let Some(zone) = self.plane_rectangular_zone() {
+            let (latitude, longitude) = plane_rectangular_origin(zone);
+            return format!(
+                r#"PROJCS["{}",{},PROJECTION["Transverse_Mercator"],PARAMETER["latitude_of_origin",{}],PARAMETER["central_meridian",{}],PARAMETER["scale_factor",{}],PARAMETER["false_easting",0],PARAMETER["false_northing",0],UNIT["metre",1,AUTHORITY["EPSG","9001"]],AUTHORITY["EPSG","{}"]]"#,
+                self.name(),
+                JGD2011_WKT,
+                latitude,
+                longitude,
+                PLANE_RECTANGULAR_SCALE_FACTOR,
+                self.0
+            );
+        }
+        match *self {
+            Self::WGS84 => WGS84_WKT.to_string(),
+            Self::WEB_MERCATOR => format!(
+                r#"PROJCS["WGS 84 / Pseudo-Mercator",{},PROJECTION["Mercator_1SP"],PARAMETER["central_meridian",0],PARAMETER["scale_factor",1],PARAMETER["false_easting",0],PARAMETER["false_northing",0],UNIT["metre",1,AUTHORITY["EPSG","9001"]],EXTENSION["PROJ4","+proj=merc +a=6378137 +b=6378137 +lat_ts=0 +lon_0=0 +x_0=0 +y_0=0 +k=1 +units=m +nadgrids=@null +wktext +no_defs"],AUTHORITY["EPSG","3857"]]"#,
+                WGS84_WKT
+            ),
+            _ => JGD2011_WKT.to_string(),
+        }
+    }
+
+    /// 空間参照系を表現するESRI形式のWKTを返す。
+    ///
+    /// シェープファイルの`.prj`ファイルに出力する。
+    ///
+    /// # 戻り値
+    ///
+    /// ESRI形式のWKT
+    pub fn esri_wkt(&self) -> String {
+        if let Some(zone) = self.plane_rectangular_zone() {
+            let (latitude, longitude) = plane_rectangular_origin(zone);
+            return format!(
+                r#"PROJCS["JGD_2011_Japan_Zone_{}",{},PROJECTION["Transverse_Mercator"],PARAMETER["False_Easting",0.0],PARAMETER["False_Northing",0.0],PARAMETER["Central_Meridian",{:?}],PARAMETER["Scale_Factor",{:?}],PARAMETER["Latitude_Of_Origin",{:?}],UNIT["Meter",1.0]]"#,
+                zone + 1,
+                JGD2011_ESRI_WKT,
+                longitude,
+                PLANE_RECTANGULAR_SCALE_FACTOR,
+                latitude
+            );
+        }
+        match *self {
+            Self::WGS84 => WGS84_ESRI_WKT.to_string(),
+            Self::WEB_MERCATOR => format!(
+                r#"PROJCS["WGS_1984_Web_Mercator_Auxiliary_Sphere",{},PROJECTION["Mercator_Auxiliary_Sphere"],PARAMETER["False_Easting",0.0],PARAMETER["False_Northing",0.0],PARAMETER["Central_Meridian",0.0],PARAMETER["Standard_Parallel_1",0.0],PARAMETER["Auxiliary_Sphere_Type",0.0],UNIT["Meter",1.0]]"#,
+                WGS84_ESRI_WKT
+            ),
+            _ => JGD2011_ESRI_WKT.to_string(),
+        }
+    }
+
+    /// 平面直角座標系の系番号から1を引いた値を返す。
+    fn plane_rectangular_zone(&self) -> Option<usize> {
+        (6669..=6687)
+            .contains(&self.0)
+            .then(|| (self.0 - 6669) as usize)
+    }
+}
+
+impl Default for Srid {
+    fn default() -> Self {
+        Self::JGD2011
+    }
+}
+
+impl TryFrom<u32> for Srid {
+    type Error = SridError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            4326 | 3857 | 6668..=6687 => Ok(Self(value)),
+            _ => Err(SridError(value.to_string())),
+        }
+    }
+}
+
+impl FromStr for Srid {
+    type Err = SridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = s.trim();
+        let code = match code.get(..5) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("EPSG:") => &code[5..],
+            _ => code,
+        };
+        code.parse::<u32>()
+            .map_err(|_| SridError(s.to_string()))
+            .and_then(|code| Self::try_from(code).map_err(|_| SridError(s.to_string())))
+    }
+}
+
+impl Display for Srid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// 平面直角座標系の原点の緯度と経度（度）を返す。
+fn plane_rectangular_origin(zone: usize) -> (f64, f64) {
+    let (latitude, longitude, minutes) = PLANE_RECTANGULAR_ORIGINS[zone];
+
+    (latitude as f64, longitude as f64 + minutes as f64 / 60.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn srid_from_str_ok() {
+        assert_eq!("6668".parse::<Srid>(), Ok(Srid::JGD2011));
+        assert_eq!("EPSG:4326".parse::<Srid>(), Ok(Srid::WGS84));
+        assert_eq!("epsg:3857".parse::<Srid>(), Ok(Srid::WEB_MERCATOR));
+        assert_eq!("6677".parse::<Srid>().unwrap().code(), 6677);
+        assert!("6688".parse::<Srid>().is_err());
+        assert!("4612".parse::<Srid>().is_err());
+        assert!("jgd2011".parse::<Srid>().is_err());
+    }
+
+    #[test]
+    fn srid_wkt_ok() {
+        assert!(Srid::JGD2011.is_geographic());
+        assert!(!Srid::WEB_MERCATOR.is_geographic());
+        let srid = Srid::try_from(6677).unwrap();
+        assert!(!srid.is_geographic());
+        assert_eq!(srid.name(), "JGD2011 / Japan Plane Rectangular CS IX");
+        assert!(srid.wkt().contains(
+            r#"PARAMETER["latitude_of_origin",36],PARAMETER["central_meridian",139.83333333333334]"#
+        ));
+        assert!(srid.wkt().ends_with(r#"AUTHORITY["EPSG","6677"]]"#));
+        assert!(srid
+            .esri_wkt()
+            .starts_with(r#"PROJCS["JGD_2011_Japan_Zone_9",GEOGCS["GCS_JGD_2011""#));
+        assert_eq!(Srid::JGD2011.esri_wkt(), JGD2011_ESRI_WKT);
+    }
+}
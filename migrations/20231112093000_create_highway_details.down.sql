@@ -0,0 +1 @@
+DROP TABLE highway_details;
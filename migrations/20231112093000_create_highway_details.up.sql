@@ -0,0 +1,25 @@
+-- 高速道路交通事故詳細（高速票）テーブル
+CREATE TABLE highway_details (
+    -- 高速道路交通事故詳細ID
+    id UUID NOT NULL,
+    -- 交通事故ID
+    accident_id UUID NOT NULL,
+    -- 上下線コード
+    up_down_code CHAR(1) NOT NULL,
+    -- キロポスト（キロメートル）
+    kilopost_km INTEGER NOT NULL,
+    -- キロポスト（メートル）
+    kilopost_m INTEGER NOT NULL,
+    -- 道路構造コード
+    road_structure_code CHAR(1) NOT NULL,
+    -- 車線コード（当事者A）
+    lane_a_code CHAR(1) NOT NULL,
+    -- 車線コード（当事者B）
+    lane_b_code CHAR(1) NOT NULL,
+    -- 主キー制約
+    PRIMARY KEY (id),
+    -- ユニークキー制約
+    UNIQUE (accident_id),
+    -- 外部参照制約 交通事故
+    FOREIGN KEY (accident_id) REFERENCES accidents(id)
+);
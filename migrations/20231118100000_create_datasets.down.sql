@@ -0,0 +1,11 @@
+ALTER TABLE highway_details
+    DROP CONSTRAINT highway_details_accident_id_fkey,
+    ADD CONSTRAINT highway_details_accident_id_fkey
+    FOREIGN KEY (accident_id) REFERENCES accidents(id);
+ALTER TABLE involved_persons
+    DROP CONSTRAINT involved_persons_accident_id_fkey,
+    ADD CONSTRAINT involved_persons_accident_id_fkey
+    FOREIGN KEY (accident_id) REFERENCES accidents(id);
+DROP INDEX idx_accidents_dataset_id;
+ALTER TABLE accidents DROP COLUMN dataset_id;
+DROP TABLE datasets;
@@ -0,0 +1,46 @@
+-- データセットテーブル
+CREATE TABLE datasets (
+    -- データセットID
+    id UUID NOT NULL,
+    -- 年
+    year INTEGER NOT NULL,
+    -- 本票ファイル名
+    main_file_name VARCHAR(255) NOT NULL,
+    -- 本票ファイルのSHA-256ハッシュ
+    main_file_sha256 CHAR(64) NOT NULL,
+    -- 補充票ファイル名
+    support_file_name VARCHAR(255) NOT NULL,
+    -- 補充票ファイルのSHA-256ハッシュ
+    support_file_sha256 CHAR(64) NOT NULL,
+    -- 高速票ファイル名
+    highway_file_name VARCHAR(255),
+    -- 高速票ファイルのSHA-256ハッシュ
+    highway_file_sha256 CHAR(64),
+    -- 交通事故の件数
+    number_of_accidents INTEGER NOT NULL,
+    -- 交通事故当事者以外関与者の件数
+    number_of_involved_persons INTEGER NOT NULL,
+    -- 高速道路交通事故詳細の件数
+    number_of_highway_details INTEGER NOT NULL,
+    -- 登録日時
+    imported_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    -- 主キー制約
+    PRIMARY KEY (id)
+);
+
+-- 交通事故テーブルにデータセットIDを追加
+ALTER TABLE accidents ADD COLUMN dataset_id UUID;
+ALTER TABLE accidents
+    ADD CONSTRAINT accidents_dataset_id_fkey
+    FOREIGN KEY (dataset_id) REFERENCES datasets(id) ON DELETE CASCADE;
+CREATE INDEX idx_accidents_dataset_id ON accidents (dataset_id);
+
+-- 交通事故の削除を交通事故当事者以外関与者と高速道路交通事故詳細に伝播
+ALTER TABLE involved_persons
+    DROP CONSTRAINT involved_persons_accident_id_fkey,
+    ADD CONSTRAINT involved_persons_accident_id_fkey
+    FOREIGN KEY (accident_id) REFERENCES accidents(id) ON DELETE CASCADE;
+ALTER TABLE highway_details
+    DROP CONSTRAINT highway_details_accident_id_fkey,
+    ADD CONSTRAINT highway_details_accident_id_fkey
+    FOREIGN KEY (accident_id) REFERENCES accidents(id) ON DELETE CASCADE;
@@ -0,0 +1,2 @@
+DROP INDEX idx_import_rejects_dataset_id;
+DROP TABLE import_rejects;
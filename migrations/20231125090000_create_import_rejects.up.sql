@@ -0,0 +1,20 @@
+-- 登録除外行テーブル
+CREATE TABLE import_rejects (
+    -- 登録除外行ID
+    id UUID NOT NULL,
+    -- データセットID
+    dataset_id UUID NOT NULL,
+    -- 票の種類（main: 本票、support: 補充票、highway: 高速票）
+    sheet VARCHAR(10) NOT NULL,
+    -- 行番号（ヘッダ行を除き1から開始）
+    row_number INTEGER NOT NULL,
+    -- CSV形式の行（行を読み込めなかった場合はNULL）
+    line TEXT,
+    -- エラーメッセージ
+    error_message TEXT NOT NULL,
+    -- 主キー制約
+    PRIMARY KEY (id),
+    -- 外部参照制約 データセット
+    FOREIGN KEY (dataset_id) REFERENCES datasets(id) ON DELETE CASCADE
+);
+CREATE INDEX idx_import_rejects_dataset_id ON import_rejects(dataset_id);
@@ -0,0 +1,35 @@
+ALTER TABLE riding_classes DROP COLUMN name_en;
+ALTER TABLE riding_types DROP COLUMN name_en;
+ALTER TABLE driving_practices DROP COLUMN name_en;
+ALTER TABLE holidays DROP COLUMN name_en;
+ALTER TABLE weeks DROP COLUMN name_en;
+ALTER TABLE injuries DROP COLUMN name_en;
+ALTER TABLE side_airbags DROP COLUMN name_en;
+ALTER TABLE airbags DROP COLUMN name_en;
+ALTER TABLE vehicle_damages DROP COLUMN name_en;
+ALTER TABLE speed_regulations DROP COLUMN name_en;
+ALTER TABLE support_cars DROP COLUMN name_en;
+ALTER TABLE automatics DROP COLUMN name_en;
+ALTER TABLE vehicle_types DROP COLUMN name_en;
+ALTER TABLE purposes DROP COLUMN name_en;
+ALTER TABLE parties DROP COLUMN name_en;
+ALTER TABLE ages DROP COLUMN name_en;
+ALTER TABLE accident_types DROP COLUMN name_en;
+ALTER TABLE road_segmentations DROP COLUMN name_en;
+ALTER TABLE central_separations DROP COLUMN name_en;
+ALTER TABLE zone_regulations DROP COLUMN name_en;
+ALTER TABLE collision_points DROP COLUMN name_en;
+ALTER TABLE road_alignments DROP COLUMN name_en;
+ALTER TABLE road_widths DROP COLUMN name_en;
+ALTER TABLE stop_regulation_displays DROP COLUMN name_en;
+ALTER TABLE stop_regulation_signs DROP COLUMN name_en;
+ALTER TABLE traffic_signals DROP COLUMN name_en;
+ALTER TABLE road_models DROP COLUMN name_en;
+ALTER TABLE surface_conditions DROP COLUMN name_en;
+ALTER TABLE districts DROP COLUMN name_en;
+ALTER TABLE weathers DROP COLUMN name_en;
+ALTER TABLE day_nights DROP COLUMN name_en;
+ALTER TABLE route_classes DROP COLUMN name_en;
+ALTER TABLE routes DROP COLUMN name_en;
+ALTER TABLE accident_details DROP COLUMN name_en;
+ALTER TABLE prefectures DROP COLUMN name_en;
@@ -0,0 +1,392 @@
+-- コード表の英語の名前
+-- 市区町村テーブル及び警察署テーブルの名前は固有名詞のため、英語の名前を登録せずに日本語の名前を使用する。
+
+-- 都道府県テーブル
+ALTER TABLE prefectures ADD COLUMN name_en VARCHAR(60);
+UPDATE prefectures SET name_en = 'Hokkaido (Sapporo)' WHERE code = '10';
+UPDATE prefectures SET name_en = 'Hokkaido (Hakodate)' WHERE code = '11';
+UPDATE prefectures SET name_en = 'Hokkaido (Asahikawa)' WHERE code = '12';
+UPDATE prefectures SET name_en = 'Hokkaido (Kushiro)' WHERE code = '13';
+UPDATE prefectures SET name_en = 'Hokkaido (Kitami)' WHERE code = '14';
+UPDATE prefectures SET name_en = 'Aomori' WHERE code = '20';
+UPDATE prefectures SET name_en = 'Iwate' WHERE code = '21';
+UPDATE prefectures SET name_en = 'Miyagi' WHERE code = '22';
+UPDATE prefectures SET name_en = 'Akita' WHERE code = '23';
+UPDATE prefectures SET name_en = 'Yamagata' WHERE code = '24';
+UPDATE prefectures SET name_en = 'Fukushima' WHERE code = '25';
+UPDATE prefectures SET name_en = 'Tokyo' WHERE code = '30';
+UPDATE prefectures SET name_en = 'Ibaraki' WHERE code = '40';
+UPDATE prefectures SET name_en = 'Tochigi' WHERE code = '41';
+UPDATE prefectures SET name_en = 'Gunma' WHERE code = '42';
+UPDATE prefectures SET name_en = 'Saitama' WHERE code = '43';
+UPDATE prefectures SET name_en = 'Chiba' WHERE code = '44';
+UPDATE prefectures SET name_en = 'Kanagawa' WHERE code = '45';
+UPDATE prefectures SET name_en = 'Niigata' WHERE code = '46';
+UPDATE prefectures SET name_en = 'Yamanashi' WHERE code = '47';
+UPDATE prefectures SET name_en = 'Nagano' WHERE code = '48';
+UPDATE prefectures SET name_en = 'Shizuoka' WHERE code = '49';
+UPDATE prefectures SET name_en = 'Toyama' WHERE code = '50';
+UPDATE prefectures SET name_en = 'Ishikawa' WHERE code = '51';
+UPDATE prefectures SET name_en = 'Fukui' WHERE code = '52';
+UPDATE prefectures SET name_en = 'Gifu' WHERE code = '53';
+UPDATE prefectures SET name_en = 'Aichi' WHERE code = '54';
+UPDATE prefectures SET name_en = 'Mie' WHERE code = '55';
+UPDATE prefectures SET name_en = 'Shiga' WHERE code = '60';
+UPDATE prefectures SET name_en = 'Kyoto' WHERE code = '61';
+UPDATE prefectures SET name_en = 'Osaka' WHERE code = '62';
+UPDATE prefectures SET name_en = 'Hyogo' WHERE code = '63';
+UPDATE prefectures SET name_en = 'Nara' WHERE code = '64';
+UPDATE prefectures SET name_en = 'Wakayama' WHERE code = '65';
+UPDATE prefectures SET name_en = 'Tottori' WHERE code = '70';
+UPDATE prefectures SET name_en = 'Shimane' WHERE code = '71';
+UPDATE prefectures SET name_en = 'Okayama' WHERE code = '72';
+UPDATE prefectures SET name_en = 'Hiroshima' WHERE code = '73';
+UPDATE prefectures SET name_en = 'Yamaguchi' WHERE code = '74';
+UPDATE prefectures SET name_en = 'Tokushima' WHERE code = '80';
+UPDATE prefectures SET name_en = 'Kagawa' WHERE code = '81';
+UPDATE prefectures SET name_en = 'Ehime' WHERE code = '82';
+UPDATE prefectures SET name_en = 'Kochi' WHERE code = '83';
+UPDATE prefectures SET name_en = 'Fukuoka' WHERE code = '90';
+UPDATE prefectures SET name_en = 'Saga' WHERE code = '91';
+UPDATE prefectures SET name_en = 'Nagasaki' WHERE code = '92';
+UPDATE prefectures SET name_en = 'Kumamoto' WHERE code = '93';
+UPDATE prefectures SET name_en = 'Oita' WHERE code = '94';
+UPDATE prefectures SET name_en = 'Miyazaki' WHERE code = '95';
+UPDATE prefectures SET name_en = 'Kagoshima' WHERE code = '96';
+UPDATE prefectures SET name_en = 'Okinawa' WHERE code = '97';
+
+-- 事故内容テーブル
+ALTER TABLE accident_details ADD COLUMN name_en VARCHAR(60);
+UPDATE accident_details SET name_en = 'Fatal' WHERE code = '1';
+UPDATE accident_details SET name_en = 'Injury' WHERE code = '2';
+
+-- 路線テーブル
+ALTER TABLE routes ADD COLUMN name_en VARCHAR(60);
+UPDATE routes SET name_en = 'National road (route number)' WHERE lower_code = '0001';
+UPDATE routes SET name_en = 'Major local road - prefectural' WHERE lower_code = '1000';
+UPDATE routes SET name_en = 'Major local road - municipal' WHERE lower_code = '1500';
+UPDATE routes SET name_en = 'Prefectural road' WHERE lower_code = '2000';
+UPDATE routes SET name_en = 'Municipal road' WHERE lower_code = '3000';
+UPDATE routes SET name_en = 'National expressway' WHERE lower_code = '4000';
+UPDATE routes SET name_en = 'Motorway - designated' WHERE lower_code = '5000';
+UPDATE routes SET name_en = 'Motorway - other' WHERE lower_code = '5500';
+UPDATE routes SET name_en = 'Road under the Road Transport Act' WHERE lower_code = '6000';
+UPDATE routes SET name_en = 'Agricultural road' WHERE lower_code = '7000';
+UPDATE routes SET name_en = 'Forest road' WHERE lower_code = '8000';
+UPDATE routes SET name_en = 'Port road' WHERE lower_code = '8500';
+UPDATE routes SET name_en = 'Private road' WHERE lower_code = '9000';
+UPDATE routes SET name_en = 'Other' WHERE lower_code = '9500';
+UPDATE routes SET name_en = 'Other road for general traffic' WHERE lower_code = '9900';
+
+-- 路線区分テーブル
+ALTER TABLE route_classes ADD COLUMN name_en VARCHAR(60);
+UPDATE route_classes SET name_en = 'Current or comprehensive section' WHERE code = '0';
+UPDATE route_classes SET name_en = 'Bypass section' WHERE code = '1';
+UPDATE route_classes SET name_en = 'Bypass section' WHERE code = '2';
+UPDATE route_classes SET name_en = 'Bypass section' WHERE code = '3';
+UPDATE route_classes SET name_en = 'Bypass section' WHERE code = '4';
+UPDATE route_classes SET name_en = 'Bypass section' WHERE code = '5';
+UPDATE route_classes SET name_en = 'Bypass section' WHERE code = '6';
+UPDATE route_classes SET name_en = 'Bypass section' WHERE code = '7';
+UPDATE route_classes SET name_en = 'Bypass section' WHERE code = '8';
+UPDATE route_classes SET name_en = 'Bypass section' WHERE code = '9';
+
+-- 昼夜テーブル
+ALTER TABLE day_nights ADD COLUMN name_en VARCHAR(60);
+UPDATE day_nights SET name_en = 'Day - dawn' WHERE code = '11';
+UPDATE day_nights SET name_en = 'Day - daytime' WHERE code = '12';
+UPDATE day_nights SET name_en = 'Day - dusk' WHERE code = '13';
+UPDATE day_nights SET name_en = 'Night - dusk' WHERE code = '21';
+UPDATE day_nights SET name_en = 'Night - night' WHERE code = '22';
+UPDATE day_nights SET name_en = 'Night - dawn' WHERE code = '23';
+
+-- 天候テーブル
+ALTER TABLE weathers ADD COLUMN name_en VARCHAR(60);
+UPDATE weathers SET name_en = 'Fine' WHERE code = '1';
+UPDATE weathers SET name_en = 'Cloudy' WHERE code = '2';
+UPDATE weathers SET name_en = 'Rain' WHERE code = '3';
+UPDATE weathers SET name_en = 'Fog' WHERE code = '4';
+UPDATE weathers SET name_en = 'Snow' WHERE code = '5';
+
+-- 地区（地形）テーブル
+ALTER TABLE districts ADD COLUMN name_en VARCHAR(60);
+UPDATE districts SET name_en = 'Urban - densely inhabited' WHERE code = '1';
+UPDATE districts SET name_en = 'Urban - other' WHERE code = '2';
+UPDATE districts SET name_en = 'Non-urban' WHERE code = '3';
+
+-- 路面状態テーブル
+ALTER TABLE surface_conditions ADD COLUMN name_en VARCHAR(60);
+UPDATE surface_conditions SET name_en = 'Paved - dry' WHERE code = '1';
+UPDATE surface_conditions SET name_en = 'Paved - wet' WHERE code = '2';
+UPDATE surface_conditions SET name_en = 'Paved - frozen' WHERE code = '3';
+UPDATE surface_conditions SET name_en = 'Paved - snow' WHERE code = '4';
+UPDATE surface_conditions SET name_en = 'Unpaved' WHERE code = '5';
+
+-- 道路形状テーブル
+ALTER TABLE road_models ADD COLUMN name_en VARCHAR(60);
+UPDATE road_models SET name_en = 'General traffic area' WHERE code = '00';
+UPDATE road_models SET name_en = 'Intersection - other' WHERE code = '01';
+UPDATE road_models SET name_en = 'Near intersection - other' WHERE code = '07';
+UPDATE road_models SET name_en = 'Single road - tunnel' WHERE code = '11';
+UPDATE road_models SET name_en = 'Single road - bridge' WHERE code = '12';
+UPDATE road_models SET name_en = 'Single road - curve or bend' WHERE code = '13';
+UPDATE road_models SET name_en = 'Single road - other' WHERE code = '14';
+UPDATE road_models SET name_en = 'Level crossing - class 1' WHERE code = '21';
+UPDATE road_models SET name_en = 'Level crossing - class 3' WHERE code = '22';
+UPDATE road_models SET name_en = 'Level crossing - class 4' WHERE code = '23';
+UPDATE road_models SET name_en = 'Intersection - roundabout' WHERE code = '31';
+UPDATE road_models SET name_en = 'Near intersection - roundabout' WHERE code = '37';
+
+-- 信号機テーブル
+ALTER TABLE traffic_signals ADD COLUMN name_en VARCHAR(60);
+UPDATE traffic_signals SET name_en = 'On - three-light' WHERE code = '1';
+UPDATE traffic_signals SET name_en = 'On - push-button' WHERE code = '2';
+UPDATE traffic_signals SET name_en = 'Flashing - three-light' WHERE code = '3';
+UPDATE traffic_signals SET name_en = 'Flashing - single-light' WHERE code = '4';
+UPDATE traffic_signals SET name_en = 'Off' WHERE code = '5';
+UPDATE traffic_signals SET name_en = 'Out of order' WHERE code = '6';
+UPDATE traffic_signals SET name_en = 'No signal' WHERE code = '7';
+UPDATE traffic_signals SET name_en = 'On - pedestrian scramble' WHERE code = '8';
+
+-- 一時停止規制標識テーブル
+ALTER TABLE stop_regulation_signs ADD COLUMN name_en VARCHAR(60);
+UPDATE stop_regulation_signs SET name_en = 'Not applicable' WHERE code = '00';
+UPDATE stop_regulation_signs SET name_en = 'Standard - reflective' WHERE code = '01';
+UPDATE stop_regulation_signs SET name_en = 'Standard - self-luminous' WHERE code = '02';
+UPDATE stop_regulation_signs SET name_en = 'Standard - internally illuminated' WHERE code = '03';
+UPDATE stop_regulation_signs SET name_en = 'Enlarged - reflective' WHERE code = '04';
+UPDATE stop_regulation_signs SET name_en = 'Enlarged - self-luminous' WHERE code = '05';
+UPDATE stop_regulation_signs SET name_en = 'Enlarged - internally illuminated' WHERE code = '06';
+UPDATE stop_regulation_signs SET name_en = 'Reduced' WHERE code = '07';
+UPDATE stop_regulation_signs SET name_en = 'Other' WHERE code = '08';
+UPDATE stop_regulation_signs SET name_en = 'No regulation' WHERE code = '09';
+
+-- 一時停止規制表示テーブル
+ALTER TABLE stop_regulation_displays ADD COLUMN name_en VARCHAR(60);
+UPDATE stop_regulation_displays SET name_en = 'Not applicable' WHERE code = '00';
+UPDATE stop_regulation_displays SET name_en = 'Marked' WHERE code = '21';
+UPDATE stop_regulation_displays SET name_en = 'Not marked' WHERE code = '22';
+
+-- 車道幅員テーブル
+ALTER TABLE road_widths ADD COLUMN name_en VARCHAR(60);
+UPDATE road_widths SET name_en = 'General traffic area' WHERE code = '00';
+UPDATE road_widths SET name_en = 'Single road - under 3.5m' WHERE code = '01';
+UPDATE road_widths SET name_en = 'Single road - 3.5m or more' WHERE code = '02';
+UPDATE road_widths SET name_en = 'Single road - 5.5m or more' WHERE code = '03';
+UPDATE road_widths SET name_en = 'Single road - 9.0m or more' WHERE code = '04';
+UPDATE road_widths SET name_en = 'Single road - 13.0m or more' WHERE code = '05';
+UPDATE road_widths SET name_en = 'Single road - 19.5m or more' WHERE code = '06';
+UPDATE road_widths SET name_en = 'Intersection - small (under 5.5m) - small' WHERE code = '11';
+UPDATE road_widths SET name_en = '(Not in code table)' WHERE code = '12';
+UPDATE road_widths SET name_en = '(Not in code table)' WHERE code = '13';
+UPDATE road_widths SET name_en = 'Intersection - medium (5.5m or more) - small' WHERE code = '14';
+UPDATE road_widths SET name_en = 'Intersection - medium (5.5m or more) - medium' WHERE code = '15';
+UPDATE road_widths SET name_en = '(Not in code table)' WHERE code = '16';
+UPDATE road_widths SET name_en = 'Intersection - large (13.0m or more) - small' WHERE code = '17';
+UPDATE road_widths SET name_en = 'Intersection - large (13.0m or more) - medium' WHERE code = '18';
+UPDATE road_widths SET name_en = 'Intersection - large (13.0m or more) - large' WHERE code = '19';
+
+-- 道路線形テーブル
+ALTER TABLE road_alignments ADD COLUMN name_en VARCHAR(60);
+UPDATE road_alignments SET name_en = 'General traffic area' WHERE code = '0';
+UPDATE road_alignments SET name_en = 'Curve or bend - right - uphill' WHERE code = '1';
+UPDATE road_alignments SET name_en = 'Curve or bend - right - downhill' WHERE code = '2';
+UPDATE road_alignments SET name_en = 'Curve or bend - right - flat' WHERE code = '3';
+UPDATE road_alignments SET name_en = 'Curve or bend - left - uphill' WHERE code = '4';
+UPDATE road_alignments SET name_en = 'Curve or bend - left - downhill' WHERE code = '5';
+UPDATE road_alignments SET name_en = 'Curve or bend - left - flat' WHERE code = '6';
+UPDATE road_alignments SET name_en = 'Straight - uphill' WHERE code = '7';
+UPDATE road_alignments SET name_en = 'Straight - downhill' WHERE code = '8';
+UPDATE road_alignments SET name_en = 'Straight - flat' WHERE code = '9';
+
+-- 衝突地点テーブル
+ALTER TABLE collision_points ADD COLUMN name_en VARCHAR(60);
+UPDATE collision_points SET name_en = 'Single road (including near intersection)' WHERE code = '01';
+UPDATE collision_points SET name_en = 'Other' WHERE code = '20';
+UPDATE collision_points SET name_en = 'Within intersection' WHERE code = '30';
+
+-- ゾーン規制テーブル
+ALTER TABLE zone_regulations ADD COLUMN name_en VARCHAR(60);
+UPDATE zone_regulations SET name_en = 'Zone 30' WHERE code = '01';
+UPDATE zone_regulations SET name_en = 'No regulation' WHERE code = '70';
+
+-- 中央分離帯施設テーブル
+ALTER TABLE central_separations ADD COLUMN name_en VARCHAR(60);
+UPDATE central_separations SET name_en = 'General traffic area' WHERE code = '0';
+UPDATE central_separations SET name_en = 'Median strip' WHERE code = '1';
+UPDATE central_separations SET name_en = 'Center line - high-visibility marking' WHERE code = '2';
+UPDATE central_separations SET name_en = 'Center line - chatter bars' WHERE code = '3';
+UPDATE central_separations SET name_en = 'Center line - paint' WHERE code = '4';
+UPDATE central_separations SET name_en = 'No center separation' WHERE code = '5';
+UPDATE central_separations SET name_en = 'Center line - post cones' WHERE code = '6';
+UPDATE central_separations SET name_en = 'Center line - wire rope' WHERE code = '7';
+
+-- 歩車道区分テーブル
+ALTER TABLE road_segmentations ADD COLUMN name_en VARCHAR(60);
+UPDATE road_segmentations SET name_en = 'Separated - guard fence' WHERE code = '1';
+UPDATE road_segmentations SET name_en = 'Separated - curb or blocks' WHERE code = '2';
+UPDATE road_segmentations SET name_en = 'Separated - roadside strip' WHERE code = '3';
+UPDATE road_segmentations SET name_en = 'Not separated' WHERE code = '4';
+
+-- 事故類型テーブル
+ALTER TABLE accident_types ADD COLUMN name_en VARCHAR(60);
+UPDATE accident_types SET name_en = 'Vehicle-pedestrian' WHERE code = '01';
+UPDATE accident_types SET name_en = 'Vehicle-vehicle' WHERE code = '21';
+UPDATE accident_types SET name_en = 'Single vehicle' WHERE code = '41';
+UPDATE accident_types SET name_en = 'Train' WHERE code = '61';
+
+-- 年齢テーブル
+ALTER TABLE ages ADD COLUMN name_en VARCHAR(60);
+UPDATE ages SET name_en = 'Unknown' WHERE code = '00';
+UPDATE ages SET name_en = '0-24 years' WHERE code = '01';
+UPDATE ages SET name_en = '25-34 years' WHERE code = '25';
+UPDATE ages SET name_en = '35-44 years' WHERE code = '35';
+UPDATE ages SET name_en = '45-54 years' WHERE code = '45';
+UPDATE ages SET name_en = '55-64 years' WHERE code = '55';
+UPDATE ages SET name_en = '65-74 years' WHERE code = '65';
+UPDATE ages SET name_en = '75 years or older' WHERE code = '75';
+
+-- 当事者テーブル
+ALTER TABLE parties ADD COLUMN name_en VARCHAR(60);
+UPDATE parties SET name_en = 'Not applicable' WHERE code = '00';
+UPDATE parties SET name_en = 'Passenger car - large' WHERE code = '01';
+UPDATE parties SET name_en = 'Passenger car - medium' WHERE code = '02';
+UPDATE parties SET name_en = 'Passenger car - standard' WHERE code = '03';
+UPDATE parties SET name_en = 'Passenger car - kei car' WHERE code = '04';
+UPDATE parties SET name_en = 'Passenger car - microcar' WHERE code = '05';
+UPDATE parties SET name_en = 'Passenger car - semi-medium' WHERE code = '07';
+UPDATE parties SET name_en = 'Truck - large' WHERE code = '11';
+UPDATE parties SET name_en = 'Truck - medium' WHERE code = '12';
+UPDATE parties SET name_en = 'Truck - standard' WHERE code = '13';
+UPDATE parties SET name_en = 'Truck - kei truck' WHERE code = '14';
+UPDATE parties SET name_en = 'Truck - semi-medium' WHERE code = '17';
+UPDATE parties SET name_en = 'Special vehicle - large - agricultural' WHERE code = '21';
+UPDATE parties SET name_en = 'Special vehicle - large - other' WHERE code = '22';
+UPDATE parties SET name_en = 'Special vehicle - small - agricultural' WHERE code = '23';
+UPDATE parties SET name_en = 'Special vehicle - small - other' WHERE code = '24';
+UPDATE parties SET name_en = 'Motorcycle - 751cc or more' WHERE code = '31';
+UPDATE parties SET name_en = 'Motorcycle - 401-750cc' WHERE code = '32';
+UPDATE parties SET name_en = 'Motorcycle - 251-400cc' WHERE code = '33';
+UPDATE parties SET name_en = 'Motorcycle - light - 126-250cc' WHERE code = '34';
+UPDATE parties SET name_en = 'Motorcycle - moped class 2 - 51-125cc' WHERE code = '35';
+UPDATE parties SET name_en = 'Moped' WHERE code = '36';
+UPDATE parties SET name_en = 'Tram' WHERE code = '41';
+UPDATE parties SET name_en = 'Train' WHERE code = '42';
+UPDATE parties SET name_en = 'Light vehicle - bicycle' WHERE code = '51';
+UPDATE parties SET name_en = 'Light vehicle - power-assisted bicycle' WHERE code = '52';
+UPDATE parties SET name_en = 'Light vehicle - other' WHERE code = '59';
+UPDATE parties SET name_en = 'Pedestrian' WHERE code = '61';
+UPDATE parties SET name_en = 'Person on road other than pedestrian (supplement only)' WHERE code = '71';
+UPDATE parties SET name_en = 'Person off road (supplement only)' WHERE code = '72';
+UPDATE parties SET name_en = 'Object' WHERE code = '75';
+UPDATE parties SET name_en = 'No other party' WHERE code = '76';
+
+-- 用途テーブル
+ALTER TABLE purposes ADD COLUMN name_en VARCHAR(60);
+UPDATE purposes SET name_en = 'Not applicable' WHERE code = '00';
+UPDATE purposes SET name_en = 'Commercial' WHERE code = '01';
+UPDATE purposes SET name_en = 'Private' WHERE code = '31';
+UPDATE purposes SET name_en = 'Bicycle' WHERE code = '41';
+
+-- 車両形状テーブル
+ALTER TABLE vehicle_types ADD COLUMN name_en VARCHAR(60);
+UPDATE vehicle_types SET name_en = 'Not applicable' WHERE code = '00';
+UPDATE vehicle_types SET name_en = 'Passenger car' WHERE code = '01';
+UPDATE vehicle_types SET name_en = 'Truck' WHERE code = '11';
+UPDATE vehicle_types SET name_en = 'Stand-up electric vehicle' WHERE code = '31';
+
+-- オートマチック車テーブル
+ALTER TABLE automatics ADD COLUMN name_en VARCHAR(60);
+UPDATE automatics SET name_en = 'Not applicable' WHERE code = '0';
+UPDATE automatics SET name_en = 'Automatic' WHERE code = '1';
+UPDATE automatics SET name_en = 'Other' WHERE code = '2';
+
+-- サポカーテーブル
+ALTER TABLE support_cars ADD COLUMN name_en VARCHAR(60);
+UPDATE support_cars SET name_en = 'Not applicable' WHERE code = '00';
+UPDATE support_cars SET name_en = 'Safety support car' WHERE code = '01';
+UPDATE support_cars SET name_en = 'Other car' WHERE code = '11';
+
+-- 速度規制（指定のみ）テーブル
+ALTER TABLE speed_regulations ADD COLUMN name_en VARCHAR(60);
+UPDATE speed_regulations SET name_en = 'Not applicable' WHERE code = '00';
+UPDATE speed_regulations SET name_en = '20 km/h or less' WHERE code = '01';
+UPDATE speed_regulations SET name_en = '30 km/h or less' WHERE code = '02';
+UPDATE speed_regulations SET name_en = '40 km/h or less' WHERE code = '03';
+UPDATE speed_regulations SET name_en = '50 km/h or less' WHERE code = '04';
+UPDATE speed_regulations SET name_en = '60 km/h or less' WHERE code = '05';
+UPDATE speed_regulations SET name_en = '70 km/h or less' WHERE code = '06';
+UPDATE speed_regulations SET name_en = '80 km/h or less' WHERE code = '07';
+UPDATE speed_regulations SET name_en = '100 km/h or less' WHERE code = '08';
+UPDATE speed_regulations SET name_en = '120 km/h or less' WHERE code = '11';
+UPDATE speed_regulations SET name_en = 'No designated speed limit' WHERE code = '10';
+UPDATE speed_regulations SET name_en = 'Over 120 km/h' WHERE code = '12';
+UPDATE speed_regulations SET name_en = '(Not in code table)' WHERE code = '40';
+
+-- 車両の損傷程度テーブル
+ALTER TABLE vehicle_damages ADD COLUMN name_en VARCHAR(60);
+UPDATE vehicle_damages SET name_en = 'Not applicable' WHERE code = '0';
+UPDATE vehicle_damages SET name_en = 'Severe' WHERE code = '1';
+UPDATE vehicle_damages SET name_en = 'Moderate' WHERE code = '2';
+UPDATE vehicle_damages SET name_en = 'Minor' WHERE code = '3';
+UPDATE vehicle_damages SET name_en = 'No damage' WHERE code = '4';
+
+-- エアバッグの装備テーブル
+ALTER TABLE airbags ADD COLUMN name_en VARCHAR(60);
+UPDATE airbags SET name_en = 'Not applicable' WHERE code = '0';
+UPDATE airbags SET name_en = 'Equipped and deployed' WHERE code = '1';
+UPDATE airbags SET name_en = 'Other' WHERE code = '2';
+
+-- サイドエアバッグの装備テーブル
+ALTER TABLE side_airbags ADD COLUMN name_en VARCHAR(60);
+UPDATE side_airbags SET name_en = 'Not applicable' WHERE code = '0';
+UPDATE side_airbags SET name_en = 'Equipped and deployed' WHERE code = '1';
+UPDATE side_airbags SET name_en = 'Other' WHERE code = '2';
+
+-- 人身損傷程度テーブル
+ALTER TABLE injuries ADD COLUMN name_en VARCHAR(60);
+UPDATE injuries SET name_en = 'Not applicable' WHERE code = '0';
+UPDATE injuries SET name_en = 'Fatal' WHERE code = '1';
+UPDATE injuries SET name_en = 'Injured' WHERE code = '2';
+UPDATE injuries SET name_en = 'Not injured' WHERE code = '4';
+
+-- 曜日テーブル
+ALTER TABLE weeks ADD COLUMN name_en VARCHAR(60);
+UPDATE weeks SET name_en = 'Sun' WHERE code = '1';
+UPDATE weeks SET name_en = 'Mon' WHERE code = '2';
+UPDATE weeks SET name_en = 'Tue' WHERE code = '3';
+UPDATE weeks SET name_en = 'Wed' WHERE code = '4';
+UPDATE weeks SET name_en = 'Thu' WHERE code = '5';
+UPDATE weeks SET name_en = 'Fri' WHERE code = '6';
+UPDATE weeks SET name_en = 'Sat' WHERE code = '7';
+
+-- 祝日テーブル
+ALTER TABLE holidays ADD COLUMN name_en VARCHAR(60);
+UPDATE holidays SET name_en = '(Not in code table)' WHERE code = '0';
+UPDATE holidays SET name_en = 'Holiday' WHERE code = '1';
+UPDATE holidays SET name_en = 'Day before holiday' WHERE code = '2';
+UPDATE holidays SET name_en = 'Other' WHERE code = '3';
+
+-- 運転練習の方法テーブル
+ALTER TABLE driving_practices ADD COLUMN name_en VARCHAR(60);
+UPDATE driving_practices SET name_en = '(Not in code table)' WHERE code = '0';
+UPDATE driving_practices SET name_en = 'General (designated driving school)' WHERE code = '1';
+UPDATE driving_practices SET name_en = 'General (registered driving school)' WHERE code = '2';
+UPDATE driving_practices SET name_en = 'General (other driving school)' WHERE code = '3';
+UPDATE driving_practices SET name_en = 'Partial exemption with foreign license' WHERE code = '4';
+UPDATE driving_practices SET name_en = 'Other' WHERE code = '5';
+UPDATE driving_practices SET name_en = 'Other' WHERE code = '6';
+UPDATE driving_practices SET name_en = 'Unknown' WHERE code = '9';
+
+-- 乗車別テーブル
+ALTER TABLE riding_types ADD COLUMN name_en VARCHAR(60);
+UPDATE riding_types SET name_en = 'Other' WHERE code = '0';
+UPDATE riding_types SET name_en = 'Driver' WHERE code = '1';
+UPDATE riding_types SET name_en = 'Passenger' WHERE code = '2';
+UPDATE riding_types SET name_en = 'Pedestrian etc.' WHERE code = '5';
+
+-- 乗車等の区分テーブル
+ALTER TABLE riding_classes ADD COLUMN name_en VARCHAR(60);
+UPDATE riding_classes SET name_en = 'Not applicable' WHERE code = '00';
+UPDATE riding_classes SET name_en = 'Passenger' WHERE code = '01';
+UPDATE riding_classes SET name_en = 'Driver or pedestrian etc.' WHERE code = '07';
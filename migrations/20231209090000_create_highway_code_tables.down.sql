@@ -0,0 +1,3 @@
+DROP TABLE lanes;
+DROP TABLE road_structures;
+DROP TABLE up_downs;
@@ -0,0 +1,56 @@
+-- 高速票のコード表
+
+-- 上下線テーブル
+CREATE TABLE up_downs (
+    -- 上下線コード
+    code CHAR(1) NOT NULL,
+    -- 上下線名
+    name VARCHAR(10) NOT NULL,
+    -- 英語の上下線名
+    name_en VARCHAR(60),
+    -- 主キー制約
+    PRIMARY KEY (code)
+);
+
+-- 道路構造テーブル
+CREATE TABLE road_structures (
+    -- 道路構造コード
+    code CHAR(1) NOT NULL,
+    -- 道路構造名
+    name VARCHAR(20) NOT NULL,
+    -- 英語の道路構造名
+    name_en VARCHAR(60),
+    -- 主キー制約
+    PRIMARY KEY (code)
+);
+
+-- 車線テーブル
+CREATE TABLE lanes (
+    -- 車線コード
+    code CHAR(1) NOT NULL,
+    -- 車線名
+    name VARCHAR(20) NOT NULL,
+    -- 英語の車線名
+    name_en VARCHAR(60),
+    -- 主キー制約
+    PRIMARY KEY (code)
+);
+
+-- 上下線テーブル
+INSERT INTO up_downs (code, name, name_en) VALUES ('1', '上り', 'Inbound');
+INSERT INTO up_downs (code, name, name_en) VALUES ('2', '下り', 'Outbound');
+
+-- 道路構造テーブル
+INSERT INTO road_structures (code, name, name_en) VALUES ('1', '本線', 'Main line');
+INSERT INTO road_structures (code, name, name_en) VALUES ('2', 'ランプ', 'Ramp');
+INSERT INTO road_structures (code, name, name_en) VALUES ('3', '料金所', 'Toll gate');
+INSERT INTO road_structures (code, name, name_en) VALUES ('4', 'サービスエリア・パーキングエリア', 'Service or parking area');
+
+-- 車線テーブル
+INSERT INTO lanes (code, name, name_en) VALUES ('0', '対象外当事者', 'Not applicable');
+INSERT INTO lanes (code, name, name_en) VALUES ('1', '第1車線', 'Lane 1');
+INSERT INTO lanes (code, name, name_en) VALUES ('2', '第2車線', 'Lane 2');
+INSERT INTO lanes (code, name, name_en) VALUES ('3', '第3車線', 'Lane 3');
+INSERT INTO lanes (code, name, name_en) VALUES ('4', '第4車線以上', 'Lane 4 or higher');
+INSERT INTO lanes (code, name, name_en) VALUES ('5', '路肩', 'Shoulder');
+INSERT INTO lanes (code, name, name_en) VALUES ('6', '付加車線', 'Auxiliary lane');
@@ -0,0 +1,5 @@
+ALTER TABLE highway_details
+    DROP CONSTRAINT highway_details_lane_b_code_fkey,
+    DROP CONSTRAINT highway_details_lane_a_code_fkey,
+    DROP CONSTRAINT highway_details_road_structure_code_fkey,
+    DROP CONSTRAINT highway_details_up_down_code_fkey;
@@ -0,0 +1,14 @@
+-- 高速道路交通事故詳細（高速票）テーブルに、高速票のコード表への外部参照制約を追加
+ALTER TABLE highway_details
+    -- 外部参照制約 上下線
+    ADD CONSTRAINT highway_details_up_down_code_fkey
+        FOREIGN KEY (up_down_code) REFERENCES up_downs(code),
+    -- 外部参照制約 道路構造
+    ADD CONSTRAINT highway_details_road_structure_code_fkey
+        FOREIGN KEY (road_structure_code) REFERENCES road_structures(code),
+    -- 外部参照制約 車線（当事者A）
+    ADD CONSTRAINT highway_details_lane_a_code_fkey
+        FOREIGN KEY (lane_a_code) REFERENCES lanes(code),
+    -- 外部参照制約 車線（当事者B）
+    ADD CONSTRAINT highway_details_lane_b_code_fkey
+        FOREIGN KEY (lane_b_code) REFERENCES lanes(code);
@@ -0,0 +1,740 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::layouts::{Layout, MainColumns, SupportColumns};
+use crate::sheet::{
+    dms_to_latitude, dms_to_longitude, is_route_format, offset_datetime, open_sheet, time,
+    RawAccidentIdentifier, Sheet,
+};
+use code_tables::CodeTables;
+
+/// 検査結果の出力形式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// テキスト
+    #[default]
+    Text,
+    /// JSON
+    Json,
+}
+
+/// 検査で見つかった問題
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Problem {
+    /// 票の種類
+    pub sheet: Sheet,
+    /// 行番号（ヘッダ行を除き1から開始）
+    pub row: usize,
+    /// 列名（行全体の問題の場合は`None`）
+    pub column: Option<String>,
+    /// 値（行全体の問題の場合は`None`）
+    pub value: Option<String>,
+    /// 問題の内容
+    pub message: String,
+}
+
+/// 検査結果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CheckReport {
+    /// 検査した本票の行数
+    pub main_rows: usize,
+    /// 検査した補充票の行数
+    pub support_rows: usize,
+    /// 検査で見つかった問題
+    pub problems: Vec<Problem>,
+}
+
+/// データベースに接続せずに本票と補充票を検査して、見つかったすべての問題を出力する。
+///
+/// # 引数
+///
+/// * `main_file` - 本票ファイルパス
+/// * `support_file` - 補充票ファイルパス
+/// * `layout` - 本票及び補充票のレイアウト（指定しない場合はヘッダ行から列の位置を決定）
+/// * `format` - 検査結果の出力形式
+///
+/// # 戻り値
+///
+/// `()`、問題が見つかった場合はエラー
+pub fn check<P: AsRef<Path>>(
+    main_file: P,
+    support_file: P,
+    layout: Option<Layout>,
+    format: ReportFormat,
+) -> anyhow::Result<()> {
+    let code_tables = CodeTables::load()?;
+    let report = check_sheets(main_file, support_file, layout, &code_tables)?;
+
+    match format {
+        ReportFormat::Text => {
+            for problem in &report.problems {
+                println!("{}", problem_to_text(problem));
+            }
+            println!(
+                "本票{}行、補充票{}行を検査し、{}件の問題が見つかりました。",
+                report.main_rows,
+                report.support_rows,
+                report.problems.len()
+            );
+        }
+        ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    if !report.problems.is_empty() {
+        return Err(anyhow::anyhow!(
+            "{}件の問題が見つかりました。",
+            report.problems.len()
+        ));
+    }
+
+    Ok(())
+}
+
+/// 本票と補充票を検査する。
+///
+/// # 引数
+///
+/// * `main_file` - 本票ファイルパス
+/// * `support_file` - 補充票ファイルパス
+/// * `layout` - 本票及び補充票のレイアウト（指定しない場合はヘッダ行から列の位置を決定）
+/// * `code_tables` - コード表
+///
+/// # 戻り値
+///
+/// 検査結果
+pub fn check_sheets<P: AsRef<Path>>(
+    main_file: P,
+    support_file: P,
+    layout: Option<Layout>,
+    code_tables: &CodeTables,
+) -> anyhow::Result<CheckReport> {
+    let mut report = CheckReport::default();
+    // 補充票の交通事故が本票に存在するか確認するため、本票の交通事故識別子を記録
+    let mut identifiers = HashSet::new();
+
+    let mut reader = open_sheet(main_file)?;
+    let headers = reader.headers()?.clone();
+    let columns = MainColumns::resolve(layout, &headers)?;
+    for (row_index, row) in reader.into_records().enumerate() {
+        report.main_rows += 1;
+        let mut checker = RowChecker::new(Sheet::Main, &headers, row_index, code_tables);
+        let mut stop = false;
+        match row {
+            Ok(row) => {
+                if let Some(identifier) = checker.check_main_row(&row, &columns) {
+                    identifiers.insert(identifier);
+                }
+            }
+            Err(e) => {
+                // 入出力エラーの場合は以降の行を読み込めないため検査を中止
+                stop = matches!(e.kind(), csv::ErrorKind::Io(_));
+                checker.push_row_problem(format!("行を読み込めません。{}", e));
+            }
+        }
+        report.problems.append(&mut checker.problems);
+        if stop {
+            break;
+        }
+    }
+
+    let mut reader = open_sheet(support_file)?;
+    let headers = reader.headers()?.clone();
+    let columns = SupportColumns::resolve(layout, &headers)?;
+    for (row_index, row) in reader.into_records().enumerate() {
+        report.support_rows += 1;
+        let mut checker = RowChecker::new(Sheet::Support, &headers, row_index, code_tables);
+        let mut stop = false;
+        match row {
+            Ok(row) => checker.check_support_row(&row, &columns, Some(&identifiers)),
+            Err(e) => {
+                // 入出力エラーの場合は以降の行を読み込めないため検査を中止
+                stop = matches!(e.kind(), csv::ErrorKind::Io(_));
+                checker.push_row_problem(format!("行を読み込めません。{}", e));
+            }
+        }
+        report.problems.append(&mut checker.problems);
+        if stop {
+            break;
+        }
+    }
+
+    Ok(report)
+}
+
+/// 本票の行をコード表などで検査する。
+///
+/// # 引数
+///
+/// * `row` - 本票の行
+/// * `row_index` - 行のインデックス
+/// * `headers` - 本票のヘッダ行
+/// * `columns` - 本票の列の位置
+/// * `code_tables` - コード表
+///
+/// # 戻り値
+///
+/// 見つかった問題
+pub(crate) fn main_row_problems(
+    row: &csv::StringRecord,
+    row_index: usize,
+    headers: &csv::StringRecord,
+    columns: &MainColumns,
+    code_tables: &CodeTables,
+) -> Vec<Problem> {
+    let mut checker = RowChecker::new(Sheet::Main, headers, row_index, code_tables);
+    checker.check_main_row(row, columns);
+
+    checker.problems
+}
+
+/// 補充票の行をコード表などで検査する。
+///
+/// 補充票の交通事故が本票に存在するかは検査しない。
+///
+/// # 引数
+///
+/// * `row` - 補充票の行
+/// * `row_index` - 行のインデックス
+/// * `headers` - 補充票のヘッダ行
+/// * `columns` - 補充票の列の位置
+/// * `code_tables` - コード表
+///
+/// # 戻り値
+///
+/// 見つかった問題
+pub(crate) fn support_row_problems(
+    row: &csv::StringRecord,
+    row_index: usize,
+    headers: &csv::StringRecord,
+    columns: &SupportColumns,
+    code_tables: &CodeTables,
+) -> Vec<Problem> {
+    let mut checker = RowChecker::new(Sheet::Support, headers, row_index, code_tables);
+    checker.check_support_row(row, columns, None);
+
+    checker.problems
+}
+
+/// 問題をテキストで表現する。
+pub(crate) fn problem_to_text(problem: &Problem) -> String {
+    match (&problem.column, &problem.value) {
+        (Some(column), Some(value)) => format!(
+            "{} {}行目 {}: 値({}) {}",
+            problem.sheet.name(),
+            problem.row,
+            column,
+            value,
+            problem.message
+        ),
+        (Some(column), None) => format!(
+            "{} {}行目 {}: {}",
+            problem.sheet.name(),
+            problem.row,
+            column,
+            problem.message
+        ),
+        _ => format!(
+            "{} {}行目: {}",
+            problem.sheet.name(),
+            problem.row,
+            problem.message
+        ),
+    }
+}
+
+/// 1行を検査して、見つかった問題を記録する検査器
+struct RowChecker<'a> {
+    /// 票の種類
+    sheet: Sheet,
+    /// ヘッダ行
+    headers: &'a csv::StringRecord,
+    /// 行のインデックス
+    row_index: usize,
+    /// コード表
+    code_tables: &'a CodeTables,
+    /// 見つかった問題
+    problems: Vec<Problem>,
+}
+
+impl<'a> RowChecker<'a> {
+    fn new(
+        sheet: Sheet,
+        headers: &'a csv::StringRecord,
+        row_index: usize,
+        code_tables: &'a CodeTables,
+    ) -> Self {
+        Self {
+            sheet,
+            headers,
+            row_index,
+            code_tables,
+            problems: Vec::new(),
+        }
+    }
+
+    /// 本票の行を検査する。
+    ///
+    /// 都道府県コード、警察署コード及び本票番号が正しい場合は、交通事故識別子を返す。
+    fn check_main_row(
+        &mut self,
+        row: &csv::StringRecord,
+        columns: &MainColumns,
+    ) -> Option<RawAccidentIdentifier> {
+        let prefecture_code = self.code(row, columns.prefecture_code, "prefectures");
+        let police_station_code = self.police_station(
+            row,
+            prefecture_code.is_some(),
+            columns.prefecture_code,
+            columns.police_station_code,
+        );
+        let main_number = self.integer(row, columns.main_number);
+        self.code(row, columns.accident_detail_code, "accident_details");
+        self.integer(row, columns.number_of_deaths);
+        self.integer(row, columns.number_of_injuries);
+        self.route(row, columns.route_code);
+        self.integer(row, columns.location_code);
+        self.city(row, prefecture_code.as_deref(), columns.city_code);
+        self.datetime(row, columns.occurred_at);
+        self.time(row, columns.sunrise_time);
+        self.time(row, columns.sunset_time);
+        for (column_index, table) in [
+            (columns.day_night_code, "day_nights"),
+            (columns.weather_code, "weathers"),
+            (columns.district_code, "districts"),
+            (columns.surface_condition_code, "surface_conditions"),
+            (columns.road_model_code, "road_models"),
+            (columns.traffic_signal_code, "traffic_signals"),
+            (columns.stop_regulation_sign_a_code, "stop_regulation_signs"),
+            (
+                columns.stop_regulation_display_a_code,
+                "stop_regulation_displays",
+            ),
+            (columns.stop_regulation_sign_b_code, "stop_regulation_signs"),
+            (
+                columns.stop_regulation_display_b_code,
+                "stop_regulation_displays",
+            ),
+            (columns.road_width_code, "road_widths"),
+            (columns.road_alignment_code, "road_alignments"),
+            (columns.collision_point_code, "collision_points"),
+            (columns.zone_regulation_code, "zone_regulations"),
+            (columns.central_separation_code, "central_separations"),
+            (columns.road_segmentation_code, "road_segmentations"),
+            (columns.accident_type_code, "accident_types"),
+            (columns.age_a_code, "ages"),
+            (columns.age_b_code, "ages"),
+            (columns.party_a_code, "parties"),
+            (columns.party_b_code, "parties"),
+            (columns.purpose_a_code, "purposes"),
+            (columns.purpose_b_code, "purposes"),
+            (columns.vehicle_type_a_code, "vehicle_types"),
+            (columns.vehicle_type_b_code, "vehicle_types"),
+            (columns.automatic_a_code, "automatics"),
+            (columns.automatic_b_code, "automatics"),
+            (columns.support_car_a_code, "support_cars"),
+            (columns.support_car_b_code, "support_cars"),
+            (columns.speed_regulation_a_code, "speed_regulations"),
+            (columns.speed_regulation_b_code, "speed_regulations"),
+            (columns.vehicle_damage_a_code, "vehicle_damages"),
+            (columns.vehicle_damage_b_code, "vehicle_damages"),
+            (columns.airbag_a_code, "airbags"),
+            (columns.airbag_b_code, "airbags"),
+            (columns.side_airbag_a_code, "side_airbags"),
+            (columns.side_airbag_b_code, "side_airbags"),
+            (columns.injury_a_code, "injuries"),
+            (columns.injury_b_code, "injuries"),
+            (columns.week_code, "weeks"),
+            (columns.holiday_code, "holidays"),
+        ] {
+            self.code(row, column_index, table);
+        }
+        self.coordinate(row, columns.latitude, dms_to_latitude);
+        self.coordinate(row, columns.longitude, dms_to_longitude);
+        for column_index in [columns.cognitive_days_a, columns.cognitive_days_b]
+            .into_iter()
+            .flatten()
+        {
+            self.integer(row, column_index);
+        }
+        for column_index in [
+            columns.driving_practice_a_code,
+            columns.driving_practice_b_code,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            self.code(row, column_index, "driving_practices");
+        }
+
+        Some(RawAccidentIdentifier {
+            prefecture_code: prefecture_code?,
+            police_station_code: police_station_code?,
+            main_number: main_number?,
+        })
+    }
+
+    /// 補充票の行を検査する。
+    ///
+    /// 本票の交通事故識別子を指定した場合は、補充票の交通事故が本票に存在するかも検査する。
+    fn check_support_row(
+        &mut self,
+        row: &csv::StringRecord,
+        columns: &SupportColumns,
+        identifiers: Option<&HashSet<RawAccidentIdentifier>>,
+    ) {
+        let prefecture_code = self.code(row, columns.prefecture_code, "prefectures");
+        let police_station_code = self.police_station(
+            row,
+            prefecture_code.is_some(),
+            columns.prefecture_code,
+            columns.police_station_code,
+        );
+        let main_number = self.integer(row, columns.main_number);
+        if let (
+            Some(identifiers),
+            Some(prefecture_code),
+            Some(police_station_code),
+            Some(main_number),
+        ) = (
+            identifiers,
+            prefecture_code,
+            police_station_code,
+            main_number,
+        ) {
+            let identifier = RawAccidentIdentifier {
+                prefecture_code,
+                police_station_code,
+                main_number,
+            };
+            if !identifiers.contains(&identifier) {
+                self.push_problem(
+                    columns.main_number,
+                    row,
+                    "本票に該当する交通事故がありません。",
+                );
+            }
+        }
+        self.integer(row, columns.sub_number);
+        for (column_index, table) in [
+            (columns.party_code, "parties"),
+            (columns.riding_type_code, "riding_types"),
+            (columns.riding_class_code, "riding_classes"),
+            (columns.support_car_code, "support_cars"),
+            (columns.airbag_code, "airbags"),
+            (columns.side_airbag_code, "side_airbags"),
+            (columns.injury_code, "injuries"),
+        ] {
+            self.code(row, column_index, table);
+        }
+        for (column_index, table) in [
+            (columns.purpose_code, "purposes"),
+            (columns.vehicle_type_code, "vehicle_types"),
+            (columns.vehicle_damage_code, "vehicle_damages"),
+        ] {
+            self.optional_code(row, column_index, table);
+        }
+    }
+
+    /// 行全体の問題を記録する。
+    fn push_row_problem(&mut self, message: String) {
+        self.problems.push(Problem {
+            sheet: self.sheet,
+            row: self.row_index + 1,
+            column: None,
+            value: None,
+            message,
+        });
+    }
+
+    /// 列の問題を記録する。
+    fn push_problem(&mut self, column_index: usize, row: &csv::StringRecord, message: &str) {
+        self.problems.push(Problem {
+            sheet: self.sheet,
+            row: self.row_index + 1,
+            column: Some(self.column_name(column_index)),
+            value: row.get(column_index).map(|value| value.to_string()),
+            message: message.to_string(),
+        });
+    }
+
+    /// 列名を返す。
+    fn column_name(&self, column_index: usize) -> String {
+        match self.headers.get(column_index) {
+            Some(header) => header.to_string(),
+            None => format!("{}列", column_index + 1),
+        }
+    }
+
+    /// 列の値を返す。
+    fn value<'r>(&mut self, row: &'r csv::StringRecord, column_index: usize) -> Option<&'r str> {
+        let value = row.get(column_index);
+        if value.is_none() {
+            self.push_problem(column_index, row, "列がありません。");
+        }
+
+        value
+    }
+
+    /// 列の値がコード表に存在するか検査する。
+    fn code(
+        &mut self,
+        row: &csv::StringRecord,
+        column_index: usize,
+        table: &str,
+    ) -> Option<String> {
+        let value = self.value(row, column_index)?;
+        if !self.code_tables.contains(table, value) {
+            let message = format!("コード表({})に存在しないコードです。", table);
+            self.push_problem(column_index, row, &message);
+            return None;
+        }
+
+        Some(value.to_string())
+    }
+
+    /// 列の値が空でない場合に、コード表に存在するか検査する。
+    fn optional_code(&mut self, row: &csv::StringRecord, column_index: usize, table: &str) {
+        if let Some(value) = self.value(row, column_index) {
+            if !value.is_empty() {
+                self.code(row, column_index, table);
+            }
+        }
+    }
+
+    /// 列の値が整数か検査する。
+    fn integer(&mut self, row: &csv::StringRecord, column_index: usize) -> Option<i32> {
+        let value = self.value(row, column_index)?;
+        match value.parse::<i32>() {
+            Ok(value) => Some(value),
+            Err(_) => {
+                self.push_problem(column_index, row, "数値に変換できません。");
+                None
+            }
+        }
+    }
+
+    /// 都道府県コードと警察署コードの組み合わせが警察署テーブルに存在するか検査する。
+    fn police_station(
+        &mut self,
+        row: &csv::StringRecord,
+        valid_prefecture_code: bool,
+        prefecture_column_index: usize,
+        police_station_column_index: usize,
+    ) -> Option<String> {
+        let police_station_code = self.value(row, police_station_column_index)?;
+        if !valid_prefecture_code {
+            return None;
+        }
+        let prefecture_code = row.get(prefecture_column_index)?;
+        let code = format!("{}{}", prefecture_code, police_station_code);
+        if !self.code_tables.contains("police_stations", &code) {
+            self.push_problem(
+                police_station_column_index,
+                row,
+                "コード表(police_stations)に存在しない警察署等コードです。",
+            );
+            return None;
+        }
+
+        Some(police_station_code.to_string())
+    }
+
+    /// 路線コードと路線区分コードを検査する。
+    fn route(&mut self, row: &csv::StringRecord, column_index: usize) {
+        let Some(route) = self.value(row, column_index) else {
+            return;
+        };
+        if !is_route_format(route) {
+            self.push_problem(
+                column_index,
+                row,
+                "路線コード4桁と路線区分コード1桁の形式ではありません。",
+            );
+            return;
+        }
+        if !self.code_tables.contains_route(&route[0..4]) {
+            self.push_problem(
+                column_index,
+                row,
+                "コード表(routes)に存在しない路線コードです。",
+            );
+        }
+        if !self.code_tables.contains("route_classes", &route[4..5]) {
+            self.push_problem(
+                column_index,
+                row,
+                "コード表(route_classes)に存在しない路線区分コードです。",
+            );
+        }
+    }
+
+    /// 都道府県コードと市区町村コードから決定した市区町村コードが市区町村テーブルに存在するか検査する。
+    fn city(
+        &mut self,
+        row: &csv::StringRecord,
+        prefecture_code: Option<&str>,
+        column_index: usize,
+    ) {
+        let Some(city_code) = self.value(row, column_index) else {
+            return;
+        };
+        let Some(prefecture_jis_code) =
+            prefecture_code.and_then(|code| self.code_tables.prefectures().get(code))
+        else {
+            return;
+        };
+        let city_jis_code = format!("{}{}", prefecture_jis_code, city_code);
+        if !self.code_tables.contains("cities", &city_jis_code) {
+            let message = format!(
+                "コード表(cities)に存在しない市区町村コード({})です。",
+                city_jis_code
+            );
+            self.push_problem(column_index, row, &message);
+        }
+    }
+
+    /// 発生日時の列を検査する。
+    fn datetime(&mut self, row: &csv::StringRecord, column_indexes: [usize; 5]) {
+        let values = column_indexes.map(|column_index| self.integer(row, column_index));
+        let [Some(year), Some(month), Some(day), Some(hour), Some(minute)] = values else {
+            return;
+        };
+        let result = match (
+            u8::try_from(month),
+            u8::try_from(day),
+            u8::try_from(hour),
+            u8::try_from(minute),
+        ) {
+            (Ok(month), Ok(day), Ok(hour), Ok(minute)) => {
+                offset_datetime(year, month, day, hour, minute).map(|_| ())
+            }
+            _ => Err(anyhow::anyhow!("日時が範囲外です。")),
+        };
+        if let Err(e) = result {
+            let value = column_indexes
+                .iter()
+                .map(|column_index| row.get(*column_index).unwrap_or_default())
+                .collect::<Vec<_>>()
+                .join("/");
+            self.problems.push(Problem {
+                sheet: self.sheet,
+                row: self.row_index + 1,
+                column: Some(self.column_name(column_indexes[0])),
+                value: Some(value),
+                message: e.to_string(),
+            });
+        }
+    }
+
+    /// 時刻（時と分）の列を検査する。
+    fn time(&mut self, row: &csv::StringRecord, column_indexes: [usize; 2]) {
+        let values = column_indexes.map(|column_index| self.integer(row, column_index));
+        let [Some(hour), Some(minute)] = values else {
+            return;
+        };
+        let result = match (u8::try_from(hour), u8::try_from(minute)) {
+            (Ok(hour), Ok(minute)) => time(hour, minute).map(|_| ()),
+            _ => Err(anyhow::anyhow!("時刻({}:{})が範囲外です。", hour, minute)),
+        };
+        if let Err(e) = result {
+            self.problems.push(Problem {
+                sheet: self.sheet,
+                row: self.row_index + 1,
+                column: Some(self.column_name(column_indexes[0])),
+                value: Some(format!("{}:{}", hour, minute)),
+                message: e.to_string(),
+            });
+        }
+    }
+
+    /// 緯度または経度の列を検査する。
+    fn coordinate(
+        &mut self,
+        row: &csv::StringRecord,
+        column_index: usize,
+        dms_to_degree: fn(&str) -> anyhow::Result<f64>,
+    ) {
+        let Some(value) = self.value(row, column_index) else {
+            return;
+        };
+        if let Err(e) = dms_to_degree(value) {
+            self.push_problem(column_index, row, &e.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIN_ROW: &str = "1,10,101,0001,2,000,001,40010,0000,101,2022,01,22,14,18,12,06,59,16,33,5,1,3,14,7,00,00,00,00,04,9,01,70,1,4,21,35,25,03,04,31,31,01,01,1,1,00,00,04,04,30,30,3,3,2,2,2,2,2,4,430234789,1412612831,7,3,9999,9999,1,1";
+
+    fn record(row: &str) -> csv::StringRecord {
+        csv::StringRecord::from(row.split(',').collect::<Vec<_>>())
+    }
+
+    fn check_main_row_for_test(row: &str) -> (Option<RawAccidentIdentifier>, Vec<Problem>) {
+        let code_tables = CodeTables::load().unwrap();
+        let headers = csv::StringRecord::new();
+        let columns = Layout::Y2022.main_columns();
+        let mut checker = RowChecker::new(Sheet::Main, &headers, 0, &code_tables);
+        let identifier = checker.check_main_row(&record(row), &columns);
+
+        (identifier, checker.problems)
+    }
+
+    #[test]
+    fn check_main_row_ok() {
+        let (identifier, problems) = check_main_row_for_test(MAIN_ROW);
+
+        assert!(problems.is_empty(), "{:?}", problems);
+        assert_eq!(
+            identifier,
+            Some(RawAccidentIdentifier {
+                prefecture_code: String::from("10"),
+                police_station_code: String::from("101"),
+                main_number: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn check_main_row_reports_every_problem() {
+        // 天候を99、月を13、緯度を不正な値に変更
+        let row = MAIN_ROW
+            .replacen(",2022,01,22,", ",2022,13,22,", 1)
+            .replacen(",12,06,59,16,33,5,", ",12,06,59,16,33,99,", 1)
+            .replacen(",430234789,", ",43X234789,", 1);
+        let (_, problems) = check_main_row_for_test(&row);
+
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+        assert_eq!(problems[0].value.as_deref(), Some("2022/13/22/14/18"));
+        assert_eq!(problems[1].column.as_deref(), Some("21列"));
+        assert_eq!(problems[1].value.as_deref(), Some("99"));
+        assert_eq!(problems[2].value.as_deref(), Some("43X234789"));
+        assert!(problems
+            .iter()
+            .all(|p| p.row == 1 && p.sheet == Sheet::Main));
+    }
+
+    #[test]
+    fn check_support_row_without_accident() {
+        let code_tables = CodeTables::load().unwrap();
+        let headers = csv::StringRecord::new();
+        let columns = Layout::Y2022.support_columns();
+        let mut checker = RowChecker::new(Sheet::Support, &headers, 0, &code_tables);
+        checker.check_support_row(
+            &record("2,10,101,0001,001,03,01,01,2,01,00,2,2,2,,"),
+            &columns,
+            Some(&HashSet::new()),
+        );
+
+        assert_eq!(checker.problems.len(), 1, "{:?}", checker.problems);
+        assert_eq!(
+            checker.problems[0].message,
+            "本票に該当する交通事故がありません。"
+        );
+    }
+}
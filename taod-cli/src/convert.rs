@@ -0,0 +1,111 @@
+use std::path::Path;
+
+use crate::export::{accident_layer, geojson, involved_person_layer, parquet, write_files};
+use crate::layouts::Layout;
+use code_tables::Language;
+use geometries::Srid;
+
+/// 変換形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ConvertFormat {
+    /// GeoJSON
+    #[value(name = "geojson")]
+    GeoJson,
+    /// GeoParquet
+    Parquet,
+}
+
+impl ConvertFormat {
+    /// 出力ファイルの拡張子から変換形式を判定する。
+    ///
+    /// # 引数
+    ///
+    /// * `path` - 出力ファイルパス
+    ///
+    /// # 戻り値
+    ///
+    /// 変換形式、拡張子から判定できない場合は`None`
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+        match extension.as_str() {
+            "geojson" | "json" => Some(Self::GeoJson),
+            "parquet" | "geoparquet" => Some(Self::Parquet),
+            _ => None,
+        }
+    }
+}
+
+/// データベースに接続せずに、本票と補充票をGeoJSONまたはGeoParquetに変換する。
+///
+/// 交通事故はすべての属性とコードの名前を持つポイントのフィーチャー、交通事故当事者以外の関与者は
+/// 交通事故IDで交通事故と関連付けた表として、`<ファイル名>_involved_persons`ファイルに出力する。
+/// 本票と補充票は、1行ずつ読み込みながら出力する。
+///
+/// # 引数
+///
+/// * `main_file` - 本票ファイルパス
+/// * `support_file` - 補充票ファイルパス
+/// * `output` - 出力ファイルパス
+/// * `format` - 変換形式（指定しない場合は出力ファイルの拡張子から判定）
+/// * `layout` - 本票及び補充票のレイアウト（指定しない場合はヘッダ行から列の位置を決定）
+/// * `labels` - コードの名前の言語
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn convert<P: AsRef<Path>>(
+    main_file: P,
+    support_file: P,
+    output: P,
+    format: Option<ConvertFormat>,
+    layout: Option<Layout>,
+    labels: Language,
+) -> anyhow::Result<()> {
+    let output = output.as_ref();
+    let format = format
+        .or_else(|| ConvertFormat::from_path(output))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "出力ファイルの拡張子から変換形式を判定できません。--formatを指定してください({})。",
+                output.display()
+            )
+        })?;
+    let main_file = main_file.as_ref();
+    let support_file = support_file.as_ref();
+
+    // 本票ファイルの緯度と経度をそのまま出力するため、空間参照系はJGD2011とする
+    let accidents = accident_layer(labels, Srid::JGD2011);
+    let involved_persons = involved_person_layer(labels, Srid::JGD2011);
+    let summary = match format {
+        ConvertFormat::GeoJson => {
+            let writer = geojson::Writer::create(output, accidents, involved_persons)?;
+            write_files(writer, main_file, support_file, layout, labels).await?
+        }
+        ConvertFormat::Parquet => {
+            let writer = parquet::Writer::create(output, accidents, involved_persons)?;
+            write_files(writer, main_file, support_file, layout, labels).await?
+        }
+    };
+    summary.print();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_format_from_path_ok() {
+        assert_eq!(
+            ConvertFormat::from_path(Path::new("out.geojson")),
+            Some(ConvertFormat::GeoJson)
+        );
+        assert_eq!(
+            ConvertFormat::from_path(Path::new("out/accidents.PARQUET")),
+            Some(ConvertFormat::Parquet)
+        );
+        assert_eq!(ConvertFormat::from_path(Path::new("out.csv")), None);
+        assert_eq!(ConvertFormat::from_path(Path::new("out")), None);
+    }
+}
@@ -0,0 +1,91 @@
+use time::format_description::well_known::Rfc3339;
+use uuid::Uuid;
+
+use crate::db::{count_import_rejects, delete_dataset, find_dataset, list_datasets};
+use db::connection_pool;
+
+/// 登録されているデータセットを一覧表示する。
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn list() -> anyhow::Result<()> {
+    let pool = connection_pool().await?;
+    let datasets = list_datasets(&pool).await?;
+
+    println!("ID\t年\t登録日時\t交通事故\t関係者\t高速票\t本票ファイル");
+    for dataset in datasets {
+        println!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            dataset.id,
+            dataset.year,
+            dataset.imported_at.format(&Rfc3339)?,
+            dataset.number_of_accidents,
+            dataset.number_of_involved_persons,
+            dataset.number_of_highway_details,
+            dataset.main_file_name,
+        );
+    }
+
+    Ok(())
+}
+
+/// データセットの詳細を表示する。
+///
+/// # 引数
+///
+/// * `id` - データセットID
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn show(id: Uuid) -> anyhow::Result<()> {
+    let pool = connection_pool().await?;
+    let dataset = find_dataset(&pool, id)
+        .await?
+        .ok_or(anyhow::anyhow!("データセット({})が見つかりません。", id))?;
+
+    println!("ID: {}", dataset.id);
+    println!("年: {}", dataset.year);
+    println!("登録日時: {}", dataset.imported_at.format(&Rfc3339)?);
+    println!(
+        "本票: {} (SHA-256: {})",
+        dataset.main_file_name, dataset.main_file_sha256
+    );
+    println!(
+        "補充票: {} (SHA-256: {})",
+        dataset.support_file_name, dataset.support_file_sha256
+    );
+    match (dataset.highway_file_name, dataset.highway_file_sha256) {
+        (Some(name), Some(sha256)) => println!("高速票: {} (SHA-256: {})", name, sha256),
+        _ => println!("高速票: なし"),
+    }
+    println!("交通事故: {}件", dataset.number_of_accidents);
+    println!("関係者: {}件", dataset.number_of_involved_persons);
+    println!("高速票: {}件", dataset.number_of_highway_details);
+    println!(
+        "登録除外: {}件",
+        count_import_rejects(&pool, dataset.id).await?
+    );
+
+    Ok(())
+}
+
+/// データセットと、データセットに含まれる交通事故などを削除する。
+///
+/// # 引数
+///
+/// * `id` - データセットID
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn delete(id: Uuid) -> anyhow::Result<()> {
+    let pool = connection_pool().await?;
+    if !delete_dataset(&pool, id).await? {
+        return Err(anyhow::anyhow!("データセット({})が見つかりません。", id));
+    }
+    println!("データセット({})を削除しました。", id);
+
+    Ok(())
+}
@@ -1,12 +1,23 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use futures::stream::BoxStream;
+use futures::StreamExt;
 use geo_types::Point;
-use geozero::wkb;
-use sqlx::PgPool;
+use geozero::{wkb, CoordDimensions, ToWkb};
+use sqlx::postgres::PgRow;
+use sqlx::{PgConnection, PgPool, Row};
+use time::format_description::well_known::Rfc3339;
+use time::{OffsetDateTime, Time};
+use uuid::Uuid;
 
-use crate::files::{RawAccident, RawInvolvedPerson};
+use crate::files::{RawAccident, RawHighwayRecord, RawInvolvedPerson, RowError};
+use crate::sheet::Sheet;
+use code_tables::{LaneCode, RoadStructureCode, UpDownCode};
 use db::PgTransaction;
-use geometries::GeometryF64;
+use geometries::{GeometryF64, Srid};
+
+/// COPY文で1回に送信する行数
+const COPY_CHUNK_ROWS: usize = 10_000;
 
 /// 本票の都道府県コードとJIS規格の都道府県コードの対応を記録したハッシュマップを返す。
 ///
@@ -34,12 +45,605 @@ pub async fn prefecture_hash_map(pool: &PgPool) -> anyhow::Result<HashMap<String
     Ok(prefectures)
 }
 
+/// データセット
+#[derive(Debug, Clone)]
+pub struct Dataset {
+    /// データセットID
+    pub id: Uuid,
+    /// 年
+    pub year: i32,
+    /// 本票ファイル名
+    pub main_file_name: String,
+    /// 本票ファイルのSHA-256ハッシュ
+    pub main_file_sha256: String,
+    /// 補充票ファイル名
+    pub support_file_name: String,
+    /// 補充票ファイルのSHA-256ハッシュ
+    pub support_file_sha256: String,
+    /// 高速票ファイル名
+    pub highway_file_name: Option<String>,
+    /// 高速票ファイルのSHA-256ハッシュ
+    pub highway_file_sha256: Option<String>,
+    /// 交通事故の件数
+    pub number_of_accidents: i32,
+    /// 交通事故当事者以外の関係者の件数
+    pub number_of_involved_persons: i32,
+    /// 高速道路で発生した交通事故の詳細の件数
+    pub number_of_highway_details: i32,
+    /// 登録日時
+    pub imported_at: OffsetDateTime,
+}
+
+/// データセットをデータベースに登録する。
+///
+/// # 引数
+///
+/// * `tx` - データベーストランザクション
+/// * `dataset` - データセット
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn register_dataset(tx: &mut PgTransaction<'_>, dataset: &Dataset) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO datasets (
+            id,
+            year,
+            main_file_name,
+            main_file_sha256,
+            support_file_name,
+            support_file_sha256,
+            highway_file_name,
+            highway_file_sha256,
+            number_of_accidents,
+            number_of_involved_persons,
+            number_of_highway_details,
+            imported_at
+        ) VALUES (
+            $1,
+            $2,
+            $3,
+            $4,
+            $5,
+            $6,
+            $7,
+            $8,
+            $9,
+            $10,
+            $11,
+            $12
+        );
+        "#,
+        dataset.id,
+        dataset.year,
+        dataset.main_file_name,
+        dataset.main_file_sha256,
+        dataset.support_file_name,
+        dataset.support_file_sha256,
+        dataset.highway_file_name,
+        dataset.highway_file_sha256,
+        dataset.number_of_accidents,
+        dataset.number_of_involved_persons,
+        dataset.number_of_highway_details,
+        dataset.imported_at,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "データセットをデータベースに登録する際に、INSERT文を実行できませんでした。{}",
+            e
+        )
+    })?;
+
+    Ok(())
+}
+
+/// データセットの年と件数を更新する。
+///
+/// # 引数
+///
+/// * `tx` - トランザクション
+/// * `dataset` - データセット
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn update_dataset_summary(
+    tx: &mut PgTransaction<'_>,
+    dataset: &Dataset,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE datasets
+        SET
+            year = $2,
+            number_of_accidents = $3,
+            number_of_involved_persons = $4,
+            number_of_highway_details = $5
+        WHERE id = $1
+        "#,
+        dataset.id,
+        dataset.year,
+        dataset.number_of_accidents,
+        dataset.number_of_involved_persons,
+        dataset.number_of_highway_details,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "データセットの件数を更新する際に、UPDATE文を実行できませんでした。{}",
+            e
+        )
+    })?;
+
+    Ok(())
+}
+
+/// 交通事故を登録したデータセットのデータセットIDを返す。
+///
+/// # 引数
+///
+/// * `tx` - トランザクション
+/// * `accident_ids` - 交通事故IDを格納したハッシュセット
+///
+/// # 戻り値
+///
+/// データセットIDを格納したハッシュセット
+pub async fn accident_dataset_ids(
+    tx: &mut PgTransaction<'_>,
+    accident_ids: &HashSet<Uuid>,
+) -> anyhow::Result<HashSet<Uuid>> {
+    let accident_ids = accident_ids.iter().copied().collect::<Vec<_>>();
+    let dataset_ids = sqlx::query_scalar!(
+        r#"
+        SELECT DISTINCT dataset_id "dataset_id!"
+        FROM accidents
+        WHERE id = ANY($1) AND dataset_id IS NOT NULL
+        "#,
+        &accident_ids,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "交通事故を登録したデータセットをデータベースから取得できませんでした。{}",
+            e
+        )
+    })?;
+
+    Ok(dataset_ids.into_iter().collect())
+}
+
+/// データセットに属する交通事故、関係者及び高速票の件数を数え直す。
+///
+/// 登録済みの交通事故を更新すると、交通事故は更新したデータセットに移るため、
+/// 移る前のデータセットの件数を数え直す。
+///
+/// # 引数
+///
+/// * `tx` - トランザクション
+/// * `dataset_ids` - データセットIDを格納したハッシュセット
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn recount_datasets(
+    tx: &mut PgTransaction<'_>,
+    dataset_ids: &HashSet<Uuid>,
+) -> anyhow::Result<()> {
+    let dataset_ids = dataset_ids.iter().copied().collect::<Vec<_>>();
+    sqlx::query!(
+        r#"
+        UPDATE datasets d
+        SET
+            number_of_accidents = (
+                SELECT COUNT(*) FROM accidents a WHERE a.dataset_id = d.id
+            ),
+            number_of_involved_persons = (
+                SELECT COUNT(*)
+                FROM involved_persons p
+                INNER JOIN accidents a ON p.accident_id = a.id
+                WHERE a.dataset_id = d.id
+            ),
+            number_of_highway_details = (
+                SELECT COUNT(*)
+                FROM highway_details h
+                INNER JOIN accidents a ON h.accident_id = a.id
+                WHERE a.dataset_id = d.id
+            )
+        WHERE d.id = ANY($1)
+        "#,
+        &dataset_ids,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "データセットの件数を数え直す際に、UPDATE文を実行できませんでした。{}",
+            e
+        )
+    })?;
+
+    Ok(())
+}
+
+/// データセットを登録日時の順に返す。
+///
+/// # 引数
+///
+/// * `pool` - データベースコネクションプール
+///
+/// # 戻り値
+///
+/// データセットを格納したベクタ
+pub async fn list_datasets(pool: &PgPool) -> anyhow::Result<Vec<Dataset>> {
+    let datasets = sqlx::query_as!(
+        Dataset,
+        r#"
+        SELECT
+            id,
+            year,
+            main_file_name,
+            main_file_sha256,
+            support_file_name,
+            support_file_sha256,
+            highway_file_name,
+            highway_file_sha256,
+            number_of_accidents,
+            number_of_involved_persons,
+            number_of_highway_details,
+            imported_at
+        FROM datasets
+        ORDER BY imported_at
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(datasets)
+}
+
+/// データセットを返す。
+///
+/// # 引数
+///
+/// * `pool` - データベースコネクションプール
+/// * `id` - データセットID
+///
+/// # 戻り値
+///
+/// データセット、データセットが存在しない場合は`None`
+pub async fn find_dataset(pool: &PgPool, id: Uuid) -> anyhow::Result<Option<Dataset>> {
+    let dataset = sqlx::query_as!(
+        Dataset,
+        r#"
+        SELECT
+            id,
+            year,
+            main_file_name,
+            main_file_sha256,
+            support_file_name,
+            support_file_sha256,
+            highway_file_name,
+            highway_file_sha256,
+            number_of_accidents,
+            number_of_involved_persons,
+            number_of_highway_details,
+            imported_at
+        FROM datasets
+        WHERE id = $1
+        "#,
+        id,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(dataset)
+}
+
+/// データセットを削除する。
+///
+/// データセットに含まれる交通事故、交通事故当事者以外の関係者及び高速道路で発生した
+/// 交通事故の詳細も削除される。
+///
+/// # 引数
+///
+/// * `pool` - データベースコネクションプール
+/// * `id` - データセットID
+///
+/// # 戻り値
+///
+/// データセットを削除した場合は`true`、データセットが存在しない場合は`false`
+pub async fn delete_dataset(pool: &PgPool, id: Uuid) -> anyhow::Result<bool> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM datasets WHERE id = $1
+        "#,
+        id,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(0 < result.rows_affected())
+}
+
+/// 登録から除外した票の行
+#[derive(Debug, Clone)]
+pub struct ImportReject {
+    /// 票の種類
+    pub sheet: Sheet,
+    /// 行番号（ヘッダ行を除き1から開始）
+    pub row_number: i32,
+    /// CSV形式の行（行を読み込めなかった場合は`None`）
+    pub line: Option<String>,
+    /// エラーメッセージ
+    pub error_message: String,
+}
+
+impl ImportReject {
+    /// 票の行を読み込めなかった、または変換できなかったことを示すエラーから、登録から除外した行を構築する。
+    ///
+    /// # 引数
+    ///
+    /// * `sheet` - 票の種類
+    /// * `error` - 票の行を読み込めなかった、または変換できなかったことを示すエラー
+    ///
+    /// # 戻り値
+    ///
+    /// 登録から除外した票の行
+    pub fn new(sheet: Sheet, error: RowError) -> Self {
+        Self {
+            sheet,
+            row_number: error.row_index as i32 + 1,
+            line: error.line,
+            error_message: error.error.to_string(),
+        }
+    }
+}
+
+/// 登録から除外した票の行をデータベースに登録する。
+///
+/// # 引数
+///
+/// * `tx` - トランザクション
+/// * `dataset_id` - データセットID
+/// * `rejects` - 登録から除外した票の行
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn register_import_rejects(
+    tx: &mut PgTransaction<'_>,
+    dataset_id: Uuid,
+    rejects: &[ImportReject],
+) -> anyhow::Result<()> {
+    if rejects.is_empty() {
+        return Ok(());
+    }
+    let ids = rejects.iter().map(|_| Uuid::new_v4()).collect::<Vec<_>>();
+    let sheets = rejects
+        .iter()
+        .map(|r| r.sheet.code().to_string())
+        .collect::<Vec<_>>();
+    let row_numbers = rejects.iter().map(|r| r.row_number).collect::<Vec<_>>();
+    let lines = rejects.iter().map(|r| r.line.clone()).collect::<Vec<_>>();
+    let error_messages = rejects
+        .iter()
+        .map(|r| r.error_message.clone())
+        .collect::<Vec<_>>();
+    sqlx::query!(
+        r#"
+        INSERT INTO import_rejects (id, dataset_id, sheet, row_number, line, error_message)
+        SELECT id, $2, sheet, row_number, line, error_message
+        FROM UNNEST($1::uuid[], $3::text[], $4::integer[], $5::text[], $6::text[])
+            AS r(id, sheet, row_number, line, error_message)
+        "#,
+        &ids,
+        dataset_id,
+        &sheets,
+        &row_numbers,
+        &lines as &[Option<String>],
+        &error_messages,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "登録から除外した行をデータベースに登録する際に、INSERT文を実行できませんでした。{}",
+            e
+        )
+    })?;
+
+    Ok(())
+}
+
+/// データセットの登録から除外した票の行数を返す。
+///
+/// # 引数
+///
+/// * `pool` - データベースコネクションプール
+/// * `dataset_id` - データセットID
+///
+/// # 戻り値
+///
+/// 登録から除外した票の行数
+pub async fn count_import_rejects(pool: &PgPool, dataset_id: Uuid) -> anyhow::Result<i64> {
+    let count = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) AS "count!"
+        FROM import_rejects
+        WHERE dataset_id = $1
+        "#,
+        dataset_id,
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "登録から除外した行の数をデータベースから取得できませんでした。{}",
+            e
+        )
+    })?;
+
+    Ok(count)
+}
+
+/// 登録済みの交通事故と重複した場合の動作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OnConflict {
+    /// 登録済みの交通事故を更新する。
+    Update,
+    /// 登録済みの交通事故を登録しない。
+    Skip,
+    /// エラーにする。
+    #[default]
+    Fail,
+}
+
+/// 登録済みの交通事故の交通事故IDを、交通事故に設定する。
+///
+/// 都道府県コード、警察署コード及び本票番号が一致する交通事故が登録されている場合、
+/// その交通事故IDを交通事故に設定する。
+///
+/// # 引数
+///
+/// * `tx` - トランザクション
+/// * `accidents` - 交通事故を格納したベクタ
+///
+/// # 戻り値
+///
+/// 登録済みの交通事故の交通事故IDを格納したハッシュセット
+pub async fn assign_existing_accident_ids(
+    tx: &mut PgTransaction<'_>,
+    accidents: &mut [RawAccident],
+) -> anyhow::Result<HashSet<Uuid>> {
+    let prefecture_codes = accidents
+        .iter()
+        .map(|a| a.prefecture_code.to_string())
+        .collect::<Vec<_>>();
+    let police_station_codes = accidents
+        .iter()
+        .map(|a| a.police_station_code.station_code().to_string())
+        .collect::<Vec<_>>();
+    let main_numbers = accidents.iter().map(|a| a.main_number).collect::<Vec<_>>();
+    let rows = sqlx::query!(
+        r#"
+        SELECT a.id, a.prefecture_code, a.police_station_code, a.main_number
+        FROM accidents a
+        INNER JOIN UNNEST($1::text[], $2::text[], $3::integer[])
+            AS k(prefecture_code, police_station_code, main_number)
+            ON a.prefecture_code = k.prefecture_code
+            AND a.police_station_code = k.police_station_code
+            AND a.main_number = k.main_number
+        "#,
+        &prefecture_codes,
+        &police_station_codes,
+        &main_numbers,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "登録済みの交通事故をデータベースから取得できませんでした。{}",
+            e
+        )
+    })?;
+    let existing_ids = rows
+        .into_iter()
+        .map(|row| {
+            (
+                (
+                    row.prefecture_code,
+                    row.police_station_code,
+                    row.main_number,
+                ),
+                row.id,
+            )
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut ids = HashSet::new();
+    for accident in accidents.iter_mut() {
+        let key = (
+            accident.prefecture_code.to_string(),
+            accident.police_station_code.station_code().to_string(),
+            accident.main_number,
+        );
+        if let Some(id) = existing_ids.get(&key) {
+            accident.id = *id;
+            ids.insert(*id);
+        }
+    }
+
+    Ok(ids)
+}
+
+/// 交通事故に関連する交通事故当事者以外の関係者と高速道路で発生した交通事故の詳細を
+/// データベースから削除する。
+///
+/// 高速票を登録し直さない場合は、登録済みの高速道路で発生した交通事故の詳細を削除しない。
+///
+/// # 引数
+///
+/// * `tx` - データベーストランザクション
+/// * `accident_ids` - 交通事故IDを格納したハッシュセット
+/// * `highway_details` - 高速道路で発生した交通事故の詳細を削除するかを示すフラグ
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn delete_accident_children(
+    tx: &mut PgTransaction<'_>,
+    accident_ids: &HashSet<Uuid>,
+    highway_details: bool,
+) -> anyhow::Result<()> {
+    let accident_ids = accident_ids.iter().copied().collect::<Vec<_>>();
+    sqlx::query!(
+        r#"
+        DELETE FROM involved_persons WHERE accident_id = ANY($1)
+        "#,
+        &accident_ids,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "交通事故当事者以外の関係者をデータベースから削除する際に、DELETE文を実行できませんでした。{}",
+            e
+        )
+    })?;
+    if !highway_details {
+        return Ok(());
+    }
+    sqlx::query!(
+        r#"
+        DELETE FROM highway_details WHERE accident_id = ANY($1)
+        "#,
+        &accident_ids,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "高速道路で発生した交通事故の詳細をデータベースから削除する際に、DELETE文を実行できませんでした。{}",
+            e
+        )
+    })?;
+
+    Ok(())
+}
+
 /// 交通事故をデータベースに登録する。
 ///
 /// # 引数
 ///
 /// * `tx` - データベーストランザクション
 /// * `accidents` - 交通事故を格納したベクタ
+/// * `dataset_id` - データセットID
+/// * `on_conflict` - 登録済みの交通事故と重複した場合の動作
 ///
 /// # 戻り値
 ///
@@ -47,11 +651,19 @@ pub async fn prefecture_hash_map(pool: &PgPool) -> anyhow::Result<HashMap<String
 pub async fn register_accidents(
     tx: &mut PgTransaction<'_>,
     accidents: &[RawAccident],
+    dataset_id: Uuid,
+    on_conflict: OnConflict,
 ) -> anyhow::Result<()> {
     for (index, accident) in accidents.iter().enumerate() {
         let location = Point::new(accident.location.x(), accident.location.y());
         let location: GeometryF64 = location.into();
-        insert_accident(tx, accident, location).await.map_err(|e| {
+        let result = match on_conflict {
+            OnConflict::Update => upsert_accident(tx, accident, location, dataset_id).await,
+            OnConflict::Skip | OnConflict::Fail => {
+                insert_accident(tx, accident, location, dataset_id).await
+            }
+        };
+        result.map_err(|e| {
             anyhow::anyhow!(
                 "交通事故をデータベースに登録する際に、INSERT文を実行できませんでした。{}: {:?}: {}データ目",
                 e,
@@ -68,6 +680,7 @@ async fn insert_accident(
     tx: &mut PgTransaction<'_>,
     accident: &RawAccident,
     location: GeometryF64,
+    dataset_id: Uuid,
 ) -> anyhow::Result<()> {
     sqlx::query!(
         r#"
@@ -133,7 +746,8 @@ async fn insert_accident(
             cognitive_days_a,
             cognitive_days_b,
             driving_practice_a_code,
-            driving_practice_b_code
+            driving_practice_b_code,
+            dataset_id
         ) VALUES (
             $1,
             $2,
@@ -190,76 +804,79 @@ async fn insert_accident(
             $53,
             $54,
             $55,
-            ST_SetSRID($56::geometry, 6668),
+            ST_SetSRID($56::geometry, $64),
             $57,
             $58,
             $59,
             $60,
             $61,
-            $62
+            $62,
+            $63
         );"#,
         accident.id,
-        accident.prefecture_code,
-        accident.police_station_code,
+        accident.prefecture_code.as_str(),
+        accident.police_station_code.station_code(),
         accident.main_number,
-        accident.accident_detail_code,
+        accident.accident_detail_code.as_str(),
         accident.number_of_deaths,
         accident.number_of_injuries,
-        accident.route_code,
-        accident.route_class_code,
+        accident.route_code.as_str(),
+        accident.route_class_code.as_str(),
         accident.location_code,
-        accident.city_jis_code,
+        accident.city_jis_code.as_str(),
         accident.occurred_at,
-        accident.day_night_code,
+        accident.day_night_code.as_str(),
         accident.sunrise_time,
         accident.sunset_time,
-        accident.weather_code,
-        accident.district_code,
-        accident.surface_condition_code,
-        accident.road_model_code,
-        accident.traffic_signal_code,
-        accident.stop_regulation_sign_a_code,
-        accident.stop_regulation_display_a_code,
-        accident.stop_regulation_sign_b_code,
-        accident.stop_regulation_display_b_code,
-        accident.road_width_code,
-        accident.road_alignment_code,
-        accident.collision_point_code,
-        accident.zone_regulation_code,
-        accident.central_separation_code,
-        accident.road_segmentation_code,
-        accident.accident_type_code,
-        accident.age_a_code,
-        accident.age_b_code,
-        accident.party_a_code,
-        accident.party_b_code,
-        accident.purpose_a_code,
-        accident.purpose_b_code,
-        accident.vehicle_type_a_code,
-        accident.vehicle_type_b_code,
-        accident.automatic_a_code,
-        accident.automatic_b_code,
-        accident.support_car_a_code,
-        accident.support_car_b_code,
-        accident.speed_regulation_a_code,
-        accident.speed_regulation_b_code,
+        accident.weather_code.as_str(),
+        accident.district_code.as_str(),
+        accident.surface_condition_code.as_str(),
+        accident.road_model_code.as_str(),
+        accident.traffic_signal_code.as_str(),
+        accident.stop_regulation_sign_a_code.as_str(),
+        accident.stop_regulation_display_a_code.as_str(),
+        accident.stop_regulation_sign_b_code.as_str(),
+        accident.stop_regulation_display_b_code.as_str(),
+        accident.road_width_code.as_str(),
+        accident.road_alignment_code.as_str(),
+        accident.collision_point_code.as_str(),
+        accident.zone_regulation_code.as_str(),
+        accident.central_separation_code.as_str(),
+        accident.road_segmentation_code.as_str(),
+        accident.accident_type_code.as_str(),
+        accident.age_a_code.as_str(),
+        accident.age_b_code.as_str(),
+        accident.party_a_code.as_str(),
+        accident.party_b_code.as_str(),
+        accident.purpose_a_code.as_str(),
+        accident.purpose_b_code.as_str(),
+        accident.vehicle_type_a_code.as_str(),
+        accident.vehicle_type_b_code.as_str(),
+        accident.automatic_a_code.as_str(),
+        accident.automatic_b_code.as_str(),
+        accident.support_car_a_code.as_str(),
+        accident.support_car_b_code.as_str(),
+        accident.speed_regulation_a_code.as_str(),
+        accident.speed_regulation_b_code.as_str(),
         accident.collision_part_a,
         accident.collision_part_b,
-        accident.vehicle_damage_a_code,
-        accident.vehicle_damage_b_code,
-        accident.airbag_a_code,
-        accident.airbag_b_code,
-        accident.side_airbag_a_code,
-        accident.side_airbag_b_code,
-        accident.injury_a_code,
-        accident.injury_b_code,
+        accident.vehicle_damage_a_code.as_str(),
+        accident.vehicle_damage_b_code.as_str(),
+        accident.airbag_a_code.as_str(),
+        accident.airbag_b_code.as_str(),
+        accident.side_airbag_a_code.as_str(),
+        accident.side_airbag_b_code.as_str(),
+        accident.injury_a_code.as_str(),
+        accident.injury_b_code.as_str(),
         wkb::Encode(location) as _,
-        accident.week_code,
-        accident.holiday_code,
+        accident.week_code.as_str(),
+        accident.holiday_code.as_str(),
         accident.cognitive_days_a,
         accident.cognitive_days_b,
-        accident.driving_practice_a_code,
-        accident.driving_practice_b_code,
+        accident.driving_practice_a_code.as_str(),
+        accident.driving_practice_b_code.as_str(),
+        dataset_id,
+        Srid::JGD2011.code() as i32,
     )
     .execute(&mut **tx)
     .await?;
@@ -267,48 +884,745 @@ async fn insert_accident(
     Ok(())
 }
 
-/// 交通事故当事者以外の関係者をデータベースに登録する。
-///
-/// # 引数
-///
-/// * `tx` - データベーストランザクション
-/// * `involved_persons` - 交通事故当事者以外の関係者を格納したベクタ
-///
-/// # 戻り値
-///
-/// `()`
-pub async fn register_involved_persons(
-    tx: &mut PgTransaction<'_>,
-    involved_persons: &[RawInvolvedPerson],
-) -> anyhow::Result<()> {
-    for (index, involved_person) in involved_persons.iter().enumerate() {
-        insert_involved_person(tx, involved_person).await.map_err(|e| {
-            anyhow::anyhow!(
-                "交通事故当事者以外の関係者をデータベースに登録する際に、INSERT文を実行できませんでした。{}: {:?}: {}データ目",
-                e,
-                involved_person,
-                index,
-            )
-        })?;
-    }
-
-    Ok(())
-}
-
-async fn insert_involved_person(
+async fn upsert_accident(
     tx: &mut PgTransaction<'_>,
-    involved_person: &RawInvolvedPerson,
+    accident: &RawAccident,
+    location: GeometryF64,
+    dataset_id: Uuid,
 ) -> anyhow::Result<()> {
     sqlx::query!(
         r#"
-        INSERT INTO involved_persons (
+        INSERT INTO accidents (
             id,
-            accident_id,
-            sub_number,
-            party_code,
-            purpose_code,
-            vehicle_type_code,
-            riding_type_code,
+            prefecture_code,
+            police_station_code,
+            main_number,
+            accident_detail_code,
+            number_of_deaths,
+            number_of_injuries,
+            route_code,
+            route_class_code,
+            location_code,
+            city_jis_code,
+            occurred_at,
+            day_night_code,
+            sunrise_time,
+            sunset_time,
+            weather_code,
+            district_code,
+            surface_condition_code,
+            road_model_code,
+            traffic_signal_code,
+            stop_regulation_sign_a_code,
+            stop_regulation_display_a_code,
+            stop_regulation_sign_b_code,
+            stop_regulation_display_b_code,
+            road_width_code,
+            road_alignment_code,
+            collision_point_code,
+            zone_regulation_code,
+            central_separation_code,
+            road_segmentation_code,
+            accident_type_code,
+            age_a_code,
+            age_b_code,
+            party_a_code,
+            party_b_code,
+            purpose_a_code,
+            purpose_b_code,
+            vehicle_type_a_code,
+            vehicle_type_b_code,
+            automatic_a_code,
+            automatic_b_code,
+            support_car_a_code,
+            support_car_b_code,
+            speed_regulation_a_code,
+            speed_regulation_b_code,
+            collision_part_a,
+            collision_part_b,
+            vehicle_damage_a_code,
+            vehicle_damage_b_code,
+            airbag_a_code,
+            airbag_b_code,
+            side_airbag_a_code,
+            side_airbag_b_code,
+            injury_a_code,
+            injury_b_code,
+            location,
+            week_code,
+            holiday_code,
+            cognitive_days_a,
+            cognitive_days_b,
+            driving_practice_a_code,
+            driving_practice_b_code,
+            dataset_id
+        ) VALUES (
+            $1,
+            $2,
+            $3,
+            $4,
+            $5,
+            $6,
+            $7,
+            $8,
+            $9,
+            $10,
+            $11,
+            $12,
+            $13,
+            $14,
+            $15,
+            $16,
+            $17,
+            $18,
+            $19,
+            $20,
+            $21,
+            $22,
+            $23,
+            $24,
+            $25,
+            $26,
+            $27,
+            $28,
+            $29,
+            $30,
+            $31,
+            $32,
+            $33,
+            $34,
+            $35,
+            $36,
+            $37,
+            $38,
+            $39,
+            $40,
+            $41,
+            $42,
+            $43,
+            $44,
+            $45,
+            $46,
+            $47,
+            $48,
+            $49,
+            $50,
+            $51,
+            $52,
+            $53,
+            $54,
+            $55,
+            ST_SetSRID($56::geometry, $64),
+            $57,
+            $58,
+            $59,
+            $60,
+            $61,
+            $62,
+            $63
+        )
+        ON CONFLICT (prefecture_code, police_station_code, main_number) DO UPDATE SET
+            accident_detail_code = EXCLUDED.accident_detail_code,
+            number_of_deaths = EXCLUDED.number_of_deaths,
+            number_of_injuries = EXCLUDED.number_of_injuries,
+            route_code = EXCLUDED.route_code,
+            route_class_code = EXCLUDED.route_class_code,
+            location_code = EXCLUDED.location_code,
+            city_jis_code = EXCLUDED.city_jis_code,
+            occurred_at = EXCLUDED.occurred_at,
+            day_night_code = EXCLUDED.day_night_code,
+            sunrise_time = EXCLUDED.sunrise_time,
+            sunset_time = EXCLUDED.sunset_time,
+            weather_code = EXCLUDED.weather_code,
+            district_code = EXCLUDED.district_code,
+            surface_condition_code = EXCLUDED.surface_condition_code,
+            road_model_code = EXCLUDED.road_model_code,
+            traffic_signal_code = EXCLUDED.traffic_signal_code,
+            stop_regulation_sign_a_code = EXCLUDED.stop_regulation_sign_a_code,
+            stop_regulation_display_a_code = EXCLUDED.stop_regulation_display_a_code,
+            stop_regulation_sign_b_code = EXCLUDED.stop_regulation_sign_b_code,
+            stop_regulation_display_b_code = EXCLUDED.stop_regulation_display_b_code,
+            road_width_code = EXCLUDED.road_width_code,
+            road_alignment_code = EXCLUDED.road_alignment_code,
+            collision_point_code = EXCLUDED.collision_point_code,
+            zone_regulation_code = EXCLUDED.zone_regulation_code,
+            central_separation_code = EXCLUDED.central_separation_code,
+            road_segmentation_code = EXCLUDED.road_segmentation_code,
+            accident_type_code = EXCLUDED.accident_type_code,
+            age_a_code = EXCLUDED.age_a_code,
+            age_b_code = EXCLUDED.age_b_code,
+            party_a_code = EXCLUDED.party_a_code,
+            party_b_code = EXCLUDED.party_b_code,
+            purpose_a_code = EXCLUDED.purpose_a_code,
+            purpose_b_code = EXCLUDED.purpose_b_code,
+            vehicle_type_a_code = EXCLUDED.vehicle_type_a_code,
+            vehicle_type_b_code = EXCLUDED.vehicle_type_b_code,
+            automatic_a_code = EXCLUDED.automatic_a_code,
+            automatic_b_code = EXCLUDED.automatic_b_code,
+            support_car_a_code = EXCLUDED.support_car_a_code,
+            support_car_b_code = EXCLUDED.support_car_b_code,
+            speed_regulation_a_code = EXCLUDED.speed_regulation_a_code,
+            speed_regulation_b_code = EXCLUDED.speed_regulation_b_code,
+            collision_part_a = EXCLUDED.collision_part_a,
+            collision_part_b = EXCLUDED.collision_part_b,
+            vehicle_damage_a_code = EXCLUDED.vehicle_damage_a_code,
+            vehicle_damage_b_code = EXCLUDED.vehicle_damage_b_code,
+            airbag_a_code = EXCLUDED.airbag_a_code,
+            airbag_b_code = EXCLUDED.airbag_b_code,
+            side_airbag_a_code = EXCLUDED.side_airbag_a_code,
+            side_airbag_b_code = EXCLUDED.side_airbag_b_code,
+            injury_a_code = EXCLUDED.injury_a_code,
+            injury_b_code = EXCLUDED.injury_b_code,
+            location = EXCLUDED.location,
+            week_code = EXCLUDED.week_code,
+            holiday_code = EXCLUDED.holiday_code,
+            cognitive_days_a = EXCLUDED.cognitive_days_a,
+            cognitive_days_b = EXCLUDED.cognitive_days_b,
+            driving_practice_a_code = EXCLUDED.driving_practice_a_code,
+            driving_practice_b_code = EXCLUDED.driving_practice_b_code,
+            dataset_id = EXCLUDED.dataset_id;"#,
+        accident.id,
+        accident.prefecture_code.as_str(),
+        accident.police_station_code.station_code(),
+        accident.main_number,
+        accident.accident_detail_code.as_str(),
+        accident.number_of_deaths,
+        accident.number_of_injuries,
+        accident.route_code.as_str(),
+        accident.route_class_code.as_str(),
+        accident.location_code,
+        accident.city_jis_code.as_str(),
+        accident.occurred_at,
+        accident.day_night_code.as_str(),
+        accident.sunrise_time,
+        accident.sunset_time,
+        accident.weather_code.as_str(),
+        accident.district_code.as_str(),
+        accident.surface_condition_code.as_str(),
+        accident.road_model_code.as_str(),
+        accident.traffic_signal_code.as_str(),
+        accident.stop_regulation_sign_a_code.as_str(),
+        accident.stop_regulation_display_a_code.as_str(),
+        accident.stop_regulation_sign_b_code.as_str(),
+        accident.stop_regulation_display_b_code.as_str(),
+        accident.road_width_code.as_str(),
+        accident.road_alignment_code.as_str(),
+        accident.collision_point_code.as_str(),
+        accident.zone_regulation_code.as_str(),
+        accident.central_separation_code.as_str(),
+        accident.road_segmentation_code.as_str(),
+        accident.accident_type_code.as_str(),
+        accident.age_a_code.as_str(),
+        accident.age_b_code.as_str(),
+        accident.party_a_code.as_str(),
+        accident.party_b_code.as_str(),
+        accident.purpose_a_code.as_str(),
+        accident.purpose_b_code.as_str(),
+        accident.vehicle_type_a_code.as_str(),
+        accident.vehicle_type_b_code.as_str(),
+        accident.automatic_a_code.as_str(),
+        accident.automatic_b_code.as_str(),
+        accident.support_car_a_code.as_str(),
+        accident.support_car_b_code.as_str(),
+        accident.speed_regulation_a_code.as_str(),
+        accident.speed_regulation_b_code.as_str(),
+        accident.collision_part_a,
+        accident.collision_part_b,
+        accident.vehicle_damage_a_code.as_str(),
+        accident.vehicle_damage_b_code.as_str(),
+        accident.airbag_a_code.as_str(),
+        accident.airbag_b_code.as_str(),
+        accident.side_airbag_a_code.as_str(),
+        accident.side_airbag_b_code.as_str(),
+        accident.injury_a_code.as_str(),
+        accident.injury_b_code.as_str(),
+        wkb::Encode(location) as _,
+        accident.week_code.as_str(),
+        accident.holiday_code.as_str(),
+        accident.cognitive_days_a,
+        accident.cognitive_days_b,
+        accident.driving_practice_a_code.as_str(),
+        accident.driving_practice_b_code.as_str(),
+        dataset_id,
+        Srid::JGD2011.code() as i32,
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// 交通事故をデータベースから削除する。
+///
+/// # 引数
+///
+/// * `tx` - データベーストランザクション
+/// * `accident_ids` - 交通事故IDを格納したハッシュセット
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn delete_accidents(
+    tx: &mut PgTransaction<'_>,
+    accident_ids: &HashSet<Uuid>,
+) -> anyhow::Result<()> {
+    let accident_ids = accident_ids.iter().copied().collect::<Vec<_>>();
+    sqlx::query!(
+        r#"
+        DELETE FROM accidents WHERE id = ANY($1)
+        "#,
+        &accident_ids,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "交通事故をデータベースから削除する際に、DELETE文を実行できませんでした。{}",
+            e
+        )
+    })?;
+
+    Ok(())
+}
+
+/// 交通事故に関連する高速道路で発生した交通事故の詳細をデータベースから取得する。
+///
+/// # 引数
+///
+/// * `tx` - データベーストランザクション
+/// * `accident_ids` - 交通事故IDを格納したハッシュセット
+///
+/// # 戻り値
+///
+/// 高速道路で発生した交通事故の詳細を格納したベクタ
+pub async fn fetch_highway_records(
+    tx: &mut PgTransaction<'_>,
+    accident_ids: &HashSet<Uuid>,
+) -> anyhow::Result<Vec<RawHighwayRecord>> {
+    let accident_ids = accident_ids.iter().copied().collect::<Vec<_>>();
+    let highway_records = sqlx::query_as!(
+        RawHighwayRecord,
+        r#"
+        SELECT
+            id,
+            accident_id,
+            up_down_code::text "up_down_code!: UpDownCode",
+            kilopost_km,
+            kilopost_m,
+            road_structure_code::text "road_structure_code!: RoadStructureCode",
+            lane_a_code::text "lane_a_code!: LaneCode",
+            lane_b_code::text "lane_b_code!: LaneCode"
+        FROM highway_details
+        WHERE accident_id = ANY($1)
+        "#,
+        &accident_ids,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "高速道路で発生した交通事故の詳細をデータベースから取得できませんでした。{}",
+            e
+        )
+    })?;
+
+    Ok(highway_records)
+}
+
+/// COPY文で交通事故をデータベースに登録する。
+///
+/// # 引数
+///
+/// * `tx` - データベーストランザクション
+/// * `accidents` - 交通事故を格納したベクタ
+/// * `dataset_id` - データセットID
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn copy_accidents(
+    tx: &mut PgTransaction<'_>,
+    accidents: &[RawAccident],
+    dataset_id: Uuid,
+) -> anyhow::Result<()> {
+    copy_records(
+        tx,
+        r#"
+        COPY accidents (
+            id,
+            prefecture_code,
+            police_station_code,
+            main_number,
+            accident_detail_code,
+            number_of_deaths,
+            number_of_injuries,
+            route_code,
+            route_class_code,
+            location_code,
+            city_jis_code,
+            occurred_at,
+            day_night_code,
+            sunrise_time,
+            sunset_time,
+            weather_code,
+            district_code,
+            surface_condition_code,
+            road_model_code,
+            traffic_signal_code,
+            stop_regulation_sign_a_code,
+            stop_regulation_display_a_code,
+            stop_regulation_sign_b_code,
+            stop_regulation_display_b_code,
+            road_width_code,
+            road_alignment_code,
+            collision_point_code,
+            zone_regulation_code,
+            central_separation_code,
+            road_segmentation_code,
+            accident_type_code,
+            age_a_code,
+            age_b_code,
+            party_a_code,
+            party_b_code,
+            purpose_a_code,
+            purpose_b_code,
+            vehicle_type_a_code,
+            vehicle_type_b_code,
+            automatic_a_code,
+            automatic_b_code,
+            support_car_a_code,
+            support_car_b_code,
+            speed_regulation_a_code,
+            speed_regulation_b_code,
+            collision_part_a,
+            collision_part_b,
+            vehicle_damage_a_code,
+            vehicle_damage_b_code,
+            airbag_a_code,
+            airbag_b_code,
+            side_airbag_a_code,
+            side_airbag_b_code,
+            injury_a_code,
+            injury_b_code,
+            location,
+            week_code,
+            holiday_code,
+            cognitive_days_a,
+            cognitive_days_b,
+            driving_practice_a_code,
+            driving_practice_b_code,
+            dataset_id
+        ) FROM STDIN WITH (FORMAT csv)
+        "#,
+        accidents,
+        |accident| accident_copy_record(accident, dataset_id),
+    )
+    .await
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "交通事故をデータベースに登録する際に、COPY文を実行できませんでした。{}",
+            e
+        )
+    })
+}
+
+/// COPY文で交通事故当事者以外の関係者をデータベースに登録する。
+///
+/// # 引数
+///
+/// * `tx` - データベーストランザクション
+/// * `involved_persons` - 交通事故当事者以外の関係者を格納したベクタ
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn copy_involved_persons(
+    tx: &mut PgTransaction<'_>,
+    involved_persons: &[RawInvolvedPerson],
+) -> anyhow::Result<()> {
+    copy_records(
+        tx,
+        r#"
+        COPY involved_persons (
+            id,
+            accident_id,
+            sub_number,
+            party_code,
+            purpose_code,
+            vehicle_type_code,
+            riding_type_code,
+            riding_class_code,
+            support_car_code,
+            airbag_code,
+            side_airbag_code,
+            injury_code,
+            collision_part,
+            vehicle_damage_code
+        ) FROM STDIN WITH (FORMAT csv)
+        "#,
+        involved_persons,
+        |p| {
+            Ok(vec![
+                p.id.to_string(),
+                p.accident_id.to_string(),
+                p.sub_number.to_string(),
+                p.party_code.to_string(),
+                p.purpose_code.as_ref().map(ToString::to_string).unwrap_or_default(),
+                p.vehicle_type_code.as_ref().map(ToString::to_string).unwrap_or_default(),
+                p.riding_type_code.to_string(),
+                p.riding_class_code.to_string(),
+                p.support_car_code.to_string(),
+                p.airbag_code.to_string(),
+                p.side_airbag_code.to_string(),
+                p.injury_code.to_string(),
+                p.collision_part.clone().unwrap_or_default(),
+                p.vehicle_damage_code.as_ref().map(ToString::to_string).unwrap_or_default(),
+            ])
+        },
+    )
+    .await
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "交通事故当事者以外の関係者をデータベースに登録する際に、COPY文を実行できませんでした。{}",
+            e
+        )
+    })
+}
+
+/// COPY文で高速道路で発生した交通事故の詳細をデータベースに登録する。
+///
+/// # 引数
+///
+/// * `tx` - データベーストランザクション
+/// * `highway_records` - 高速道路で発生した交通事故の詳細を格納したベクタ
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn copy_highway_records(
+    tx: &mut PgTransaction<'_>,
+    highway_records: &[RawHighwayRecord],
+) -> anyhow::Result<()> {
+    copy_records(
+        tx,
+        r#"
+        COPY highway_details (
+            id,
+            accident_id,
+            up_down_code,
+            kilopost_km,
+            kilopost_m,
+            road_structure_code,
+            lane_a_code,
+            lane_b_code
+        ) FROM STDIN WITH (FORMAT csv)
+        "#,
+        highway_records,
+        |r| {
+            Ok(vec![
+                r.id.to_string(),
+                r.accident_id.to_string(),
+                r.up_down_code.to_string(),
+                r.kilopost_km.to_string(),
+                r.kilopost_m.to_string(),
+                r.road_structure_code.to_string(),
+                r.lane_a_code.to_string(),
+                r.lane_b_code.to_string(),
+            ])
+        },
+    )
+    .await
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "高速道路で発生した交通事故の詳細をデータベースに登録する際に、COPY文を実行できませんでした。{}",
+            e
+        )
+    })
+}
+
+/// COPY文でレコードをデータベースに登録する。
+///
+/// レコードをCSV形式に変換して、`COPY_CHUNK_ROWS`行ごとにデータベースに送信する。
+/// CSV形式では、空の列はNULLとして扱われる。
+async fn copy_records<T, F>(
+    tx: &mut PgTransaction<'_>,
+    statement: &str,
+    records: &[T],
+    to_record: F,
+) -> anyhow::Result<()>
+where
+    F: Fn(&T) -> anyhow::Result<Vec<String>>,
+{
+    let mut copy_in = tx.copy_in_raw(statement).await?;
+    for chunk in records.chunks(COPY_CHUNK_ROWS) {
+        // 行の変換または送信に失敗した場合は、COPY文を中断してからエラーを返す
+        let sent = match copy_chunk(chunk, &to_record) {
+            Ok(data) => copy_in.send(data).await.map_err(anyhow::Error::from),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = sent {
+            copy_in.abort(e.to_string()).await.ok();
+            return Err(e);
+        }
+    }
+    copy_in.finish().await?;
+
+    Ok(())
+}
+
+/// COPY文で送信する行をCSV形式に変換する。
+///
+/// # 引数
+///
+/// * `chunk` - 送信する行
+/// * `to_record` - 行をCSVのレコードに変換する関数
+///
+/// # 戻り値
+///
+/// CSV形式のデータ
+fn copy_chunk<T, F>(chunk: &[T], to_record: &F) -> anyhow::Result<Vec<u8>>
+where
+    F: Fn(&T) -> anyhow::Result<Vec<String>>,
+{
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+    for record in chunk {
+        writer.write_record(to_record(record)?)?;
+    }
+
+    writer.into_inner().map_err(|e| anyhow::anyhow!("{}", e))
+}
+
+fn accident_copy_record(accident: &RawAccident, dataset_id: Uuid) -> anyhow::Result<Vec<String>> {
+    Ok(vec![
+        accident.id.to_string(),
+        accident.prefecture_code.to_string(),
+        accident.police_station_code.station_code().to_string(),
+        accident.main_number.to_string(),
+        accident.accident_detail_code.to_string(),
+        accident.number_of_deaths.to_string(),
+        accident.number_of_injuries.to_string(),
+        accident.route_code.to_string(),
+        accident.route_class_code.to_string(),
+        accident.location_code.to_string(),
+        accident.city_jis_code.to_string(),
+        accident.occurred_at.format(&Rfc3339)?,
+        accident.day_night_code.to_string(),
+        copy_time(accident.sunrise_time),
+        copy_time(accident.sunset_time),
+        accident.weather_code.to_string(),
+        accident.district_code.to_string(),
+        accident.surface_condition_code.to_string(),
+        accident.road_model_code.to_string(),
+        accident.traffic_signal_code.to_string(),
+        accident.stop_regulation_sign_a_code.to_string(),
+        accident.stop_regulation_display_a_code.to_string(),
+        accident.stop_regulation_sign_b_code.to_string(),
+        accident.stop_regulation_display_b_code.to_string(),
+        accident.road_width_code.to_string(),
+        accident.road_alignment_code.to_string(),
+        accident.collision_point_code.to_string(),
+        accident.zone_regulation_code.to_string(),
+        accident.central_separation_code.to_string(),
+        accident.road_segmentation_code.to_string(),
+        accident.accident_type_code.to_string(),
+        accident.age_a_code.to_string(),
+        accident.age_b_code.to_string(),
+        accident.party_a_code.to_string(),
+        accident.party_b_code.to_string(),
+        accident.purpose_a_code.to_string(),
+        accident.purpose_b_code.to_string(),
+        accident.vehicle_type_a_code.to_string(),
+        accident.vehicle_type_b_code.to_string(),
+        accident.automatic_a_code.to_string(),
+        accident.automatic_b_code.to_string(),
+        accident.support_car_a_code.to_string(),
+        accident.support_car_b_code.to_string(),
+        accident.speed_regulation_a_code.to_string(),
+        accident.speed_regulation_b_code.to_string(),
+        accident.collision_part_a.clone(),
+        accident.collision_part_b.clone(),
+        accident.vehicle_damage_a_code.to_string(),
+        accident.vehicle_damage_b_code.to_string(),
+        accident.airbag_a_code.to_string(),
+        accident.airbag_b_code.to_string(),
+        accident.side_airbag_a_code.to_string(),
+        accident.side_airbag_b_code.to_string(),
+        accident.injury_a_code.to_string(),
+        accident.injury_b_code.to_string(),
+        ewkb_hex(accident.location)?,
+        accident.week_code.to_string(),
+        accident.holiday_code.to_string(),
+        accident.cognitive_days_a.to_string(),
+        accident.cognitive_days_b.to_string(),
+        accident.driving_practice_a_code.to_string(),
+        accident.driving_practice_b_code.to_string(),
+        dataset_id.to_string(),
+    ])
+}
+
+fn copy_time(time: Time) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        time.hour(),
+        time.minute(),
+        time.second()
+    )
+}
+
+/// 地点を日本測地系2011のSRIDを埋め込んだEWKBの16進数文字列に変換する。
+fn ewkb_hex(location: Point) -> anyhow::Result<String> {
+    let location: GeometryF64 = location.into();
+    let ewkb = location
+        .to_ewkb(CoordDimensions::xy(), Some(Srid::JGD2011.code() as i32))
+        .map_err(|e| anyhow::anyhow!("地点をEWKBに変換できません。{}", e))?;
+
+    Ok(ewkb.iter().map(|b| format!("{:02X}", b)).collect())
+}
+
+/// 交通事故当事者以外の関係者をデータベースに登録する。
+///
+/// # 引数
+///
+/// * `tx` - データベーストランザクション
+/// * `involved_persons` - 交通事故当事者以外の関係者を格納したベクタ
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn register_involved_persons(
+    tx: &mut PgTransaction<'_>,
+    involved_persons: &[RawInvolvedPerson],
+) -> anyhow::Result<()> {
+    for (index, involved_person) in involved_persons.iter().enumerate() {
+        insert_involved_person(tx, involved_person).await.map_err(|e| {
+            anyhow::anyhow!(
+                "交通事故当事者以外の関係者をデータベースに登録する際に、INSERT文を実行できませんでした。{}: {:?}: {}データ目",
+                e,
+                involved_person,
+                index,
+            )
+        })?;
+    }
+
+    Ok(())
+}
+
+async fn insert_involved_person(
+    tx: &mut PgTransaction<'_>,
+    involved_person: &RawInvolvedPerson,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO involved_persons (
+            id,
+            accident_id,
+            sub_number,
+            party_code,
+            purpose_code,
+            vehicle_type_code,
+            riding_type_code,
             riding_class_code,
             support_car_code,
             airbag_code,
@@ -336,20 +1650,440 @@ async fn insert_involved_person(
         involved_person.id,
         involved_person.accident_id,
         involved_person.sub_number,
-        involved_person.party_code,
-        involved_person.purpose_code,
-        involved_person.vehicle_type_code,
-        involved_person.riding_type_code,
-        involved_person.riding_class_code,
-        involved_person.support_car_code,
-        involved_person.airbag_code,
-        involved_person.side_airbag_code,
-        involved_person.injury_code,
+        involved_person.party_code.as_str(),
+        involved_person.purpose_code.as_ref().map(|c| c.as_str()),
+        involved_person
+            .vehicle_type_code
+            .as_ref()
+            .map(|c| c.as_str()),
+        involved_person.riding_type_code.as_str(),
+        involved_person.riding_class_code.as_str(),
+        involved_person.support_car_code.as_str(),
+        involved_person.airbag_code.as_str(),
+        involved_person.side_airbag_code.as_str(),
+        involved_person.injury_code.as_str(),
         involved_person.collision_part,
-        involved_person.vehicle_damage_code
+        involved_person
+            .vehicle_damage_code
+            .as_ref()
+            .map(|c| c.as_str())
     )
     .execute(&mut **tx)
     .await?;
 
     Ok(())
 }
+
+/// 高速道路で発生した交通事故の詳細をデータベースに登録する。
+///
+/// # 引数
+///
+/// * `tx` - データベーストランザクション
+/// * `highway_records` - 高速道路で発生した交通事故の詳細を格納したベクタ
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn register_highway_records(
+    tx: &mut PgTransaction<'_>,
+    highway_records: &[RawHighwayRecord],
+) -> anyhow::Result<()> {
+    for (index, highway_record) in highway_records.iter().enumerate() {
+        insert_highway_record(tx, highway_record).await.map_err(|e| {
+            anyhow::anyhow!(
+                "高速道路で発生した交通事故の詳細をデータベースに登録する際に、INSERT文を実行できませんでした。{}: {:?}: {}データ目",
+                e,
+                highway_record,
+                index,
+            )
+        })?;
+    }
+
+    Ok(())
+}
+
+async fn insert_highway_record(
+    tx: &mut PgTransaction<'_>,
+    highway_record: &RawHighwayRecord,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO highway_details (
+            id,
+            accident_id,
+            up_down_code,
+            kilopost_km,
+            kilopost_m,
+            road_structure_code,
+            lane_a_code,
+            lane_b_code
+        ) VALUES (
+            $1,
+            $2,
+            $3,
+            $4,
+            $5,
+            $6,
+            $7,
+            $8
+        );
+        "#,
+        highway_record.id,
+        highway_record.accident_id,
+        highway_record.up_down_code.as_str(),
+        highway_record.kilopost_km,
+        highway_record.kilopost_m,
+        highway_record.road_structure_code.as_str(),
+        highway_record.lane_a_code.as_str(),
+        highway_record.lane_b_code.as_str(),
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// エクスポートする交通事故を取得するSELECT文（交通事故テーブルの別名は`a`）
+///
+/// 固定長文字列の末尾の空白を除くため、コードは`text`に変換する。
+const EXPORT_ACCIDENT_SELECT_SQL: &str = r#"
+    SELECT
+        a.id,
+        a.prefecture_code::text prefecture_code,
+        (a.prefecture_code || a.police_station_code)::text police_station_code,
+        a.main_number,
+        a.accident_detail_code::text accident_detail_code,
+        a.number_of_deaths,
+        a.number_of_injuries,
+        a.route_code::text route_code,
+        a.route_class_code::text route_class_code,
+        a.location_code,
+        a.city_jis_code::text city_jis_code,
+        a.occurred_at,
+        a.day_night_code::text day_night_code,
+        a.sunrise_time,
+        a.sunset_time,
+        a.weather_code::text weather_code,
+        a.district_code::text district_code,
+        a.surface_condition_code::text surface_condition_code,
+        a.road_model_code::text road_model_code,
+        a.traffic_signal_code::text traffic_signal_code,
+        a.stop_regulation_sign_a_code::text stop_regulation_sign_a_code,
+        a.stop_regulation_display_a_code::text stop_regulation_display_a_code,
+        a.stop_regulation_sign_b_code::text stop_regulation_sign_b_code,
+        a.stop_regulation_display_b_code::text stop_regulation_display_b_code,
+        a.road_width_code::text road_width_code,
+        a.road_alignment_code::text road_alignment_code,
+        a.collision_point_code::text collision_point_code,
+        a.zone_regulation_code::text zone_regulation_code,
+        a.central_separation_code::text central_separation_code,
+        a.road_segmentation_code::text road_segmentation_code,
+        a.accident_type_code::text accident_type_code,
+        a.age_a_code::text age_a_code,
+        a.age_b_code::text age_b_code,
+        a.party_a_code::text party_a_code,
+        a.party_b_code::text party_b_code,
+        a.purpose_a_code::text purpose_a_code,
+        a.purpose_b_code::text purpose_b_code,
+        a.vehicle_type_a_code::text vehicle_type_a_code,
+        a.vehicle_type_b_code::text vehicle_type_b_code,
+        a.automatic_a_code::text automatic_a_code,
+        a.automatic_b_code::text automatic_b_code,
+        a.support_car_a_code::text support_car_a_code,
+        a.support_car_b_code::text support_car_b_code,
+        a.speed_regulation_a_code::text speed_regulation_a_code,
+        a.speed_regulation_b_code::text speed_regulation_b_code,
+        a.collision_part_a::text collision_part_a,
+        a.collision_part_b::text collision_part_b,
+        a.vehicle_damage_a_code::text vehicle_damage_a_code,
+        a.vehicle_damage_b_code::text vehicle_damage_b_code,
+        a.airbag_a_code::text airbag_a_code,
+        a.airbag_b_code::text airbag_b_code,
+        a.side_airbag_a_code::text side_airbag_a_code,
+        a.side_airbag_b_code::text side_airbag_b_code,
+        a.injury_a_code::text injury_a_code,
+        a.injury_b_code::text injury_b_code,
+        a.week_code::text week_code,
+        a.holiday_code::text holiday_code,
+        a.cognitive_days_a,
+        a.cognitive_days_b,
+        a.driving_practice_a_code::text driving_practice_a_code,
+        a.driving_practice_b_code::text driving_practice_b_code,
+        ST_X({location}) longitude,
+        ST_Y({location}) latitude
+    FROM accidents a
+"#;
+
+/// エクスポートする交通事故の位置を、指定された空間参照系に変換する式を返す。
+fn export_location_sql(srid: Srid) -> String {
+    match srid {
+        Srid::JGD2011 => String::from("a.location"),
+        _ => format!("ST_Transform(a.location, {})", srid),
+    }
+}
+
+/// 条件に一致する交通事故を、発生日時の順に取得するSELECT文を返す。
+///
+/// # 引数
+///
+/// * `where_clause` - 交通事故を絞り込むSQLの式（交通事故テーブルの別名は`a`）
+/// * `srid` - 交通事故の発生場所の空間参照系
+///
+/// # 戻り値
+///
+/// SELECT文
+pub fn export_accidents_sql(where_clause: Option<&str>, srid: Srid) -> String {
+    let mut sql = EXPORT_ACCIDENT_SELECT_SQL.replace("{location}", &export_location_sql(srid));
+    if let Some(where_clause) = where_clause {
+        sql.push_str(&format!(" WHERE ({})", where_clause));
+    }
+    sql.push_str(" ORDER BY a.occurred_at, a.id");
+
+    sql
+}
+
+/// SELECT文を実行して、交通事故を1件ずつ返すストリームを返す。
+///
+/// 条件にはSQLの式を指定できるため、読み取り専用トランザクションで実行する。
+///
+/// # 引数
+///
+/// * `conn` - データベースコネクション
+/// * `sql` - `export_accidents_sql`で作成したSELECT文
+///
+/// # 戻り値
+///
+/// 交通事故（発生場所は指定された空間参照系の座標）を返すストリーム
+pub fn fetch_accidents<'a>(
+    conn: &'a mut PgConnection,
+    sql: &'a str,
+) -> BoxStream<'a, anyhow::Result<RawAccident>> {
+    sqlx::query(sql)
+        .fetch(conn)
+        .map(|row| {
+            row.and_then(|row| accident_from_row(&row)).map_err(|e| {
+                anyhow::anyhow!(
+                    "交通事故を取得する際に、SELECT文を実行できませんでした。{}",
+                    e
+                )
+            })
+        })
+        .boxed()
+}
+
+fn accident_from_row(row: &PgRow) -> Result<RawAccident, sqlx::Error> {
+    Ok(RawAccident {
+        id: row.try_get("id")?,
+        prefecture_code: row.try_get("prefecture_code")?,
+        police_station_code: row.try_get("police_station_code")?,
+        main_number: row.try_get("main_number")?,
+        accident_detail_code: row.try_get("accident_detail_code")?,
+        number_of_deaths: row.try_get("number_of_deaths")?,
+        number_of_injuries: row.try_get("number_of_injuries")?,
+        route_code: row.try_get("route_code")?,
+        route_class_code: row.try_get("route_class_code")?,
+        location_code: row.try_get("location_code")?,
+        city_jis_code: row.try_get("city_jis_code")?,
+        occurred_at: row.try_get("occurred_at")?,
+        day_night_code: row.try_get("day_night_code")?,
+        sunrise_time: row.try_get("sunrise_time")?,
+        sunset_time: row.try_get("sunset_time")?,
+        weather_code: row.try_get("weather_code")?,
+        district_code: row.try_get("district_code")?,
+        surface_condition_code: row.try_get("surface_condition_code")?,
+        road_model_code: row.try_get("road_model_code")?,
+        traffic_signal_code: row.try_get("traffic_signal_code")?,
+        stop_regulation_sign_a_code: row.try_get("stop_regulation_sign_a_code")?,
+        stop_regulation_display_a_code: row.try_get("stop_regulation_display_a_code")?,
+        stop_regulation_sign_b_code: row.try_get("stop_regulation_sign_b_code")?,
+        stop_regulation_display_b_code: row.try_get("stop_regulation_display_b_code")?,
+        road_width_code: row.try_get("road_width_code")?,
+        road_alignment_code: row.try_get("road_alignment_code")?,
+        collision_point_code: row.try_get("collision_point_code")?,
+        zone_regulation_code: row.try_get("zone_regulation_code")?,
+        central_separation_code: row.try_get("central_separation_code")?,
+        road_segmentation_code: row.try_get("road_segmentation_code")?,
+        accident_type_code: row.try_get("accident_type_code")?,
+        age_a_code: row.try_get("age_a_code")?,
+        age_b_code: row.try_get("age_b_code")?,
+        party_a_code: row.try_get("party_a_code")?,
+        party_b_code: row.try_get("party_b_code")?,
+        purpose_a_code: row.try_get("purpose_a_code")?,
+        purpose_b_code: row.try_get("purpose_b_code")?,
+        vehicle_type_a_code: row.try_get("vehicle_type_a_code")?,
+        vehicle_type_b_code: row.try_get("vehicle_type_b_code")?,
+        automatic_a_code: row.try_get("automatic_a_code")?,
+        automatic_b_code: row.try_get("automatic_b_code")?,
+        support_car_a_code: row.try_get("support_car_a_code")?,
+        support_car_b_code: row.try_get("support_car_b_code")?,
+        speed_regulation_a_code: row.try_get("speed_regulation_a_code")?,
+        speed_regulation_b_code: row.try_get("speed_regulation_b_code")?,
+        collision_part_a: row.try_get("collision_part_a")?,
+        collision_part_b: row.try_get("collision_part_b")?,
+        vehicle_damage_a_code: row.try_get("vehicle_damage_a_code")?,
+        vehicle_damage_b_code: row.try_get("vehicle_damage_b_code")?,
+        airbag_a_code: row.try_get("airbag_a_code")?,
+        airbag_b_code: row.try_get("airbag_b_code")?,
+        side_airbag_a_code: row.try_get("side_airbag_a_code")?,
+        side_airbag_b_code: row.try_get("side_airbag_b_code")?,
+        injury_a_code: row.try_get("injury_a_code")?,
+        injury_b_code: row.try_get("injury_b_code")?,
+        location: Point::new(row.try_get("longitude")?, row.try_get("latitude")?),
+        week_code: row.try_get("week_code")?,
+        holiday_code: row.try_get("holiday_code")?,
+        cognitive_days_a: row.try_get("cognitive_days_a")?,
+        cognitive_days_b: row.try_get("cognitive_days_b")?,
+        driving_practice_a_code: row.try_get("driving_practice_a_code")?,
+        driving_practice_b_code: row.try_get("driving_practice_b_code")?,
+    })
+}
+
+/// 条件に一致する交通事故に関与した交通事故当事者以外の関与者を、交通事故の発生日時と
+/// 補充票番号の順に取得するSELECT文を返す。
+///
+/// 関与した交通事故の位置を、参考として取得する。
+///
+/// # 引数
+///
+/// * `where_clause` - 交通事故を絞り込むSQLの式（交通事故テーブルの別名は`a`）
+/// * `srid` - 交通事故の発生場所の空間参照系
+///
+/// # 戻り値
+///
+/// SELECT文
+pub fn export_involved_persons_sql(where_clause: Option<&str>, srid: Srid) -> String {
+    let location = export_location_sql(srid);
+    let mut sql = format!(
+        r#"
+        SELECT
+            p.id,
+            p.accident_id,
+            p.sub_number,
+            p.party_code::text party_code,
+            p.purpose_code::text purpose_code,
+            p.vehicle_type_code::text vehicle_type_code,
+            p.riding_type_code::text riding_type_code,
+            p.riding_class_code::text riding_class_code,
+            p.support_car_code::text support_car_code,
+            p.airbag_code::text airbag_code,
+            p.side_airbag_code::text side_airbag_code,
+            p.injury_code::text injury_code,
+            p.collision_part::text collision_part,
+            p.vehicle_damage_code::text vehicle_damage_code,
+            ST_X({location}) longitude,
+            ST_Y({location}) latitude
+        FROM involved_persons p
+        INNER JOIN accidents a ON a.id = p.accident_id
+        "#
+    );
+    // 条件の列名が交通事故当事者以外の関与者の列名と重複しないように、副問い合わせで絞り込む
+    if let Some(where_clause) = where_clause {
+        sql.push_str(&format!(
+            " WHERE p.accident_id IN (SELECT a.id FROM accidents a WHERE ({}))",
+            where_clause
+        ));
+    }
+    sql.push_str(" ORDER BY a.occurred_at, a.id, p.sub_number");
+
+    sql
+}
+
+/// SELECT文を実行して、交通事故当事者以外の関与者と関与した交通事故の位置を1件ずつ返す
+/// ストリームを返す。
+///
+/// # 引数
+///
+/// * `conn` - データベースコネクション
+/// * `sql` - `export_involved_persons_sql`で作成したSELECT文
+///
+/// # 戻り値
+///
+/// 交通事故当事者以外の関与者と、関与した交通事故の位置を返すストリーム
+pub fn fetch_involved_persons<'a>(
+    conn: &'a mut PgConnection,
+    sql: &'a str,
+) -> BoxStream<'a, anyhow::Result<(RawInvolvedPerson, Point)>> {
+    sqlx::query(sql)
+        .fetch(conn)
+        .map(|row| {
+            row.and_then(|row| {
+                let location = Point::new(row.try_get("longitude")?, row.try_get("latitude")?);
+                Ok((involved_person_from_row(&row)?, location))
+            })
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "交通事故当事者以外の関与者を取得する際に、SELECT文を実行できませんでした。{}",
+                    e
+                )
+            })
+        })
+        .boxed()
+}
+
+fn involved_person_from_row(row: &PgRow) -> Result<RawInvolvedPerson, sqlx::Error> {
+    Ok(RawInvolvedPerson {
+        id: row.try_get("id")?,
+        accident_id: row.try_get("accident_id")?,
+        sub_number: row.try_get("sub_number")?,
+        party_code: row.try_get("party_code")?,
+        purpose_code: row.try_get("purpose_code")?,
+        vehicle_type_code: row.try_get("vehicle_type_code")?,
+        riding_type_code: row.try_get("riding_type_code")?,
+        riding_class_code: row.try_get("riding_class_code")?,
+        support_car_code: row.try_get("support_car_code")?,
+        airbag_code: row.try_get("airbag_code")?,
+        side_airbag_code: row.try_get("side_airbag_code")?,
+        injury_code: row.try_get("injury_code")?,
+        collision_part: row.try_get("collision_part")?,
+        vehicle_damage_code: row.try_get("vehicle_damage_code")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ewkb_hex_ok() {
+        let hex = ewkb_hex(Point::new(1.0, 2.0)).unwrap();
+        assert_eq!(hex, "01010000200C1A0000000000000000F03F0000000000000040");
+    }
+
+    #[test]
+    fn copy_time_ok() {
+        assert_eq!(copy_time(time::macros::time!(06:59)), "06:59:00");
+    }
+
+    #[tokio::test]
+    #[ignore = "PostgreSQLのデータベースが必要"]
+    async fn copy_records_fail() {
+        let pool = db::connection_pool().await.unwrap();
+        let mut tx = pool.begin().await.unwrap();
+        sqlx::query("CREATE TEMPORARY TABLE copy_records_for_test (value INTEGER)")
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+
+        // 行を変換できない場合は、COPY文を中断してエラーを返す
+        let result = copy_records(
+            &mut tx,
+            "COPY copy_records_for_test (value) FROM STDIN WITH (FORMAT csv)",
+            &[1, 2, 3],
+            |value: &i32| match value {
+                2 => Err(anyhow::anyhow!("変換できません。")),
+                _ => Ok(vec![value.to_string()]),
+            },
+        )
+        .await;
+        assert_eq!(result.unwrap_err().to_string(), "変換できません。");
+
+        // COPY文を中断したため、トランザクションをロールバックしてコネクションを再利用できる
+        tx.rollback().await.unwrap();
+        let mut connection = pool.acquire().await.unwrap();
+        let value: i32 = sqlx::query_scalar("SELECT 1")
+            .fetch_one(&mut *connection)
+            .await
+            .unwrap();
+        assert_eq!(value, 1);
+    }
+}
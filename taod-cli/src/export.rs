@@ -0,0 +1,1269 @@
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use futures::TryStreamExt;
+use geo_types::Point;
+use sqlx::PgPool;
+use time::format_description::well_known::Rfc3339;
+use time::macros::offset;
+use time::{OffsetDateTime, Time};
+
+use crate::db::{
+    export_accidents_sql, export_involved_persons_sql, fetch_accidents, fetch_involved_persons,
+};
+use crate::files::{AccidentReader, InvolvedPersonReader, RawAccident, RawInvolvedPerson};
+use crate::layouts::Layout;
+use code_tables::{CodeTables, Language};
+use db::connection_pool;
+use geometries::Srid;
+
+mod fgb;
+pub mod geojson;
+mod geojsonseq;
+mod gpkg;
+pub mod parquet;
+mod shp;
+
+/// 交通事故のレイヤー名
+pub const ACCIDENTS_LAYER: &str = "accidents";
+
+/// 交通事故当事者以外の関与者のレイヤー名
+pub const INVOLVED_PERSONS_LAYER: &str = "involved_persons";
+
+/// 交通事故と交通事故当事者以外の関与者を関連付ける属性の名前
+pub const ACCIDENT_ID_FIELD: &str = "accident_id";
+
+/// エクスポート形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// GeoPackage
+    Gpkg,
+    /// FlatGeobuf
+    Fgb,
+    /// Shapefile
+    Shp,
+    /// GeoJSON Text Sequences（RFC 8142）
+    #[value(name = "geojsonseq")]
+    GeoJsonSeq,
+}
+
+/// 交通事故のエクスポートオプション
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    /// エクスポート形式
+    pub format: ExportFormat,
+    /// データベースから交通事故を取得する条件（交通事故テーブルの別名は`a`）
+    pub where_clause: Option<String>,
+    /// 本票ファイルと補充票ファイル（指定した場合はデータベースに接続しない）
+    pub files: Option<(PathBuf, PathBuf)>,
+    /// 本票及び補充票のレイアウト（指定しない場合はヘッダ行から列の位置を決定）
+    pub layout: Option<Layout>,
+    /// コードの名前の言語
+    pub labels: Language,
+    /// 出力する座標の空間参照系（データベースから取得する場合のみ変換できる）
+    pub srid: Srid,
+}
+
+/// 交通事故と交通事故当事者以外の関与者をGISデータとしてエクスポートする。
+///
+/// 交通事故はすべての属性とコードの名前を持つポイントのレイヤー、交通事故当事者以外の関与者は
+/// 交通事故IDで交通事故と関連付けた表として出力する。
+/// 1つのファイルに複数のレイヤーを格納できない形式では、交通事故当事者以外の関与者を
+/// 出力ファイルと同じディレクトリの`<ファイル名>_involved_persons`ファイルに出力する。
+/// 交通事故と交通事故当事者以外の関与者は、読み込みながら1件ずつ出力する。
+///
+/// # 引数
+///
+/// * `output` - 出力ファイルパス
+/// * `options` - 交通事故のエクスポートオプション
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn export<P: AsRef<Path>>(output: P, options: ExportOptions) -> anyhow::Result<()> {
+    let output = output.as_ref();
+    if options.files.is_some() && options.srid != Srid::JGD2011 {
+        anyhow::bail!(
+            "本票ファイルと補充票ファイルからエクスポートする場合は、座標系を変換できません。"
+        );
+    }
+    let accidents = accident_layer(options.labels, options.srid);
+    let involved_persons = involved_person_layer(options.labels, options.srid);
+    let summary = match options.format {
+        ExportFormat::Gpkg => {
+            let writer = gpkg::Writer::create(output, accidents, involved_persons).await?;
+            export_records(writer, &options).await?
+        }
+        ExportFormat::Fgb => {
+            let writer = fgb::Writer::create(output, accidents, involved_persons)?;
+            export_records(writer, &options).await?
+        }
+        ExportFormat::Shp => {
+            let writer = shp::Writer::create(output, accidents, involved_persons)?;
+            export_records(writer, &options).await?
+        }
+        ExportFormat::GeoJsonSeq => {
+            let writer = geojsonseq::Writer::create(output, accidents, involved_persons)?;
+            export_records(writer, &options).await?
+        }
+    };
+    summary.print();
+
+    Ok(())
+}
+
+/// エクスポートオプションに従って、本票と補充票またはデータベースからレコードを出力する。
+async fn export_records<W: RecordWriter>(
+    writer: W,
+    options: &ExportOptions,
+) -> anyhow::Result<Summary> {
+    match &options.files {
+        Some((main_file, support_file)) => {
+            write_files(
+                writer,
+                main_file,
+                support_file,
+                options.layout,
+                options.labels,
+            )
+            .await
+        }
+        None => {
+            let pool = connection_pool().await?;
+            write_database(
+                writer,
+                &pool,
+                options.where_clause.as_deref(),
+                options.labels,
+                options.srid,
+            )
+            .await
+        }
+    }
+}
+
+/// レイヤーのレコードを1件ずつ出力するライター
+///
+/// 交通事故のレコードをすべて出力した後に交通事故当事者以外の関与者のレコードを出力して、
+/// 最後に`finish`で出力を完了する。
+pub(crate) trait RecordWriter: Sized {
+    /// 交通事故のレコードを出力する。
+    async fn write_accident(&mut self, record: &Record) -> anyhow::Result<()>;
+
+    /// 交通事故当事者以外の関与者のレコードを出力する。
+    async fn write_involved_person(&mut self, record: &Record) -> anyhow::Result<()>;
+
+    /// 出力を完了して、出力したファイルのパスを返す。
+    async fn finish(self) -> anyhow::Result<Vec<PathBuf>>;
+}
+
+/// 出力したレコードの件数とファイルのパス
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    /// 出力した交通事故の件数
+    pub accidents: usize,
+    /// 出力した交通事故当事者以外の関与者の件数
+    pub involved_persons: usize,
+    /// 出力したファイルのパス
+    pub paths: Vec<PathBuf>,
+}
+
+impl Summary {
+    /// 出力したレコードの件数とファイルのパスを標準出力に出力する。
+    pub fn print(&self) {
+        println!(
+            "交通事故{}件、関係者{}件を出力しました。",
+            self.accidents, self.involved_persons
+        );
+        for path in &self.paths {
+            println!("{}", path.display());
+        }
+    }
+}
+
+/// データベースに接続せずに、本票と補充票を1行ずつ読み込みながら出力する。
+///
+/// 本票の都道府県コードはそのまま出力し、コード表の都道府県コードの対応はJIS規格の市区町村コードを組み立てるためだけに使用する。
+/// 交通事故当事者以外の関与者と交通事故を関連付けるため、交通事故の識別子と位置だけを保持する。
+///
+/// # 引数
+///
+/// * `writer` - レコードを出力するライター
+/// * `main_file` - 本票のファイルパス
+/// * `support_file` - 補充票のファイルパス
+/// * `layout` - 本票及び補充票のレイアウト（指定しない場合はヘッダ行から列の位置を決定）
+/// * `language` - コードの名前の言語
+///
+/// # 戻り値
+///
+/// 出力したレコードの件数とファイルのパス
+pub(crate) async fn write_files<W: RecordWriter>(
+    mut writer: W,
+    main_file: &Path,
+    support_file: &Path,
+    layout: Option<Layout>,
+    language: Language,
+) -> anyhow::Result<Summary> {
+    let columns = accident_columns(language);
+    let mut accident_ids = HashMap::new();
+    let mut locations = HashMap::new();
+    for accident in AccidentReader::open(main_file, CodeTables::global().prefectures(), layout)? {
+        let accident = accident?;
+        writer
+            .write_accident(&record(&columns, &accident, Some(accident.location)))
+            .await?;
+        accident_ids.insert(accident.identifier(), accident.id);
+        locations.insert(accident.id, accident.location);
+    }
+
+    let columns = involved_person_columns(language);
+    let mut involved_persons = 0;
+    for involved_person in InvolvedPersonReader::open(support_file, &accident_ids, layout)? {
+        let involved_person = involved_person?;
+        let location = locations.get(&involved_person.accident_id).copied();
+        writer
+            .write_involved_person(&record(&columns, &involved_person, location))
+            .await?;
+        involved_persons += 1;
+    }
+
+    Ok(Summary {
+        accidents: locations.len(),
+        involved_persons,
+        paths: writer.finish().await?,
+    })
+}
+
+/// データベースから交通事故と交通事故当事者以外の関与者を1件ずつ取得しながら出力する。
+///
+/// 条件にはSQLの式を指定できるため、読み取り専用トランザクションでSELECT文を実行する。
+/// 交通事故と交通事故当事者以外の関与者を同じスナップショットから取得するため、
+/// トランザクション分離レベルはREPEATABLE READとする。
+///
+/// # 引数
+///
+/// * `writer` - レコードを出力するライター
+/// * `pool` - データベースコネクションプール
+/// * `where_clause` - 交通事故を絞り込むSQLの式（交通事故テーブルの別名は`a`）
+/// * `language` - コードの名前の言語
+/// * `srid` - 交通事故の発生場所の空間参照系
+///
+/// # 戻り値
+///
+/// 出力したレコードの件数とファイルのパス
+async fn write_database<W: RecordWriter>(
+    mut writer: W,
+    pool: &PgPool,
+    where_clause: Option<&str>,
+    language: Language,
+    srid: Srid,
+) -> anyhow::Result<Summary> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+        .execute(&mut *tx)
+        .await?;
+
+    let columns = accident_columns(language);
+    let sql = export_accidents_sql(where_clause, srid);
+    let mut rows = fetch_accidents(&mut tx, &sql);
+    let mut accidents = 0;
+    while let Some(accident) = rows.try_next().await? {
+        writer
+            .write_accident(&record(&columns, &accident, Some(accident.location)))
+            .await?;
+        accidents += 1;
+    }
+    drop(rows);
+
+    let columns = involved_person_columns(language);
+    let sql = export_involved_persons_sql(where_clause, srid);
+    let mut rows = fetch_involved_persons(&mut tx, &sql);
+    let mut involved_persons = 0;
+    while let Some((involved_person, location)) = rows.try_next().await? {
+        writer
+            .write_involved_person(&record(&columns, &involved_person, Some(location)))
+            .await?;
+        involved_persons += 1;
+    }
+    drop(rows);
+    tx.rollback().await?;
+
+    Ok(Summary {
+        accidents,
+        involved_persons,
+        paths: writer.finish().await?,
+    })
+}
+
+/// 属性の型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    /// 文字列
+    Text,
+    /// 整数
+    Integer,
+}
+
+/// 属性
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Field {
+    /// 属性の名前
+    pub name: &'static str,
+    /// 属性の型
+    pub field_type: FieldType,
+}
+
+/// 属性の値
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldValue {
+    /// 文字列
+    Text(String),
+    /// 整数
+    Integer(i64),
+    /// 値なし
+    Null,
+}
+
+impl FieldValue {
+    /// 文字列の値を返す。
+    fn text<T: ToString>(value: T) -> Self {
+        Self::Text(value.to_string())
+    }
+
+    /// 値がない場合は`Null`、値がある場合は文字列の値を返す。
+    fn optional_text<T: ToString>(value: Option<T>) -> Self {
+        value.map(Self::text).unwrap_or(Self::Null)
+    }
+
+    /// コードの名前を返す。コード表にコードが存在しない場合は`Null`を返す。
+    fn name(name: Option<&str>) -> Self {
+        Self::optional_text(name)
+    }
+}
+
+/// レイヤーのレコード
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    /// 位置
+    pub point: Option<Point>,
+    /// 属性の値
+    pub values: Vec<FieldValue>,
+}
+
+/// レイヤー
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layer {
+    /// レイヤー名
+    pub name: &'static str,
+    /// 位置を持つレイヤーかを示すフラグ
+    ///
+    /// 位置を持たないレイヤーのレコードは、関連する交通事故の位置を参考として記録する。
+    pub spatial: bool,
+    /// 位置の空間参照系
+    pub srid: Srid,
+    /// 属性
+    pub fields: Vec<Field>,
+}
+
+/// 出力したレコードの位置を含む範囲
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Extent(Option<[f64; 4]>);
+
+impl Extent {
+    /// 範囲を広げて、位置を含める。
+    ///
+    /// # 引数
+    ///
+    /// * `point` - 位置
+    pub fn include(&mut self, point: Point) {
+        let [x_min, y_min, x_max, y_max] =
+            self.0
+                .unwrap_or([point.x(), point.y(), point.x(), point.y()]);
+        self.0 = Some([
+            x_min.min(point.x()),
+            y_min.min(point.y()),
+            x_max.max(point.x()),
+            y_max.max(point.y()),
+        ]);
+    }
+
+    /// 範囲を返す。
+    ///
+    /// # 戻り値
+    ///
+    /// `[x_min, y_min, x_max, y_max]`、位置を含めていない場合は`None`
+    pub fn bbox(&self) -> Option<[f64; 4]> {
+        self.0
+    }
+}
+
+/// レイヤーの列
+struct Column<T> {
+    /// 属性
+    field: Field,
+    /// 属性の値を返す関数
+    value: Box<dyn Fn(&T) -> FieldValue>,
+}
+
+impl<T: 'static> Column<T> {
+    fn text(name: &'static str, value: fn(&T) -> FieldValue) -> Self {
+        Self {
+            field: Field {
+                name,
+                field_type: FieldType::Text,
+            },
+            value: Box::new(value),
+        }
+    }
+
+    fn integer(name: &'static str, value: fn(&T) -> FieldValue) -> Self {
+        Self {
+            field: Field {
+                name,
+                field_type: FieldType::Integer,
+            },
+            value: Box::new(value),
+        }
+    }
+
+    /// コードの名前の列を返す。
+    ///
+    /// 属性の値を返す関数には、コードの名前の言語を渡す。
+    fn label(
+        name: &'static str,
+        language: Language,
+        value: fn(&T, Language) -> FieldValue,
+    ) -> Self {
+        Self {
+            field: Field {
+                name,
+                field_type: FieldType::Text,
+            },
+            value: Box::new(move |row| value(row, language)),
+        }
+    }
+}
+
+/// 列の定義に従って、値をレイヤーのレコードに変換する。
+///
+/// # 引数
+///
+/// * `columns` - レイヤーの列
+/// * `value` - 交通事故または交通事故当事者以外の関与者
+/// * `point` - 交通事故の発生場所
+///
+/// # 戻り値
+///
+/// レイヤーのレコード
+fn record<T>(columns: &[Column<T>], value: &T, point: Option<Point>) -> Record {
+    Record {
+        point,
+        values: columns.iter().map(|column| (column.value)(value)).collect(),
+    }
+}
+
+/// 交通事故のレイヤーの列を返す。
+///
+/// コードの列の後には、コード表から取得したコードの名前の列を配置する。
+fn accident_columns(language: Language) -> Vec<Column<RawAccident>> {
+    vec![
+        Column::text("id", |a| FieldValue::text(a.id)),
+        Column::text("prefecture_code", |a| FieldValue::text(&a.prefecture_code)),
+        Column::label("prefecture_name", language, |a, language| {
+            FieldValue::name(a.prefecture_code.label(language))
+        }),
+        Column::text("police_station_code", |a| {
+            FieldValue::text(a.police_station_code.station_code())
+        }),
+        Column::label("police_station_name", language, |a, language| {
+            FieldValue::name(a.police_station_code.label(language))
+        }),
+        Column::integer("main_number", |a| FieldValue::Integer(a.main_number as i64)),
+        Column::text("accident_detail_code", |a| {
+            FieldValue::text(&a.accident_detail_code)
+        }),
+        Column::label("accident_detail_name", language, |a, language| {
+            FieldValue::name(a.accident_detail_code.label(language))
+        }),
+        Column::integer("number_of_deaths", |a| {
+            FieldValue::Integer(a.number_of_deaths as i64)
+        }),
+        Column::integer("number_of_injuries", |a| {
+            FieldValue::Integer(a.number_of_injuries as i64)
+        }),
+        Column::text("route_code", |a| FieldValue::text(&a.route_code)),
+        Column::label("route_name", language, |a, language| {
+            FieldValue::name(a.route_code.label(language))
+        }),
+        Column::text("route_class_code", |a| {
+            FieldValue::text(&a.route_class_code)
+        }),
+        Column::label("route_class_name", language, |a, language| {
+            FieldValue::name(a.route_class_code.label(language))
+        }),
+        Column::integer("location_code", |a| {
+            FieldValue::Integer(a.location_code as i64)
+        }),
+        Column::text("city_jis_code", |a| FieldValue::text(&a.city_jis_code)),
+        Column::label("city_name", language, |a, language| {
+            FieldValue::name(a.city_jis_code.label(language))
+        }),
+        Column::text("occurred_at", |a| {
+            FieldValue::text(format_datetime(a.occurred_at))
+        }),
+        Column::text("day_night_code", |a| FieldValue::text(&a.day_night_code)),
+        Column::label("day_night_name", language, |a, language| {
+            FieldValue::name(a.day_night_code.label(language))
+        }),
+        Column::text("sunrise_time", |a| {
+            FieldValue::text(format_time(a.sunrise_time))
+        }),
+        Column::text("sunset_time", |a| {
+            FieldValue::text(format_time(a.sunset_time))
+        }),
+        Column::text("weather_code", |a| FieldValue::text(&a.weather_code)),
+        Column::label("weather_name", language, |a, language| {
+            FieldValue::name(a.weather_code.label(language))
+        }),
+        Column::text("district_code", |a| FieldValue::text(&a.district_code)),
+        Column::label("district_name", language, |a, language| {
+            FieldValue::name(a.district_code.label(language))
+        }),
+        Column::text("surface_condition_code", |a| {
+            FieldValue::text(&a.surface_condition_code)
+        }),
+        Column::label("surface_condition_name", language, |a, language| {
+            FieldValue::name(a.surface_condition_code.label(language))
+        }),
+        Column::text("road_model_code", |a| FieldValue::text(&a.road_model_code)),
+        Column::label("road_model_name", language, |a, language| {
+            FieldValue::name(a.road_model_code.label(language))
+        }),
+        Column::text("traffic_signal_code", |a| {
+            FieldValue::text(&a.traffic_signal_code)
+        }),
+        Column::label("traffic_signal_name", language, |a, language| {
+            FieldValue::name(a.traffic_signal_code.label(language))
+        }),
+        Column::text("stop_regulation_sign_a_code", |a| {
+            FieldValue::text(&a.stop_regulation_sign_a_code)
+        }),
+        Column::label("stop_regulation_sign_a_name", language, |a, language| {
+            FieldValue::name(a.stop_regulation_sign_a_code.label(language))
+        }),
+        Column::text("stop_regulation_display_a_code", |a| {
+            FieldValue::text(&a.stop_regulation_display_a_code)
+        }),
+        Column::label("stop_regulation_display_a_name", language, |a, language| {
+            FieldValue::name(a.stop_regulation_display_a_code.label(language))
+        }),
+        Column::text("stop_regulation_sign_b_code", |a| {
+            FieldValue::text(&a.stop_regulation_sign_b_code)
+        }),
+        Column::label("stop_regulation_sign_b_name", language, |a, language| {
+            FieldValue::name(a.stop_regulation_sign_b_code.label(language))
+        }),
+        Column::text("stop_regulation_display_b_code", |a| {
+            FieldValue::text(&a.stop_regulation_display_b_code)
+        }),
+        Column::label("stop_regulation_display_b_name", language, |a, language| {
+            FieldValue::name(a.stop_regulation_display_b_code.label(language))
+        }),
+        Column::text("road_width_code", |a| FieldValue::text(&a.road_width_code)),
+        Column::label("road_width_name", language, |a, language| {
+            FieldValue::name(a.road_width_code.label(language))
+        }),
+        Column::text("road_alignment_code", |a| {
+            FieldValue::text(&a.road_alignment_code)
+        }),
+        Column::label("road_alignment_name", language, |a, language| {
+            FieldValue::name(a.road_alignment_code.label(language))
+        }),
+        Column::text("collision_point_code", |a| {
+            FieldValue::text(&a.collision_point_code)
+        }),
+        Column::label("collision_point_name", language, |a, language| {
+            FieldValue::name(a.collision_point_code.label(language))
+        }),
+        Column::text("zone_regulation_code", |a| {
+            FieldValue::text(&a.zone_regulation_code)
+        }),
+        Column::label("zone_regulation_name", language, |a, language| {
+            FieldValue::name(a.zone_regulation_code.label(language))
+        }),
+        Column::text("central_separation_code", |a| {
+            FieldValue::text(&a.central_separation_code)
+        }),
+        Column::label("central_separation_name", language, |a, language| {
+            FieldValue::name(a.central_separation_code.label(language))
+        }),
+        Column::text("road_segmentation_code", |a| {
+            FieldValue::text(&a.road_segmentation_code)
+        }),
+        Column::label("road_segmentation_name", language, |a, language| {
+            FieldValue::name(a.road_segmentation_code.label(language))
+        }),
+        Column::text("accident_type_code", |a| {
+            FieldValue::text(&a.accident_type_code)
+        }),
+        Column::label("accident_type_name", language, |a, language| {
+            FieldValue::name(a.accident_type_code.label(language))
+        }),
+        Column::text("age_a_code", |a| FieldValue::text(&a.age_a_code)),
+        Column::label("age_a_name", language, |a, language| {
+            FieldValue::name(a.age_a_code.label(language))
+        }),
+        Column::text("age_b_code", |a| FieldValue::text(&a.age_b_code)),
+        Column::label("age_b_name", language, |a, language| {
+            FieldValue::name(a.age_b_code.label(language))
+        }),
+        Column::text("party_a_code", |a| FieldValue::text(&a.party_a_code)),
+        Column::label("party_a_name", language, |a, language| {
+            FieldValue::name(a.party_a_code.label(language))
+        }),
+        Column::text("party_b_code", |a| FieldValue::text(&a.party_b_code)),
+        Column::label("party_b_name", language, |a, language| {
+            FieldValue::name(a.party_b_code.label(language))
+        }),
+        Column::text("purpose_a_code", |a| FieldValue::text(&a.purpose_a_code)),
+        Column::label("purpose_a_name", language, |a, language| {
+            FieldValue::name(a.purpose_a_code.label(language))
+        }),
+        Column::text("purpose_b_code", |a| FieldValue::text(&a.purpose_b_code)),
+        Column::label("purpose_b_name", language, |a, language| {
+            FieldValue::name(a.purpose_b_code.label(language))
+        }),
+        Column::text("vehicle_type_a_code", |a| {
+            FieldValue::text(&a.vehicle_type_a_code)
+        }),
+        Column::label("vehicle_type_a_name", language, |a, language| {
+            FieldValue::name(a.vehicle_type_a_code.label(language))
+        }),
+        Column::text("vehicle_type_b_code", |a| {
+            FieldValue::text(&a.vehicle_type_b_code)
+        }),
+        Column::label("vehicle_type_b_name", language, |a, language| {
+            FieldValue::name(a.vehicle_type_b_code.label(language))
+        }),
+        Column::text("automatic_a_code", |a| {
+            FieldValue::text(&a.automatic_a_code)
+        }),
+        Column::label("automatic_a_name", language, |a, language| {
+            FieldValue::name(a.automatic_a_code.label(language))
+        }),
+        Column::text("automatic_b_code", |a| {
+            FieldValue::text(&a.automatic_b_code)
+        }),
+        Column::label("automatic_b_name", language, |a, language| {
+            FieldValue::name(a.automatic_b_code.label(language))
+        }),
+        Column::text("support_car_a_code", |a| {
+            FieldValue::text(&a.support_car_a_code)
+        }),
+        Column::label("support_car_a_name", language, |a, language| {
+            FieldValue::name(a.support_car_a_code.label(language))
+        }),
+        Column::text("support_car_b_code", |a| {
+            FieldValue::text(&a.support_car_b_code)
+        }),
+        Column::label("support_car_b_name", language, |a, language| {
+            FieldValue::name(a.support_car_b_code.label(language))
+        }),
+        Column::text("speed_regulation_a_code", |a| {
+            FieldValue::text(&a.speed_regulation_a_code)
+        }),
+        Column::label("speed_regulation_a_name", language, |a, language| {
+            FieldValue::name(a.speed_regulation_a_code.label(language))
+        }),
+        Column::text("speed_regulation_b_code", |a| {
+            FieldValue::text(&a.speed_regulation_b_code)
+        }),
+        Column::label("speed_regulation_b_name", language, |a, language| {
+            FieldValue::name(a.speed_regulation_b_code.label(language))
+        }),
+        Column::text("collision_part_a", |a| {
+            FieldValue::text(&a.collision_part_a)
+        }),
+        Column::text("collision_part_b", |a| {
+            FieldValue::text(&a.collision_part_b)
+        }),
+        Column::text("vehicle_damage_a_code", |a| {
+            FieldValue::text(&a.vehicle_damage_a_code)
+        }),
+        Column::label("vehicle_damage_a_name", language, |a, language| {
+            FieldValue::name(a.vehicle_damage_a_code.label(language))
+        }),
+        Column::text("vehicle_damage_b_code", |a| {
+            FieldValue::text(&a.vehicle_damage_b_code)
+        }),
+        Column::label("vehicle_damage_b_name", language, |a, language| {
+            FieldValue::name(a.vehicle_damage_b_code.label(language))
+        }),
+        Column::text("airbag_a_code", |a| FieldValue::text(&a.airbag_a_code)),
+        Column::label("airbag_a_name", language, |a, language| {
+            FieldValue::name(a.airbag_a_code.label(language))
+        }),
+        Column::text("airbag_b_code", |a| FieldValue::text(&a.airbag_b_code)),
+        Column::label("airbag_b_name", language, |a, language| {
+            FieldValue::name(a.airbag_b_code.label(language))
+        }),
+        Column::text("side_airbag_a_code", |a| {
+            FieldValue::text(&a.side_airbag_a_code)
+        }),
+        Column::label("side_airbag_a_name", language, |a, language| {
+            FieldValue::name(a.side_airbag_a_code.label(language))
+        }),
+        Column::text("side_airbag_b_code", |a| {
+            FieldValue::text(&a.side_airbag_b_code)
+        }),
+        Column::label("side_airbag_b_name", language, |a, language| {
+            FieldValue::name(a.side_airbag_b_code.label(language))
+        }),
+        Column::text("injury_a_code", |a| FieldValue::text(&a.injury_a_code)),
+        Column::label("injury_a_name", language, |a, language| {
+            FieldValue::name(a.injury_a_code.label(language))
+        }),
+        Column::text("injury_b_code", |a| FieldValue::text(&a.injury_b_code)),
+        Column::label("injury_b_name", language, |a, language| {
+            FieldValue::name(a.injury_b_code.label(language))
+        }),
+        Column::text("week_code", |a| FieldValue::text(&a.week_code)),
+        Column::label("week_name", language, |a, language| {
+            FieldValue::name(a.week_code.label(language))
+        }),
+        Column::text("holiday_code", |a| FieldValue::text(&a.holiday_code)),
+        Column::label("holiday_name", language, |a, language| {
+            FieldValue::name(a.holiday_code.label(language))
+        }),
+        Column::integer("cognitive_days_a", |a| {
+            FieldValue::Integer(a.cognitive_days_a as i64)
+        }),
+        Column::integer("cognitive_days_b", |a| {
+            FieldValue::Integer(a.cognitive_days_b as i64)
+        }),
+        Column::text("driving_practice_a_code", |a| {
+            FieldValue::text(&a.driving_practice_a_code)
+        }),
+        Column::label("driving_practice_a_name", language, |a, language| {
+            FieldValue::name(a.driving_practice_a_code.label(language))
+        }),
+        Column::text("driving_practice_b_code", |a| {
+            FieldValue::text(&a.driving_practice_b_code)
+        }),
+        Column::label("driving_practice_b_name", language, |a, language| {
+            FieldValue::name(a.driving_practice_b_code.label(language))
+        }),
+    ]
+}
+
+/// 交通事故当事者以外の関与者のレイヤーの列を返す。
+fn involved_person_columns(language: Language) -> Vec<Column<RawInvolvedPerson>> {
+    vec![
+        Column::text("id", |p| FieldValue::text(p.id)),
+        Column::text("accident_id", |p| FieldValue::text(p.accident_id)),
+        Column::integer("sub_number", |p| FieldValue::Integer(p.sub_number as i64)),
+        Column::text("party_code", |p| FieldValue::text(&p.party_code)),
+        Column::label("party_name", language, |p, language| {
+            FieldValue::name(p.party_code.label(language))
+        }),
+        Column::text("purpose_code", |p| {
+            FieldValue::optional_text(p.purpose_code.as_ref())
+        }),
+        Column::label("purpose_name", language, |p, language| {
+            FieldValue::name(p.purpose_code.as_ref().and_then(|c| c.label(language)))
+        }),
+        Column::text("vehicle_type_code", |p| {
+            FieldValue::optional_text(p.vehicle_type_code.as_ref())
+        }),
+        Column::label("vehicle_type_name", language, |p, language| {
+            FieldValue::name(p.vehicle_type_code.as_ref().and_then(|c| c.label(language)))
+        }),
+        Column::text("riding_type_code", |p| {
+            FieldValue::text(&p.riding_type_code)
+        }),
+        Column::label("riding_type_name", language, |p, language| {
+            FieldValue::name(p.riding_type_code.label(language))
+        }),
+        Column::text("riding_class_code", |p| {
+            FieldValue::text(&p.riding_class_code)
+        }),
+        Column::label("riding_class_name", language, |p, language| {
+            FieldValue::name(p.riding_class_code.label(language))
+        }),
+        Column::text("support_car_code", |p| {
+            FieldValue::text(&p.support_car_code)
+        }),
+        Column::label("support_car_name", language, |p, language| {
+            FieldValue::name(p.support_car_code.label(language))
+        }),
+        Column::text("airbag_code", |p| FieldValue::text(&p.airbag_code)),
+        Column::label("airbag_name", language, |p, language| {
+            FieldValue::name(p.airbag_code.label(language))
+        }),
+        Column::text("side_airbag_code", |p| {
+            FieldValue::text(&p.side_airbag_code)
+        }),
+        Column::label("side_airbag_name", language, |p, language| {
+            FieldValue::name(p.side_airbag_code.label(language))
+        }),
+        Column::text("injury_code", |p| FieldValue::text(&p.injury_code)),
+        Column::label("injury_name", language, |p, language| {
+            FieldValue::name(p.injury_code.label(language))
+        }),
+        Column::text("collision_part", |p| {
+            FieldValue::optional_text(p.collision_part.as_ref())
+        }),
+        Column::text("vehicle_damage_code", |p| {
+            FieldValue::optional_text(p.vehicle_damage_code.as_ref())
+        }),
+        Column::label("vehicle_damage_name", language, |p, language| {
+            FieldValue::name(
+                p.vehicle_damage_code
+                    .as_ref()
+                    .and_then(|c| c.label(language)),
+            )
+        }),
+    ]
+}
+
+/// 交通事故のレイヤーを返す。
+///
+/// # 引数
+///
+/// * `language` - コードの名前の言語
+/// * `srid` - 交通事故の発生場所の空間参照系
+///
+/// # 戻り値
+///
+/// 交通事故のレイヤー
+pub fn accident_layer(language: Language, srid: Srid) -> Layer {
+    Layer {
+        name: ACCIDENTS_LAYER,
+        spatial: true,
+        srid,
+        fields: accident_columns(language)
+            .into_iter()
+            .map(|column| column.field)
+            .collect(),
+    }
+}
+
+/// 交通事故当事者以外の関与者のレイヤーを返す。
+///
+/// # 引数
+///
+/// * `language` - コードの名前の言語
+/// * `srid` - 交通事故の発生場所の空間参照系
+///
+/// # 戻り値
+///
+/// 交通事故当事者以外の関与者のレイヤー
+pub fn involved_person_layer(language: Language, srid: Srid) -> Layer {
+    Layer {
+        name: INVOLVED_PERSONS_LAYER,
+        spatial: false,
+        srid,
+        fields: involved_person_columns(language)
+            .into_iter()
+            .map(|column| column.field)
+            .collect(),
+    }
+}
+
+/// 関連する表を出力するファイルのパスを返す。
+///
+/// # 引数
+///
+/// * `output` - 出力ファイルパス
+/// * `layer` - 関連する表のレイヤー
+/// * `extension` - 関連する表を出力するファイルの拡張子
+///
+/// # 戻り値
+///
+/// `<出力ファイル名>_<レイヤー名>.<拡張子>`形式のファイルパス
+pub fn related_path(output: &Path, layer: &Layer, extension: &str) -> PathBuf {
+    let stem = output
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    output.with_file_name(format!("{}_{}.{}", stem, layer.name, extension))
+}
+
+/// 出力ファイルの完成前にレコードを書き込む一時ファイル
+///
+/// 出力ファイルと同じディレクトリに`<出力ファイル名>.tmp`形式で作成する。
+/// 出力が完了したら`persist`で出力ファイルに置き換えて、エラーで中断した場合は破棄するときに削除する。
+pub(crate) struct TempFile {
+    /// 一時ファイルのパス
+    path: PathBuf,
+    /// 一時ファイルのライター
+    writer: BufWriter<File>,
+}
+
+impl TempFile {
+    /// 一時ファイルを作成する。
+    ///
+    /// # 引数
+    ///
+    /// * `path` - 出力ファイルパス
+    ///
+    /// # 戻り値
+    ///
+    /// 読み書きできる一時ファイル
+    pub fn create(path: &Path) -> anyhow::Result<Self> {
+        let mut temp_path = path.as_os_str().to_os_string();
+        temp_path.push(".tmp");
+        let path = PathBuf::from(temp_path);
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .map_err(|e| anyhow::anyhow!("{}を作成できませんでした。{}", path.display(), e))?;
+
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+        })
+    }
+
+    /// 書き込んだ内容を先頭から読み込むリーダーを返す。
+    ///
+    /// # 戻り値
+    ///
+    /// 一時ファイルのリーダー
+    pub fn reader(&mut self) -> anyhow::Result<BufReader<&File>> {
+        self.writer.flush()?;
+        self.writer.get_mut().rewind()?;
+
+        Ok(BufReader::new(self.writer.get_ref()))
+    }
+
+    /// 一時ファイルのパスを返す。
+    ///
+    /// # 戻り値
+    ///
+    /// 一時ファイルのパス
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 書き込んだ内容を保存して、一時ファイルを出力ファイルに置き換える。
+    ///
+    /// # 引数
+    ///
+    /// * `path` - 出力ファイルパス
+    pub fn persist(mut self, path: &Path) -> anyhow::Result<()> {
+        self.writer.flush()?;
+        std::fs::rename(&self.path, path)
+            .map_err(|e| anyhow::anyhow!("{}を作成できませんでした。{}", path.display(), e))?;
+        self.path = PathBuf::new();
+
+        Ok(())
+    }
+}
+
+impl Write for TempFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl Seek for TempFile {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.writer.seek(pos)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if !self.path.as_os_str().is_empty() {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// ポイントをリトルエンディアンのWKBに変換する。
+///
+/// # 引数
+///
+/// * `point` - ポイント
+///
+/// # 戻り値
+///
+/// WKB
+pub fn wkb_point(point: Point) -> Vec<u8> {
+    let mut wkb = Vec::with_capacity(21);
+    wkb.push(0x01);
+    wkb.extend(1_u32.to_le_bytes());
+    wkb.extend(point.x().to_le_bytes());
+    wkb.extend(point.y().to_le_bytes());
+
+    wkb
+}
+
+/// 発生日時を日本標準時のRFC 3339形式の文字列に変換する。
+fn format_datetime(datetime: OffsetDateTime) -> String {
+    datetime.to_offset(offset!(+9)).format(&Rfc3339).unwrap()
+}
+
+/// 時刻を`HH:MM`形式の文字列に変換する。
+fn format_time(time: Time) -> String {
+    format!("{:02}:{:02}", time.hour(), time.minute())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::Read;
+    use uuid::Uuid;
+
+    use crate::test_utils::temp_dir_for_test;
+
+    /// テスト用のレイヤーとレコード
+    pub(crate) struct LayersForTest {
+        pub(crate) accidents: Layer,
+        pub(crate) accident_records: Vec<Record>,
+        pub(crate) involved_persons: Layer,
+        pub(crate) involved_person_records: Vec<Record>,
+    }
+
+    impl LayersForTest {
+        /// レコードをライターで出力する。
+        pub(crate) async fn write<W: RecordWriter>(&self, mut writer: W) -> Vec<PathBuf> {
+            for record in &self.accident_records {
+                writer.write_accident(record).await.unwrap();
+            }
+            for record in &self.involved_person_records {
+                writer.write_involved_person(record).await.unwrap();
+            }
+            writer.finish().await.unwrap()
+        }
+    }
+
+    pub(crate) fn layers_for_test() -> LayersForTest {
+        let field = |name, field_type| Field { name, field_type };
+        let accident_ids = [Uuid::new_v4(), Uuid::new_v4()];
+
+        LayersForTest {
+            accidents: Layer {
+                name: ACCIDENTS_LAYER,
+                spatial: true,
+                srid: Srid::JGD2011,
+                fields: vec![
+                    field("id", FieldType::Text),
+                    field("weather_name", FieldType::Text),
+                    field("number_of_deaths", FieldType::Integer),
+                    field("route_name", FieldType::Text),
+                ],
+            },
+            accident_records: vec![
+                Record {
+                    point: Some(Point::new(139.5, 35.5)),
+                    values: vec![
+                        FieldValue::text(accident_ids[0]),
+                        FieldValue::text("晴"),
+                        FieldValue::Integer(1),
+                        FieldValue::Null,
+                    ],
+                },
+                Record {
+                    point: Some(Point::new(140.0, 36.0)),
+                    values: vec![
+                        FieldValue::text(accident_ids[1]),
+                        FieldValue::text("雨"),
+                        FieldValue::Integer(10),
+                        FieldValue::text("一般国道"),
+                    ],
+                },
+            ],
+            involved_persons: Layer {
+                name: INVOLVED_PERSONS_LAYER,
+                spatial: false,
+                srid: Srid::JGD2011,
+                fields: vec![
+                    field("id", FieldType::Text),
+                    field(ACCIDENT_ID_FIELD, FieldType::Text),
+                    field("sub_number", FieldType::Integer),
+                ],
+            },
+            involved_person_records: vec![Record {
+                point: Some(Point::new(140.0, 36.0)),
+                values: vec![
+                    FieldValue::text(Uuid::new_v4()),
+                    FieldValue::text(accident_ids[1]),
+                    FieldValue::Integer(1),
+                ],
+            }],
+        }
+    }
+
+    /// 出力したレコードを保持するライター
+    #[derive(Default)]
+    struct RecordsForTest {
+        accidents: Vec<Record>,
+        involved_persons: Vec<Record>,
+    }
+
+    impl RecordWriter for &mut RecordsForTest {
+        async fn write_accident(&mut self, record: &Record) -> anyhow::Result<()> {
+            self.accidents.push(record.clone());
+            Ok(())
+        }
+
+        async fn write_involved_person(&mut self, record: &Record) -> anyhow::Result<()> {
+            self.involved_persons.push(record.clone());
+            Ok(())
+        }
+
+        async fn finish(self) -> anyhow::Result<Vec<PathBuf>> {
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn extent_ok() {
+        let layers = layers_for_test();
+        let mut extent = Extent::default();
+        assert_eq!(extent.bbox(), None);
+        for record in &layers.accident_records {
+            extent.include(record.point.unwrap());
+        }
+        assert_eq!(extent.bbox(), Some([139.5, 35.5, 140.0, 36.0]));
+    }
+
+    #[test]
+    fn related_path_ok() {
+        let layers = layers_for_test();
+        assert_eq!(
+            related_path(
+                Path::new("out/accidents.shp"),
+                &layers.involved_persons,
+                "dbf"
+            ),
+            PathBuf::from("out/accidents_involved_persons.dbf")
+        );
+    }
+
+    #[test]
+    fn temp_file_ok() {
+        let dir = temp_dir_for_test("temp-file");
+        let output = dir.join("accidents.fgb");
+        let mut temp_file = TempFile::create(&output).unwrap();
+        let temp_path = dir.join("accidents.fgb.tmp");
+        assert!(temp_path.exists());
+        temp_file.write_all(b"features").unwrap();
+        let mut buf = String::new();
+        temp_file
+            .reader()
+            .unwrap()
+            .read_to_string(&mut buf)
+            .unwrap();
+        assert_eq!(buf, "features");
+        drop(temp_file);
+        assert!(!temp_path.exists());
+
+        let mut temp_file = TempFile::create(&output).unwrap();
+        temp_file.write_all(b"features").unwrap();
+        temp_file.persist(&output).unwrap();
+        assert!(!temp_path.exists());
+        assert_eq!(std::fs::read_to_string(&output).unwrap(), "features");
+    }
+
+    #[test]
+    fn format_datetime_ok() {
+        let datetime = time::macros::datetime!(2022-01-01 01:30 UTC);
+        assert_eq!(format_datetime(datetime), "2022-01-01T10:30:00+09:00");
+        assert_eq!(format_time(time::macros::time!(06:05)), "06:05");
+    }
+
+    #[test]
+    fn accident_columns_ok() {
+        let names = accident_columns(Language::Ja)
+            .iter()
+            .map(|column| column.field.name)
+            .collect::<Vec<_>>();
+        let unique = names.iter().collect::<std::collections::HashSet<_>>();
+        assert_eq!(names.len(), unique.len());
+        assert!(names.contains(&"weather_name"));
+        assert!(names.contains(&"police_station_name"));
+    }
+
+    #[tokio::test]
+    async fn write_files_ok() {
+        let dir = temp_dir_for_test("labels");
+        let main_file = dir.join("honhyo.csv");
+        let support_file = dir.join("hojuhyo.csv");
+        let row = "1,10,059,0001,2,000,001,40010,0000,104,2022,01,22,14,18,12,06,59,16,33,5,1,3,14,7,00,00,00,00,04,9,01,70,1,4,21,35,25,03,04,31,31,01,01,1,1,00,00,04,04,30,30,3,3,2,2,2,2,2,4,430234789,1412612831,7,3,9999,9999,1,1";
+        // レイアウトを指定するため、ヘッダ行の内容は読み込まない
+        let header = vec!["h"; row.split(',').count()].join(",");
+        std::fs::write(&main_file, format!("{}\n{}\n", header, row)).unwrap();
+        std::fs::write(&support_file, format!("{}\n", vec!["h"; 16].join(","))).unwrap();
+        let value = |language, records: &RecordsForTest, name: &str| {
+            let layer = accident_layer(language, Srid::JGD2011);
+            let index = layer.fields.iter().position(|f| f.name == name).unwrap();
+            records.accidents[0].values[index].clone()
+        };
+
+        let mut records = RecordsForTest::default();
+        let summary = write_files(
+            &mut records,
+            &main_file,
+            &support_file,
+            Some(Layout::Y2022),
+            Language::Ja,
+        )
+        .await
+        .unwrap();
+        assert_eq!((summary.accidents, summary.involved_persons), (1, 0));
+        assert!(records.accidents[0].point.is_some());
+        assert_eq!(
+            value(Language::Ja, &records, "weather_code"),
+            FieldValue::text("5")
+        );
+        assert_eq!(
+            value(Language::Ja, &records, "weather_name"),
+            FieldValue::text("雪")
+        );
+        assert_eq!(
+            value(Language::Ja, &records, "route_name"),
+            FieldValue::text("高速自動車国道")
+        );
+
+        let mut records = RecordsForTest::default();
+        write_files(
+            &mut records,
+            &main_file,
+            &support_file,
+            Some(Layout::Y2022),
+            Language::En,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            value(Language::En, &records, "weather_code"),
+            FieldValue::text("5")
+        );
+        assert_eq!(
+            value(Language::En, &records, "weather_name"),
+            FieldValue::text("Snow")
+        );
+        assert_eq!(
+            value(Language::En, &records, "route_name"),
+            FieldValue::text("National expressway")
+        );
+        assert_eq!(
+            value(Language::En, &records, "prefecture_name"),
+            FieldValue::text("Hokkaido (Sapporo)")
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,664 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use flatbuffers::{FlatBufferBuilder, VOffsetT, WIPOffset};
+
+use super::{related_path, Extent, FieldType, FieldValue, Layer, Record, RecordWriter, TempFile};
+
+/// FlatGeobufのマジックバイト（バージョン3）
+const FGB_MAGIC_BYTES: [u8; 8] = [b'f', b'g', b'b', 3, b'f', b'g', b'b', 0];
+
+/// FlatGeobufのジオメトリの種類（ポイント）
+const FGB_GEOMETRY_TYPE_POINT: u8 = 1;
+
+/// FlatGeobufの属性の型（64ビット整数）
+const FGB_COLUMN_TYPE_LONG: u8 = 7;
+
+/// FlatGeobufの属性の型（文字列）
+const FGB_COLUMN_TYPE_STRING: u8 = 11;
+
+/// `Header`テーブルのフィールドの位置
+const HEADER_NAME: VOffsetT = slot(0);
+const HEADER_ENVELOPE: VOffsetT = slot(1);
+const HEADER_GEOMETRY_TYPE: VOffsetT = slot(2);
+const HEADER_COLUMNS: VOffsetT = slot(7);
+const HEADER_FEATURES_COUNT: VOffsetT = slot(8);
+const HEADER_INDEX_NODE_SIZE: VOffsetT = slot(9);
+const HEADER_CRS: VOffsetT = slot(10);
+
+/// `Column`テーブルのフィールドの位置
+const COLUMN_NAME: VOffsetT = slot(0);
+const COLUMN_TYPE: VOffsetT = slot(1);
+
+/// `Crs`テーブルのフィールドの位置
+const CRS_ORG: VOffsetT = slot(0);
+const CRS_CODE: VOffsetT = slot(1);
+
+/// `Feature`テーブルのフィールドの位置
+const FEATURE_GEOMETRY: VOffsetT = slot(0);
+const FEATURE_PROPERTIES: VOffsetT = slot(1);
+
+/// `Geometry`テーブルのフィールドの位置
+const GEOMETRY_XY: VOffsetT = slot(1);
+
+/// FlatBuffersのテーブルのフィールドの番号を、vtable内の位置に変換する。
+const fn slot(index: VOffsetT) -> VOffsetT {
+    4 + 2 * index
+}
+
+/// 交通事故と交通事故当事者以外の関与者をFlatGeobuf形式で出力するライター
+///
+/// 空間インデックスは作成しない。
+/// 交通事故当事者以外の関与者は、関与した交通事故の位置を持つフィーチャーとして、
+/// `<ファイル名>_involved_persons.fgb`ファイルに出力する。
+pub struct Writer {
+    /// 交通事故を出力するライター
+    accidents: LayerWriter,
+    /// 交通事故当事者以外の関与者を出力するライター
+    involved_persons: LayerWriter,
+}
+
+impl Writer {
+    /// 出力ファイルを作成する。
+    ///
+    /// # 引数
+    ///
+    /// * `output` - 出力ファイルパス
+    /// * `accidents` - 交通事故のレイヤー
+    /// * `involved_persons` - 交通事故当事者以外の関与者のレイヤー
+    ///
+    /// # 戻り値
+    ///
+    /// ライター
+    pub fn create(
+        output: &Path,
+        accidents: Layer,
+        involved_persons: Layer,
+    ) -> anyhow::Result<Self> {
+        let related = related_path(output, &involved_persons, "fgb");
+
+        Ok(Self {
+            accidents: LayerWriter::create(output.to_path_buf(), accidents)?,
+            involved_persons: LayerWriter::create(related, involved_persons)?,
+        })
+    }
+}
+
+impl RecordWriter for Writer {
+    async fn write_accident(&mut self, record: &Record) -> anyhow::Result<()> {
+        self.accidents.write(record)
+    }
+
+    async fn write_involved_person(&mut self, record: &Record) -> anyhow::Result<()> {
+        self.involved_persons.write(record)
+    }
+
+    async fn finish(self) -> anyhow::Result<Vec<PathBuf>> {
+        Ok(vec![
+            self.accidents.finish()?,
+            self.involved_persons.finish()?,
+        ])
+    }
+}
+
+/// レイヤーをFlatGeobuf形式で出力するライター
+///
+/// ヘッダにはフィーチャーの数と範囲を記録するため、フィーチャーを一時ファイルに出力して、
+/// `finish`でマジックバイトとヘッダの後に連結する。
+struct LayerWriter {
+    /// 出力ファイルパス
+    path: PathBuf,
+    /// レイヤー
+    layer: Layer,
+    /// フィーチャーを出力する一時ファイル
+    features: TempFile,
+    /// フィーチャーを作成するビルダー
+    builder: FlatBufferBuilder<'static>,
+    /// 出力したフィーチャーの数
+    count: u64,
+    /// 出力したフィーチャーの位置を含む範囲
+    extent: Extent,
+}
+
+impl LayerWriter {
+    fn create(path: PathBuf, layer: Layer) -> anyhow::Result<Self> {
+        let features = TempFile::create(&path)?;
+
+        Ok(Self {
+            path,
+            layer,
+            features,
+            builder: FlatBufferBuilder::new(),
+            count: 0,
+            extent: Extent::default(),
+        })
+    }
+
+    fn write(&mut self, record: &Record) -> anyhow::Result<()> {
+        self.builder.reset();
+        self.features
+            .write_all(feature(&mut self.builder, record))?;
+        self.count += 1;
+        if let Some(point) = record.point {
+            self.extent.include(point);
+        }
+
+        Ok(())
+    }
+
+    fn finish(mut self) -> anyhow::Result<PathBuf> {
+        let mut features = self.features.reader()?;
+        let file = File::create(&self.path)
+            .map_err(|e| anyhow::anyhow!("{}を作成できませんでした。{}", self.path.display(), e))?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(&FGB_MAGIC_BYTES)?;
+        let mut builder = FlatBufferBuilder::new();
+        writer.write_all(header(
+            &mut builder,
+            &self.layer,
+            self.count,
+            self.extent.bbox(),
+        ))?;
+        std::io::copy(&mut features, &mut writer)?;
+        writer.flush()?;
+
+        Ok(self.path)
+    }
+}
+
+/// サイズを前置した`Header`テーブルを作成する。
+fn header<'a>(
+    builder: &'a mut FlatBufferBuilder,
+    layer: &Layer,
+    features_count: u64,
+    bbox: Option<[f64; 4]>,
+) -> &'a [u8] {
+    let name = builder.create_string(layer.name);
+    let envelope = bbox.map(|bbox| builder.create_vector(&bbox));
+    let columns = layer
+        .fields
+        .iter()
+        .map(|field| {
+            let name = builder.create_string(field.name);
+            let column_type = match field.field_type {
+                FieldType::Text => FGB_COLUMN_TYPE_STRING,
+                FieldType::Integer => FGB_COLUMN_TYPE_LONG,
+            };
+            let start = builder.start_table();
+            builder.push_slot_always(COLUMN_NAME, name);
+            builder.push_slot(COLUMN_TYPE, column_type, 0);
+            builder.end_table(start)
+        })
+        .collect::<Vec<_>>();
+    let columns = builder.create_vector(&columns);
+    let org = builder.create_string("EPSG");
+    let start = builder.start_table();
+    builder.push_slot_always(CRS_ORG, org);
+    builder.push_slot(CRS_CODE, layer.srid.code() as i32, 0);
+    let crs = builder.end_table(start);
+
+    let start = builder.start_table();
+    builder.push_slot_always(HEADER_NAME, name);
+    if let Some(envelope) = envelope {
+        builder.push_slot_always(HEADER_ENVELOPE, envelope);
+    }
+    builder.push_slot(HEADER_GEOMETRY_TYPE, FGB_GEOMETRY_TYPE_POINT, 0);
+    builder.push_slot_always(HEADER_COLUMNS, columns);
+    builder.push_slot(HEADER_FEATURES_COUNT, features_count, 0);
+    // 空間インデックスを作成しないため、ノードのサイズを0とする
+    builder.push_slot_always(HEADER_INDEX_NODE_SIZE, 0_u16);
+    builder.push_slot_always(HEADER_CRS, crs);
+    let header = builder.end_table(start);
+    builder.finish_size_prefixed(header, None);
+
+    builder.finished_data()
+}
+
+/// サイズを前置した`Feature`テーブルを作成する。
+fn feature<'a>(builder: &'a mut FlatBufferBuilder, record: &Record) -> &'a [u8] {
+    let geometry = record.point.map(|point| {
+        let xy = builder.create_vector(&[point.x(), point.y()]);
+        let start = builder.start_table();
+        builder.push_slot_always(GEOMETRY_XY, xy);
+        builder.end_table(start)
+    });
+    let properties = builder.create_vector(&properties(record));
+    let start = builder.start_table();
+    if let Some(geometry) = geometry {
+        builder.push_slot_always::<WIPOffset<_>>(FEATURE_GEOMETRY, geometry);
+    }
+    builder.push_slot_always(FEATURE_PROPERTIES, properties);
+    let feature = builder.end_table(start);
+    builder.finish_size_prefixed(feature, None);
+
+    builder.finished_data()
+}
+
+/// レコードの属性の値を、FlatGeobufの属性のバイト列に変換する。
+///
+/// 属性の値は、リトルエンディアンの属性の番号（`u16`）と値の組で表現して、値がない属性は省略する。
+/// 文字列は長さ（`u32`）を前置したUTF-8、整数は64ビット整数で表現する。
+fn properties(record: &Record) -> Vec<u8> {
+    let mut properties = vec![];
+    for (index, value) in record.values.iter().enumerate() {
+        match value {
+            FieldValue::Text(value) => {
+                properties.extend((index as u16).to_le_bytes());
+                properties.extend((value.len() as u32).to_le_bytes());
+                properties.extend(value.as_bytes());
+            }
+            FieldValue::Integer(value) => {
+                properties.extend((index as u16).to_le_bytes());
+                properties.extend(value.to_le_bytes());
+            }
+            FieldValue::Null => {}
+        }
+    }
+
+    properties
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::layers_for_test;
+    use crate::test_utils::temp_dir_for_test;
+    use flatbuffers::{
+        size_prefixed_root, Follow, ForwardsUOffset, InvalidFlatbuffer, Table, Vector, Verifiable,
+        Verifier,
+    };
+    use geo_types::Point;
+
+    // FlatGeobufのスキーマ（header.fbs及びfeature.fbs）に従って、出力したファイルを読み込む。
+    // フィールドの位置は、スキーマのフィールドの順番からflatcが生成する値と同じ値とする。
+    // フィールドは、バッファを検証した後に、スキーマの型で読み込む。
+
+    /// `Header`テーブル
+    #[derive(Clone, Copy)]
+    struct HeaderTable<'a>(Table<'a>);
+
+    impl<'a> HeaderTable<'a> {
+        const VT_NAME: VOffsetT = 4;
+        const VT_ENVELOPE: VOffsetT = 6;
+        const VT_GEOMETRY_TYPE: VOffsetT = 8;
+        const VT_HAS_Z: VOffsetT = 10;
+        const VT_COLUMNS: VOffsetT = 18;
+        const VT_FEATURES_COUNT: VOffsetT = 20;
+        const VT_INDEX_NODE_SIZE: VOffsetT = 22;
+        const VT_CRS: VOffsetT = 24;
+
+        fn name(&self) -> &'a str {
+            unsafe { self.0.get::<ForwardsUOffset<&str>>(Self::VT_NAME, None) }.unwrap()
+        }
+
+        fn envelope(&self) -> Option<Vec<f64>> {
+            unsafe {
+                self.0
+                    .get::<ForwardsUOffset<Vector<f64>>>(Self::VT_ENVELOPE, None)
+            }
+            .map(|envelope| envelope.iter().collect())
+        }
+
+        fn geometry_type(&self) -> u8 {
+            unsafe { self.0.get::<u8>(Self::VT_GEOMETRY_TYPE, Some(0)) }.unwrap()
+        }
+
+        fn columns(&self) -> Vec<(String, u8)> {
+            unsafe {
+                self.0
+                    .get::<ForwardsUOffset<Vector<ForwardsUOffset<ColumnTable>>>>(
+                        Self::VT_COLUMNS,
+                        None,
+                    )
+            }
+            .unwrap()
+            .iter()
+            .map(|column| (column.name().to_string(), column.column_type()))
+            .collect()
+        }
+
+        fn features_count(&self) -> u64 {
+            unsafe { self.0.get::<u64>(Self::VT_FEATURES_COUNT, Some(0)) }.unwrap()
+        }
+
+        fn index_node_size(&self) -> u16 {
+            unsafe { self.0.get::<u16>(Self::VT_INDEX_NODE_SIZE, Some(16)) }.unwrap()
+        }
+
+        fn crs(&self) -> CrsTable<'a> {
+            unsafe { self.0.get::<ForwardsUOffset<CrsTable>>(Self::VT_CRS, None) }.unwrap()
+        }
+    }
+
+    /// `Column`テーブル
+    #[derive(Clone, Copy)]
+    struct ColumnTable<'a>(Table<'a>);
+
+    impl<'a> ColumnTable<'a> {
+        const VT_NAME: VOffsetT = 4;
+        const VT_TYPE: VOffsetT = 6;
+
+        fn name(&self) -> &'a str {
+            unsafe { self.0.get::<ForwardsUOffset<&str>>(Self::VT_NAME, None) }.unwrap()
+        }
+
+        fn column_type(&self) -> u8 {
+            unsafe { self.0.get::<u8>(Self::VT_TYPE, Some(0)) }.unwrap()
+        }
+    }
+
+    /// `Crs`テーブル
+    #[derive(Clone, Copy)]
+    struct CrsTable<'a>(Table<'a>);
+
+    impl<'a> CrsTable<'a> {
+        const VT_ORG: VOffsetT = 4;
+        const VT_CODE: VOffsetT = 6;
+
+        fn org(&self) -> &'a str {
+            unsafe { self.0.get::<ForwardsUOffset<&str>>(Self::VT_ORG, None) }.unwrap()
+        }
+
+        fn code(&self) -> i32 {
+            unsafe { self.0.get::<i32>(Self::VT_CODE, Some(0)) }.unwrap()
+        }
+    }
+
+    /// `Feature`テーブル
+    #[derive(Clone, Copy)]
+    struct FeatureTable<'a>(Table<'a>);
+
+    impl<'a> FeatureTable<'a> {
+        const VT_GEOMETRY: VOffsetT = 4;
+        const VT_PROPERTIES: VOffsetT = 6;
+
+        fn xy(&self) -> Option<Vec<f64>> {
+            let geometry = unsafe {
+                self.0
+                    .get::<ForwardsUOffset<GeometryTable>>(Self::VT_GEOMETRY, None)
+            }?;
+            unsafe {
+                geometry
+                    .0
+                    .get::<ForwardsUOffset<Vector<f64>>>(GeometryTable::VT_XY, None)
+            }
+            .map(|xy| xy.iter().collect())
+        }
+
+        fn properties(&self) -> &'a [u8] {
+            unsafe {
+                self.0
+                    .get::<ForwardsUOffset<Vector<u8>>>(Self::VT_PROPERTIES, None)
+            }
+            .map(|properties| properties.bytes())
+            .unwrap_or_default()
+        }
+    }
+
+    /// `Geometry`テーブル
+    #[derive(Clone, Copy)]
+    struct GeometryTable<'a>(Table<'a>);
+
+    impl GeometryTable<'_> {
+        const VT_ENDS: VOffsetT = 4;
+        const VT_XY: VOffsetT = 6;
+    }
+
+    macro_rules! follow_table {
+        ($($name:ident),*) => {
+            $(
+                impl<'a> Follow<'a> for $name<'a> {
+                    type Inner = $name<'a>;
+
+                    unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                        $name(Table::new(buf, loc))
+                    }
+                }
+            )*
+        };
+    }
+
+    follow_table!(
+        HeaderTable,
+        ColumnTable,
+        CrsTable,
+        FeatureTable,
+        GeometryTable
+    );
+
+    impl Verifiable for HeaderTable<'_> {
+        fn run_verifier(v: &mut Verifier, pos: usize) -> Result<(), InvalidFlatbuffer> {
+            v.visit_table(pos)?
+                .visit_field::<ForwardsUOffset<&str>>("name", Self::VT_NAME, false)?
+                .visit_field::<ForwardsUOffset<Vector<f64>>>("envelope", Self::VT_ENVELOPE, false)?
+                .visit_field::<u8>("geometry_type", Self::VT_GEOMETRY_TYPE, false)?
+                .visit_field::<bool>("has_z", Self::VT_HAS_Z, false)?
+                .visit_field::<ForwardsUOffset<Vector<ForwardsUOffset<ColumnTable>>>>(
+                    "columns",
+                    Self::VT_COLUMNS,
+                    false,
+                )?
+                .visit_field::<u64>("features_count", Self::VT_FEATURES_COUNT, false)?
+                .visit_field::<u16>("index_node_size", Self::VT_INDEX_NODE_SIZE, false)?
+                .visit_field::<ForwardsUOffset<CrsTable>>("crs", Self::VT_CRS, false)?
+                .finish();
+            Ok(())
+        }
+    }
+
+    impl Verifiable for ColumnTable<'_> {
+        fn run_verifier(v: &mut Verifier, pos: usize) -> Result<(), InvalidFlatbuffer> {
+            v.visit_table(pos)?
+                .visit_field::<ForwardsUOffset<&str>>("name", Self::VT_NAME, true)?
+                .visit_field::<u8>("type", Self::VT_TYPE, false)?
+                .finish();
+            Ok(())
+        }
+    }
+
+    impl Verifiable for CrsTable<'_> {
+        fn run_verifier(v: &mut Verifier, pos: usize) -> Result<(), InvalidFlatbuffer> {
+            v.visit_table(pos)?
+                .visit_field::<ForwardsUOffset<&str>>("org", Self::VT_ORG, false)?
+                .visit_field::<i32>("code", Self::VT_CODE, false)?
+                .finish();
+            Ok(())
+        }
+    }
+
+    impl Verifiable for FeatureTable<'_> {
+        fn run_verifier(v: &mut Verifier, pos: usize) -> Result<(), InvalidFlatbuffer> {
+            v.visit_table(pos)?
+                .visit_field::<ForwardsUOffset<GeometryTable>>(
+                    "geometry",
+                    Self::VT_GEOMETRY,
+                    false,
+                )?
+                .visit_field::<ForwardsUOffset<Vector<u8>>>(
+                    "properties",
+                    Self::VT_PROPERTIES,
+                    false,
+                )?
+                .finish();
+            Ok(())
+        }
+    }
+
+    impl Verifiable for GeometryTable<'_> {
+        fn run_verifier(v: &mut Verifier, pos: usize) -> Result<(), InvalidFlatbuffer> {
+            v.visit_table(pos)?
+                .visit_field::<ForwardsUOffset<Vector<u32>>>("ends", Self::VT_ENDS, false)?
+                .visit_field::<ForwardsUOffset<Vector<f64>>>("xy", Self::VT_XY, false)?
+                .finish();
+            Ok(())
+        }
+    }
+
+    /// 読み込んだレイヤー
+    #[derive(Debug, PartialEq)]
+    struct FgbLayer {
+        name: String,
+        envelope: Option<Vec<f64>>,
+        geometry_type: u8,
+        columns: Vec<(String, u8)>,
+        features_count: u64,
+        index_node_size: u16,
+        crs: (String, i32),
+        features: Vec<Record>,
+    }
+
+    /// サイズを前置したFlatBuffersのバッファを返して、読み込む位置を進める。
+    fn next_buffer<'a>(data: &'a [u8], offset: &mut usize) -> &'a [u8] {
+        let size = u32::from_le_bytes(data[*offset..*offset + 4].try_into().unwrap()) as usize;
+        let buffer = &data[*offset..*offset + 4 + size];
+        *offset += 4 + size;
+        buffer
+    }
+
+    /// FlatGeobufファイルを、バッファを検証しながら読み込む。
+    fn read_fgb(data: &[u8]) -> FgbLayer {
+        assert_eq!(&data[0..8], b"fgb\x03fgb\x00");
+        let mut offset = 8;
+        let header = size_prefixed_root::<HeaderTable>(next_buffer(data, &mut offset)).unwrap();
+        let columns = header.columns();
+
+        // 空間インデックスがないため、ヘッダの後にフィーチャーが連続する
+        let mut features = vec![];
+        while offset < data.len() {
+            let feature =
+                size_prefixed_root::<FeatureTable>(next_buffer(data, &mut offset)).unwrap();
+            features.push(Record {
+                point: feature.xy().map(|xy| Point::new(xy[0], xy[1])),
+                values: read_properties(feature.properties(), &columns),
+            });
+        }
+
+        FgbLayer {
+            name: header.name().to_string(),
+            envelope: header.envelope(),
+            geometry_type: header.geometry_type(),
+            columns,
+            features_count: header.features_count(),
+            index_node_size: header.index_node_size(),
+            crs: (header.crs().org().to_string(), header.crs().code()),
+            features,
+        }
+    }
+
+    /// フィーチャーの属性のバイト列を、列の型に従って属性の値に変換する。
+    fn read_properties(properties: &[u8], columns: &[(String, u8)]) -> Vec<FieldValue> {
+        let mut values = vec![FieldValue::Null; columns.len()];
+        let mut offset = 0;
+        while offset < properties.len() {
+            let index = u16::from_le_bytes([properties[offset], properties[offset + 1]]) as usize;
+            offset += 2;
+            values[index] = match columns[index].1 {
+                // Long
+                7 => {
+                    let value =
+                        i64::from_le_bytes(properties[offset..offset + 8].try_into().unwrap());
+                    offset += 8;
+                    FieldValue::Integer(value)
+                }
+                // String
+                11 => {
+                    let size =
+                        u32::from_le_bytes(properties[offset..offset + 4].try_into().unwrap())
+                            as usize;
+                    let value =
+                        String::from_utf8(properties[offset + 4..offset + 4 + size].to_vec())
+                            .unwrap();
+                    offset += 4 + size;
+                    FieldValue::Text(value)
+                }
+                column_type => panic!("{}", column_type),
+            };
+        }
+
+        values
+    }
+
+    #[tokio::test]
+    async fn write_ok() {
+        let dir = temp_dir_for_test("fgb");
+        let layers = layers_for_test();
+        let writer = Writer::create(
+            &dir.join("accidents.fgb"),
+            layers.accidents.clone(),
+            layers.involved_persons.clone(),
+        )
+        .unwrap();
+        let paths = layers.write(writer).await;
+        assert_eq!(
+            paths[1].file_name().unwrap(),
+            "accidents_involved_persons.fgb"
+        );
+        // 一時ファイルは削除する
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+
+        let layer = read_fgb(&std::fs::read(&paths[0]).unwrap());
+        assert_eq!(
+            layer,
+            FgbLayer {
+                name: String::from("accidents"),
+                envelope: Some(vec![139.5, 35.5, 140.0, 36.0]),
+                geometry_type: 1,
+                columns: vec![
+                    (String::from("id"), 11),
+                    (String::from("weather_name"), 11),
+                    (String::from("number_of_deaths"), 7),
+                    (String::from("route_name"), 11),
+                ],
+                features_count: 2,
+                index_node_size: 0,
+                crs: (String::from("EPSG"), 6668),
+                features: layers.accident_records.clone(),
+            }
+        );
+        let layer = read_fgb(&std::fs::read(&paths[1]).unwrap());
+        assert_eq!(layer.name, "involved_persons");
+        assert_eq!(layer.features_count, 1);
+        assert_eq!(layer.features, layers.involved_person_records);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn write_empty_ok() {
+        let dir = temp_dir_for_test("fgb-empty");
+        let layers = layers_for_test();
+        let writer = Writer::create(
+            &dir.join("accidents.fgb"),
+            layers.accidents.clone(),
+            layers.involved_persons.clone(),
+        )
+        .unwrap();
+        let paths = writer.finish().await.unwrap();
+
+        let layer = read_fgb(&std::fs::read(&paths[0]).unwrap());
+        assert_eq!(layer.features_count, 0);
+        assert_eq!(layer.envelope, None);
+        assert!(layer.features.is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn properties_ok() {
+        let layers = layers_for_test();
+        let record = &layers.accident_records[0];
+        let properties = properties(record);
+        let id = record.values[0].clone();
+        let FieldValue::Text(id) = id else {
+            panic!("{:?}", id)
+        };
+        let mut expected = vec![0, 0];
+        expected.extend((id.len() as u32).to_le_bytes());
+        expected.extend(id.as_bytes());
+        expected.extend([1, 0, 3, 0, 0, 0]);
+        expected.extend("晴".as_bytes());
+        expected.extend([2, 0]);
+        expected.extend(1_i64.to_le_bytes());
+        // 値がない路線名は省略
+        assert_eq!(properties, expected);
+    }
+}
@@ -0,0 +1,207 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::ser::SerializeMap;
+
+use super::{related_path, FieldValue, Layer, Record, RecordWriter, TempFile};
+
+/// 交通事故と交通事故当事者以外の関与者をGeoJSON形式のフィーチャーコレクションで出力するライター
+///
+/// 交通事故当事者以外の関与者は、ジオメトリを`null`としたフィーチャーとして、
+/// `<ファイル名>_involved_persons.geojson`ファイルに出力する。
+pub struct Writer {
+    /// 交通事故を出力するライター
+    accidents: LayerWriter,
+    /// 交通事故当事者以外の関与者を出力するライター
+    involved_persons: LayerWriter,
+}
+
+impl Writer {
+    /// 出力ファイルを作成する。
+    ///
+    /// # 引数
+    ///
+    /// * `output` - 出力ファイルパス
+    /// * `accidents` - 交通事故のレイヤー
+    /// * `involved_persons` - 交通事故当事者以外の関与者のレイヤー
+    ///
+    /// # 戻り値
+    ///
+    /// ライター
+    pub fn create(
+        output: &Path,
+        accidents: Layer,
+        involved_persons: Layer,
+    ) -> anyhow::Result<Self> {
+        let related = related_path(output, &involved_persons, "geojson");
+
+        Ok(Self {
+            accidents: LayerWriter::create(output.to_path_buf(), accidents)?,
+            involved_persons: LayerWriter::create(related, involved_persons)?,
+        })
+    }
+}
+
+impl RecordWriter for Writer {
+    async fn write_accident(&mut self, record: &Record) -> anyhow::Result<()> {
+        self.accidents.write(record)
+    }
+
+    async fn write_involved_person(&mut self, record: &Record) -> anyhow::Result<()> {
+        self.involved_persons.write(record)
+    }
+
+    async fn finish(self) -> anyhow::Result<Vec<PathBuf>> {
+        Ok(vec![
+            self.accidents.finish()?,
+            self.involved_persons.finish()?,
+        ])
+    }
+}
+
+/// レイヤーのレコードを、1行に1つのフィーチャーを記述したフィーチャーコレクションとして出力するライター
+struct LayerWriter {
+    /// 出力ファイルパス
+    path: PathBuf,
+    /// レイヤー
+    layer: Layer,
+    /// 出力ファイルが完成するまでフィーチャーを出力する一時ファイル
+    writer: TempFile,
+    /// 出力したフィーチャーの数
+    count: usize,
+}
+
+impl LayerWriter {
+    fn create(path: PathBuf, layer: Layer) -> anyhow::Result<Self> {
+        let mut writer = TempFile::create(&path)?;
+        writer.write_all(br#"{"type":"FeatureCollection","features":["#)?;
+
+        Ok(Self {
+            path,
+            layer,
+            writer,
+            count: 0,
+        })
+    }
+
+    fn write(&mut self, record: &Record) -> anyhow::Result<()> {
+        self.writer
+            .write_all(if self.count == 0 { b"\n" } else { b",\n" })?;
+        serde_json::to_writer(&mut self.writer, &Feature::new(&self.layer, record))?;
+        self.count += 1;
+
+        Ok(())
+    }
+
+    fn finish(mut self) -> anyhow::Result<PathBuf> {
+        self.writer.write_all(b"\n]}\n")?;
+        self.writer.persist(&self.path)?;
+
+        Ok(self.path)
+    }
+}
+
+/// GeoJSONのフィーチャー
+#[derive(serde::Serialize)]
+pub struct Feature<'a> {
+    r#type: &'static str,
+    geometry: Option<PointGeometry>,
+    properties: Properties<'a>,
+}
+
+impl<'a> Feature<'a> {
+    /// レイヤーのレコードからフィーチャーを構築する。
+    ///
+    /// 位置を持たないレイヤーのフィーチャーは、ジオメトリを`null`とする。
+    ///
+    /// # 引数
+    ///
+    /// * `layer` - レイヤー
+    /// * `record` - レイヤーのレコード
+    ///
+    /// # 戻り値
+    ///
+    /// GeoJSONのフィーチャー
+    pub fn new(layer: &'a Layer, record: &'a Record) -> Self {
+        let point = record.point.filter(|_| layer.spatial);
+
+        Self {
+            r#type: "Feature",
+            geometry: point.map(|point| PointGeometry {
+                r#type: "Point",
+                coordinates: [point.x(), point.y()],
+            }),
+            properties: Properties { layer, record },
+        }
+    }
+}
+
+/// GeoJSONのポイント
+#[derive(serde::Serialize)]
+struct PointGeometry {
+    r#type: &'static str,
+    coordinates: [f64; 2],
+}
+
+/// レイヤーの属性の順番で出力するGeoJSONのフィーチャーのプロパティ
+struct Properties<'a> {
+    layer: &'a Layer,
+    record: &'a Record,
+}
+
+impl<'a> serde::Serialize for Properties<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.layer.fields.len()))?;
+        for (field, value) in self.layer.fields.iter().zip(&self.record.values) {
+            match value {
+                FieldValue::Text(value) => map.serialize_entry(field.name, value)?,
+                FieldValue::Integer(value) => map.serialize_entry(field.name, value)?,
+                FieldValue::Null => map.serialize_entry(field.name, &())?,
+            }
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::layers_for_test;
+    use crate::test_utils::temp_dir_for_test;
+
+    #[tokio::test]
+    async fn write_ok() {
+        let dir = temp_dir_for_test("geojson");
+        let layers = layers_for_test();
+        let writer = Writer::create(
+            &dir.join("accidents.geojson"),
+            layers.accidents.clone(),
+            layers.involved_persons.clone(),
+        )
+        .unwrap();
+        let paths = layers.write(writer).await;
+        assert_eq!(
+            paths[1].file_name().unwrap(),
+            "accidents_involved_persons.geojson"
+        );
+
+        let content = std::fs::read_to_string(&paths[0]).unwrap();
+        let collection = content.parse::<geojson::GeoJson>().unwrap();
+        let geojson::GeoJson::FeatureCollection(collection) = collection else {
+            panic!("{}", content)
+        };
+        assert_eq!(collection.features.len(), 2);
+        let feature = &collection.features[1];
+        assert_eq!(
+            feature.geometry.as_ref().unwrap().value,
+            geojson::Value::Point(vec![140.0, 36.0])
+        );
+        assert_eq!(feature.property("route_name").unwrap(), "一般国道");
+        assert_eq!(feature.property("number_of_deaths").unwrap(), 10);
+
+        let content = std::fs::read_to_string(&paths[1]).unwrap();
+        let collection = content.parse::<geojson::FeatureCollection>().unwrap();
+        assert!(collection.features[0].geometry.is_none());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
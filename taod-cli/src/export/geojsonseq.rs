@@ -0,0 +1,140 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::geojson::Feature;
+use super::{related_path, Layer, Record, RecordWriter, TempFile};
+
+/// GeoJSON Text Sequencesのレコード区切り文字（RS）
+const RECORD_SEPARATOR: u8 = 0x1E;
+
+/// 交通事故と交通事故当事者以外の関与者をGeoJSON Text Sequences（RFC 8142）形式で出力するライター
+///
+/// 交通事故当事者以外の関与者は、ジオメトリを`null`としたフィーチャーとして、
+/// `<ファイル名>_involved_persons.geojsonseq`ファイルに出力する。
+pub struct Writer {
+    /// 交通事故を出力するライター
+    accidents: LayerWriter,
+    /// 交通事故当事者以外の関与者を出力するライター
+    involved_persons: LayerWriter,
+}
+
+impl Writer {
+    /// 出力ファイルを作成する。
+    ///
+    /// # 引数
+    ///
+    /// * `output` - 出力ファイルパス
+    /// * `accidents` - 交通事故のレイヤー
+    /// * `involved_persons` - 交通事故当事者以外の関与者のレイヤー
+    ///
+    /// # 戻り値
+    ///
+    /// ライター
+    pub fn create(
+        output: &Path,
+        accidents: Layer,
+        involved_persons: Layer,
+    ) -> anyhow::Result<Self> {
+        let related = related_path(output, &involved_persons, "geojsonseq");
+
+        Ok(Self {
+            accidents: LayerWriter::create(output.to_path_buf(), accidents)?,
+            involved_persons: LayerWriter::create(related, involved_persons)?,
+        })
+    }
+}
+
+impl RecordWriter for Writer {
+    async fn write_accident(&mut self, record: &Record) -> anyhow::Result<()> {
+        self.accidents.write(record)
+    }
+
+    async fn write_involved_person(&mut self, record: &Record) -> anyhow::Result<()> {
+        self.involved_persons.write(record)
+    }
+
+    async fn finish(self) -> anyhow::Result<Vec<PathBuf>> {
+        Ok(vec![
+            self.accidents.finish()?,
+            self.involved_persons.finish()?,
+        ])
+    }
+}
+
+/// レイヤーのレコードを、1行に1つのフィーチャーとして出力するライター
+struct LayerWriter {
+    /// 出力ファイルパス
+    path: PathBuf,
+    /// レイヤー
+    layer: Layer,
+    /// 出力ファイルが完成するまでフィーチャーを出力する一時ファイル
+    writer: TempFile,
+}
+
+impl LayerWriter {
+    fn create(path: PathBuf, layer: Layer) -> anyhow::Result<Self> {
+        Ok(Self {
+            writer: TempFile::create(&path)?,
+            path,
+            layer,
+        })
+    }
+
+    fn write(&mut self, record: &Record) -> anyhow::Result<()> {
+        self.writer.write_all(&[RECORD_SEPARATOR])?;
+        serde_json::to_writer(&mut self.writer, &Feature::new(&self.layer, record))?;
+        self.writer.write_all(b"\n")?;
+
+        Ok(())
+    }
+
+    fn finish(self) -> anyhow::Result<PathBuf> {
+        self.writer.persist(&self.path)?;
+
+        Ok(self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::layers_for_test;
+    use crate::test_utils::temp_dir_for_test;
+
+    #[tokio::test]
+    async fn write_ok() {
+        let dir = temp_dir_for_test("geojsonseq");
+        let layers = layers_for_test();
+        let writer = Writer::create(
+            &dir.join("accidents.geojsonseq"),
+            layers.accidents.clone(),
+            layers.involved_persons.clone(),
+        )
+        .unwrap();
+        let paths = layers.write(writer).await;
+        assert_eq!(
+            paths[1].file_name().unwrap(),
+            "accidents_involved_persons.geojsonseq"
+        );
+
+        let content = std::fs::read_to_string(&paths[0]).unwrap();
+        let lines = content.split_terminator('\n').collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        let feature: serde_json::Value =
+            serde_json::from_str(lines[0].strip_prefix('\u{1E}').unwrap()).unwrap();
+        assert_eq!(feature["geometry"]["coordinates"][0], 139.5);
+        assert_eq!(feature["properties"]["weather_name"], "晴");
+        assert_eq!(feature["properties"]["number_of_deaths"], 1);
+        assert!(feature["properties"]["route_name"].is_null());
+        // 属性はレイヤーの属性の順番で出力
+        assert!(
+            lines[0].find("weather_name").unwrap() < lines[0].find("number_of_deaths").unwrap()
+        );
+
+        let content = std::fs::read_to_string(&paths[1]).unwrap();
+        let feature: serde_json::Value =
+            serde_json::from_str(content.trim_start_matches('\u{1E}')).unwrap();
+        assert!(feature["geometry"].is_null());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,669 @@
+use std::path::{Path, PathBuf};
+
+use geo_types::{Geometry, Point};
+use geometries::Srid;
+use geozero::{CoordDimensions, ToWkb};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use sqlx::{Sqlite, SqlitePool, Transaction};
+
+use super::{
+    Extent, FieldType, FieldValue, Layer, Record, RecordWriter, TempFile, ACCIDENT_ID_FIELD,
+};
+
+/// GeoPackageのアプリケーションID（`GPKG`）
+const GPKG_APPLICATION_ID: i32 = 0x47504B47;
+
+/// GeoPackageのバージョン（1.3.0）
+const GPKG_USER_VERSION: i32 = 10300;
+
+/// 主キーの列名
+const PRIMARY_KEY_COLUMN: &str = "fid";
+
+/// ジオメトリの列名
+const GEOMETRY_COLUMN: &str = "geom";
+
+/// Related Tables Extensionの定義
+const RELATED_TABLES_DEFINITION: &str = "http://docs.opengeospatial.org/is/18-000/18-000.html";
+
+/// GeoPackageの必須テーブルを作成するSQL
+const GPKG_SCHEMA_SQL: &str = r#"
+CREATE TABLE gpkg_spatial_ref_sys (
+    srs_name TEXT NOT NULL,
+    srs_id INTEGER PRIMARY KEY,
+    organization TEXT NOT NULL,
+    organization_coordsys_id INTEGER NOT NULL,
+    definition TEXT NOT NULL,
+    description TEXT
+);
+CREATE TABLE gpkg_contents (
+    table_name TEXT NOT NULL PRIMARY KEY,
+    data_type TEXT NOT NULL,
+    identifier TEXT UNIQUE,
+    description TEXT DEFAULT '',
+    last_change DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    min_x DOUBLE,
+    min_y DOUBLE,
+    max_x DOUBLE,
+    max_y DOUBLE,
+    srs_id INTEGER,
+    CONSTRAINT fk_gc_r_srs_id FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys(srs_id)
+);
+CREATE TABLE gpkg_geometry_columns (
+    table_name TEXT NOT NULL,
+    column_name TEXT NOT NULL,
+    geometry_type_name TEXT NOT NULL,
+    srs_id INTEGER NOT NULL,
+    z TINYINT NOT NULL,
+    m TINYINT NOT NULL,
+    CONSTRAINT pk_geom_cols PRIMARY KEY (table_name, column_name),
+    CONSTRAINT uk_gc_table_name UNIQUE (table_name),
+    CONSTRAINT fk_gc_tn FOREIGN KEY (table_name) REFERENCES gpkg_contents(table_name),
+    CONSTRAINT fk_gc_srs FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys (srs_id)
+);
+CREATE TABLE gpkg_extensions (
+    table_name TEXT,
+    column_name TEXT,
+    extension_name TEXT NOT NULL,
+    definition TEXT NOT NULL,
+    scope TEXT NOT NULL,
+    CONSTRAINT ge_tce UNIQUE (table_name, column_name, extension_name)
+);
+CREATE TABLE gpkgext_relations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    base_table_name TEXT NOT NULL,
+    base_primary_column TEXT NOT NULL DEFAULT 'id',
+    related_table_name TEXT NOT NULL,
+    related_primary_column TEXT NOT NULL DEFAULT 'id',
+    relation_name TEXT NOT NULL,
+    mapping_table_name TEXT NOT NULL UNIQUE
+);
+INSERT INTO gpkg_spatial_ref_sys VALUES (
+    'Undefined cartesian SRS', -1, 'NONE', -1, 'undefined', 'undefined cartesian coordinate reference system'
+);
+INSERT INTO gpkg_spatial_ref_sys VALUES (
+    'Undefined geographic SRS', 0, 'NONE', 0, 'undefined', 'undefined geographic coordinate reference system'
+);
+INSERT INTO gpkg_spatial_ref_sys VALUES (
+    'WGS 84 geodetic', 4326, 'EPSG', 4326,
+    'GEOGCS["WGS 84",DATUM["WGS_1984",SPHEROID["WGS 84",6378137,298.257223563,AUTHORITY["EPSG","7030"]],AUTHORITY["EPSG","6326"]],PRIMEM["Greenwich",0,AUTHORITY["EPSG","8901"]],UNIT["degree",0.0174532925199433,AUTHORITY["EPSG","9122"]],AUTHORITY["EPSG","4326"]]',
+    'longitude/latitude coordinates in decimal degrees on the WGS 84 spheroid'
+);
+INSERT INTO gpkg_spatial_ref_sys VALUES (
+    'JGD2011', 6668, 'EPSG', 6668,
+    'GEOGCS["JGD2011",DATUM["Japanese_Geodetic_Datum_2011",SPHEROID["GRS 1980",6378137,298.257222101,AUTHORITY["EPSG","7019"]],AUTHORITY["EPSG","1128"]],PRIMEM["Greenwich",0,AUTHORITY["EPSG","8901"]],UNIT["degree",0.0174532925199433,AUTHORITY["EPSG","9122"]],AUTHORITY["EPSG","6668"]]',
+    'Japanese Geodetic Datum 2011'
+);
+"#;
+
+/// 交通事故と交通事故当事者以外の関与者をGeoPackage形式で出力するライター
+///
+/// 交通事故はポイントのフィーチャーテーブル、交通事故当事者以外の関与者は属性テーブルとして
+/// 1つのファイルに出力して、Related Tables Extensionで関連付ける。
+/// レコードは1つのトランザクションで登録して、`finish`でコミットしてから一時ファイルを出力ファイルに置き換える。
+pub struct Writer {
+    /// 出力ファイルパス
+    output: PathBuf,
+    /// 出力ファイルのコネクションプール（コネクションは1つ）
+    pool: SqlitePool,
+    /// レコードを登録するトランザクション
+    tx: Transaction<'static, Sqlite>,
+    /// 交通事故のレイヤー
+    accidents: LayerTable,
+    /// 交通事故当事者以外の関与者のレイヤー
+    involved_persons: LayerTable,
+    /// 出力ファイルが完成するまでレコードを登録する一時ファイル
+    file: TempFile,
+}
+
+impl Writer {
+    /// 出力ファイルを作成して、GeoPackageの必須テーブルとレイヤーのテーブルを作成する。
+    ///
+    /// # 引数
+    ///
+    /// * `output` - 出力ファイルパス
+    /// * `accidents` - 交通事故のレイヤー
+    /// * `involved_persons` - 交通事故当事者以外の関与者のレイヤー
+    ///
+    /// # 戻り値
+    ///
+    /// ライター
+    pub async fn create(
+        output: &Path,
+        accidents: Layer,
+        involved_persons: Layer,
+    ) -> anyhow::Result<Self> {
+        let file = TempFile::create(output)?;
+        let options = SqliteConnectOptions::new()
+            .filename(file.path())
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Delete);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}を作成できませんでした。{}", output.display(), e))?;
+
+        sqlx::query(&format!("PRAGMA application_id = {}", GPKG_APPLICATION_ID))
+            .execute(&pool)
+            .await?;
+        sqlx::query(&format!("PRAGMA user_version = {}", GPKG_USER_VERSION))
+            .execute(&pool)
+            .await?;
+
+        let mut tx = pool.begin().await?;
+        sqlx::query(GPKG_SCHEMA_SQL).execute(&mut *tx).await?;
+        write_spatial_ref_sys(&mut tx, accidents.srid).await?;
+        let accidents = LayerTable::create(&mut tx, accidents).await?;
+        let involved_persons = LayerTable::create(&mut tx, involved_persons).await?;
+
+        Ok(Self {
+            output: output.to_path_buf(),
+            pool,
+            tx,
+            accidents,
+            involved_persons,
+            file,
+        })
+    }
+}
+
+impl RecordWriter for Writer {
+    async fn write_accident(&mut self, record: &Record) -> anyhow::Result<()> {
+        self.accidents.insert(&mut self.tx, record).await
+    }
+
+    async fn write_involved_person(&mut self, record: &Record) -> anyhow::Result<()> {
+        self.involved_persons.insert(&mut self.tx, record).await
+    }
+
+    async fn finish(mut self) -> anyhow::Result<Vec<PathBuf>> {
+        self.accidents.write_extent(&mut self.tx).await?;
+        write_relation(
+            &mut self.tx,
+            &self.accidents.layer,
+            &self.involved_persons.layer,
+        )
+        .await?;
+        self.tx.commit().await?;
+        self.pool.close().await;
+        self.file.persist(&self.output)?;
+
+        Ok(vec![self.output])
+    }
+}
+
+/// 必須テーブルに登録していない空間参照系を、`gpkg_spatial_ref_sys`テーブルに登録する。
+async fn write_spatial_ref_sys(tx: &mut Transaction<'_, Sqlite>, srid: Srid) -> anyhow::Result<()> {
+    if srid == Srid::JGD2011 || srid == Srid::WGS84 {
+        return Ok(());
+    }
+    sqlx::query(
+        r#"
+        INSERT INTO gpkg_spatial_ref_sys (srs_name, srs_id, organization, organization_coordsys_id, definition, description)
+        VALUES ($1, $2, 'EPSG', $2, $3, $1)
+        "#,
+    )
+    .bind(srid.name())
+    .bind(srid.code() as i32)
+    .bind(srid.wkt())
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// レイヤーのテーブル
+struct LayerTable {
+    /// レイヤー
+    layer: Layer,
+    /// レコードを登録するINSERT文
+    insert_sql: String,
+    /// 登録したレコードの位置を含む範囲
+    extent: Extent,
+}
+
+impl LayerTable {
+    /// レイヤーのテーブルを作成して、`gpkg_contents`テーブルに登録する。
+    ///
+    /// 範囲はレコードを登録した後に`write_extent`で記録する。
+    async fn create(tx: &mut Transaction<'_, Sqlite>, layer: Layer) -> anyhow::Result<Self> {
+        let mut columns = vec![format!(
+            r#""{}" INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL"#,
+            PRIMARY_KEY_COLUMN
+        )];
+        if layer.spatial {
+            columns.push(format!(r#""{}" POINT"#, GEOMETRY_COLUMN));
+        }
+        columns.extend(layer.fields.iter().map(|field| {
+            let field_type = match field.field_type {
+                FieldType::Text => "TEXT",
+                FieldType::Integer => "INTEGER",
+            };
+            format!(r#""{}" {}"#, field.name, field_type)
+        }));
+        sqlx::query(&format!(
+            r#"CREATE TABLE "{}" ({})"#,
+            layer.name,
+            columns.join(", ")
+        ))
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO gpkg_contents (table_name, data_type, identifier, srs_id)
+            VALUES ($1, $2, $1, $3)
+            "#,
+        )
+        .bind(layer.name)
+        .bind(if layer.spatial {
+            "features"
+        } else {
+            "attributes"
+        })
+        .bind(layer.spatial.then_some(layer.srid.code() as i32))
+        .execute(&mut **tx)
+        .await?;
+        if layer.spatial {
+            sqlx::query(
+                r#"
+                INSERT INTO gpkg_geometry_columns (table_name, column_name, geometry_type_name, srs_id, z, m)
+                VALUES ($1, $2, 'POINT', $3, 0, 0)
+                "#,
+            )
+            .bind(layer.name)
+            .bind(GEOMETRY_COLUMN)
+            .bind(layer.srid.code() as i32)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        let mut names = layer
+            .fields
+            .iter()
+            .map(|field| format!(r#""{}""#, field.name))
+            .collect::<Vec<_>>();
+        if layer.spatial {
+            names.insert(0, format!(r#""{}""#, GEOMETRY_COLUMN));
+        }
+        let insert_sql = format!(
+            r#"INSERT INTO "{}" ({}) VALUES ({})"#,
+            layer.name,
+            names.join(", "),
+            (1..=names.len())
+                .map(|index| format!("${}", index))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        Ok(Self {
+            layer,
+            insert_sql,
+            extent: Extent::default(),
+        })
+    }
+
+    /// レコードを登録する。
+    async fn insert(
+        &mut self,
+        tx: &mut Transaction<'_, Sqlite>,
+        record: &Record,
+    ) -> anyhow::Result<()> {
+        let mut query = sqlx::query(&self.insert_sql);
+        if self.layer.spatial {
+            let blob = record
+                .point
+                .map(|point| geometry_blob(point, self.layer.srid))
+                .transpose()?;
+            query = query.bind(blob);
+            if let Some(point) = record.point {
+                self.extent.include(point);
+            }
+        }
+        for value in &record.values {
+            query = match value {
+                FieldValue::Text(value) => query.bind(value.as_str()),
+                FieldValue::Integer(value) => query.bind(*value),
+                FieldValue::Null => query.bind(None::<String>),
+            };
+        }
+        query.execute(&mut **tx).await?;
+
+        Ok(())
+    }
+
+    /// 登録したレコードの位置を含む範囲を、`gpkg_contents`テーブルに記録する。
+    async fn write_extent(&self, tx: &mut Transaction<'_, Sqlite>) -> anyhow::Result<()> {
+        let Some(bbox) = self.extent.bbox() else {
+            return Ok(());
+        };
+        sqlx::query(
+            r#"
+            UPDATE gpkg_contents SET min_x = $2, min_y = $3, max_x = $4, max_y = $5
+            WHERE table_name = $1
+            "#,
+        )
+        .bind(self.layer.name)
+        .bind(bbox[0])
+        .bind(bbox[1])
+        .bind(bbox[2])
+        .bind(bbox[3])
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// 交通事故と交通事故当事者以外の関与者を、Related Tables Extensionで関連付ける。
+///
+/// 交通事故IDで関連付けるマッピングテーブルを作成して、`gpkgext_relations`テーブルに登録する。
+async fn write_relation(
+    tx: &mut Transaction<'_, Sqlite>,
+    base: &Layer,
+    related: &Layer,
+) -> anyhow::Result<()> {
+    let mapping = format!("{}_{}", base.name, related.name);
+    sqlx::query(&format!(
+        r#"
+        CREATE INDEX "idx_{related}_{accident_id}" ON "{related}" ("{accident_id}");
+        CREATE TABLE "{mapping}" (base_id INTEGER NOT NULL, related_id INTEGER NOT NULL);
+        INSERT INTO "{mapping}" (base_id, related_id)
+        SELECT b."{fid}", r."{fid}"
+        FROM "{base}" b
+        INNER JOIN "{related}" r ON r."{accident_id}" = b.id;
+        "#,
+        base = base.name,
+        related = related.name,
+        mapping = mapping,
+        fid = PRIMARY_KEY_COLUMN,
+        accident_id = ACCIDENT_ID_FIELD,
+    ))
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO gpkg_contents (table_name, data_type, identifier)
+        VALUES ($1, 'attributes', $1)
+        "#,
+    )
+    .bind(&mapping)
+    .execute(&mut **tx)
+    .await?;
+    for table_name in ["gpkgext_relations", mapping.as_str()] {
+        sqlx::query(
+            r#"
+            INSERT INTO gpkg_extensions (table_name, extension_name, definition, scope)
+            VALUES ($1, 'related_tables', $2, 'read-write')
+            "#,
+        )
+        .bind(table_name)
+        .bind(RELATED_TABLES_DEFINITION)
+        .execute(&mut **tx)
+        .await?;
+    }
+    sqlx::query(
+        r#"
+        INSERT INTO gpkgext_relations (
+            base_table_name,
+            base_primary_column,
+            related_table_name,
+            related_primary_column,
+            relation_name,
+            mapping_table_name
+        ) VALUES ($1, $2, $3, $2, 'attributes', $4)
+        "#,
+    )
+    .bind(base.name)
+    .bind(PRIMARY_KEY_COLUMN)
+    .bind(related.name)
+    .bind(&mapping)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// ポイントをGeoPackageのジオメトリのバイナリ表現に変換する。
+///
+/// ヘッダはリトルエンディアンで範囲を含めず、ジオメトリはリトルエンディアンのWKBで表現する。
+///
+/// # 引数
+///
+/// * `point` - ポイント
+/// * `srid` - ポイントの空間参照系
+///
+/// # 戻り値
+///
+/// GeoPackageのジオメトリのバイナリ表現
+fn geometry_blob(point: Point, srid: Srid) -> anyhow::Result<Vec<u8>> {
+    Ok(Geometry::Point(point).to_gpkg_wkb(
+        CoordDimensions::xy(),
+        Some(srid.code() as i32),
+        vec![],
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::layers_for_test;
+    use crate::test_utils::temp_dir_for_test;
+    use geozero::wkb;
+    use sqlx::{Connection, Row, SqliteConnection};
+
+    #[test]
+    fn geometry_blob_ok() {
+        let blob = geometry_blob(Point::new(139.5, 35.5), Srid::JGD2011).unwrap();
+        assert_eq!(blob.len(), 29);
+        assert_eq!(&blob[0..4], b"GP\x00\x01");
+        assert_eq!(&blob[4..8], &6668_i32.to_le_bytes());
+        assert_eq!(&blob[8..13], &[0x01, 0x01, 0x00, 0x00, 0x00]);
+        assert_eq!(&blob[13..21], &139.5_f64.to_le_bytes());
+        assert_eq!(&blob[21..29], &35.5_f64.to_le_bytes());
+    }
+
+    #[tokio::test]
+    async fn write_ok() {
+        let dir = temp_dir_for_test("gpkg");
+        let layers = layers_for_test();
+        let path = dir.join("accidents.gpkg");
+        let writer = Writer::create(
+            &path,
+            layers.accidents.clone(),
+            layers.involved_persons.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(layers.write(writer).await, vec![path.clone()]);
+
+        let options = SqliteConnectOptions::new().filename(&path);
+        let mut conn = SqliteConnection::connect_with(&options).await.unwrap();
+        let application_id: i32 = sqlx::query_scalar("PRAGMA application_id")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(application_id, GPKG_APPLICATION_ID);
+        let user_version: i32 = sqlx::query_scalar("PRAGMA user_version")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(user_version, GPKG_USER_VERSION);
+        let integrity: String = sqlx::query_scalar("PRAGMA integrity_check")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(integrity, "ok");
+        let violations = sqlx::query("PRAGMA foreign_key_check")
+            .fetch_all(&mut conn)
+            .await
+            .unwrap();
+        assert!(violations.is_empty());
+
+        // フィーチャーテーブルと属性テーブルの登録
+        let contents = sqlx::query_as::<_, (String, String, Option<f64>, Option<f64>, Option<f64>, Option<f64>, Option<i32>)>(
+            "SELECT table_name, data_type, min_x, min_y, max_x, max_y, srs_id FROM gpkg_contents ORDER BY table_name",
+        )
+        .fetch_all(&mut conn)
+        .await
+        .unwrap();
+        assert_eq!(
+            contents,
+            vec![
+                (
+                    String::from("accidents"),
+                    String::from("features"),
+                    Some(139.5),
+                    Some(35.5),
+                    Some(140.0),
+                    Some(36.0),
+                    Some(6668)
+                ),
+                (
+                    String::from("accidents_involved_persons"),
+                    String::from("attributes"),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None
+                ),
+                (
+                    String::from("involved_persons"),
+                    String::from("attributes"),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None
+                ),
+            ]
+        );
+        let geometry_column = sqlx::query_as::<_, (String, String, String, i32, i32, i32)>(
+            "SELECT table_name, column_name, geometry_type_name, srs_id, z, m FROM gpkg_geometry_columns",
+        )
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+        assert_eq!(
+            geometry_column,
+            (
+                String::from("accidents"),
+                String::from("geom"),
+                String::from("POINT"),
+                6668,
+                0,
+                0
+            )
+        );
+
+        // ジオメトリはGeoPackageのバイナリ表現として読み込める
+        let rows = sqlx::query(
+            "SELECT geom, id, weather_name, number_of_deaths, route_name FROM accidents ORDER BY fid",
+        )
+        .fetch_all(&mut conn)
+        .await
+        .unwrap();
+        assert_eq!(rows.len(), layers.accident_records.len());
+        for (row, record) in rows.iter().zip(&layers.accident_records) {
+            let geometry = row
+                .get::<wkb::Decode<Geometry<f64>>, _>("geom")
+                .geometry
+                .unwrap();
+            assert_eq!(geometry, Geometry::Point(record.point.unwrap()));
+            let values = vec![
+                FieldValue::Text(row.get("id")),
+                FieldValue::Text(row.get("weather_name")),
+                FieldValue::Integer(row.get("number_of_deaths")),
+                row.get::<Option<String>, _>("route_name")
+                    .map(FieldValue::Text)
+                    .unwrap_or(FieldValue::Null),
+            ];
+            assert_eq!(values, record.values);
+        }
+        let row = sqlx::query("SELECT id, accident_id, sub_number FROM involved_persons")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![
+                FieldValue::Text(row.get("id")),
+                FieldValue::Text(row.get("accident_id")),
+                FieldValue::Integer(row.get("sub_number")),
+            ],
+            layers.involved_person_records[0].values
+        );
+
+        // 交通事故当事者以外の関与者は、2件目の交通事故と関連付ける
+        let relation = sqlx::query_as::<_, (String, String, String, String, String, String)>(
+            r#"
+            SELECT base_table_name, base_primary_column, related_table_name, related_primary_column,
+                relation_name, mapping_table_name
+            FROM gpkgext_relations
+            "#,
+        )
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+        assert_eq!(
+            relation,
+            (
+                String::from("accidents"),
+                String::from("fid"),
+                String::from("involved_persons"),
+                String::from("fid"),
+                String::from("attributes"),
+                String::from("accidents_involved_persons")
+            )
+        );
+        let mapping = sqlx::query_as::<_, (i64, i64)>(
+            "SELECT base_id, related_id FROM accidents_involved_persons",
+        )
+        .fetch_all(&mut conn)
+        .await
+        .unwrap();
+        assert_eq!(mapping, vec![(2, 1)]);
+        conn.close().await.unwrap();
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn write_projected_ok() {
+        let dir = temp_dir_for_test("gpkg-projected");
+        let mut layers = layers_for_test();
+        layers.accidents.srid = Srid::try_from(6677).unwrap();
+        let path = dir.join("accidents.gpkg");
+        let writer = Writer::create(
+            &path,
+            layers.accidents.clone(),
+            layers.involved_persons.clone(),
+        )
+        .await
+        .unwrap();
+        layers.write(writer).await;
+
+        let options = SqliteConnectOptions::new().filename(&path);
+        let mut conn = SqliteConnection::connect_with(&options).await.unwrap();
+        let srs_name: String =
+            sqlx::query_scalar("SELECT srs_name FROM gpkg_spatial_ref_sys WHERE srs_id = 6677")
+                .fetch_one(&mut conn)
+                .await
+                .unwrap();
+        assert_eq!(srs_name, "JGD2011 / Japan Plane Rectangular CS IX");
+        let srs_id: i32 = sqlx::query_scalar(
+            "SELECT srs_id FROM gpkg_geometry_columns WHERE table_name = 'accidents'",
+        )
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+        assert_eq!(srs_id, 6677);
+        let geom: Vec<u8> = sqlx::query_scalar("SELECT geom FROM accidents ORDER BY fid")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(&geom[4..8], &6677_i32.to_le_bytes());
+        conn.close().await.unwrap();
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,412 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arrow_array::builder::{BinaryBuilder, Int64Builder, StringBuilder};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field as ArrowField, Schema, SchemaRef};
+use geo_types::Point;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::metadata::KeyValue;
+use parquet::file::properties::WriterProperties;
+
+use super::{
+    related_path, wkb_point, Extent, FieldType, FieldValue, Layer, Record, RecordWriter, TempFile,
+};
+
+/// 1つの行グループに格納する行数
+const ROW_GROUP_SIZE: usize = 65_536;
+
+/// 1つのレコードバッチに格納する行数
+const BATCH_SIZE: usize = 8_192;
+
+/// ジオメトリの列名
+const GEOMETRY_COLUMN: &str = "geometry";
+
+/// JGD2011の地理座標系を表現するPROJJSON
+const JGD2011_PROJJSON: &str = r#"{"$schema":"https://proj.org/schemas/v0.7/projjson.schema.json","type":"GeographicCRS","name":"JGD2011","datum":{"type":"GeodeticReferenceFrame","name":"Japanese Geodetic Datum 2011","ellipsoid":{"name":"GRS 1980","semi_major_axis":6378137,"inverse_flattening":298.257222101}},"coordinate_system":{"subtype":"ellipsoidal","axis":[{"name":"Geodetic latitude","abbreviation":"Lat","direction":"north","unit":"degree"},{"name":"Geodetic longitude","abbreviation":"Lon","direction":"east","unit":"degree"}]},"id":{"authority":"EPSG","code":6668}}"#;
+
+/// 交通事故と交通事故当事者以外の関与者をGeoParquet形式で出力するライター
+///
+/// 交通事故の位置はWKBで`geometry`列に出力して、ファイルのメタデータにGeoParquetのメタデータを記録する。
+/// 交通事故当事者以外の関与者は、ジオメトリの列を持たないParquetファイルとして、
+/// `<ファイル名>_involved_persons.parquet`ファイルに出力する。
+/// 値は圧縮せずに出力する。
+pub struct Writer {
+    /// 交通事故を出力するライター
+    accidents: LayerWriter,
+    /// 交通事故当事者以外の関与者を出力するライター
+    involved_persons: LayerWriter,
+}
+
+impl Writer {
+    /// 出力ファイルを作成する。
+    ///
+    /// # 引数
+    ///
+    /// * `output` - 出力ファイルパス
+    /// * `accidents` - 交通事故のレイヤー
+    /// * `involved_persons` - 交通事故当事者以外の関与者のレイヤー
+    ///
+    /// # 戻り値
+    ///
+    /// ライター
+    pub fn create(
+        output: &Path,
+        accidents: Layer,
+        involved_persons: Layer,
+    ) -> anyhow::Result<Self> {
+        let related = related_path(output, &involved_persons, "parquet");
+
+        Ok(Self {
+            accidents: LayerWriter::create(output.to_path_buf(), accidents)?,
+            involved_persons: LayerWriter::create(related, involved_persons)?,
+        })
+    }
+}
+
+impl RecordWriter for Writer {
+    async fn write_accident(&mut self, record: &Record) -> anyhow::Result<()> {
+        self.accidents.write(record)
+    }
+
+    async fn write_involved_person(&mut self, record: &Record) -> anyhow::Result<()> {
+        self.involved_persons.write(record)
+    }
+
+    async fn finish(self) -> anyhow::Result<Vec<PathBuf>> {
+        Ok(vec![
+            self.accidents.finish()?,
+            self.involved_persons.finish()?,
+        ])
+    }
+}
+
+/// 列の値を追加するビルダー
+enum ColumnBuilder {
+    /// 文字列の属性
+    Text(StringBuilder),
+    /// 整数の属性
+    Integer(Int64Builder),
+    /// WKBで表現したジオメトリ
+    Geometry(BinaryBuilder),
+}
+
+impl ColumnBuilder {
+    /// 属性の値を追加する。
+    fn append_value(&mut self, value: &FieldValue) {
+        match (self, value) {
+            (Self::Text(builder), FieldValue::Text(value)) => builder.append_value(value),
+            (Self::Integer(builder), FieldValue::Integer(value)) => builder.append_value(*value),
+            (Self::Text(builder), _) => builder.append_null(),
+            (Self::Integer(builder), _) => builder.append_null(),
+            (Self::Geometry(builder), _) => builder.append_null(),
+        }
+    }
+
+    /// ジオメトリを追加する。
+    fn append_point(&mut self, point: Option<Point>) {
+        if let Self::Geometry(builder) = self {
+            builder.append_option(point.map(wkb_point));
+        }
+    }
+
+    /// 追加した値から列の配列を作成して、ビルダーを空にする。
+    fn finish(&mut self) -> ArrayRef {
+        match self {
+            Self::Text(builder) => Arc::new(builder.finish()),
+            Self::Integer(builder) => Arc::new(builder.finish()),
+            Self::Geometry(builder) => Arc::new(builder.finish()),
+        }
+    }
+}
+
+/// レイヤーをParquet形式で出力するライター
+///
+/// レコードを`BATCH_SIZE`件ずつレコードバッチにまとめて出力する。
+/// GeoParquetのメタデータにはジオメトリの範囲を記録するため、`finish`でファイルのメタデータに追加する。
+struct LayerWriter {
+    /// 出力ファイルパス
+    path: PathBuf,
+    /// レイヤー
+    layer: Layer,
+    /// スキーマ
+    schema: SchemaRef,
+    /// 出力ファイルが完成するまでレコードを出力する一時ファイルのライター
+    writer: ArrowWriter<TempFile>,
+    /// 列のビルダー
+    columns: Vec<ColumnBuilder>,
+    /// ビルダーに追加したレコードの数
+    rows: usize,
+    /// 出力したレコードの位置を含む範囲
+    extent: Extent,
+}
+
+impl LayerWriter {
+    fn create(path: PathBuf, layer: Layer) -> anyhow::Result<Self> {
+        let mut fields = vec![];
+        let mut columns = vec![];
+        for field in &layer.fields {
+            let (data_type, column) = match field.field_type {
+                FieldType::Text => (DataType::Utf8, ColumnBuilder::Text(StringBuilder::new())),
+                FieldType::Integer => {
+                    (DataType::Int64, ColumnBuilder::Integer(Int64Builder::new()))
+                }
+            };
+            fields.push(ArrowField::new(field.name, data_type, true));
+            columns.push(column);
+        }
+        if layer.spatial {
+            fields.push(ArrowField::new(GEOMETRY_COLUMN, DataType::Binary, true));
+            columns.push(ColumnBuilder::Geometry(BinaryBuilder::new()));
+        }
+        let schema = Arc::new(Schema::new(fields));
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::UNCOMPRESSED)
+            .set_max_row_group_size(ROW_GROUP_SIZE)
+            .set_created_by(concat!("taod-cli version ", env!("CARGO_PKG_VERSION")).to_string())
+            .build();
+        let writer =
+            ArrowWriter::try_new(TempFile::create(&path)?, schema.clone(), Some(properties))?;
+
+        Ok(Self {
+            path,
+            layer,
+            schema,
+            writer,
+            columns,
+            rows: 0,
+            extent: Extent::default(),
+        })
+    }
+
+    fn write(&mut self, record: &Record) -> anyhow::Result<()> {
+        for (column, value) in self.columns.iter_mut().zip(&record.values) {
+            column.append_value(value);
+        }
+        if self.layer.spatial {
+            if let Some(column) = self.columns.last_mut() {
+                column.append_point(record.point);
+            }
+            if let Some(point) = record.point {
+                self.extent.include(point);
+            }
+        }
+        self.rows += 1;
+        if BATCH_SIZE <= self.rows {
+            self.write_batch()?;
+        }
+
+        Ok(())
+    }
+
+    /// ビルダーに追加したレコードを、レコードバッチとして出力する。
+    fn write_batch(&mut self) -> anyhow::Result<()> {
+        let columns = self
+            .columns
+            .iter_mut()
+            .map(|column| column.finish())
+            .collect();
+        self.writer
+            .write(&RecordBatch::try_new(self.schema.clone(), columns)?)?;
+        self.rows = 0;
+
+        Ok(())
+    }
+
+    fn finish(mut self) -> anyhow::Result<PathBuf> {
+        if 0 < self.rows {
+            self.write_batch()?;
+        }
+        if self.layer.spatial {
+            self.writer.append_key_value_metadata(KeyValue::new(
+                String::from("geo"),
+                geo_metadata(&self.extent),
+            ));
+        }
+        self.writer.into_inner()?.persist(&self.path)?;
+
+        Ok(self.path)
+    }
+}
+
+/// GeoParquetのメタデータを作成する。
+fn geo_metadata(extent: &Extent) -> String {
+    let mut column = serde_json::json!({
+        "encoding": "WKB",
+        "geometry_types": ["Point"],
+        "crs": serde_json::from_str::<serde_json::Value>(JGD2011_PROJJSON).unwrap(),
+    });
+    if let Some(bbox) = extent.bbox() {
+        column["bbox"] = serde_json::json!(bbox);
+    }
+
+    serde_json::json!({
+        "version": "1.0.0",
+        "primary_column": GEOMETRY_COLUMN,
+        "columns": { GEOMETRY_COLUMN: column },
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::layers_for_test;
+    use crate::test_utils::temp_dir_for_test;
+    use arrow_array::{Array, BinaryArray, Int64Array, StringArray};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    /// Parquetファイルを読み込んで、スキーマ、レコードバッチ及びファイルのメタデータの`geo`の値を返す。
+    fn read_parquet(path: &Path) -> (SchemaRef, Vec<RecordBatch>, Option<String>) {
+        let file = std::fs::File::open(path).unwrap();
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+        let geo = builder
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()
+            .and_then(|metadata| metadata.iter().find(|kv| kv.key == "geo"))
+            .and_then(|kv| kv.value.clone());
+        let schema = builder.schema().clone();
+        let batches = builder
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        (schema, batches, geo)
+    }
+
+    /// レコードバッチの値が、レイヤーのレコードと一致することを確認する。
+    fn assert_records(layer: &Layer, batch: &RecordBatch, records: &[Record]) {
+        assert_eq!(batch.num_rows(), records.len());
+        for (index, field) in layer.fields.iter().enumerate() {
+            let column = batch.column_by_name(field.name).unwrap();
+            for (row, record) in records.iter().enumerate() {
+                match &record.values[index] {
+                    FieldValue::Text(value) => {
+                        let column = column.as_any().downcast_ref::<StringArray>().unwrap();
+                        assert_eq!(column.value(row), value);
+                    }
+                    FieldValue::Integer(value) => {
+                        let column = column.as_any().downcast_ref::<Int64Array>().unwrap();
+                        assert_eq!(column.value(row), *value);
+                    }
+                    FieldValue::Null => assert!(column.is_null(row)),
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn write_ok() {
+        let dir = temp_dir_for_test("parquet");
+        let layers = layers_for_test();
+        let writer = Writer::create(
+            &dir.join("accidents.parquet"),
+            layers.accidents.clone(),
+            layers.involved_persons.clone(),
+        )
+        .unwrap();
+        let paths = layers.write(writer).await;
+        assert_eq!(
+            paths[1].file_name().unwrap(),
+            "accidents_involved_persons.parquet"
+        );
+
+        let (schema, batches, geo) = read_parquet(&paths[0]);
+        assert_eq!(
+            schema
+                .fields()
+                .iter()
+                .map(|field| (
+                    field.name().as_str(),
+                    field.data_type().clone(),
+                    field.is_nullable()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("id", DataType::Utf8, true),
+                ("weather_name", DataType::Utf8, true),
+                ("number_of_deaths", DataType::Int64, true),
+                ("route_name", DataType::Utf8, true),
+                (GEOMETRY_COLUMN, DataType::Binary, true),
+            ]
+        );
+        assert_eq!(batches.len(), 1);
+        assert_records(&layers.accidents, &batches[0], &layers.accident_records);
+        let geometries = batches[0]
+            .column_by_name(GEOMETRY_COLUMN)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<BinaryArray>()
+            .unwrap();
+        for (row, record) in layers.accident_records.iter().enumerate() {
+            assert_eq!(geometries.value(row), wkb_point(record.point.unwrap()));
+        }
+        let geo = serde_json::from_str::<serde_json::Value>(&geo.unwrap()).unwrap();
+        assert_eq!(geo["primary_column"], GEOMETRY_COLUMN);
+        assert_eq!(geo["columns"][GEOMETRY_COLUMN]["encoding"], "WKB");
+        assert_eq!(
+            geo["columns"][GEOMETRY_COLUMN]["bbox"],
+            serde_json::json!([139.5, 35.5, 140.0, 36.0])
+        );
+        assert_eq!(geo["columns"][GEOMETRY_COLUMN]["crs"]["id"]["code"], 6668);
+
+        let (schema, batches, geo) = read_parquet(&paths[1]);
+        assert_eq!(schema.fields().len(), layers.involved_persons.fields.len());
+        assert!(schema.field_with_name(GEOMETRY_COLUMN).is_err());
+        assert_eq!(batches.len(), 1);
+        assert_records(
+            &layers.involved_persons,
+            &batches[0],
+            &layers.involved_person_records,
+        );
+        assert!(geo.is_none());
+        assert!(!dir.join("accidents.parquet.tmp").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn write_batches_ok() {
+        let dir = temp_dir_for_test("parquet-batches");
+        let layers = layers_for_test();
+        let mut writer = Writer::create(
+            &dir.join("accidents.parquet"),
+            layers.accidents.clone(),
+            layers.involved_persons.clone(),
+        )
+        .unwrap();
+        let count = BATCH_SIZE + 1;
+        for index in 0..count {
+            let mut record = layers.accident_records[index % 2].clone();
+            record.values[2] = FieldValue::Integer(index as i64);
+            writer.write_accident(&record).await.unwrap();
+        }
+        let paths = writer.finish().await.unwrap();
+
+        let (_, batches, geo) = read_parquet(&paths[0]);
+        let numbers = batches
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .column_by_name("number_of_deaths")
+                    .unwrap()
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, (0..count as i64).collect::<Vec<_>>());
+        assert!(geo.unwrap().contains("[139.5,35.5,140.0,36.0]"));
+
+        let (_, batches, _) = read_parquet(&paths[1]);
+        assert_eq!(
+            batches.iter().map(|batch| batch.num_rows()).sum::<usize>(),
+            0
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,667 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+
+use geo_types::Point;
+use geometries::Srid;
+use time::OffsetDateTime;
+
+use super::{related_path, Extent, FieldType, FieldValue, Layer, Record, RecordWriter, TempFile};
+
+/// シェープファイルのファイルコード
+const SHP_FILE_CODE: i32 = 9994;
+
+/// シェープファイルのバージョン
+const SHP_VERSION: i32 = 1000;
+
+/// ポイントのシェープタイプ
+const SHP_POINT: i32 = 1;
+
+/// シェープファイルのヘッダのバイト数
+const SHP_HEADER_SIZE: usize = 100;
+
+/// ポイントのレコードの内容のバイト数（シェープタイプと座標）
+const SHP_POINT_CONTENT_SIZE: usize = 20;
+
+/// ポイントのレコードのバイト数（レコードヘッダと内容）
+const SHP_POINT_RECORD_SIZE: usize = 8 + SHP_POINT_CONTENT_SIZE;
+
+/// dBASEファイルのバージョン（dBASE III）
+const DBF_VERSION: u8 = 0x03;
+
+/// dBASEファイルの属性名の最大バイト数
+const DBF_MAX_FIELD_NAME_SIZE: usize = 10;
+
+/// dBASEファイルの文字列の属性の最大バイト数
+const DBF_MAX_TEXT_SIZE: usize = 254;
+
+/// 交通事故と交通事故当事者以外の関与者をシェープファイル形式で出力するライター
+///
+/// 交通事故は`.shp`、`.shx`、`.dbf`、`.prj`及び`.cpg`ファイルに出力する。
+/// 交通事故当事者以外の関与者は、`<ファイル名>_involved_persons.dbf`ファイルに属性だけを出力する。
+/// dBASEファイルの属性名は10バイトまでのため、属性名を切り詰めて、重複する場合は末尾を番号に置き換える。
+pub struct Writer {
+    /// 交通事故の位置を出力するライター
+    shapes: ShapeWriter,
+    /// 交通事故の属性を出力するライター
+    accidents: DbfWriter,
+    /// 交通事故当事者以外の関与者の属性を出力するライター
+    involved_persons: DbfWriter,
+    /// 交通事故の位置の空間参照系
+    srid: Srid,
+    /// 出力するファイルのパス
+    paths: Vec<PathBuf>,
+}
+
+impl Writer {
+    /// 出力ファイルを作成する。
+    ///
+    /// # 引数
+    ///
+    /// * `output` - 出力ファイルパス（`.shp`ファイル）
+    /// * `accidents` - 交通事故のレイヤー
+    /// * `involved_persons` - 交通事故当事者以外の関与者のレイヤー
+    ///
+    /// # 戻り値
+    ///
+    /// ライター
+    pub fn create(
+        output: &Path,
+        accidents: Layer,
+        involved_persons: Layer,
+    ) -> anyhow::Result<Self> {
+        let shp = output.with_extension("shp");
+        let shx = output.with_extension("shx");
+        let dbf = output.with_extension("dbf");
+        let prj = output.with_extension("prj");
+        let cpg = output.with_extension("cpg");
+        let related_dbf = related_path(&shp, &involved_persons, "dbf");
+        let related_cpg = related_dbf.with_extension("cpg");
+
+        Ok(Self {
+            shapes: ShapeWriter::create(shp.clone(), shx.clone())?,
+            accidents: DbfWriter::create(dbf.clone(), &accidents)?,
+            involved_persons: DbfWriter::create(related_dbf.clone(), &involved_persons)?,
+            srid: accidents.srid,
+            paths: vec![shp, shx, dbf, prj, cpg, related_dbf, related_cpg],
+        })
+    }
+}
+
+impl RecordWriter for Writer {
+    async fn write_accident(&mut self, record: &Record) -> anyhow::Result<()> {
+        self.shapes.write(record.point)?;
+        self.accidents.write(record)
+    }
+
+    async fn write_involved_person(&mut self, record: &Record) -> anyhow::Result<()> {
+        self.involved_persons.write(record)
+    }
+
+    async fn finish(self) -> anyhow::Result<Vec<PathBuf>> {
+        let related_cpg = self.involved_persons.path.with_extension("cpg");
+        let shp = self.shapes.finish()?;
+        self.accidents.finish()?;
+        self.involved_persons.finish()?;
+        write_file(&shp.with_extension("prj"), self.srid.esri_wkt().as_bytes())?;
+        write_file(&shp.with_extension("cpg"), b"UTF-8")?;
+        write_file(&related_cpg, b"UTF-8")?;
+
+        Ok(self.paths)
+    }
+}
+
+/// ファイルを作成して、データを書き込む。
+fn write_file(path: &Path, data: &[u8]) -> anyhow::Result<()> {
+    let file = File::create(path)
+        .map_err(|e| anyhow::anyhow!("{}を作成できませんでした。{}", path.display(), e))?;
+    let mut writer = BufWriter::new(file);
+    writer.write_all(data)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// 出力ファイルを作成する。
+fn create_file(path: &Path) -> anyhow::Result<BufWriter<File>> {
+    let file = File::create(path)
+        .map_err(|e| anyhow::anyhow!("{}を作成できませんでした。{}", path.display(), e))?;
+
+    Ok(BufWriter::new(file))
+}
+
+/// `.shp`ファイルと`.shx`ファイルにポイントを出力するライター
+///
+/// ヘッダにはファイル長と範囲を記録するため、一時ファイルに出力して、`finish`でヘッダを書き直してから
+/// 出力ファイルに置き換える。
+struct ShapeWriter {
+    /// `.shp`ファイルのパス
+    shp_path: PathBuf,
+    /// `.shx`ファイルのパス
+    shx_path: PathBuf,
+    /// `.shp`ファイルの一時ファイル
+    shp: TempFile,
+    /// `.shx`ファイルの一時ファイル
+    shx: TempFile,
+    /// 出力したレコードの数
+    count: usize,
+    /// 出力したポイントを含む範囲
+    extent: Extent,
+}
+
+impl ShapeWriter {
+    fn create(shp_path: PathBuf, shx_path: PathBuf) -> anyhow::Result<Self> {
+        let mut shp = TempFile::create(&shp_path)?;
+        let mut shx = TempFile::create(&shx_path)?;
+        shp.write_all(&shp_header(SHP_HEADER_SIZE, [0.0; 4]))?;
+        shx.write_all(&shp_header(SHP_HEADER_SIZE, [0.0; 4]))?;
+
+        Ok(Self {
+            shp_path,
+            shx_path,
+            shp,
+            shx,
+            count: 0,
+            extent: Extent::default(),
+        })
+    }
+
+    fn write(&mut self, point: Option<Point>) -> anyhow::Result<()> {
+        // 位置がないレコードはないが、NaNの座標で空のポイントを表現
+        let (x, y) = point
+            .map(|point| (point.x(), point.y()))
+            .unwrap_or((f64::NAN, f64::NAN));
+        if let Some(point) = point {
+            self.extent.include(point);
+        }
+        let offset = SHP_HEADER_SIZE + SHP_POINT_RECORD_SIZE * self.count;
+        self.shx.write_all(&((offset / 2) as i32).to_be_bytes())?;
+        self.shx
+            .write_all(&((SHP_POINT_CONTENT_SIZE / 2) as i32).to_be_bytes())?;
+        self.shp.write_all(&(self.count as i32 + 1).to_be_bytes())?;
+        self.shp
+            .write_all(&((SHP_POINT_CONTENT_SIZE / 2) as i32).to_be_bytes())?;
+        self.shp.write_all(&SHP_POINT.to_le_bytes())?;
+        self.shp.write_all(&x.to_le_bytes())?;
+        self.shp.write_all(&y.to_le_bytes())?;
+        self.count += 1;
+
+        Ok(())
+    }
+
+    /// ヘッダを書き直して、`.shp`ファイルのパスを返す。
+    fn finish(mut self) -> anyhow::Result<PathBuf> {
+        let bbox = self.extent.bbox().unwrap_or_default();
+        let shp_size = SHP_HEADER_SIZE + SHP_POINT_RECORD_SIZE * self.count;
+        let shx_size = SHP_HEADER_SIZE + 8 * self.count;
+        for (writer, size) in [(&mut self.shp, shp_size), (&mut self.shx, shx_size)] {
+            writer.rewind()?;
+            writer.write_all(&shp_header(size, bbox))?;
+        }
+        self.shp.persist(&self.shp_path)?;
+        self.shx.persist(&self.shx_path)?;
+
+        Ok(self.shp_path)
+    }
+}
+
+/// `.shp`ファイルと`.shx`ファイルのヘッダを作成する。
+fn shp_header(file_size: usize, bbox: [f64; 4]) -> Vec<u8> {
+    let mut header = Vec::with_capacity(SHP_HEADER_SIZE);
+    header.extend(SHP_FILE_CODE.to_be_bytes());
+    header.extend([0; 20]);
+    // ファイル長は16ビットワード単位
+    header.extend(((file_size / 2) as i32).to_be_bytes());
+    header.extend(SHP_VERSION.to_le_bytes());
+    header.extend(SHP_POINT.to_le_bytes());
+    for value in bbox {
+        header.extend(value.to_le_bytes());
+    }
+    // Z及びMの範囲
+    header.extend([0; 32]);
+
+    header
+}
+
+/// dBASEファイルの属性
+struct DbfField {
+    /// 属性名
+    name: String,
+    /// 属性の型（`C`または`N`）
+    field_type: u8,
+    /// 属性のバイト数
+    size: usize,
+}
+
+/// `.dbf`ファイルにレコードを出力するライター
+///
+/// 文字列はUTF-8で符号化して、属性のバイト数は値の最大のバイト数とする。
+/// 属性のバイト数はすべてのレコードを出力するまで決まらないため、値を一時ファイルに出力して、
+/// `finish`でヘッダを出力した後に、値を属性のバイト数に揃えて出力する。
+struct DbfWriter {
+    /// 出力ファイルパス
+    path: PathBuf,
+    /// 値を出力する一時ファイル
+    values: TempFile,
+    /// 属性
+    fields: Vec<DbfField>,
+    /// 出力したレコードの数
+    count: usize,
+}
+
+impl DbfWriter {
+    fn create(path: PathBuf, layer: &Layer) -> anyhow::Result<Self> {
+        let values = TempFile::create(&path)?;
+        let fields = layer
+            .fields
+            .iter()
+            .zip(dbf_field_names(layer))
+            .map(|(field, name)| DbfField {
+                name,
+                field_type: match field.field_type {
+                    FieldType::Text => b'C',
+                    FieldType::Integer => b'N',
+                },
+                size: 1,
+            })
+            .collect();
+
+        Ok(Self {
+            path,
+            values,
+            fields,
+            count: 0,
+        })
+    }
+
+    /// 値のバイト数を前置して、値を一時ファイルに出力する。
+    fn write(&mut self, record: &Record) -> anyhow::Result<()> {
+        for (field, value) in self.fields.iter_mut().zip(&record.values) {
+            let value = dbf_value(value);
+            field.size = field.size.max(value.len());
+            self.values.write_all(&[value.len() as u8])?;
+            self.values.write_all(&value)?;
+        }
+        self.count += 1;
+
+        Ok(())
+    }
+
+    fn finish(mut self) -> anyhow::Result<()> {
+        let mut values = self.values.reader()?;
+        let mut writer = create_file(&self.path)?;
+        writer.write_all(&dbf_header(&self.fields, self.count))?;
+        let mut value = [0; DBF_MAX_TEXT_SIZE];
+        for _ in 0..self.count {
+            // 削除フラグ
+            writer.write_all(b" ")?;
+            for field in &self.fields {
+                let mut size = [0];
+                values.read_exact(&mut size)?;
+                let value = &mut value[..size[0] as usize];
+                values.read_exact(value)?;
+                let padding = vec![b' '; field.size - value.len()];
+                match field.field_type {
+                    b'N' => {
+                        writer.write_all(&padding)?;
+                        writer.write_all(value)?;
+                    }
+                    _ => {
+                        writer.write_all(value)?;
+                        writer.write_all(&padding)?;
+                    }
+                }
+            }
+        }
+        writer.write_all(&[0x1A])?;
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+/// `.dbf`ファイルのヘッダを作成する。
+///
+/// # 引数
+///
+/// * `fields` - 属性
+/// * `count` - レコードの数
+///
+/// # 戻り値
+///
+/// フィールド記述子とその終端を含むヘッダ
+fn dbf_header(fields: &[DbfField], count: usize) -> Vec<u8> {
+    let header_size = 32 + 32 * fields.len() + 1;
+    let record_size = 1 + fields.iter().map(|field| field.size).sum::<usize>();
+    let today = OffsetDateTime::now_utc().date();
+    let mut header = Vec::with_capacity(header_size);
+    header.push(DBF_VERSION);
+    header.extend([
+        (today.year() - 1900) as u8,
+        today.month() as u8,
+        today.day(),
+    ]);
+    header.extend((count as u32).to_le_bytes());
+    header.extend((header_size as u16).to_le_bytes());
+    header.extend((record_size as u16).to_le_bytes());
+    header.extend([0; 20]);
+    for field in fields {
+        let mut name = [0; 11];
+        name[..field.name.len()].copy_from_slice(field.name.as_bytes());
+        header.extend(name);
+        header.push(field.field_type);
+        header.extend([0; 4]);
+        header.push(field.size as u8);
+        header.push(0);
+        header.extend([0; 14]);
+    }
+    header.push(0x0D);
+
+    header
+}
+
+/// 属性の値をdBASEファイルに記録するバイト列に変換する。
+///
+/// 値がない場合は空のバイト列を返し、dBASEファイルでは空白で埋める。
+fn dbf_value(value: &FieldValue) -> Vec<u8> {
+    match value {
+        FieldValue::Text(value) => {
+            let mut size = value.len().min(DBF_MAX_TEXT_SIZE);
+            while !value.is_char_boundary(size) {
+                size -= 1;
+            }
+            value.as_bytes()[..size].to_vec()
+        }
+        FieldValue::Integer(value) => value.to_string().into_bytes(),
+        FieldValue::Null => vec![],
+    }
+}
+
+/// dBASEファイルの属性名を返す。
+///
+/// 属性名を10バイトに切り詰めて、既存の属性名と重複する場合は末尾を番号に置き換える。
+///
+/// # 引数
+///
+/// * `layer` - レイヤー
+///
+/// # 戻り値
+///
+/// 属性名を格納したベクタ
+fn dbf_field_names(layer: &Layer) -> Vec<String> {
+    let mut names = HashSet::new();
+    layer
+        .fields
+        .iter()
+        .map(|field| {
+            let base = &field.name[..field.name.len().min(DBF_MAX_FIELD_NAME_SIZE)];
+            let mut name = base.to_string();
+            let mut number = 1;
+            while names.contains(&name) {
+                let suffix = number.to_string();
+                name = format!(
+                    "{}{}",
+                    &base[..base.len().min(DBF_MAX_FIELD_NAME_SIZE - suffix.len())],
+                    suffix
+                );
+                number += 1;
+            }
+            names.insert(name.clone());
+
+            name
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::layers_for_test;
+    use crate::export::Field;
+    use crate::test_utils::temp_dir_for_test;
+
+    /// 読み込んだ`.shp`ファイルまたは`.shx`ファイルのヘッダ
+    #[derive(Debug, PartialEq)]
+    struct ShpHeader {
+        file_code: i32,
+        file_length: usize,
+        version: i32,
+        shape_type: i32,
+        bbox: [f64; 4],
+    }
+
+    fn be_i32(data: &[u8], offset: usize) -> i32 {
+        i32::from_be_bytes(data[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn le_i32(data: &[u8], offset: usize) -> i32 {
+        i32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn le_f64(data: &[u8], offset: usize) -> f64 {
+        f64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+    }
+
+    /// ESRIのシェープファイルの技術仕様に従って、ヘッダを読み込む。
+    fn read_shp_header(data: &[u8]) -> ShpHeader {
+        ShpHeader {
+            file_code: be_i32(data, 0),
+            file_length: be_i32(data, 24) as usize * 2,
+            version: le_i32(data, 28),
+            shape_type: le_i32(data, 32),
+            bbox: [
+                le_f64(data, 36),
+                le_f64(data, 44),
+                le_f64(data, 52),
+                le_f64(data, 60),
+            ],
+        }
+    }
+
+    /// `.shx`ファイルの索引に従って、`.shp`ファイルのポイントを読み込む。
+    fn read_points(shp: &[u8], shx: &[u8]) -> Vec<Point> {
+        (100..shx.len())
+            .step_by(8)
+            .enumerate()
+            .map(|(index, entry)| {
+                let offset = be_i32(shx, entry) as usize * 2;
+                let content_length = be_i32(shx, entry + 4) as usize * 2;
+                assert_eq!(be_i32(shp, offset), index as i32 + 1);
+                assert_eq!(be_i32(shp, offset + 4) as usize * 2, content_length);
+                assert_eq!(le_i32(shp, offset + 8), 1);
+                Point::new(le_f64(shp, offset + 12), le_f64(shp, offset + 20))
+            })
+            .collect()
+    }
+
+    /// 読み込んだ`.dbf`ファイル
+    #[derive(Debug, PartialEq)]
+    struct Dbf {
+        fields: Vec<(String, u8, usize)>,
+        records: Vec<Vec<FieldValue>>,
+    }
+
+    /// dBASE IIIの形式に従って、`.dbf`ファイルを読み込む。
+    fn read_dbf(data: &[u8]) -> Dbf {
+        assert_eq!(data[0], 0x03);
+        let count = u32::from_le_bytes(data[4..8].try_into().unwrap()) as usize;
+        let header_size = u16::from_le_bytes([data[8], data[9]]) as usize;
+        let record_size = u16::from_le_bytes([data[10], data[11]]) as usize;
+        assert_eq!(data[header_size - 1], 0x0D);
+        assert_eq!(data.len(), header_size + record_size * count + 1);
+        assert_eq!(data[data.len() - 1], 0x1A);
+
+        let fields = data[32..header_size - 1]
+            .chunks(32)
+            .map(|descriptor| {
+                let name = descriptor[..11].split(|&b| b == 0).next().unwrap();
+                (
+                    String::from_utf8(name.to_vec()).unwrap(),
+                    descriptor[11],
+                    descriptor[16] as usize,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            1 + fields.iter().map(|(_, _, size)| size).sum::<usize>(),
+            record_size
+        );
+        let records = data[header_size..header_size + record_size * count]
+            .chunks(record_size)
+            .map(|record| {
+                assert_eq!(record[0], b' ');
+                let mut offset = 1;
+                fields
+                    .iter()
+                    .map(|(_, field_type, size)| {
+                        let value = std::str::from_utf8(&record[offset..offset + size]).unwrap();
+                        offset += size;
+                        match (field_type, value.trim()) {
+                            (_, "") => FieldValue::Null,
+                            (b'N', value) => FieldValue::Integer(value.parse().unwrap()),
+                            (_, value) => FieldValue::text(value),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Dbf { fields, records }
+    }
+
+    #[tokio::test]
+    async fn write_ok() {
+        let dir = temp_dir_for_test("shp");
+        let layers = layers_for_test();
+        let writer = Writer::create(
+            &dir.join("accidents.shp"),
+            layers.accidents.clone(),
+            layers.involved_persons.clone(),
+        )
+        .unwrap();
+        let paths = layers.write(writer).await;
+        assert_eq!(paths.len(), 7);
+        assert_eq!(
+            paths[5].file_name().unwrap(),
+            "accidents_involved_persons.dbf"
+        );
+        // 一時ファイルは削除する
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 7);
+
+        let shp = std::fs::read(&paths[0]).unwrap();
+        let shx = std::fs::read(&paths[1]).unwrap();
+        let header = ShpHeader {
+            file_code: 9994,
+            file_length: 100 + 28 * 2,
+            version: 1000,
+            shape_type: 1,
+            bbox: [139.5, 35.5, 140.0, 36.0],
+        };
+        assert_eq!(read_shp_header(&shp), header);
+        assert_eq!(shp.len(), header.file_length);
+        assert_eq!(
+            read_shp_header(&shx),
+            ShpHeader {
+                file_length: 100 + 8 * 2,
+                ..header
+            }
+        );
+        assert_eq!(shx.len(), 100 + 8 * 2);
+        let points = layers
+            .accident_records
+            .iter()
+            .map(|record| record.point.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(read_points(&shp, &shx), points);
+
+        // 「晴」は3バイト、「number_of_deaths」の値は2桁
+        let dbf = read_dbf(&std::fs::read(&paths[2]).unwrap());
+        assert_eq!(
+            dbf.fields,
+            vec![
+                (String::from("id"), b'C', 36),
+                (String::from("weather_na"), b'C', 3),
+                (String::from("number_of_"), b'N', 2),
+                (String::from("route_name"), b'C', 12),
+            ]
+        );
+        let values = layers
+            .accident_records
+            .iter()
+            .map(|record| record.values.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(dbf.records, values);
+        let dbf = read_dbf(&std::fs::read(&paths[5]).unwrap());
+        assert_eq!(dbf.fields[1], (String::from("accident_i"), b'C', 36));
+        assert_eq!(
+            dbf.records,
+            vec![layers.involved_person_records[0].values.clone()]
+        );
+
+        assert_eq!(
+            std::fs::read_to_string(&paths[3]).unwrap(),
+            Srid::JGD2011.esri_wkt()
+        );
+        assert_eq!(std::fs::read_to_string(&paths[4]).unwrap(), "UTF-8");
+        assert_eq!(std::fs::read_to_string(&paths[6]).unwrap(), "UTF-8");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn write_empty_ok() {
+        let dir = temp_dir_for_test("shp-empty");
+        let layers = layers_for_test();
+        let writer = Writer::create(
+            &dir.join("accidents.shp"),
+            layers.accidents.clone(),
+            layers.involved_persons.clone(),
+        )
+        .unwrap();
+        let paths = writer.finish().await.unwrap();
+
+        let shp = std::fs::read(&paths[0]).unwrap();
+        assert_eq!(read_shp_header(&shp).file_length, 100);
+        assert_eq!(shp.len(), 100);
+        let dbf = read_dbf(&std::fs::read(&paths[2]).unwrap());
+        assert_eq!(dbf.fields.len(), 4);
+        assert!(dbf.records.is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn dbf_value_ok() {
+        assert_eq!(dbf_value(&FieldValue::Integer(-12)), b"-12");
+        assert_eq!(dbf_value(&FieldValue::Null), b"");
+        // 文字の途中で切り詰めない
+        let value = dbf_value(&FieldValue::text("あ".repeat(100)));
+        assert_eq!(value.len(), 252);
+        assert!(std::str::from_utf8(&value).is_ok());
+    }
+
+    #[test]
+    fn dbf_field_names_ok() {
+        let fields = [
+            "id",
+            "accident_detail_code",
+            "accident_detail_name",
+            "accident_d",
+        ];
+        let layer = Layer {
+            name: "test",
+            spatial: false,
+            srid: Srid::JGD2011,
+            fields: fields
+                .into_iter()
+                .map(|name| Field {
+                    name,
+                    field_type: FieldType::Text,
+                })
+                .collect(),
+        };
+        assert_eq!(
+            dbf_field_names(&layer),
+            vec!["id", "accident_d", "accident_1", "accident_2"]
+        );
+    }
+}
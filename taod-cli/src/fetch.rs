@@ -0,0 +1,528 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::files::file_sha256;
+use crate::sheet::Sheet;
+
+/// 警察庁の交通事故統計情報オープンデータのURL
+pub const NPA_OPEN_DATA_BASE_URL: &str =
+    "https://www.npa.go.jp/publications/statistics/koutsuu/opendata";
+
+/// ダウンロードしたファイルのSHA-256ハッシュを記録するファイルの名前
+const CHECKSUMS_FILE_NAME: &str = "SHA256SUMS";
+
+/// 票を公開しているファイルの拡張子（優先する順）
+const SHEET_FILE_EXTENSIONS: [&str; 2] = ["csv", "zip"];
+
+/// 交通事故統計情報オープンデータのダウンロードオプション
+#[derive(Debug, Clone)]
+pub struct FetchOptions {
+    /// オープンデータを公開しているURL（年ごとのディレクトリの親）
+    pub base_url: String,
+    /// ダウンロードしたファイルを保存するディレクトリ
+    pub cache_dir: PathBuf,
+    /// ファイル名と期待するSHA-256ハッシュを`sha256sum`形式で記録したファイル
+    pub checksums: Option<PathBuf>,
+    /// キャッシュしたファイルを使用せずにダウンロードするかを示すフラグ
+    pub force: bool,
+}
+
+impl Default for FetchOptions {
+    fn default() -> Self {
+        Self {
+            base_url: NPA_OPEN_DATA_BASE_URL.to_string(),
+            cache_dir: PathBuf::from("cache"),
+            checksums: None,
+            force: false,
+        }
+    }
+}
+
+/// ダウンロードした票のファイル
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedFiles {
+    /// 本票ファイルパス
+    pub main_file: PathBuf,
+    /// 補充票ファイルパス
+    pub support_file: PathBuf,
+    /// 高速票ファイルパス（公開されていない場合は`None`）
+    pub highway_file: Option<PathBuf>,
+}
+
+/// 交通事故統計情報オープンデータをダウンロードして、`insert`サブコマンドに渡す票のファイルパスを出力する。
+///
+/// # 引数
+///
+/// * `year` - ダウンロードする年
+/// * `options` - ダウンロードオプション
+///
+/// # 戻り値
+///
+/// `()`
+pub async fn fetch(year: i32, options: FetchOptions) -> anyhow::Result<()> {
+    let files = fetch_files(year, &options).await?;
+
+    // 進捗は標準エラー出力に出力しているため、標準出力にはファイルパスだけを出力
+    let mut paths = vec![files.main_file, files.support_file];
+    paths.extend(files.highway_file);
+    println!(
+        "{}",
+        paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+
+    Ok(())
+}
+
+/// 交通事故統計情報オープンデータをダウンロードする。
+///
+/// # 引数
+///
+/// * `year` - ダウンロードする年
+/// * `options` - ダウンロードオプション
+///
+/// # 戻り値
+///
+/// ダウンロードした票のファイル
+pub async fn fetch_files(year: i32, options: &FetchOptions) -> anyhow::Result<FetchedFiles> {
+    let dir = options.cache_dir.join(year.to_string());
+    std::fs::create_dir_all(&dir).map_err(|e| {
+        anyhow::anyhow!(
+            "キャッシュディレクトリ({})を作成できません。{}",
+            dir.display(),
+            e
+        )
+    })?;
+    let checksums_path = dir.join(CHECKSUMS_FILE_NAME);
+    let mut cached = match checksums_path.exists() {
+        true => read_checksums(&checksums_path)?,
+        false => BTreeMap::new(),
+    };
+    let expected = match &options.checksums {
+        Some(path) => read_checksums(path)?,
+        None => BTreeMap::new(),
+    };
+
+    let client = reqwest::Client::new();
+    let mut fetcher = SheetFetcher {
+        client: &client,
+        base_url: options.base_url.trim_end_matches('/'),
+        year,
+        dir: &dir,
+        cached: &mut cached,
+        expected: &expected,
+        force: options.force,
+    };
+    let main_file = fetcher.fetch(Sheet::Main).await?;
+    let support_file = fetcher.fetch(Sheet::Support).await?;
+    let highway_file = fetcher.fetch(Sheet::Highway).await?;
+    write_checksums(&checksums_path, &cached)?;
+
+    let required = |file: Option<PathBuf>, sheet: Sheet| {
+        file.ok_or(anyhow::anyhow!(
+            "{}年の{}が見つかりません。",
+            year,
+            sheet.name()
+        ))
+    };
+    if highway_file.is_none() {
+        eprintln!("{}年の高速票は公開されていません。", year);
+    }
+
+    Ok(FetchedFiles {
+        main_file: required(main_file, Sheet::Main)?,
+        support_file: required(support_file, Sheet::Support)?,
+        highway_file,
+    })
+}
+
+/// 票のファイル名の語幹を返す。
+fn sheet_file_stem(sheet: Sheet) -> &'static str {
+    match sheet {
+        Sheet::Main => "honhyo",
+        Sheet::Support => "hojuhyo",
+        Sheet::Highway => "kousokuhyo",
+    }
+}
+
+/// 票のファイル名を返す。
+///
+/// # 引数
+///
+/// * `sheet` - 票の種類
+/// * `year` - 年
+/// * `extension` - 拡張子
+///
+/// # 戻り値
+///
+/// `honhyo_2022.csv`のようなファイル名
+pub fn sheet_file_name(sheet: Sheet, year: i32, extension: &str) -> String {
+    format!("{}_{}.{}", sheet_file_stem(sheet), year, extension)
+}
+
+/// 票をダウンロードする構造体
+struct SheetFetcher<'a> {
+    /// HTTPクライアント
+    client: &'a reqwest::Client,
+    /// オープンデータを公開しているURL
+    base_url: &'a str,
+    /// 年
+    year: i32,
+    /// ファイルを保存するディレクトリ
+    dir: &'a Path,
+    /// キャッシュしたファイルの名前とSHA-256ハッシュ
+    cached: &'a mut BTreeMap<String, String>,
+    /// ファイルの名前と期待するSHA-256ハッシュ
+    expected: &'a BTreeMap<String, String>,
+    /// キャッシュしたファイルを使用しないかを示すフラグ
+    force: bool,
+}
+
+impl<'a> SheetFetcher<'a> {
+    /// 票をダウンロードして、CSVファイルのパスを返す。
+    ///
+    /// ZIPファイルで公開されている場合は、ZIPファイルに含まれるCSVファイルを展開する。
+    /// 票が公開されていない場合は`None`を返す。
+    async fn fetch(&mut self, sheet: Sheet) -> anyhow::Result<Option<PathBuf>> {
+        let csv_name = sheet_file_name(sheet, self.year, "csv");
+        let csv_path = self.dir.join(&csv_name);
+        if !self.force && self.is_cached(&csv_name)? {
+            eprintln!(
+                "{}: キャッシュ({})を使用します。",
+                sheet.name(),
+                csv_path.display()
+            );
+            return Ok(Some(csv_path));
+        }
+
+        for extension in SHEET_FILE_EXTENSIONS {
+            let name = sheet_file_name(sheet, self.year, extension);
+            let url = format!("{}/{}/{}", self.base_url, self.year, name);
+            let path = self.dir.join(&name);
+            if !self.download(&url, &path).await? {
+                continue;
+            }
+            let sha256 = file_sha256(&path)?;
+            self.verify(&name, &sha256)?;
+            self.cached.insert(name, sha256);
+            if extension == "zip" {
+                extract_csv(&path, &csv_path)?;
+                self.cached.insert(csv_name, file_sha256(&csv_path)?);
+            }
+            return Ok(Some(csv_path));
+        }
+
+        Ok(None)
+    }
+
+    /// ファイルがキャッシュされていて、SHA-256ハッシュが一致するか確認する。
+    fn is_cached(&self, name: &str) -> anyhow::Result<bool> {
+        let path = self.dir.join(name);
+        let Some(sha256) = self.cached.get(name) else {
+            return Ok(false);
+        };
+        if !path.exists() {
+            return Ok(false);
+        }
+        if &file_sha256(&path)? != sha256 {
+            eprintln!(
+                "{}のSHA-256ハッシュが記録と一致しないため、ダウンロードし直します。",
+                path.display()
+            );
+            return Ok(false);
+        }
+        self.verify(name, sha256)?;
+
+        Ok(true)
+    }
+
+    /// ファイルのSHA-256ハッシュが、期待するSHA-256ハッシュと一致するか確認する。
+    fn verify(&self, name: &str, sha256: &str) -> anyhow::Result<()> {
+        match self.expected.get(name) {
+            Some(expected) if !expected.eq_ignore_ascii_case(sha256) => Err(anyhow::anyhow!(
+                "{}のSHA-256ハッシュ({})が、期待するSHA-256ハッシュ({})と一致しません。",
+                name,
+                sha256,
+                expected
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// URLのファイルをダウンロードする。
+    ///
+    /// ファイルが存在しない場合は`false`を返す。
+    async fn download(&self, url: &str, path: &Path) -> anyhow::Result<bool> {
+        let mut response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("{}をダウンロードできません。{}", url, e))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "{}をダウンロードできません。ステータスコード: {}",
+                url,
+                response.status()
+            ));
+        }
+
+        eprintln!("{}をダウンロードしています。", url);
+        // ダウンロードを中断した場合に不完全なファイルが残らないように、一時ファイルに書き込んでから名前を変更
+        let part_path = path.with_extension("part");
+        let mut file = std::fs::File::create(&part_path)?;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| anyhow::anyhow!("{}をダウンロードできません。{}", url, e))?
+        {
+            file.write_all(&chunk)?;
+        }
+        file.flush()?;
+        std::fs::rename(&part_path, path)?;
+
+        Ok(true)
+    }
+}
+
+/// ZIPファイルに含まれるCSVファイルを展開する。
+///
+/// # 引数
+///
+/// * `zip_path` - ZIPファイルのパス
+/// * `csv_path` - 展開したCSVファイルのパス
+///
+/// # 戻り値
+///
+/// `()`
+pub fn extract_csv(zip_path: &Path, csv_path: &Path) -> anyhow::Result<()> {
+    let file = std::fs::File::open(zip_path)?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| anyhow::anyhow!("{}を開けません。{}", zip_path.display(), e))?;
+    let csv_names = archive
+        .file_names()
+        .filter(|name| name.to_ascii_lowercase().ends_with(".csv"))
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+    if csv_names.len() != 1 {
+        return Err(anyhow::anyhow!(
+            "{}には、CSVファイルが1つだけ含まれている必要があります。{:?}",
+            zip_path.display(),
+            csv_names
+        ));
+    }
+
+    let mut entry = archive.by_name(&csv_names[0])?;
+    let part_path = csv_path.with_extension("part");
+    let mut file = std::fs::File::create(&part_path)?;
+    std::io::copy(&mut entry, &mut file)?;
+    file.flush()?;
+    std::fs::rename(&part_path, csv_path)?;
+
+    Ok(())
+}
+
+/// `sha256sum`形式で記録されたファイルの名前とSHA-256ハッシュを読み込む。
+///
+/// # 引数
+///
+/// * `path` - ファイルの名前とSHA-256ハッシュを記録したファイルのパス
+///
+/// # 戻り値
+///
+/// ファイルの名前とSHA-256ハッシュの対応を記録したマップ
+pub fn read_checksums(path: &Path) -> anyhow::Result<BTreeMap<String, String>> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("{}を読み込めません。{}", path.display(), e))?;
+
+    parse_checksums(&text)
+}
+
+fn parse_checksums(text: &str) -> anyhow::Result<BTreeMap<String, String>> {
+    let mut checksums = BTreeMap::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (sha256, name) = line.split_once(char::is_whitespace).ok_or(anyhow::anyhow!(
+            "{}行目: SHA-256ハッシュとファイル名を空白で区切ってください。",
+            index + 1
+        ))?;
+        // sha256sumはバイナリモードのファイル名の先頭に`*`を付ける
+        let name = name.trim_start().trim_start_matches('*');
+        checksums.insert(name.to_string(), sha256.to_ascii_lowercase());
+    }
+
+    Ok(checksums)
+}
+
+fn write_checksums(path: &Path, checksums: &BTreeMap<String, String>) -> anyhow::Result<()> {
+    let text = checksums
+        .iter()
+        .map(|(name, sha256)| format!("{}  {}\n", sha256, name))
+        .collect::<String>();
+    std::fs::write(path, text)
+        .map_err(|e| anyhow::anyhow!("{}に書き込めません。{}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read};
+    use std::net::TcpListener;
+
+    use sha2::{Digest, Sha256};
+
+    use super::*;
+    use crate::test_utils::temp_dir_for_test;
+
+    fn sha256_hex(bytes: &[u8]) -> String {
+        Sha256::digest(bytes)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    fn zip_for_test(name: &str, content: &[u8]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        writer
+            .start_file(name, zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(content).unwrap();
+
+        writer.finish().unwrap().into_inner()
+    }
+
+    /// パスとレスポンスボディの対応に従って応答するHTTPサーバーを起動して、URLを返す。
+    fn serve_for_test(files: BTreeMap<String, Vec<u8>>, requests: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                }
+                let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+                let (status, body) = match files.get(path) {
+                    Some(body) => ("200 OK", body.clone()),
+                    None => ("404 Not Found", Vec::new()),
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    body.len()
+                )
+                .unwrap();
+                stream.write_all(&body).unwrap();
+            }
+        });
+
+        url
+    }
+
+    #[test]
+    fn sheet_file_name_ok() {
+        assert_eq!(sheet_file_name(Sheet::Main, 2022, "csv"), "honhyo_2022.csv");
+        assert_eq!(
+            sheet_file_name(Sheet::Support, 2021, "zip"),
+            "hojuhyo_2021.zip"
+        );
+        assert_eq!(
+            sheet_file_name(Sheet::Highway, 2019, "csv"),
+            "kousokuhyo_2019.csv"
+        );
+    }
+
+    #[test]
+    fn parse_checksums_ok() {
+        let checksums =
+            parse_checksums("# コメント\nABCDEF  honhyo_2022.csv\n012345 *hojuhyo_2022.zip\n\n")
+                .unwrap();
+
+        assert_eq!(checksums.len(), 2);
+        assert_eq!(checksums["honhyo_2022.csv"], "abcdef");
+        assert_eq!(checksums["hojuhyo_2022.zip"], "012345");
+        assert!(parse_checksums("abcdef").is_err());
+    }
+
+    #[test]
+    fn extract_csv_ok() {
+        let dir = temp_dir_for_test("extract");
+        let zip_path = dir.join("honhyo_2022.zip");
+        let csv_path = dir.join("honhyo_2022.csv");
+        std::fs::write(&zip_path, zip_for_test("honhyo_2022.csv", b"a,b\n1,2\n")).unwrap();
+        extract_csv(&zip_path, &csv_path).unwrap();
+
+        let mut content = String::new();
+        std::fs::File::open(&csv_path)
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "a,b\n1,2\n");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn fetch_files_from_local_server() {
+        let main = b"main".to_vec();
+        let support = b"support".to_vec();
+        let mut files = BTreeMap::new();
+        files.insert(String::from("/2022/honhyo_2022.csv"), main.clone());
+        files.insert(
+            String::from("/2022/hojuhyo_2022.zip"),
+            zip_for_test("hojuhyo_2022.csv", &support),
+        );
+        // 本票(csv)、補充票(csv, zip)、高速票(csv, zip)
+        let base_url = serve_for_test(files, 5);
+        let dir = temp_dir_for_test("local-server");
+        let checksums = dir.join("expected");
+        std::fs::write(
+            &checksums,
+            format!("{}  honhyo_2022.csv\n", sha256_hex(&main)),
+        )
+        .unwrap();
+        let options = FetchOptions {
+            base_url,
+            cache_dir: dir.join("cache"),
+            checksums: Some(checksums),
+            force: false,
+        };
+
+        let fetched = fetch_files(2022, &options).await.unwrap();
+        assert_eq!(fetched.main_file, dir.join("cache/2022/honhyo_2022.csv"));
+        assert_eq!(
+            fetched.support_file,
+            dir.join("cache/2022/hojuhyo_2022.csv")
+        );
+        assert_eq!(fetched.highway_file, None);
+        assert_eq!(std::fs::read(&fetched.support_file).unwrap(), support);
+        let cached = read_checksums(&dir.join("cache/2022/SHA256SUMS")).unwrap();
+        assert_eq!(cached["honhyo_2022.csv"], sha256_hex(&main));
+        assert_eq!(cached["hojuhyo_2022.csv"], sha256_hex(&support));
+
+        // キャッシュを使用するため、高速票の2回を除いてサーバーにリクエストしない
+        let base_url = serve_for_test(BTreeMap::new(), 2);
+        let options = FetchOptions {
+            base_url,
+            ..options
+        };
+        assert_eq!(fetch_files(2022, &options).await.unwrap(), fetched);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,21 +1,27 @@
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
 use std::path::Path;
 
 use geo_types::Point;
-use time::macros::offset;
-use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time};
+use sha2::{Digest, Sha256};
+use time::{OffsetDateTime, Time};
 use uuid::Uuid;
 
-/// 交通事故識別子
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct RawAccidentIdentifier<'a> {
-    /// 都道府県コード
-    pub prefecture_code: &'a str,
-    /// 警察署コード
-    pub police_station_code: &'a str,
-    /// 本票番号
-    pub main_number: i32,
-}
+use code_tables::*;
+
+use crate::check::{main_row_problems, problem_to_text, support_row_problems, Problem};
+use crate::layouts::{HighwayColumns, Layout, MainColumns, SupportColumns};
+use crate::sheet::{
+    dms_to_latitude, dms_to_longitude, is_route_format, offset_datetime, open_sheet, time,
+    RawAccidentIdentifier, ShiftJisReader,
+};
+
+/// 認知機能検査経過日数の列がない本票で使用する認知機能検査経過日数
+const DEFAULT_COGNITIVE_DAYS: i32 = 9999;
+
+/// 運転練習の方法の列がない本票で使用する運転練習の方法コード（コード表に記載なし）
+const DEFAULT_DRIVING_PRACTICE_CODE: &str = "0";
 
 /// 交通事故事故
 #[derive(Debug, Clone)]
@@ -23,127 +29,127 @@ pub struct RawAccident {
     /// 事故ID
     pub id: Uuid,
     /// 都道府県コード
-    pub prefecture_code: String,
-    /// 警察署コード
-    pub police_station_code: String,
+    pub prefecture_code: PrefectureCode,
+    /// 警察署コード（都道府県コードと警察署コードを連結したコード）
+    pub police_station_code: PoliceStationCode,
     /// 本票番号
     pub main_number: i32,
     /// 事故内容
-    pub accident_detail_code: String,
+    pub accident_detail_code: AccidentDetailCode,
     /// 死者数
     pub number_of_deaths: i32,
     /// 負傷者数
     pub number_of_injuries: i32,
     /// 路線コード
-    pub route_code: String,
+    pub route_code: RouteCode,
     /// 路線区分コード
-    pub route_class_code: String,
+    pub route_class_code: RouteClassCode,
     /// 地点コード
     pub location_code: i32,
     /// 市区町村コード
-    pub city_jis_code: String,
+    pub city_jis_code: CityCode,
     /// 発生日時
     pub occurred_at: OffsetDateTime,
     /// 昼夜コード
-    pub day_night_code: String,
+    pub day_night_code: DayNightCode,
     /// 日の出時刻
     pub sunrise_time: Time,
     /// 日の入時刻
     pub sunset_time: Time,
     /// 天候コード
-    pub weather_code: String,
+    pub weather_code: WeatherCode,
     /// 地区コード
-    pub district_code: String,
+    pub district_code: DistrictCode,
     /// 路面状態コード
-    pub surface_condition_code: String,
+    pub surface_condition_code: SurfaceConditionCode,
     /// 道路形状コード
-    pub road_model_code: String,
+    pub road_model_code: RoadModelCode,
     /// 信号機コード
-    pub traffic_signal_code: String,
+    pub traffic_signal_code: TrafficSignalCode,
     /// 一時停止規制標識コード（当事者A）
-    pub stop_regulation_sign_a_code: String,
+    pub stop_regulation_sign_a_code: StopRegulationSignCode,
     /// 一時停止規制表示コード（当事者A）
-    pub stop_regulation_display_a_code: String,
+    pub stop_regulation_display_a_code: StopRegulationDisplayCode,
     /// 一時停止規制標識コード（当事者B）
-    pub stop_regulation_sign_b_code: String,
+    pub stop_regulation_sign_b_code: StopRegulationSignCode,
     /// 一時停止規制表示コード（当事者B）
-    pub stop_regulation_display_b_code: String,
+    pub stop_regulation_display_b_code: StopRegulationDisplayCode,
     /// 車道幅員コード
-    pub road_width_code: String,
+    pub road_width_code: RoadWidthCode,
     /// 道路線形コード
-    pub road_alignment_code: String,
+    pub road_alignment_code: RoadAlignmentCode,
     /// 衝突地点コード
-    pub collision_point_code: String,
+    pub collision_point_code: CollisionPointCode,
     /// ゾーン規制コード
-    pub zone_regulation_code: String,
+    pub zone_regulation_code: ZoneRegulationCode,
     /// 中央分離帯施設コード
-    pub central_separation_code: String,
+    pub central_separation_code: CentralSeparationCode,
     /// 歩車道区分コード
-    pub road_segmentation_code: String,
+    pub road_segmentation_code: RoadSegmentationCode,
     /// 事故類型コード
-    pub accident_type_code: String,
+    pub accident_type_code: AccidentTypeCode,
     /// 年齢コード（当事者A）
-    pub age_a_code: String,
+    pub age_a_code: AgeCode,
     /// 年齢コード（当事者B）
-    pub age_b_code: String,
+    pub age_b_code: AgeCode,
     /// 当事者種別コード（当事者A）
-    pub party_a_code: String,
+    pub party_a_code: PartyCode,
     /// 当事者種別コード（当事者B）
-    pub party_b_code: String,
+    pub party_b_code: PartyCode,
     /// 用途コード（当事者A）
-    pub purpose_a_code: String,
+    pub purpose_a_code: PurposeCode,
     /// 用途コード（当事者B）
-    pub purpose_b_code: String,
+    pub purpose_b_code: PurposeCode,
     /// 車両種別コード（当事者A)
-    pub vehicle_type_a_code: String,
+    pub vehicle_type_a_code: VehicleTypeCode,
     /// 車両種別コード（当事者B)
-    pub vehicle_type_b_code: String,
+    pub vehicle_type_b_code: VehicleTypeCode,
     /// オートマチック車コード（当事者A）
-    pub automatic_a_code: String,
+    pub automatic_a_code: AutomaticCode,
     /// オートマチック車コード（当事者B）
-    pub automatic_b_code: String,
+    pub automatic_b_code: AutomaticCode,
     /// サポカーコード（当事者A）
-    pub support_car_a_code: String,
+    pub support_car_a_code: SupportCarCode,
     /// サポカーコード（当事者B）
-    pub support_car_b_code: String,
+    pub support_car_b_code: SupportCarCode,
     /// 速度規制（指定のみ）コード（当事者A）
-    pub speed_regulation_a_code: String,
+    pub speed_regulation_a_code: SpeedRegulationCode,
     /// 速度規制（指定のみ）コード（当事者B）
-    pub speed_regulation_b_code: String,
+    pub speed_regulation_b_code: SpeedRegulationCode,
     /// 車両の衝突部位（当事者A）
     pub collision_part_a: String,
     /// 車両の衝突部位（当事者b）
     pub collision_part_b: String,
     /// 車両の損壊程度コード（当事者A）
-    pub vehicle_damage_a_code: String,
+    pub vehicle_damage_a_code: VehicleDamageCode,
     /// 車両の損壊程度コード（当事者B）
-    pub vehicle_damage_b_code: String,
+    pub vehicle_damage_b_code: VehicleDamageCode,
     /// エアバッグの装備コード（当事者A）
-    pub airbag_a_code: String,
+    pub airbag_a_code: AirbagCode,
     /// エアバッグの装備コード（当事者B）
-    pub airbag_b_code: String,
+    pub airbag_b_code: AirbagCode,
     /// サイドエアバッグの装備コード（当事者A）
-    pub side_airbag_a_code: String,
+    pub side_airbag_a_code: SideAirbagCode,
     /// サイドエアバッグの装備コード（当事者B）
-    pub side_airbag_b_code: String,
+    pub side_airbag_b_code: SideAirbagCode,
     /// 人身損傷程度コード（当事者A）
-    pub injury_a_code: String,
+    pub injury_a_code: InjuryCode,
     /// 人身損傷程度コード（当事者B）
-    pub injury_b_code: String,
+    pub injury_b_code: InjuryCode,
     /// 地点（JGD2011）
     pub location: Point,
     /// 曜日コード
-    pub week_code: String,
+    pub week_code: WeekCode,
     /// 祝日コード
-    pub holiday_code: String,
+    pub holiday_code: HolidayCode,
     /// 認知機能検査経過日数コード（当事者A）
     pub cognitive_days_a: i32,
     /// 認知機能検査経過日数コード（当事者B）
     pub cognitive_days_b: i32,
     /// 運転練習の方法コード（当事者A）
-    pub driving_practice_a_code: String,
+    pub driving_practice_a_code: DrivingPracticeCode,
     /// 運転練習の方法コード（当事者B）
-    pub driving_practice_b_code: String,
+    pub driving_practice_b_code: DrivingPracticeCode,
 }
 
 impl RawAccident {
@@ -154,8 +160,8 @@ impl RawAccident {
     /// 交通事故識別子
     pub fn identifier(&self) -> RawAccidentIdentifier {
         RawAccidentIdentifier {
-            prefecture_code: &self.prefecture_code,
-            police_station_code: &self.police_station_code,
+            prefecture_code: self.prefecture_code.to_string(),
+            police_station_code: self.police_station_code.station_code().to_string(),
             main_number: self.main_number,
         }
     }
@@ -171,27 +177,353 @@ pub struct RawInvolvedPerson {
     /// 補充票番号
     pub sub_number: i32,
     /// 当事者種別コード
-    pub party_code: String,
+    pub party_code: PartyCode,
     /// 用途別コード
-    pub purpose_code: Option<String>,
+    pub purpose_code: Option<PurposeCode>,
     /// 車両形状等コード
-    pub vehicle_type_code: Option<String>,
+    pub vehicle_type_code: Option<VehicleTypeCode>,
     /// 乗車別コード
-    pub riding_type_code: String,
+    pub riding_type_code: RidingTypeCode,
     /// 乗車等区分コード
-    pub riding_class_code: String,
+    pub riding_class_code: RidingClassCode,
     /// サポカーコード
-    pub support_car_code: String,
+    pub support_car_code: SupportCarCode,
     /// エアバッグの装備コード
-    pub airbag_code: String,
+    pub airbag_code: AirbagCode,
     /// サイドエアバッグの装備コード
-    pub side_airbag_code: String,
+    pub side_airbag_code: SideAirbagCode,
     /// 人身損傷程度コード
-    pub injury_code: String,
+    pub injury_code: InjuryCode,
     /// 車両の衝突部位コード
     pub collision_part: Option<String>,
     /// 車両の損壊程度
-    pub vehicle_damage_code: Option<String>,
+    pub vehicle_damage_code: Option<VehicleDamageCode>,
+}
+
+/// 高速道路で発生した交通事故の詳細（高速票）
+#[derive(Debug, Clone)]
+pub struct RawHighwayRecord {
+    /// 高速票ID
+    pub id: Uuid,
+    /// 事故ID
+    pub accident_id: Uuid,
+    /// 上下線コード
+    pub up_down_code: UpDownCode,
+    /// キロポスト（キロメートル）
+    pub kilopost_km: i32,
+    /// キロポスト（メートル）
+    pub kilopost_m: i32,
+    /// 道路構造コード
+    pub road_structure_code: RoadStructureCode,
+    /// 車線コード（当事者A）
+    pub lane_a_code: LaneCode,
+    /// 車線コード（当事者B）
+    pub lane_b_code: LaneCode,
+}
+
+/// 票の行を読み込めなかった、または変換できなかったことを示すエラー
+#[derive(Debug)]
+pub struct RowError {
+    /// 行のインデックス
+    pub row_index: usize,
+    /// CSV形式の行（行を読み込めなかった場合は`None`）
+    pub line: Option<String>,
+    /// エラー
+    pub error: anyhow::Error,
+}
+
+impl std::fmt::Display for RowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl std::error::Error for RowError {}
+
+impl RowError {
+    fn new(row_index: usize, row: &csv::StringRecord, error: anyhow::Error) -> Self {
+        Self {
+            row_index,
+            line: Some(record_to_line(row)),
+            error,
+        }
+    }
+}
+
+/// 行をCSV形式の文字列に変換する。
+fn record_to_line(row: &csv::StringRecord) -> String {
+    let mut writer = csv::WriterBuilder::new()
+        .terminator(csv::Terminator::Any(b'\n'))
+        .from_writer(Vec::new());
+    let line = writer
+        .write_record(row)
+        .ok()
+        .and_then(|_| writer.into_inner().ok())
+        .map(|line| String::from_utf8_lossy(&line).trim_end().to_string());
+
+    line.unwrap_or_else(|| row.iter().collect::<Vec<_>>().join(","))
+}
+
+/// CSVファイルから読み込んだ行を返す。
+///
+/// # 引数
+///
+/// * `records` - CSVファイルの行を返すイテレーター
+/// * `row_index` - 次に読み込む行のインデックス
+///
+/// # 戻り値
+///
+/// 行のインデックスと行、ファイルの終端に達した場合は`None`
+fn next_record<R: Read>(
+    records: &mut csv::StringRecordsIntoIter<R>,
+    row_index: &mut usize,
+) -> Option<Result<(usize, csv::StringRecord), RowError>> {
+    let row = records.next()?;
+    let index = *row_index;
+    *row_index += 1;
+
+    Some(row.map(|row| (index, row)).map_err(|e| RowError {
+        row_index: index,
+        line: None,
+        error: anyhow::anyhow!("{}行目: 行を読み込めません。{}", index + 1, e),
+    }))
+}
+
+/// コード表で検査して見つかった問題を、1つのエラーにまとめる。
+fn problems_to_error(problems: Vec<Problem>) -> Option<anyhow::Error> {
+    if problems.is_empty() {
+        return None;
+    }
+    let messages = problems
+        .iter()
+        .map(problem_to_text)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Some(anyhow::anyhow!("{}", messages))
+}
+
+/// 本票を1行ずつ読み込み、交通事故を返すイテレーター
+pub struct AccidentReader<'a> {
+    /// 本票の行を返すイテレーター
+    records: csv::StringRecordsIntoIter<ShiftJisReader<File>>,
+    /// 本票のヘッダ行
+    headers: csv::StringRecord,
+    /// 本票の列の位置
+    columns: MainColumns,
+    /// 本票の都道府県コードとJIS規格の都道府県コードの対応を記録したハッシュマップ
+    prefectures: &'a HashMap<String, String>,
+    /// 行を検査するコード表（指定しない場合は検査しない）
+    code_tables: Option<&'a CodeTables>,
+    /// 次に読み込む行のインデックス
+    row_index: usize,
+}
+
+impl<'a> AccidentReader<'a> {
+    /// 本票を開く。
+    ///
+    /// # 引数
+    ///
+    /// * `path` - 本票のファイルパス
+    /// * `prefectures` - 本票の都道府県コードとJIS規格の都道府県コードの対応を記録したハッシュマップ
+    /// * `layout` - 本票のレイアウト（指定しない場合はヘッダ行から列の位置を決定）
+    ///
+    /// # 戻り値
+    ///
+    /// 交通事故を返すイテレーター
+    pub fn open<P: AsRef<Path>>(
+        path: P,
+        prefectures: &'a HashMap<String, String>,
+        layout: Option<Layout>,
+    ) -> anyhow::Result<Self> {
+        let mut reader = open_sheet(path)?;
+        let headers = reader.headers()?.clone();
+        let columns = MainColumns::resolve(layout, &headers)?;
+
+        Ok(Self {
+            records: reader.into_records(),
+            headers,
+            columns,
+            prefectures,
+            code_tables: None,
+            row_index: 0,
+        })
+    }
+
+    /// 交通事故に変換する前に、行をコード表で検査する。
+    ///
+    /// # 引数
+    ///
+    /// * `code_tables` - コード表
+    ///
+    /// # 戻り値
+    ///
+    /// 交通事故を返すイテレーター
+    pub fn validate(mut self, code_tables: &'a CodeTables) -> Self {
+        self.code_tables = Some(code_tables);
+        self
+    }
+}
+
+impl<'a> Iterator for AccidentReader<'a> {
+    type Item = Result<RawAccident, RowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = next_record(&mut self.records, &mut self.row_index)?;
+
+        Some(record.and_then(|(row_index, row)| {
+            if let Some(code_tables) = self.code_tables {
+                let problems =
+                    main_row_problems(&row, row_index, &self.headers, &self.columns, code_tables);
+                if let Some(e) = problems_to_error(problems) {
+                    return Err(RowError::new(row_index, &row, e));
+                }
+            }
+            row_to_accident(&row, row_index, &self.columns, self.prefectures)
+                .map_err(|e| RowError::new(row_index, &row, e))
+        }))
+    }
+}
+
+/// 補充票を1行ずつ読み込み、交通事故当事者以外の関与者を返すイテレーター
+pub struct InvolvedPersonReader<'a> {
+    /// 補充票の行を返すイテレーター
+    records: csv::StringRecordsIntoIter<ShiftJisReader<File>>,
+    /// 補充票のヘッダ行
+    headers: csv::StringRecord,
+    /// 補充票の列の位置
+    columns: SupportColumns,
+    /// 交通事故識別子と交通事故IDの対応を記録したハッシュマップ
+    accident_ids: &'a HashMap<RawAccidentIdentifier, Uuid>,
+    /// 行を検査するコード表（指定しない場合は検査しない）
+    code_tables: Option<&'a CodeTables>,
+    /// 次に読み込む行のインデックス
+    row_index: usize,
+}
+
+impl<'a> InvolvedPersonReader<'a> {
+    /// 補充票を開く。
+    ///
+    /// # 引数
+    ///
+    /// * `path` - 補充票のファイルパス
+    /// * `accident_ids` - 交通事故識別子と交通事故IDの対応を記録したハッシュマップ
+    /// * `layout` - 補充票のレイアウト（指定しない場合はヘッダ行から列の位置を決定）
+    ///
+    /// # 戻り値
+    ///
+    /// 交通事故当事者以外の関与者を返すイテレーター
+    pub fn open<P: AsRef<Path>>(
+        path: P,
+        accident_ids: &'a HashMap<RawAccidentIdentifier, Uuid>,
+        layout: Option<Layout>,
+    ) -> anyhow::Result<Self> {
+        let mut reader = open_sheet(path)?;
+        let headers = reader.headers()?.clone();
+        let columns = SupportColumns::resolve(layout, &headers)?;
+
+        Ok(Self {
+            records: reader.into_records(),
+            headers,
+            columns,
+            accident_ids,
+            code_tables: None,
+            row_index: 0,
+        })
+    }
+
+    /// 交通事故当事者以外の関与者に変換する前に、行をコード表で検査する。
+    ///
+    /// # 引数
+    ///
+    /// * `code_tables` - コード表
+    ///
+    /// # 戻り値
+    ///
+    /// 交通事故当事者以外の関与者を返すイテレーター
+    pub fn validate(mut self, code_tables: &'a CodeTables) -> Self {
+        self.code_tables = Some(code_tables);
+        self
+    }
+}
+
+impl<'a> Iterator for InvolvedPersonReader<'a> {
+    type Item = Result<RawInvolvedPerson, RowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = next_record(&mut self.records, &mut self.row_index)?;
+
+        Some(record.and_then(|(row_index, row)| {
+            if let Some(code_tables) = self.code_tables {
+                let problems = support_row_problems(
+                    &row,
+                    row_index,
+                    &self.headers,
+                    &self.columns,
+                    code_tables,
+                );
+                if let Some(e) = problems_to_error(problems) {
+                    return Err(RowError::new(row_index, &row, e));
+                }
+            }
+            row_to_involved_person(&row, row_index, &self.columns, self.accident_ids)
+                .map_err(|e| RowError::new(row_index, &row, e))
+        }))
+    }
+}
+
+/// 高速票を1行ずつ読み込み、高速道路で発生した交通事故の詳細を返すイテレーター
+pub struct HighwayRecordReader<'a> {
+    /// 高速票の行を返すイテレーター
+    records: csv::StringRecordsIntoIter<ShiftJisReader<File>>,
+    /// 高速票の列の位置
+    columns: HighwayColumns,
+    /// 交通事故識別子と交通事故IDの対応を記録したハッシュマップ
+    accident_ids: &'a HashMap<RawAccidentIdentifier, Uuid>,
+    /// 次に読み込む行のインデックス
+    row_index: usize,
+}
+
+impl<'a> HighwayRecordReader<'a> {
+    /// 高速票を開く。
+    ///
+    /// # 引数
+    ///
+    /// * `path` - 高速票のファイルパス
+    /// * `accident_ids` - 交通事故識別子と交通事故IDの対応を記録したハッシュマップ
+    /// * `layout` - 高速票のレイアウト（指定しない場合はヘッダ行から列の位置を決定）
+    ///
+    /// # 戻り値
+    ///
+    /// 高速道路で発生した交通事故の詳細を返すイテレーター
+    pub fn open<P: AsRef<Path>>(
+        path: P,
+        accident_ids: &'a HashMap<RawAccidentIdentifier, Uuid>,
+        layout: Option<Layout>,
+    ) -> anyhow::Result<Self> {
+        let mut reader = open_sheet(path)?;
+        let columns = HighwayColumns::resolve(layout, reader.headers()?)?;
+
+        Ok(Self {
+            records: reader.into_records(),
+            columns,
+            accident_ids,
+            row_index: 0,
+        })
+    }
+}
+
+impl<'a> Iterator for HighwayRecordReader<'a> {
+    type Item = Result<RawHighwayRecord, RowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = next_record(&mut self.records, &mut self.row_index)?;
+
+        Some(record.and_then(|(row_index, row)| {
+            row_to_highway_record(&row, row_index, &self.columns, self.accident_ids)
+                .map_err(|e| RowError::new(row_index, &row, e))
+        }))
+    }
 }
 
 /// 本票を読み込み、交通事故を返す。
@@ -199,7 +531,8 @@ pub struct RawInvolvedPerson {
 /// # 引数
 ///
 /// * `path` - 本票のファイルパス
-/// * `cities` - 本票の都道府県コードとJIS規格の都道府県コードの対応を記録したハッシュマップ
+/// * `prefectures` - 本票の都道府県コードとJIS規格の都道府県コードの対応を記録したハッシュマップ
+/// * `layout` - 本票のレイアウト（指定しない場合はヘッダ行から列の位置を決定）
 ///
 /// # 戻り値
 ///
@@ -207,21 +540,9 @@ pub struct RawInvolvedPerson {
 pub fn read_accidents<P: AsRef<Path>>(
     path: P,
     prefectures: &HashMap<String, String>,
+    layout: Option<Layout>,
 ) -> anyhow::Result<Vec<RawAccident>> {
-    let file = std::fs::read(path)?;
-    let (reader, _, _) = encoding_rs::SHIFT_JIS.decode(&file);
-    let mut reader = csv::ReaderBuilder::new()
-        .has_headers(true)
-        .trim(csv::Trim::All)
-        .from_reader(reader.as_bytes());
-
-    let mut accidents = Vec::new();
-    for (row_index, row) in reader.records().enumerate() {
-        let row = row.unwrap();
-        accidents.push(row_to_accident(&row, row_index, prefectures)?);
-    }
-
-    Ok(accidents)
+    Ok(AccidentReader::open(path, prefectures, layout)?.collect::<Result<_, _>>()?)
 }
 
 /// 補充票を読み込み、交通事故当事者以外の関与者を返す。
@@ -230,6 +551,7 @@ pub fn read_accidents<P: AsRef<Path>>(
 ///
 /// * `path` - 補充票のファイルパス
 /// * `accident_ids` - 交通事故識別子と交通事故IDの対応を記録したハッシュマップ
+/// * `layout` - 補充票のレイアウト（指定しない場合はヘッダ行から列の位置を決定）
 ///
 /// # 戻り値
 ///
@@ -237,30 +559,100 @@ pub fn read_accidents<P: AsRef<Path>>(
 pub fn read_involved_persons<P: AsRef<Path>>(
     path: P,
     accident_ids: &HashMap<RawAccidentIdentifier, Uuid>,
+    layout: Option<Layout>,
 ) -> anyhow::Result<Vec<RawInvolvedPerson>> {
-    let file = std::fs::read(path)?;
-    let (reader, _, _) = encoding_rs::SHIFT_JIS.decode(&file);
-    let mut reader = csv::ReaderBuilder::new()
-        .has_headers(true)
-        .trim(csv::Trim::All)
-        .from_reader(reader.as_bytes());
-
-    let mut involved_persons = Vec::new();
-    for (row_index, row) in reader.records().enumerate() {
-        let row = row.unwrap();
-        involved_persons.push(row_to_involved_person(&row, row_index, accident_ids)?);
+    Ok(InvolvedPersonReader::open(path, accident_ids, layout)?.collect::<Result<_, _>>()?)
+}
+
+/// 高速票を読み込み、高速道路で発生した交通事故の詳細を返す。
+///
+/// # 引数
+///
+/// * `path` - 高速票のファイルパス
+/// * `accident_ids` - 交通事故識別子と交通事故IDの対応を記録したハッシュマップ
+/// * `layout` - 高速票のレイアウト（指定しない場合はヘッダ行から列の位置を決定）
+///
+/// # 戻り値
+///
+/// 高速道路で発生した交通事故の詳細を格納したベクタ
+pub fn read_highway_records<P: AsRef<Path>>(
+    path: P,
+    accident_ids: &HashMap<RawAccidentIdentifier, Uuid>,
+    layout: Option<Layout>,
+) -> anyhow::Result<Vec<RawHighwayRecord>> {
+    Ok(HighwayRecordReader::open(path, accident_ids, layout)?.collect::<Result<_, _>>()?)
+}
+
+/// ファイルのSHA-256ハッシュを16進数文字列で返す。
+///
+/// # 引数
+///
+/// * `path` - ファイルパス
+///
+/// # 戻り値
+///
+/// SHA-256ハッシュの16進数文字列
+pub fn file_sha256<P: AsRef<Path>>(path: P) -> anyhow::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 8192];
+    loop {
+        let size = file.read(&mut buffer)?;
+        if size == 0 {
+            break;
+        }
+        hasher.update(&buffer[..size]);
+    }
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+/// 交通事故の発生日時から、データセットの年を推定するカウンター
+///
+/// 交通事故が最も多く発生した年をデータセットの年とする。
+#[derive(Debug, Clone, Default)]
+pub struct YearCounter {
+    /// 年ごとの交通事故の件数
+    years: HashMap<i32, usize>,
+}
+
+impl YearCounter {
+    /// 交通事故の発生した年を数える。
+    ///
+    /// # 引数
+    ///
+    /// * `accidents` - 交通事故を格納したスライス
+    pub fn add(&mut self, accidents: &[RawAccident]) {
+        for accident in accidents {
+            *self.years.entry(accident.occurred_at.year()).or_insert(0) += 1;
+        }
     }
 
-    Ok(involved_persons)
+    /// 交通事故が最も多く発生した年を返す。
+    ///
+    /// # 戻り値
+    ///
+    /// データセットの年、交通事故がない場合は`None`
+    pub fn most_frequent(&self) -> Option<i32> {
+        self.years
+            .iter()
+            .max_by_key(|(year, count)| (**count, **year))
+            .map(|(year, _)| *year)
+    }
 }
 
 fn row_to_accident(
     row: &csv::StringRecord,
     row_index: usize,
+    columns: &MainColumns,
     prefectures: &HashMap<String, String>,
 ) -> anyhow::Result<RawAccident> {
-    let prefecture_code = read_str_column(row, row_index, 1)?;
-    let city_code = read_str_column(row, row_index, 9)?;
+    let prefecture_code = read_str_column(row, row_index, columns.prefecture_code)?;
+    let city_code = read_str_column(row, row_index, columns.city_code)?;
     let prefecture_jis_code = prefectures
         .get(&prefecture_code)
         .ok_or(anyhow::anyhow!(
@@ -270,85 +662,134 @@ fn row_to_accident(
         ))?
         .to_string();
     let city_jis_code = format!("{}{}", prefecture_jis_code, city_code);
-    let route = read_str_column(row, row_index, 7)?;
+    let route = read_str_column(row, row_index, columns.route_code)?;
+    if !is_route_format(&route) {
+        return Err(anyhow::anyhow!(
+            "{}行目 {}列: 路線コード({})の形式が不正です。",
+            row_index + 1,
+            columns.route_code + 1,
+            route
+        ));
+    }
+
+    let prefecture_code = to_code(&prefecture_code, row_index, columns.prefecture_code)?;
+    let police_station_code = read_str_column(row, row_index, columns.police_station_code)?;
+    let police_station_code = PoliceStationCode::new(&prefecture_code, &police_station_code)
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "{}行目 {}列: {}",
+                row_index + 1,
+                columns.police_station_code + 1,
+                e
+            )
+        })?;
 
     Ok(RawAccident {
         id: uuid::Uuid::new_v4(),
         prefecture_code,
-        police_station_code: read_str_column(row, row_index, 2)?,
-        main_number: read_i32_column(row, row_index, 3)?,
-        accident_detail_code: read_str_column(row, row_index, 4)?,
-        number_of_deaths: read_i32_column(row, row_index, 5)?,
-        number_of_injuries: read_i32_column(row, row_index, 6)?,
-        route_code: route[0..4].to_string(),
-        route_class_code: route[4..5].to_string(),
-        location_code: read_i32_column(row, row_index, 8)?,
-        city_jis_code,
-        occurred_at: read_datetime_columns(row, row_index, 10)?,
-        day_night_code: read_str_column(row, row_index, 15)?,
-        sunrise_time: read_time_columns(row, row_index, 16)?,
-        sunset_time: read_time_columns(row, row_index, 18)?,
-        weather_code: read_str_column(row, row_index, 20)?,
-        district_code: read_str_column(row, row_index, 21)?,
-        surface_condition_code: read_str_column(row, row_index, 22)?,
-        road_model_code: read_str_column(row, row_index, 23)?,
-        traffic_signal_code: read_str_column(row, row_index, 24)?,
-        stop_regulation_sign_a_code: read_str_column(row, row_index, 25)?,
-        stop_regulation_display_a_code: read_str_column(row, row_index, 26)?,
-        stop_regulation_sign_b_code: read_str_column(row, row_index, 27)?,
-        stop_regulation_display_b_code: read_str_column(row, row_index, 28)?,
-        road_width_code: read_str_column(row, row_index, 29)?,
-        road_alignment_code: read_str_column(row, row_index, 30)?,
-        collision_point_code: read_str_column(row, row_index, 31)?,
-        zone_regulation_code: read_str_column(row, row_index, 32)?,
-        central_separation_code: read_str_column(row, row_index, 33)?,
-        road_segmentation_code: read_str_column(row, row_index, 34)?,
-        accident_type_code: read_str_column(row, row_index, 35)?,
-        age_a_code: read_str_column(row, row_index, 36)?,
-        age_b_code: read_str_column(row, row_index, 37)?,
-        party_a_code: read_str_column(row, row_index, 38)?,
-        party_b_code: read_str_column(row, row_index, 39)?,
-        purpose_a_code: read_str_column(row, row_index, 40)?,
-        purpose_b_code: read_str_column(row, row_index, 41)?,
-        vehicle_type_a_code: read_str_column(row, row_index, 42)?,
-        vehicle_type_b_code: read_str_column(row, row_index, 43)?,
-        automatic_a_code: read_str_column(row, row_index, 44)?,
-        automatic_b_code: read_str_column(row, row_index, 45)?,
-        support_car_a_code: read_str_column(row, row_index, 46)?,
-        support_car_b_code: read_str_column(row, row_index, 47)?,
-        speed_regulation_a_code: read_str_column(row, row_index, 48)?,
-        speed_regulation_b_code: read_str_column(row, row_index, 49)?,
-        collision_part_a: read_str_column(row, row_index, 50)?,
-        collision_part_b: read_str_column(row, row_index, 51)?,
-        vehicle_damage_a_code: read_str_column(row, row_index, 52)?,
-        vehicle_damage_b_code: read_str_column(row, row_index, 53)?,
-        airbag_a_code: read_str_column(row, row_index, 54)?,
-        airbag_b_code: read_str_column(row, row_index, 55)?,
-        side_airbag_a_code: read_str_column(row, row_index, 56)?,
-        side_airbag_b_code: read_str_column(row, row_index, 57)?,
-        injury_a_code: read_str_column(row, row_index, 58)?,
-        injury_b_code: read_str_column(row, row_index, 59)?,
-        location: read_point_column(row, row_index, 60)?,
-        week_code: read_str_column(row, row_index, 62)?,
-        holiday_code: read_str_column(row, row_index, 63)?,
-        cognitive_days_a: read_i32_column(row, row_index, 64)?,
-        cognitive_days_b: read_i32_column(row, row_index, 65)?,
-        driving_practice_a_code: read_str_column(row, row_index, 66)?,
-        driving_practice_b_code: read_str_column(row, row_index, 67)?,
+        police_station_code,
+        main_number: read_i32_column(row, row_index, columns.main_number)?,
+        accident_detail_code: read_code_column(row, row_index, columns.accident_detail_code)?,
+        number_of_deaths: read_i32_column(row, row_index, columns.number_of_deaths)?,
+        number_of_injuries: read_i32_column(row, row_index, columns.number_of_injuries)?,
+        route_code: to_code(&route[0..4], row_index, columns.route_code)?,
+        route_class_code: to_code(&route[4..5], row_index, columns.route_code)?,
+        location_code: read_i32_column(row, row_index, columns.location_code)?,
+        city_jis_code: to_code(&city_jis_code, row_index, columns.city_code)?,
+        occurred_at: read_datetime_columns(row, row_index, columns.occurred_at)?,
+        day_night_code: read_code_column(row, row_index, columns.day_night_code)?,
+        sunrise_time: read_time_columns(row, row_index, columns.sunrise_time)?,
+        sunset_time: read_time_columns(row, row_index, columns.sunset_time)?,
+        weather_code: read_code_column(row, row_index, columns.weather_code)?,
+        district_code: read_code_column(row, row_index, columns.district_code)?,
+        surface_condition_code: read_code_column(row, row_index, columns.surface_condition_code)?,
+        road_model_code: read_code_column(row, row_index, columns.road_model_code)?,
+        traffic_signal_code: read_code_column(row, row_index, columns.traffic_signal_code)?,
+        stop_regulation_sign_a_code: read_code_column(
+            row,
+            row_index,
+            columns.stop_regulation_sign_a_code,
+        )?,
+        stop_regulation_display_a_code: read_code_column(
+            row,
+            row_index,
+            columns.stop_regulation_display_a_code,
+        )?,
+        stop_regulation_sign_b_code: read_code_column(
+            row,
+            row_index,
+            columns.stop_regulation_sign_b_code,
+        )?,
+        stop_regulation_display_b_code: read_code_column(
+            row,
+            row_index,
+            columns.stop_regulation_display_b_code,
+        )?,
+        road_width_code: read_code_column(row, row_index, columns.road_width_code)?,
+        road_alignment_code: read_code_column(row, row_index, columns.road_alignment_code)?,
+        collision_point_code: read_code_column(row, row_index, columns.collision_point_code)?,
+        zone_regulation_code: read_code_column(row, row_index, columns.zone_regulation_code)?,
+        central_separation_code: read_code_column(row, row_index, columns.central_separation_code)?,
+        road_segmentation_code: read_code_column(row, row_index, columns.road_segmentation_code)?,
+        accident_type_code: read_code_column(row, row_index, columns.accident_type_code)?,
+        age_a_code: read_code_column(row, row_index, columns.age_a_code)?,
+        age_b_code: read_code_column(row, row_index, columns.age_b_code)?,
+        party_a_code: read_code_column(row, row_index, columns.party_a_code)?,
+        party_b_code: read_code_column(row, row_index, columns.party_b_code)?,
+        purpose_a_code: read_code_column(row, row_index, columns.purpose_a_code)?,
+        purpose_b_code: read_code_column(row, row_index, columns.purpose_b_code)?,
+        vehicle_type_a_code: read_code_column(row, row_index, columns.vehicle_type_a_code)?,
+        vehicle_type_b_code: read_code_column(row, row_index, columns.vehicle_type_b_code)?,
+        automatic_a_code: read_code_column(row, row_index, columns.automatic_a_code)?,
+        automatic_b_code: read_code_column(row, row_index, columns.automatic_b_code)?,
+        support_car_a_code: read_code_column(row, row_index, columns.support_car_a_code)?,
+        support_car_b_code: read_code_column(row, row_index, columns.support_car_b_code)?,
+        speed_regulation_a_code: read_code_column(row, row_index, columns.speed_regulation_a_code)?,
+        speed_regulation_b_code: read_code_column(row, row_index, columns.speed_regulation_b_code)?,
+        collision_part_a: read_str_column(row, row_index, columns.collision_part_a)?,
+        collision_part_b: read_str_column(row, row_index, columns.collision_part_b)?,
+        vehicle_damage_a_code: read_code_column(row, row_index, columns.vehicle_damage_a_code)?,
+        vehicle_damage_b_code: read_code_column(row, row_index, columns.vehicle_damage_b_code)?,
+        airbag_a_code: read_code_column(row, row_index, columns.airbag_a_code)?,
+        airbag_b_code: read_code_column(row, row_index, columns.airbag_b_code)?,
+        side_airbag_a_code: read_code_column(row, row_index, columns.side_airbag_a_code)?,
+        side_airbag_b_code: read_code_column(row, row_index, columns.side_airbag_b_code)?,
+        injury_a_code: read_code_column(row, row_index, columns.injury_a_code)?,
+        injury_b_code: read_code_column(row, row_index, columns.injury_b_code)?,
+        location: read_point_column(row, row_index, columns.latitude, columns.longitude)?,
+        week_code: read_code_column(row, row_index, columns.week_code)?,
+        holiday_code: read_code_column(row, row_index, columns.holiday_code)?,
+        cognitive_days_a: match columns.cognitive_days_a {
+            Some(column_index) => read_i32_column(row, row_index, column_index)?,
+            None => DEFAULT_COGNITIVE_DAYS,
+        },
+        cognitive_days_b: match columns.cognitive_days_b {
+            Some(column_index) => read_i32_column(row, row_index, column_index)?,
+            None => DEFAULT_COGNITIVE_DAYS,
+        },
+        driving_practice_a_code: match columns.driving_practice_a_code {
+            Some(column_index) => read_code_column(row, row_index, column_index)?,
+            None => DrivingPracticeCode::try_from(DEFAULT_DRIVING_PRACTICE_CODE)?,
+        },
+        driving_practice_b_code: match columns.driving_practice_b_code {
+            Some(column_index) => read_code_column(row, row_index, column_index)?,
+            None => DrivingPracticeCode::try_from(DEFAULT_DRIVING_PRACTICE_CODE)?,
+        },
     })
 }
 
 fn row_to_involved_person(
     row: &csv::StringRecord,
     row_index: usize,
+    columns: &SupportColumns,
     accident_ids: &HashMap<RawAccidentIdentifier, Uuid>,
 ) -> anyhow::Result<RawInvolvedPerson> {
-    let prefecture_code = read_str_column(row, row_index, 1)?;
-    let police_station_code = read_str_column(row, row_index, 2)?;
-    let main_number = read_i32_column(row, row_index, 3)?;
+    let prefecture_code = read_str_column(row, row_index, columns.prefecture_code)?;
+    let police_station_code = read_str_column(row, row_index, columns.police_station_code)?;
+    let main_number = read_i32_column(row, row_index, columns.main_number)?;
     let identifier = RawAccidentIdentifier {
-        prefecture_code: &prefecture_code,
-        police_station_code: &police_station_code,
+        prefecture_code,
+        police_station_code,
         main_number,
     };
 
@@ -361,18 +802,55 @@ fn row_to_involved_person(
     Ok(RawInvolvedPerson {
         id: Uuid::new_v4(),
         accident_id: *accident_id,
-        sub_number: read_i32_column(row, row_index, 4)?,
-        party_code: read_str_column(row, row_index, 5)?,
-        purpose_code: read_optional_str_column(row, row_index, 6)?,
-        vehicle_type_code: read_optional_str_column(row, row_index, 7)?,
-        riding_type_code: read_str_column(row, row_index, 8)?,
-        riding_class_code: read_str_column(row, row_index, 9)?,
-        support_car_code: read_str_column(row, row_index, 10)?,
-        airbag_code: read_str_column(row, row_index, 11)?,
-        side_airbag_code: read_str_column(row, row_index, 12)?,
-        injury_code: read_str_column(row, row_index, 13)?,
-        collision_part: read_optional_str_column(row, row_index, 14)?,
-        vehicle_damage_code: read_optional_str_column(row, row_index, 15)?,
+        sub_number: read_i32_column(row, row_index, columns.sub_number)?,
+        party_code: read_code_column(row, row_index, columns.party_code)?,
+        purpose_code: read_optional_code_column(row, row_index, columns.purpose_code)?,
+        vehicle_type_code: read_optional_code_column(row, row_index, columns.vehicle_type_code)?,
+        riding_type_code: read_code_column(row, row_index, columns.riding_type_code)?,
+        riding_class_code: read_code_column(row, row_index, columns.riding_class_code)?,
+        support_car_code: read_code_column(row, row_index, columns.support_car_code)?,
+        airbag_code: read_code_column(row, row_index, columns.airbag_code)?,
+        side_airbag_code: read_code_column(row, row_index, columns.side_airbag_code)?,
+        injury_code: read_code_column(row, row_index, columns.injury_code)?,
+        collision_part: read_optional_str_column(row, row_index, columns.collision_part)?,
+        vehicle_damage_code: read_optional_code_column(
+            row,
+            row_index,
+            columns.vehicle_damage_code,
+        )?,
+    })
+}
+
+fn row_to_highway_record(
+    row: &csv::StringRecord,
+    row_index: usize,
+    columns: &HighwayColumns,
+    accident_ids: &HashMap<RawAccidentIdentifier, Uuid>,
+) -> anyhow::Result<RawHighwayRecord> {
+    let prefecture_code = read_str_column(row, row_index, columns.prefecture_code)?;
+    let police_station_code = read_str_column(row, row_index, columns.police_station_code)?;
+    let main_number = read_i32_column(row, row_index, columns.main_number)?;
+    let identifier = RawAccidentIdentifier {
+        prefecture_code,
+        police_station_code,
+        main_number,
+    };
+
+    let accident_id = accident_ids.get(&identifier).ok_or(anyhow::anyhow!(
+        "{}行目: 交通事故{:?}が見つかりません。",
+        row_index + 1,
+        identifier,
+    ))?;
+
+    Ok(RawHighwayRecord {
+        id: Uuid::new_v4(),
+        accident_id: *accident_id,
+        up_down_code: read_code_column(row, row_index, columns.up_down_code)?,
+        kilopost_km: read_i32_column(row, row_index, columns.kilopost_km)?,
+        kilopost_m: read_i32_column(row, row_index, columns.kilopost_m)?,
+        road_structure_code: read_code_column(row, row_index, columns.road_structure_code)?,
+        lane_a_code: read_code_column(row, row_index, columns.lane_a_code)?,
+        lane_b_code: read_code_column(row, row_index, columns.lane_b_code)?,
     })
 }
 
@@ -407,6 +885,40 @@ fn read_optional_str_column(
     }
 }
 
+fn read_code_column<T>(
+    row: &csv::StringRecord,
+    row_index: usize,
+    column_index: usize,
+) -> anyhow::Result<T>
+where
+    T: TryFrom<String, Error = CodeError>,
+{
+    let value = read_str_column(row, row_index, column_index)?;
+
+    to_code(&value, row_index, column_index)
+}
+
+fn read_optional_code_column<T>(
+    row: &csv::StringRecord,
+    row_index: usize,
+    column_index: usize,
+) -> anyhow::Result<Option<T>>
+where
+    T: TryFrom<String, Error = CodeError>,
+{
+    read_optional_str_column(row, row_index, column_index)?
+        .map(|value| to_code(&value, row_index, column_index))
+        .transpose()
+}
+
+fn to_code<T>(value: &str, row_index: usize, column_index: usize) -> anyhow::Result<T>
+where
+    T: TryFrom<String, Error = CodeError>,
+{
+    T::try_from(value.to_string())
+        .map_err(|e| anyhow::anyhow!("{}行目 {}列: {}", row_index + 1, column_index + 1, e))
+}
+
 fn read_i32_column(
     row: &csv::StringRecord,
     row_index: usize,
@@ -429,153 +941,70 @@ fn read_i32_column(
 fn read_datetime_columns(
     row: &csv::StringRecord,
     row_index: usize,
-    column_index: usize,
+    column_indexes: [usize; 5],
 ) -> anyhow::Result<OffsetDateTime> {
-    let year = read_i32_column(row, row_index, column_index)?;
-    let month = read_i32_column(row, row_index, column_index + 1)? as u8;
-    let day = read_i32_column(row, row_index, column_index + 2)? as u8;
-    let hour = read_i32_column(row, row_index, column_index + 3)? as u8;
-    let minute = read_i32_column(row, row_index, column_index + 4)? as u8;
+    let year = read_i32_column(row, row_index, column_indexes[0])?;
+    let month = read_i32_column(row, row_index, column_indexes[1])? as u8;
+    let day = read_i32_column(row, row_index, column_indexes[2])? as u8;
+    let hour = read_i32_column(row, row_index, column_indexes[3])? as u8;
+    let minute = read_i32_column(row, row_index, column_indexes[4])? as u8;
 
     offset_datetime(year, month, day, hour, minute)
-        .map_err(|e| anyhow::anyhow!("{}行目 {}列: {}", row_index + 1, column_index + 1, e))
-}
-
-fn offset_datetime(
-    year: i32,
-    month: u8,
-    day: u8,
-    hour: u8,
-    minute: u8,
-) -> anyhow::Result<OffsetDateTime> {
-    let month =
-        Month::try_from(month).map_err(|_| anyhow::anyhow!("月({})が範囲外です。", month))?;
-    let date = Date::from_calendar_date(year, month as time::Month, day)?;
-    let time = Time::from_hms(hour, minute, 0u8)
-        .map_err(|_| anyhow::anyhow!("時刻({}:{})が範囲外です。", hour, minute))?;
-    let datetime = PrimitiveDateTime::new(date, time);
-
-    Ok(datetime.assume_offset(offset!(+9)))
+        .map_err(|e| anyhow::anyhow!("{}行目 {}列: {}", row_index + 1, column_indexes[0] + 1, e))
 }
 
 fn read_time_columns(
     row: &csv::StringRecord,
     row_index: usize,
-    column_index: usize,
+    column_indexes: [usize; 2],
 ) -> anyhow::Result<Time> {
-    let hour = read_i32_column(row, row_index, column_index)? as u8;
-    let minute = read_i32_column(row, row_index, column_index + 1)? as u8;
+    let hour = read_i32_column(row, row_index, column_indexes[0])? as u8;
+    let minute = read_i32_column(row, row_index, column_indexes[1])? as u8;
 
     time(hour, minute).map_err(|_| {
         anyhow::anyhow!(
             "{}行目 {}列: 時刻({}:{})が範囲外です。",
             row_index + 1,
-            column_index + 1,
+            column_indexes[0] + 1,
             hour,
             minute
         )
     })
 }
 
-fn time(hour: u8, minute: u8) -> anyhow::Result<Time> {
-    Time::from_hms(hour, minute, 0u8)
-        .map_err(|_| anyhow::anyhow!("時刻({}:{})が範囲外です。", hour, minute))
-}
-
 fn read_point_column(
     row: &csv::StringRecord,
     row_index: usize,
-    column_index: usize,
+    latitude_column_index: usize,
+    longitude_column_index: usize,
 ) -> anyhow::Result<Point> {
-    let latitude = dms_to_latitude(&read_str_column(row, row_index, column_index)?)?;
-    let longitude = dms_to_longitude(&read_str_column(row, row_index, column_index + 1)?)?;
+    let latitude = dms_to_latitude(&read_str_column(row, row_index, latitude_column_index)?)
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "{}行目 {}列: {}",
+                row_index + 1,
+                latitude_column_index + 1,
+                e
+            )
+        })?;
+    let longitude = dms_to_longitude(&read_str_column(row, row_index, longitude_column_index)?)
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "{}行目 {}列: {}",
+                row_index + 1,
+                longitude_column_index + 1,
+                e
+            )
+        })?;
 
     Ok(Point::new(longitude, latitude))
 }
 
-fn dms_to_latitude(hms: &str) -> anyhow::Result<f64> {
-    let degree = hms[..2].parse::<f64>()?;
-    let minute = hms[2..4].parse::<f64>()?;
-    let second = hms[4..].parse::<f64>()? / 1000.0;
-
-    Ok(degree + minute / 60.0 + second / 3600.0)
-}
-
-fn dms_to_longitude(hms: &str) -> anyhow::Result<f64> {
-    let degree = hms[..3].parse::<f64>()?;
-    let minute = hms[3..5].parse::<f64>()?;
-    let second = hms[5..].parse::<f64>()? / 1000.0;
-
-    Ok(degree + minute / 60.0 + second / 3600.0)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use time::macros::{datetime, offset, time};
 
-    #[test]
-    fn offset_datetime_ok() {
-        let datetime = offset_datetime(2021, 1, 1, 10, 30).unwrap();
-        assert_eq!(datetime.year(), 2021);
-        assert_eq!(datetime.month(), Month::January);
-        assert_eq!(datetime.day(), 1);
-        assert_eq!(datetime.hour(), 10);
-        assert_eq!(datetime.minute(), 30);
-        assert_eq!(datetime.second(), 0);
-        assert_eq!(datetime.offset(), offset!(+9));
-    }
-
-    #[test]
-    fn offset_datetime_fail() {
-        assert!(offset_datetime(2021, 13, 1, 10, 30).is_err());
-        assert!(offset_datetime(2021, 1, 32, 10, 30).is_err());
-        assert!(offset_datetime(2021, 1, 1, 24, 30).is_err());
-        assert!(offset_datetime(2021, 1, 1, 10, 60).is_err());
-    }
-
-    #[test]
-    fn time_ok() {
-        let time = time(10, 30).unwrap();
-        assert_eq!(time.hour(), 10);
-        assert_eq!(time.minute(), 30);
-        assert_eq!(time.second(), 0);
-    }
-
-    #[test]
-    fn time_fail() {
-        assert!(time(24, 30).is_err());
-        assert!(time(10, 60).is_err());
-    }
-
-    #[test]
-    fn dms_to_latitude_ok() {
-        assert_eq!(dms_to_latitude("350000000").unwrap(), 35.0);
-        assert_eq!(dms_to_latitude("353000000").unwrap(), 35.0 + 30.0 / 60.0);
-        let result = dms_to_latitude("353030123").unwrap();
-        let expected = 35.0 + 30.0 / 60.0 + 30.123 / 3600.0;
-        assert!(
-            (result - expected).abs() < 1e-11,
-            "expected: {}, result: {}",
-            expected,
-            result
-        );
-    }
-
-    #[test]
-    fn dms_to_longitude_ok() {
-        assert_eq!(dms_to_longitude("1350000000").unwrap(), 135.0);
-        assert_eq!(dms_to_longitude("1353000000").unwrap(), 135.0 + 30.0 / 60.0);
-        let result = dms_to_longitude("1353030123").unwrap();
-        let expected = 135.0 + 30.0 / 60.0 + 30.123 / 3600.0;
-        assert!(
-            (result - expected).abs() < 1e-11,
-            "expected: {}, result: {}",
-            expected,
-            result
-        );
-    }
-
     fn prefecture_hash_map_for_test() -> HashMap<String, String> {
         let mut prefectures = HashMap::new();
         prefectures.insert(String::from("10"), String::from("01"));
@@ -590,10 +1019,12 @@ mod tests {
         let row = csv::ByteRecord::from(row);
         let row = csv::StringRecord::from_byte_record(row).unwrap();
         let prefectures = prefecture_hash_map_for_test();
-        let accident = row_to_accident(&row, 0, &prefectures).unwrap();
+        let columns = Layout::Y2022.main_columns();
+        let accident = row_to_accident(&row, 0, &columns, &prefectures).unwrap();
 
         assert_eq!(accident.prefecture_code, "10");
-        assert_eq!(accident.police_station_code, "059");
+        assert_eq!(accident.police_station_code, "10059");
+        assert_eq!(accident.police_station_code.station_code(), "059");
         assert_eq!(accident.main_number, 1);
         assert_eq!(accident.accident_detail_code, "2");
         assert_eq!(accident.number_of_deaths, 0);
@@ -664,22 +1095,70 @@ mod tests {
         assert_eq!(accident.driving_practice_b_code, "1");
     }
 
-    fn accident_identifiers_test(
-        accident_ids: &[Uuid],
-    ) -> HashMap<RawAccidentIdentifier<'static>, Uuid> {
+    #[test]
+    fn row_to_accident_without_driver_columns_ok() {
+        let row = "1,10,059,0001,2,000,001,40010,0000,104,2022,01,22,14,18,12,06,59,16,33,5,1,3,14,7,00,00,00,00,04,9,01,70,1,4,21,35,25,03,04,31,31,01,01,1,1,00,00,04,04,30,30,3,3,2,2,2,2,2,4,430234789,1412612831,7,3";
+        let row = row.split(",").collect::<Vec<&str>>();
+        let row = csv::ByteRecord::from(row);
+        let row = csv::StringRecord::from_byte_record(row).unwrap();
+        let prefectures = prefecture_hash_map_for_test();
+        let columns = Layout::Y2019.main_columns();
+        let accident = row_to_accident(&row, 0, &columns, &prefectures).unwrap();
+
+        assert_eq!(accident.holiday_code, "3");
+        assert_eq!(accident.cognitive_days_a, DEFAULT_COGNITIVE_DAYS);
+        assert_eq!(accident.cognitive_days_b, DEFAULT_COGNITIVE_DAYS);
+        assert_eq!(
+            accident.driving_practice_a_code,
+            DEFAULT_DRIVING_PRACTICE_CODE
+        );
+        assert_eq!(
+            accident.driving_practice_b_code,
+            DEFAULT_DRIVING_PRACTICE_CODE
+        );
+    }
+
+    #[test]
+    fn year_counter_ok() {
+        let row = "1,10,059,0001,2,000,001,40010,0000,104,2022,01,22,14,18,12,06,59,16,33,5,1,3,14,7,00,00,00,00,04,9,01,70,1,4,21,35,25,03,04,31,31,01,01,1,1,00,00,04,04,30,30,3,3,2,2,2,2,2,4,430234789,1412612831,7,3,9999,9999,1,1";
+        let row = row.split(",").collect::<Vec<&str>>();
+        let row = csv::ByteRecord::from(row);
+        let row = csv::StringRecord::from_byte_record(row).unwrap();
+        let prefectures = prefecture_hash_map_for_test();
+        let columns = Layout::Y2022.main_columns();
+        let accident = row_to_accident(&row, 0, &columns, &prefectures).unwrap();
+        let mut accidents = [accident.clone(), accident.clone(), accident];
+        accidents[0].occurred_at = datetime!(2021-12-31 23:59).assume_offset(offset!(+9));
+
+        let mut counter = YearCounter::default();
+        assert_eq!(counter.most_frequent(), None);
+        counter.add(&accidents[..1]);
+        assert_eq!(counter.most_frequent(), Some(2021));
+        counter.add(&accidents[1..]);
+        assert_eq!(counter.most_frequent(), Some(2022));
+    }
+
+    #[test]
+    fn record_to_line_ok() {
+        let row = csv::StringRecord::from(vec!["1", "a,b", "c\"d"]);
+
+        assert_eq!(record_to_line(&row), "1,\"a,b\",\"c\"\"d\"");
+    }
+
+    fn accident_identifiers_test(accident_ids: &[Uuid]) -> HashMap<RawAccidentIdentifier, Uuid> {
         let mut identifiers = HashMap::new();
         identifiers.insert(
             RawAccidentIdentifier {
-                prefecture_code: "10",
-                police_station_code: "101",
+                prefecture_code: String::from("10"),
+                police_station_code: String::from("101"),
                 main_number: 42,
             },
             accident_ids[0],
         );
         identifiers.insert(
             RawAccidentIdentifier {
-                prefecture_code: "10",
-                police_station_code: "101",
+                prefecture_code: String::from("10"),
+                police_station_code: String::from("101"),
                 main_number: 1,
             },
             accident_ids[1],
@@ -696,36 +1175,37 @@ mod tests {
             "2,10,101,0001,001,03,01,01,2,01,00,2,2,2,  ,",
         ];
         let identifiers = accident_identifiers_test(&ids);
+        let columns = Layout::Y2022.support_columns();
         let expected_persons = [
             RawInvolvedPerson {
                 id: Uuid::new_v4(),
                 accident_id: ids[0],
                 sub_number: 1,
-                party_code: String::from("14"),
-                purpose_code: Some(String::from("31")),
-                vehicle_type_code: Some(String::from("11")),
-                riding_type_code: String::from("1"),
-                riding_class_code: String::from("07"),
-                support_car_code: String::from("00"),
-                airbag_code: String::from("2"),
-                side_airbag_code: String::from("2"),
-                injury_code: String::from("2"),
+                party_code: "14".parse().unwrap(),
+                purpose_code: Some("31".parse().unwrap()),
+                vehicle_type_code: Some("11".parse().unwrap()),
+                riding_type_code: "1".parse().unwrap(),
+                riding_class_code: "07".parse().unwrap(),
+                support_car_code: "00".parse().unwrap(),
+                airbag_code: "2".parse().unwrap(),
+                side_airbag_code: "2".parse().unwrap(),
+                injury_code: "2".parse().unwrap(),
                 collision_part: Some(String::from("23")),
-                vehicle_damage_code: Some(String::from("3")),
+                vehicle_damage_code: Some("3".parse().unwrap()),
             },
             RawInvolvedPerson {
                 id: Uuid::new_v4(),
                 accident_id: ids[1],
                 sub_number: 1,
-                party_code: String::from("03"),
-                purpose_code: Some(String::from("01")),
-                vehicle_type_code: Some(String::from("01")),
-                riding_type_code: String::from("2"),
-                riding_class_code: String::from("01"),
-                support_car_code: String::from("00"),
-                airbag_code: String::from("2"),
-                side_airbag_code: String::from("2"),
-                injury_code: String::from("2"),
+                party_code: "03".parse().unwrap(),
+                purpose_code: Some("01".parse().unwrap()),
+                vehicle_type_code: Some("01".parse().unwrap()),
+                riding_type_code: "2".parse().unwrap(),
+                riding_class_code: "01".parse().unwrap(),
+                support_car_code: "00".parse().unwrap(),
+                airbag_code: "2".parse().unwrap(),
+                side_airbag_code: "2".parse().unwrap(),
+                injury_code: "2".parse().unwrap(),
                 collision_part: None,
                 vehicle_damage_code: None,
             },
@@ -734,7 +1214,7 @@ mod tests {
             let row = row.split(",").collect::<Vec<&str>>();
             let row = csv::ByteRecord::from(row);
             let row = csv::StringRecord::from_byte_record(row).unwrap();
-            let involved_person = row_to_involved_person(&row, 0, &identifiers).unwrap();
+            let involved_person = row_to_involved_person(&row, 0, &columns, &identifiers).unwrap();
             assert_eq!(expected.accident_id, involved_person.accident_id);
             assert_eq!(expected.sub_number, involved_person.sub_number);
             assert_eq!(expected.party_code, involved_person.party_code);
@@ -759,4 +1239,51 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn row_to_highway_record_ok() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4()];
+        let identifiers = accident_identifiers_test(&ids);
+        let row = "3,10,101,0042,1,0123,400,2,1,2";
+        let row = row.split(",").collect::<Vec<&str>>();
+        let row = csv::ByteRecord::from(row);
+        let row = csv::StringRecord::from_byte_record(row).unwrap();
+        let columns = Layout::Y2022.highway_columns();
+        let highway_record = row_to_highway_record(&row, 0, &columns, &identifiers).unwrap();
+
+        assert_eq!(highway_record.accident_id, ids[0]);
+        assert_eq!(highway_record.up_down_code, "1");
+        assert_eq!(highway_record.kilopost_km, 123);
+        assert_eq!(highway_record.kilopost_m, 400);
+        assert_eq!(highway_record.road_structure_code, "2");
+        assert_eq!(highway_record.lane_a_code, "1");
+        assert_eq!(highway_record.lane_b_code, "2");
+    }
+
+    #[test]
+    fn row_to_highway_record_not_found() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4()];
+        let identifiers = accident_identifiers_test(&ids);
+        let row = "3,10,101,0099,1,0123,400,2,1,2";
+        let row = row.split(",").collect::<Vec<&str>>();
+        let row = csv::ByteRecord::from(row);
+        let row = csv::StringRecord::from_byte_record(row).unwrap();
+
+        let columns = Layout::Y2022.highway_columns();
+        assert!(row_to_highway_record(&row, 0, &columns, &identifiers).is_err());
+    }
+
+    #[test]
+    fn row_to_highway_record_invalid_code() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4()];
+        let identifiers = accident_identifiers_test(&ids);
+        // 車線コード9はコード表に存在しない
+        let row = "3,10,101,0042,1,0123,400,2,9,2";
+        let row = row.split(",").collect::<Vec<&str>>();
+        let row = csv::ByteRecord::from(row);
+        let row = csv::StringRecord::from_byte_record(row).unwrap();
+        let columns = Layout::Y2022.highway_columns();
+        let e = row_to_highway_record(&row, 0, &columns, &identifiers).unwrap_err();
+        assert!(e.to_string().contains("lanes"), "{}", e);
+    }
 }
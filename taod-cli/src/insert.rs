@@ -1,51 +1,637 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::time::Instant;
 
-use crate::db::{prefecture_hash_map, register_accidents, register_involved_persons};
-use crate::files::{read_accidents, read_involved_persons};
-use db::connection_pool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::db::{
+    accident_dataset_ids, assign_existing_accident_ids, copy_accidents, copy_highway_records,
+    copy_involved_persons, delete_accident_children, delete_accidents, fetch_highway_records,
+    prefecture_hash_map, recount_datasets, register_accidents, register_dataset,
+    register_highway_records, register_import_rejects, register_involved_persons,
+    update_dataset_summary, Dataset, ImportReject, OnConflict,
+};
+use crate::files::{
+    file_sha256, AccidentReader, HighwayRecordReader, InvolvedPersonReader, RowError, YearCounter,
+};
+use crate::layouts::Layout;
+use crate::sheet::Sheet;
+use code_tables::CodeTables;
+use db::{connection_pool, PgTransaction};
+
+/// CSVファイルから読み込み、データベースに登録する1回あたりの行数
+const READ_CHUNK_ROWS: usize = 10_000;
+
+/// 交通事故の登録オプション
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InsertOptions {
+    /// 本票及び補充票のレイアウト（指定しない場合はヘッダ行から列の位置を決定）
+    pub layout: Option<Layout>,
+    /// 登録済みの交通事故と重複した場合の動作
+    pub on_conflict: OnConflict,
+    /// COPY文で登録するかを示すフラグ
+    pub copy: bool,
+    /// データセットの年（指定しない場合は交通事故の発生日時から推定）
+    pub year: Option<i32>,
+    /// 変換できない行やコード表に存在しないコードを含む行を、登録除外行テーブルに記録して
+    /// 残りの行を登録するかを示すフラグ
+    pub lenient: bool,
+}
 
 /// データベースに交通事故を登録する。
 ///
+/// 各票は一度にメモリへ読み込まず、`READ_CHUNK_ROWS`行ずつ読み込んでデータベースに登録する。
+///
 /// # 引数
 ///
 /// * `main_path` - 本票ファイルパス
 /// * `support_path` - 補充票ファイルパス
+/// * `highway_path` - 高速票ファイルパス（指定しない場合は高速票を登録しない）
+/// * `options` - 交通事故の登録オプション
 ///
 /// # 戻り値
 ///
 /// `()`
-pub async fn insert<P: AsRef<Path>>(main_file: P, _support_file: P) -> anyhow::Result<()> {
+pub async fn insert<P: AsRef<Path>>(
+    main_file: P,
+    support_file: P,
+    highway_file: Option<P>,
+    options: InsertOptions,
+) -> anyhow::Result<()> {
     // 都道府県コードとJIS規格の都道府県コードの対応を記録したハッシュマップ
     let pool = connection_pool().await?;
     let prefectures = prefecture_hash_map(&pool).await?;
 
-    // 交通事故（本票）を読み込み
-    let accidents = read_accidents(main_file, &prefectures)?;
-    // 交通事故識別子と交通事故IDの対応を記録したハッシュマップを生成
-    let mut accident_ids = HashMap::new();
-    for accident in &accidents {
-        accident_ids.insert(accident.identifier(), accident.id);
-    }
-    // 補充表を読み込み
-    let involved_persons = read_involved_persons(_support_file, &accident_ids)?;
-
     // トランザクションを開始
+    let started_at = Instant::now();
     let mut tx = pool.begin().await.map_err(|_| {
         anyhow::anyhow!(
             "交通事故をデータベースに登録する際に、トランザクションを開始できませんでした。"
         )
     })?;
 
-    // 交通事故をデータベースに登録
-    register_accidents(&mut tx, &accidents).await?;
-    // 交通事故当事者以外の関係者をデータベースに登録
-    register_involved_persons(&mut tx, &involved_persons).await?;
+    let (dataset, rejects) = import(
+        &mut tx,
+        &prefectures,
+        main_file,
+        support_file,
+        highway_file,
+        options,
+    )
+    .await?;
 
     // トランザクションをコミット
     tx.commit().await.map_err(|_| {
         anyhow::anyhow!("交通事故をデータベースに登録する際に、コミットできませんでした。")
     })?;
 
+    // 登録した行数と1秒あたりの登録行数を表示
+    let rows = dataset.number_of_accidents
+        + dataset.number_of_involved_persons
+        + dataset.number_of_highway_details;
+    let seconds = started_at.elapsed().as_secs_f64();
+    println!(
+        "データセット{}として、交通事故{}件、関係者{}件、高速票{}件を{:.1}秒で登録しました（{:.0}行/秒）。",
+        dataset.id,
+        dataset.number_of_accidents,
+        dataset.number_of_involved_persons,
+        dataset.number_of_highway_details,
+        seconds,
+        rows as f64 / seconds.max(f64::EPSILON),
+    );
+    if rejects > 0 {
+        println!(
+            "{}行を登録から除外して、登録除外行テーブル(import_rejects)に記録しました。",
+            rejects
+        );
+    }
+
     Ok(())
 }
+
+/// トランザクション内で、各票をデータベースに登録する。
+///
+/// # 引数
+///
+/// * `tx` - データベーストランザクション
+/// * `prefectures` - 本票の都道府県コードとJIS規格の都道府県コードの対応を記録したハッシュマップ
+/// * `main_path` - 本票ファイルパス
+/// * `support_path` - 補充票ファイルパス
+/// * `highway_path` - 高速票ファイルパス（指定しない場合は高速票を登録しない）
+/// * `options` - 交通事故の登録オプション
+///
+/// # 戻り値
+///
+/// 登録したデータセットと、登録から除外した行数
+async fn import<P: AsRef<Path>>(
+    tx: &mut PgTransaction<'_>,
+    prefectures: &HashMap<String, String>,
+    main_file: P,
+    support_file: P,
+    highway_file: Option<P>,
+    options: InsertOptions,
+) -> anyhow::Result<(Dataset, usize)> {
+    let InsertOptions {
+        layout,
+        on_conflict,
+        copy,
+        year,
+        lenient,
+    } = options;
+
+    // データセットを記録するため、ファイル名とハッシュを取得
+    let main_source = source_file(&main_file)?;
+    let support_source = source_file(&support_file)?;
+    let highway_source = match &highway_file {
+        Some(highway_file) => Some(source_file(highway_file)?),
+        None => None,
+    };
+
+    // 寛容モードでは、データベースの制約に違反する行を除外するため、行をコード表で検査
+    let code_tables = match lenient {
+        true => Some(CodeTables::load()?),
+        false => None,
+    };
+    // 登録から除外した行
+    let mut rejects = Rejects::new(lenient);
+
+    // 交通事故（本票）の最初の行を読み込み、データセットの年を仮に決定
+    let mut accident_reader = AccidentReader::open(&main_file, prefectures, layout)?;
+    if let Some(code_tables) = &code_tables {
+        accident_reader = accident_reader.validate(code_tables);
+    }
+    let mut accidents = rejects.next_chunk(Sheet::Main, &mut accident_reader)?;
+    let mut years = YearCounter::default();
+    years.add(&accidents);
+    let (highway_file_name, highway_file_sha256) = highway_source.unzip();
+    let mut dataset = Dataset {
+        id: Uuid::new_v4(),
+        year: year
+            .or_else(|| years.most_frequent())
+            .ok_or(anyhow::anyhow!(
+                "データセットの年を決定できません。`--year`で年を指定してください。"
+            ))?,
+        main_file_name: main_source.0,
+        main_file_sha256: main_source.1,
+        support_file_name: support_source.0,
+        support_file_sha256: support_source.1,
+        highway_file_name,
+        highway_file_sha256,
+        number_of_accidents: 0,
+        number_of_involved_persons: 0,
+        number_of_highway_details: 0,
+        imported_at: OffsetDateTime::now_utc(),
+    };
+    // データセットをデータベースに登録（件数はすべての票を登録した後に更新）
+    register_dataset(tx, &dataset).await?;
+
+    // 交通事故識別子と交通事故IDの対応を記録したハッシュマップ
+    let mut accident_ids = HashMap::new();
+    // 登録しなかった登録済みの交通事故の交通事故ID
+    let mut skipped_ids = HashSet::new();
+    // 更新した登録済みの交通事故を登録していたデータセットのデータセットID
+    let mut updated_dataset_ids = HashSet::new();
+    while !accidents.is_empty() {
+        // 登録済みの交通事故の交通事故IDを引き継ぎ
+        let existing_ids = match on_conflict {
+            OnConflict::Update | OnConflict::Skip => {
+                assign_existing_accident_ids(tx, &mut accidents).await?
+            }
+            OnConflict::Fail => HashSet::new(),
+        };
+        for accident in &accidents {
+            accident_ids.insert(accident.identifier(), accident.id);
+        }
+        match on_conflict {
+            // 登録済みの交通事故を登録しない場合は、登録済みの交通事故を除外
+            OnConflict::Skip => {
+                accidents.retain(|a| !existing_ids.contains(&a.id));
+                skipped_ids.extend(existing_ids.iter().copied());
+            }
+            // 登録済みの交通事故を更新する場合は、登録済みの関係者などを削除して登録し直す
+            // 高速票を登録しない場合は、登録済みの高速票を残す
+            // COPY文で登録する場合は、交通事故の削除が関係者などに伝播するため削除しない
+            OnConflict::Update => {
+                updated_dataset_ids.extend(accident_dataset_ids(tx, &existing_ids).await?);
+                if !copy {
+                    delete_accident_children(tx, &existing_ids, highway_file.is_some()).await?
+                }
+            }
+            OnConflict::Fail => {}
+        }
+        if copy {
+            // COPY文は重複した行を更新できないため、登録済みの交通事故を削除してから同じ交通事故IDで登録
+            // 交通事故の削除は高速票に伝播するため、高速票を登録しない場合は登録済みの高速票を登録し直す
+            let mut kept_highway_records = Vec::new();
+            if on_conflict == OnConflict::Update {
+                if highway_file.is_none() {
+                    kept_highway_records = fetch_highway_records(tx, &existing_ids).await?;
+                }
+                delete_accidents(tx, &existing_ids).await?;
+            }
+            copy_accidents(tx, &accidents, dataset.id).await?;
+            copy_highway_records(tx, &kept_highway_records).await?;
+        } else {
+            register_accidents(tx, &accidents, dataset.id, on_conflict).await?;
+        }
+        dataset.number_of_accidents += accidents.len() as i32;
+
+        rejects.register(tx, dataset.id).await?;
+        accidents = rejects.next_chunk(Sheet::Main, &mut accident_reader)?;
+        years.add(&accidents);
+    }
+
+    // 交通事故当事者以外の関係者（補充票）をデータベースに登録
+    let mut involved_person_reader =
+        InvolvedPersonReader::open(&support_file, &accident_ids, layout)?;
+    if let Some(code_tables) = &code_tables {
+        involved_person_reader = involved_person_reader.validate(code_tables);
+    }
+    loop {
+        let mut involved_persons =
+            rejects.next_chunk(Sheet::Support, &mut involved_person_reader)?;
+        if involved_persons.is_empty() {
+            break;
+        }
+        involved_persons.retain(|p| !skipped_ids.contains(&p.accident_id));
+        if copy {
+            copy_involved_persons(tx, &involved_persons).await?;
+        } else {
+            register_involved_persons(tx, &involved_persons).await?;
+        }
+        dataset.number_of_involved_persons += involved_persons.len() as i32;
+        rejects.register(tx, dataset.id).await?;
+    }
+
+    // 高速道路で発生した交通事故の詳細（高速票）をデータベースに登録
+    if let Some(highway_file) = highway_file {
+        let mut highway_record_reader =
+            HighwayRecordReader::open(highway_file, &accident_ids, layout)?;
+        loop {
+            let mut highway_records =
+                rejects.next_chunk(Sheet::Highway, &mut highway_record_reader)?;
+            if highway_records.is_empty() {
+                break;
+            }
+            highway_records.retain(|r| !skipped_ids.contains(&r.accident_id));
+            if copy {
+                copy_highway_records(tx, &highway_records).await?;
+            } else {
+                register_highway_records(tx, &highway_records).await?;
+            }
+            dataset.number_of_highway_details += highway_records.len() as i32;
+            rejects.register(tx, dataset.id).await?;
+        }
+    }
+
+    // 記録したままの除外行をデータベースに登録
+    rejects.register(tx, dataset.id).await?;
+
+    // すべての交通事故から決定した年と、登録した件数でデータセットを更新
+    dataset.year = year
+        .or_else(|| years.most_frequent())
+        .unwrap_or(dataset.year);
+    update_dataset_summary(tx, &dataset).await?;
+    // 登録済みの交通事故を更新した場合は、交通事故が移ったデータセットの件数を数え直す
+    if !updated_dataset_ids.is_empty() {
+        updated_dataset_ids.insert(dataset.id);
+        recount_datasets(tx, &updated_dataset_ids).await?;
+    }
+
+    Ok((dataset, rejects.total))
+}
+
+/// 登録から除外した行
+struct Rejects {
+    /// 変換できない行を登録から除外するかを示すフラグ（`false`の場合はエラー）
+    lenient: bool,
+    /// データベースに登録していない除外行
+    pending: Vec<ImportReject>,
+    /// 除外した行数
+    total: usize,
+}
+
+impl Rejects {
+    fn new(lenient: bool) -> Self {
+        Self {
+            lenient,
+            pending: Vec::new(),
+            total: 0,
+        }
+    }
+
+    /// イテレーターから最大`READ_CHUNK_ROWS`件の要素を取り出す。
+    ///
+    /// 寛容モードの場合、変換できなかった行は除外行として記録して読み飛ばす。
+    ///
+    /// # 引数
+    ///
+    /// * `sheet` - 票の種類
+    /// * `iter` - 要素を返すイテレーター
+    ///
+    /// # 戻り値
+    ///
+    /// 取り出した要素を格納したベクタ、イテレーターが終端に達した場合は空のベクタ
+    fn next_chunk<T, I>(&mut self, sheet: Sheet, iter: &mut I) -> anyhow::Result<Vec<T>>
+    where
+        I: Iterator<Item = Result<T, RowError>>,
+    {
+        let mut chunk = Vec::with_capacity(READ_CHUNK_ROWS);
+        while chunk.len() < READ_CHUNK_ROWS {
+            match iter.next() {
+                Some(Ok(item)) => chunk.push(item),
+                Some(Err(e)) if self.lenient => {
+                    self.pending.push(ImportReject::new(sheet, e));
+                    self.total += 1;
+                }
+                Some(Err(e)) => return Err(e.into()),
+                None => break,
+            }
+        }
+
+        Ok(chunk)
+    }
+
+    /// 記録した除外行をデータベースに登録する。
+    async fn register(
+        &mut self,
+        tx: &mut PgTransaction<'_>,
+        dataset_id: Uuid,
+    ) -> anyhow::Result<()> {
+        register_import_rejects(tx, dataset_id, &self.pending).await?;
+        self.pending.clear();
+
+        Ok(())
+    }
+}
+
+/// ファイル名とファイルのSHA-256ハッシュを返す。
+fn source_file<P: AsRef<Path>>(path: P) -> anyhow::Result<(String, String)> {
+    let path = path.as_ref();
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or(anyhow::anyhow!(
+            "ファイル名を取得できません。{}",
+            path.display()
+        ))?;
+
+    Ok((name, file_sha256(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::test_utils::temp_dir_for_test;
+
+    /// 本票の行（`{}`は本票番号に置き換える）
+    const MAIN_ROW: &str = "1,10,101,{},2,000,001,40010,0000,101,2022,01,22,14,18,12,06,59,16,33,2,1,3,14,7,00,00,00,00,04,9,01,70,1,4,21,35,25,03,04,31,31,01,01,1,1,00,00,04,04,30,30,3,3,2,2,2,2,2,4,430234789,1412612831,7,3,9999,9999,1,1";
+    const SUPPORT_ROW: &str = "2,10,101,{},001,14,31,11,1,07,00,2,2,2,23,3";
+    const HIGHWAY_ROW: &str = "3,10,101,{},1,0123,400,2,1,2";
+
+    /// 行を記録した票を作成する。
+    fn write_sheet_for_test(path: &Path, rows: &[String]) {
+        // 列の位置はレイアウトで決定するため、ヘッダ行には列の数だけ仮の列名を記録
+        let headers = (0..rows[0].split(',').count())
+            .map(|index| format!("column{}", index))
+            .collect::<Vec<_>>()
+            .join(",");
+        let content = std::iter::once(headers)
+            .chain(rows.iter().cloned())
+            .map(|line| format!("{}\n", line))
+            .collect::<String>();
+        std::fs::write(path, content).unwrap();
+    }
+
+    /// 本票番号ごとに交通事故を1件記録した本票、補充票及び高速票を作成する。
+    fn sheets_for_test(name: &str, main_numbers: &[i32]) -> (PathBuf, PathBuf, PathBuf) {
+        let dir = temp_dir_for_test(&format!("insert-{}", name));
+        let sheets = [
+            ("honhyo.csv", MAIN_ROW),
+            ("hojuhyo.csv", SUPPORT_ROW),
+            ("kousokuhyo.csv", HIGHWAY_ROW),
+        ]
+        .map(|(file_name, row)| {
+            let rows = main_numbers
+                .iter()
+                .map(|main_number| row.replace("{}", &format!("{:04}", main_number)))
+                .collect::<Vec<_>>();
+            let path = dir.join(file_name);
+            write_sheet_for_test(&path, &rows);
+            path
+        });
+        let [main, support, highway] = sheets;
+
+        (main, support, highway)
+    }
+
+    /// 本票番号の交通事故に関連する交通事故当事者以外の関係者と高速票の件数を返す。
+    async fn children_for_test(tx: &mut PgTransaction<'_>, main_number: i32) -> (i64, i64) {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM involved_persons p WHERE p.accident_id = a.id) "involved_persons!",
+                (SELECT COUNT(*) FROM highway_details h WHERE h.accident_id = a.id) "highway_details!"
+            FROM accidents a
+            WHERE a.prefecture_code = '10' AND a.police_station_code = '101' AND a.main_number = $1
+            "#,
+            main_number,
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .unwrap();
+
+        (row.involved_persons, row.highway_details)
+    }
+
+    /// データセットに記録した交通事故、関係者及び高速票の件数を返す。
+    async fn dataset_counts_for_test(tx: &mut PgTransaction<'_>, id: Uuid) -> (i32, i32, i32) {
+        let row = sqlx::query!(
+            r#"
+            SELECT number_of_accidents, number_of_involved_persons, number_of_highway_details
+            FROM datasets
+            WHERE id = $1
+            "#,
+            id,
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .unwrap();
+
+        (
+            row.number_of_accidents,
+            row.number_of_involved_persons,
+            row.number_of_highway_details,
+        )
+    }
+
+    async fn import_update_for_test(name: &str, main_number: i32, copy: bool) {
+        let (main, support, highway) = sheets_for_test(name, &[main_number]);
+        let pool = connection_pool().await.unwrap();
+        let prefectures = prefecture_hash_map(&pool).await.unwrap();
+        let mut tx = pool.begin().await.unwrap();
+        let options = InsertOptions {
+            layout: Some(Layout::Y2022),
+            copy,
+            ..Default::default()
+        };
+        let (first, _) = import(
+            &mut tx,
+            &prefectures,
+            &main,
+            &support,
+            Some(&highway),
+            options,
+        )
+        .await
+        .unwrap();
+        assert_eq!(first.number_of_highway_details, 1);
+        assert_eq!(children_for_test(&mut tx, main_number).await, (1, 1));
+
+        // 高速票を指定せずに更新した場合は、登録済みの高速票を残す
+        let options = InsertOptions {
+            on_conflict: OnConflict::Update,
+            ..options
+        };
+        let (second, _) = import(&mut tx, &prefectures, &main, &support, None, options)
+            .await
+            .unwrap();
+        assert_eq!(second.number_of_accidents, 1);
+        assert_eq!(second.number_of_involved_persons, 1);
+        assert_eq!(children_for_test(&mut tx, main_number).await, (1, 1));
+        // 交通事故が移ったデータセットの件数を数え直す
+        assert_eq!(dataset_counts_for_test(&mut tx, first.id).await, (0, 0, 0));
+        assert_eq!(dataset_counts_for_test(&mut tx, second.id).await, (1, 1, 1));
+
+        // 高速票を指定して更新した場合は、高速票を登録し直す
+        let (third, _) = import(
+            &mut tx,
+            &prefectures,
+            &main,
+            &support,
+            Some(&highway),
+            options,
+        )
+        .await
+        .unwrap();
+        assert_eq!(third.number_of_highway_details, 1);
+        assert_eq!(children_for_test(&mut tx, main_number).await, (1, 1));
+        assert_eq!(dataset_counts_for_test(&mut tx, second.id).await, (0, 0, 0));
+        assert_eq!(dataset_counts_for_test(&mut tx, third.id).await, (1, 1, 1));
+
+        tx.rollback().await.unwrap();
+        std::fs::remove_dir_all(main.parent().unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "PostgreSQLのデータベースが必要"]
+    async fn import_update_ok() {
+        import_update_for_test("update", 9901, false).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "PostgreSQLのデータベースが必要"]
+    async fn import_update_with_copy_ok() {
+        import_update_for_test("update-copy", 9902, true).await;
+    }
+
+    #[test]
+    fn rejects_next_chunk_ok() {
+        let rows = || {
+            (0..3).map(|row_index| match row_index {
+                1 => Err(RowError {
+                    row_index,
+                    line: Some(String::from("1,10,101")),
+                    error: anyhow::anyhow!("2行目: 不正な行です。"),
+                }),
+                _ => Ok(row_index),
+            })
+        };
+
+        // 寛容モードでは、変換できなかった行を除外行として記録して読み飛ばす
+        let mut rejects = Rejects::new(true);
+        let chunk = rejects.next_chunk(Sheet::Main, &mut rows()).unwrap();
+        assert_eq!(chunk, vec![0, 2]);
+        assert_eq!(rejects.total, 1);
+        assert_eq!(rejects.pending.len(), 1);
+        assert_eq!(rejects.pending[0].row_number, 2);
+        assert_eq!(rejects.pending[0].line.as_deref(), Some("1,10,101"));
+
+        // 寛容モードでなければ、変換できなかった行でエラーにする
+        let mut rejects = Rejects::new(false);
+        assert!(rejects.next_chunk(Sheet::Main, &mut rows()).is_err());
+        assert_eq!(rejects.total, 0);
+    }
+
+    #[tokio::test]
+    #[ignore = "PostgreSQLのデータベースが必要"]
+    async fn import_lenient_ok() {
+        let (main, support, highway) = sheets_for_test("lenient", &[9903, 9904]);
+        // 2件目の交通事故の天候コードを、コード表に存在しないコードに変更
+        let mut invalid = MAIN_ROW
+            .replace("{}", "9904")
+            .split(',')
+            .map(String::from)
+            .collect::<Vec<_>>();
+        invalid[20] = String::from("99");
+        write_sheet_for_test(&main, &[MAIN_ROW.replace("{}", "9903"), invalid.join(",")]);
+        let pool = connection_pool().await.unwrap();
+        let prefectures = prefecture_hash_map(&pool).await.unwrap();
+        let mut tx = pool.begin().await.unwrap();
+        let options = InsertOptions {
+            layout: Some(Layout::Y2022),
+            lenient: true,
+            ..Default::default()
+        };
+
+        // 不正な交通事故と、その交通事故の補充票及び高速票を除外して、残りの行を登録
+        let (dataset, rejects) = import(
+            &mut tx,
+            &prefectures,
+            &main,
+            &support,
+            Some(&highway),
+            options,
+        )
+        .await
+        .unwrap();
+        assert_eq!(rejects, 3);
+        assert_eq!(dataset.number_of_accidents, 1);
+        assert_eq!(dataset.number_of_involved_persons, 1);
+        assert_eq!(dataset.number_of_highway_details, 1);
+        assert_eq!(children_for_test(&mut tx, 9903).await, (1, 1));
+        let sheets = sqlx::query_scalar!(
+            r#"
+            SELECT sheet "sheet!" FROM import_rejects WHERE dataset_id = $1 ORDER BY sheet
+            "#,
+            dataset.id,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .unwrap();
+        assert_eq!(sheets, ["highway", "main", "support"]);
+
+        // 寛容モードでなければ、不正な行でエラーにする
+        let options = InsertOptions {
+            lenient: false,
+            on_conflict: OnConflict::Skip,
+            ..options
+        };
+        assert!(import(
+            &mut tx,
+            &prefectures,
+            &main,
+            &support,
+            Some(&highway),
+            options
+        )
+        .await
+        .is_err());
+
+        tx.rollback().await.unwrap();
+        std::fs::remove_dir_all(main.parent().unwrap()).unwrap();
+    }
+}
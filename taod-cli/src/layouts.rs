@@ -0,0 +1,663 @@
+use std::collections::HashMap;
+
+/// 本票及び補充票のレイアウト
+///
+/// 警察庁が公開している本票及び補充票の列の並びを表す。2019年から2023年に公開された
+/// データの列の並びは、本票の末尾に運転者に関する列（認知機能検査経過日数と運転練習の
+/// 方法）があるかどうかだけが異なるため、レイアウトはその有無で区別する。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Layout {
+    /// 2019年から2021年（運転者に関する列なし）
+    #[value(name = "2019", alias = "2020", alias = "2021")]
+    Y2019,
+    /// 2022年以降（運転者に関する列あり）
+    #[value(name = "2022", alias = "2023")]
+    Y2022,
+}
+
+impl Layout {
+    /// レイアウトに対応する本票の列の位置を返す。
+    ///
+    /// 2019年から2021年の本票には、認知機能検査経過日数と運転練習の方法の列がない。
+    ///
+    /// # 戻り値
+    ///
+    /// 本票の列の位置
+    pub fn main_columns(&self) -> MainColumns {
+        let has_driver_columns = *self == Self::Y2022;
+
+        MainColumns {
+            prefecture_code: 1,
+            police_station_code: 2,
+            main_number: 3,
+            accident_detail_code: 4,
+            number_of_deaths: 5,
+            number_of_injuries: 6,
+            route_code: 7,
+            location_code: 8,
+            city_code: 9,
+            occurred_at: [10, 11, 12, 13, 14],
+            day_night_code: 15,
+            sunrise_time: [16, 17],
+            sunset_time: [18, 19],
+            weather_code: 20,
+            district_code: 21,
+            surface_condition_code: 22,
+            road_model_code: 23,
+            traffic_signal_code: 24,
+            stop_regulation_sign_a_code: 25,
+            stop_regulation_display_a_code: 26,
+            stop_regulation_sign_b_code: 27,
+            stop_regulation_display_b_code: 28,
+            road_width_code: 29,
+            road_alignment_code: 30,
+            collision_point_code: 31,
+            zone_regulation_code: 32,
+            central_separation_code: 33,
+            road_segmentation_code: 34,
+            accident_type_code: 35,
+            age_a_code: 36,
+            age_b_code: 37,
+            party_a_code: 38,
+            party_b_code: 39,
+            purpose_a_code: 40,
+            purpose_b_code: 41,
+            vehicle_type_a_code: 42,
+            vehicle_type_b_code: 43,
+            automatic_a_code: 44,
+            automatic_b_code: 45,
+            support_car_a_code: 46,
+            support_car_b_code: 47,
+            speed_regulation_a_code: 48,
+            speed_regulation_b_code: 49,
+            collision_part_a: 50,
+            collision_part_b: 51,
+            vehicle_damage_a_code: 52,
+            vehicle_damage_b_code: 53,
+            airbag_a_code: 54,
+            airbag_b_code: 55,
+            side_airbag_a_code: 56,
+            side_airbag_b_code: 57,
+            injury_a_code: 58,
+            injury_b_code: 59,
+            latitude: 60,
+            longitude: 61,
+            week_code: 62,
+            holiday_code: 63,
+            cognitive_days_a: has_driver_columns.then_some(64),
+            cognitive_days_b: has_driver_columns.then_some(65),
+            driving_practice_a_code: has_driver_columns.then_some(66),
+            driving_practice_b_code: has_driver_columns.then_some(67),
+        }
+    }
+
+    /// レイアウトに対応する補充票の列の位置を返す。
+    ///
+    /// # 戻り値
+    ///
+    /// 補充票の列の位置
+    pub fn support_columns(&self) -> SupportColumns {
+        SupportColumns {
+            prefecture_code: 1,
+            police_station_code: 2,
+            main_number: 3,
+            sub_number: 4,
+            party_code: 5,
+            purpose_code: 6,
+            vehicle_type_code: 7,
+            riding_type_code: 8,
+            riding_class_code: 9,
+            support_car_code: 10,
+            airbag_code: 11,
+            side_airbag_code: 12,
+            injury_code: 13,
+            collision_part: 14,
+            vehicle_damage_code: 15,
+        }
+    }
+
+    /// レイアウトに対応する高速票の列の位置を返す。
+    ///
+    /// # 戻り値
+    ///
+    /// 高速票の列の位置
+    pub fn highway_columns(&self) -> HighwayColumns {
+        HighwayColumns {
+            prefecture_code: 1,
+            police_station_code: 2,
+            main_number: 3,
+            up_down_code: 4,
+            kilopost_km: 5,
+            kilopost_m: 6,
+            road_structure_code: 7,
+            lane_a_code: 8,
+            lane_b_code: 9,
+        }
+    }
+}
+
+/// 本票の列の位置
+///
+/// 年によって列が存在しない項目は`Option`で表現する。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MainColumns {
+    /// 都道府県コード
+    pub prefecture_code: usize,
+    /// 警察署等コード
+    pub police_station_code: usize,
+    /// 本票番号
+    pub main_number: usize,
+    /// 事故内容
+    pub accident_detail_code: usize,
+    /// 死者数
+    pub number_of_deaths: usize,
+    /// 負傷者数
+    pub number_of_injuries: usize,
+    /// 路線コード
+    pub route_code: usize,
+    /// 地点コード
+    pub location_code: usize,
+    /// 市区町村コード
+    pub city_code: usize,
+    /// 発生日時（年、月、日、時、分）
+    pub occurred_at: [usize; 5],
+    /// 昼夜
+    pub day_night_code: usize,
+    /// 日の出時刻（時、分）
+    pub sunrise_time: [usize; 2],
+    /// 日の入り時刻（時、分）
+    pub sunset_time: [usize; 2],
+    /// 天候
+    pub weather_code: usize,
+    /// 地形
+    pub district_code: usize,
+    /// 路面状態
+    pub surface_condition_code: usize,
+    /// 道路形状
+    pub road_model_code: usize,
+    /// 信号機
+    pub traffic_signal_code: usize,
+    /// 一時停止規制標識（当事者A）
+    pub stop_regulation_sign_a_code: usize,
+    /// 一時停止規制表示（当事者A）
+    pub stop_regulation_display_a_code: usize,
+    /// 一時停止規制標識（当事者B）
+    pub stop_regulation_sign_b_code: usize,
+    /// 一時停止規制表示（当事者B）
+    pub stop_regulation_display_b_code: usize,
+    /// 車道幅員
+    pub road_width_code: usize,
+    /// 道路線形
+    pub road_alignment_code: usize,
+    /// 衝突地点
+    pub collision_point_code: usize,
+    /// ゾーン規制
+    pub zone_regulation_code: usize,
+    /// 中央分離帯施設等
+    pub central_separation_code: usize,
+    /// 歩車道区分
+    pub road_segmentation_code: usize,
+    /// 事故類型
+    pub accident_type_code: usize,
+    /// 年齢（当事者A）
+    pub age_a_code: usize,
+    /// 年齢（当事者B）
+    pub age_b_code: usize,
+    /// 当事者種別（当事者A）
+    pub party_a_code: usize,
+    /// 当事者種別（当事者B）
+    pub party_b_code: usize,
+    /// 用途別（当事者A）
+    pub purpose_a_code: usize,
+    /// 用途別（当事者B）
+    pub purpose_b_code: usize,
+    /// 車両形状（当事者A）
+    pub vehicle_type_a_code: usize,
+    /// 車両形状（当事者B）
+    pub vehicle_type_b_code: usize,
+    /// オートマチック車（当事者A）
+    pub automatic_a_code: usize,
+    /// オートマチック車（当事者B）
+    pub automatic_b_code: usize,
+    /// サポカー（当事者A）
+    pub support_car_a_code: usize,
+    /// サポカー（当事者B）
+    pub support_car_b_code: usize,
+    /// 速度規制（指定のみ）（当事者A）
+    pub speed_regulation_a_code: usize,
+    /// 速度規制（指定のみ）（当事者B）
+    pub speed_regulation_b_code: usize,
+    /// 車両の衝突部位（当事者A）
+    pub collision_part_a: usize,
+    /// 車両の衝突部位（当事者B）
+    pub collision_part_b: usize,
+    /// 車両の損壊程度（当事者A）
+    pub vehicle_damage_a_code: usize,
+    /// 車両の損壊程度（当事者B）
+    pub vehicle_damage_b_code: usize,
+    /// エアバッグの装備（当事者A）
+    pub airbag_a_code: usize,
+    /// エアバッグの装備（当事者B）
+    pub airbag_b_code: usize,
+    /// サイドエアバッグの装備（当事者A）
+    pub side_airbag_a_code: usize,
+    /// サイドエアバッグの装備（当事者B）
+    pub side_airbag_b_code: usize,
+    /// 人身損傷程度（当事者A）
+    pub injury_a_code: usize,
+    /// 人身損傷程度（当事者B）
+    pub injury_b_code: usize,
+    /// 地点　緯度（北緯）
+    pub latitude: usize,
+    /// 地点　経度（東経）
+    pub longitude: usize,
+    /// 曜日(発生年月日)
+    pub week_code: usize,
+    /// 祝日(発生年月日)
+    pub holiday_code: usize,
+    /// 認知機能検査経過日数（当事者A）
+    pub cognitive_days_a: Option<usize>,
+    /// 認知機能検査経過日数（当事者B）
+    pub cognitive_days_b: Option<usize>,
+    /// 運転練習の方法（当事者A）
+    pub driving_practice_a_code: Option<usize>,
+    /// 運転練習の方法（当事者B）
+    pub driving_practice_b_code: Option<usize>,
+}
+
+impl MainColumns {
+    /// 本票の列の位置を決定する。
+    ///
+    /// レイアウトが指定された場合はそのレイアウトの列の位置を、指定されなかった場合は
+    /// ヘッダ行から列の位置を決定する。
+    ///
+    /// # 引数
+    ///
+    /// * `layout` - レイアウト
+    /// * `headers` - 本票のヘッダ行
+    ///
+    /// # 戻り値
+    ///
+    /// 本票の列の位置
+    pub fn resolve(layout: Option<Layout>, headers: &csv::StringRecord) -> anyhow::Result<Self> {
+        match layout {
+            Some(layout) => Ok(layout.main_columns()),
+            None => Self::from_headers(headers),
+        }
+    }
+
+    /// 本票のヘッダ行から列の位置を決定する。
+    ///
+    /// # 引数
+    ///
+    /// * `headers` - 本票のヘッダ行
+    ///
+    /// # 戻り値
+    ///
+    /// 本票の列の位置
+    pub fn from_headers(headers: &csv::StringRecord) -> anyhow::Result<Self> {
+        let headers = HeaderIndexes::new(headers);
+
+        Ok(Self {
+            prefecture_code: headers.find(&["都道府県コード"])?,
+            police_station_code: headers.find(&["警察署等コード", "警察署コード"])?,
+            main_number: headers.find(&["本票番号"])?,
+            accident_detail_code: headers.find(&["事故内容"])?,
+            number_of_deaths: headers.find(&["死者数"])?,
+            number_of_injuries: headers.find(&["負傷者数"])?,
+            route_code: headers.find(&["路線コード"])?,
+            location_code: headers.find(&["地点コード"])?,
+            city_code: headers.find(&["市区町村コード"])?,
+            occurred_at: [
+                headers.find(&["発生日時年"])?,
+                headers.find(&["発生日時月"])?,
+                headers.find(&["発生日時日"])?,
+                headers.find(&["発生日時時"])?,
+                headers.find(&["発生日時分"])?,
+            ],
+            day_night_code: headers.find(&["昼夜"])?,
+            sunrise_time: [
+                headers.find(&["日の出時刻時"])?,
+                headers.find(&["日の出時刻分"])?,
+            ],
+            sunset_time: [
+                headers.find(&["日の入り時刻時", "日の入時刻時"])?,
+                headers.find(&["日の入り時刻分", "日の入時刻分"])?,
+            ],
+            weather_code: headers.find(&["天候"])?,
+            district_code: headers.find(&["地形"])?,
+            surface_condition_code: headers.find(&["路面状態"])?,
+            road_model_code: headers.find(&["道路形状"])?,
+            traffic_signal_code: headers.find(&["信号機"])?,
+            stop_regulation_sign_a_code: headers.find(&["一時停止規制標識（当事者A）"])?,
+            stop_regulation_display_a_code: headers.find(&["一時停止規制表示（当事者A）"])?,
+            stop_regulation_sign_b_code: headers.find(&["一時停止規制標識（当事者B）"])?,
+            stop_regulation_display_b_code: headers.find(&["一時停止規制表示（当事者B）"])?,
+            road_width_code: headers.find(&["車道幅員"])?,
+            road_alignment_code: headers.find(&["道路線形"])?,
+            collision_point_code: headers.find(&["衝突地点"])?,
+            zone_regulation_code: headers.find(&["ゾーン規制"])?,
+            central_separation_code: headers.find(&["中央分離帯施設等", "中央分離帯施設"])?,
+            road_segmentation_code: headers.find(&["歩車道区分"])?,
+            accident_type_code: headers.find(&["事故類型"])?,
+            age_a_code: headers.find(&["年齢（当事者A）"])?,
+            age_b_code: headers.find(&["年齢（当事者B）"])?,
+            party_a_code: headers.find(&["当事者種別（当事者A）"])?,
+            party_b_code: headers.find(&["当事者種別（当事者B）"])?,
+            purpose_a_code: headers.find(&["用途別（当事者A）"])?,
+            purpose_b_code: headers.find(&["用途別（当事者B）"])?,
+            vehicle_type_a_code: headers.find(&["車両形状（当事者A）"])?,
+            vehicle_type_b_code: headers.find(&["車両形状（当事者B）"])?,
+            automatic_a_code: headers.find(&["オートマチック車（当事者A）"])?,
+            automatic_b_code: headers.find(&["オートマチック車（当事者B）"])?,
+            support_car_a_code: headers.find(&["サポカー（当事者A）"])?,
+            support_car_b_code: headers.find(&["サポカー（当事者B）"])?,
+            speed_regulation_a_code: headers.find(&["速度規制（指定のみ）（当事者A）"])?,
+            speed_regulation_b_code: headers.find(&["速度規制（指定のみ）（当事者B）"])?,
+            collision_part_a: headers.find(&["車両の衝突部位（当事者A）"])?,
+            collision_part_b: headers.find(&["車両の衝突部位（当事者B）"])?,
+            vehicle_damage_a_code: headers.find(&["車両の損壊程度（当事者A）"])?,
+            vehicle_damage_b_code: headers.find(&["車両の損壊程度（当事者B）"])?,
+            airbag_a_code: headers.find(&["エアバッグの装備（当事者A）"])?,
+            airbag_b_code: headers.find(&["エアバッグの装備（当事者B）"])?,
+            side_airbag_a_code: headers.find(&["サイドエアバッグの装備（当事者A）"])?,
+            side_airbag_b_code: headers.find(&["サイドエアバッグの装備（当事者B）"])?,
+            injury_a_code: headers.find(&["人身損傷程度（当事者A）"])?,
+            injury_b_code: headers.find(&["人身損傷程度（当事者B）"])?,
+            latitude: headers.find(&["地点緯度（北緯）"])?,
+            longitude: headers.find(&["地点経度（東経）"])?,
+            week_code: headers.find(&["曜日（発生年月日）"])?,
+            holiday_code: headers.find(&["祝日（発生年月日）"])?,
+            cognitive_days_a: headers.find_optional(&["認知機能検査経過日数（当事者A）"]),
+            cognitive_days_b: headers.find_optional(&["認知機能検査経過日数（当事者B）"]),
+            driving_practice_a_code: headers.find_optional(&["運転練習の方法（当事者A）"]),
+            driving_practice_b_code: headers.find_optional(&["運転練習の方法（当事者B）"]),
+        })
+    }
+}
+
+/// 補充票の列の位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupportColumns {
+    /// 都道府県コード
+    pub prefecture_code: usize,
+    /// 警察署等コード
+    pub police_station_code: usize,
+    /// 本票番号
+    pub main_number: usize,
+    /// 補充票番号
+    pub sub_number: usize,
+    /// 当事者種別
+    pub party_code: usize,
+    /// 用途別
+    pub purpose_code: usize,
+    /// 車両形状
+    pub vehicle_type_code: usize,
+    /// 乗車別
+    pub riding_type_code: usize,
+    /// 乗車等の区分
+    pub riding_class_code: usize,
+    /// サポカー
+    pub support_car_code: usize,
+    /// エアバッグの装備
+    pub airbag_code: usize,
+    /// サイドエアバッグの装備
+    pub side_airbag_code: usize,
+    /// 人身損傷程度
+    pub injury_code: usize,
+    /// 車両の衝突部位
+    pub collision_part: usize,
+    /// 車両の損壊程度
+    pub vehicle_damage_code: usize,
+}
+
+impl SupportColumns {
+    /// 補充票の列の位置を決定する。
+    ///
+    /// レイアウトが指定された場合はそのレイアウトの列の位置を、指定されなかった場合は
+    /// ヘッダ行から列の位置を決定する。
+    ///
+    /// # 引数
+    ///
+    /// * `layout` - レイアウト
+    /// * `headers` - 補充票のヘッダ行
+    ///
+    /// # 戻り値
+    ///
+    /// 補充票の列の位置
+    pub fn resolve(layout: Option<Layout>, headers: &csv::StringRecord) -> anyhow::Result<Self> {
+        match layout {
+            Some(layout) => Ok(layout.support_columns()),
+            None => Self::from_headers(headers),
+        }
+    }
+
+    /// 補充票のヘッダ行から列の位置を決定する。
+    ///
+    /// # 引数
+    ///
+    /// * `headers` - 補充票のヘッダ行
+    ///
+    /// # 戻り値
+    ///
+    /// 補充票の列の位置
+    pub fn from_headers(headers: &csv::StringRecord) -> anyhow::Result<Self> {
+        let headers = HeaderIndexes::new(headers);
+
+        Ok(Self {
+            prefecture_code: headers.find(&["都道府県コード"])?,
+            police_station_code: headers.find(&["警察署等コード", "警察署コード"])?,
+            main_number: headers.find(&["本票番号"])?,
+            sub_number: headers.find(&["補充票番号"])?,
+            party_code: headers.find(&["当事者種別"])?,
+            purpose_code: headers.find(&["用途別"])?,
+            vehicle_type_code: headers.find(&["車両形状"])?,
+            riding_type_code: headers.find(&["乗車別"])?,
+            riding_class_code: headers.find(&["乗車等の区分"])?,
+            support_car_code: headers.find(&["サポカー"])?,
+            airbag_code: headers.find(&["エアバッグの装備"])?,
+            side_airbag_code: headers.find(&["サイドエアバッグの装備"])?,
+            injury_code: headers.find(&["人身損傷程度"])?,
+            collision_part: headers.find(&["車両の衝突部位"])?,
+            vehicle_damage_code: headers.find(&["車両の損壊程度"])?,
+        })
+    }
+}
+
+/// 高速票の列の位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HighwayColumns {
+    /// 都道府県コード
+    pub prefecture_code: usize,
+    /// 警察署等コード
+    pub police_station_code: usize,
+    /// 本票番号
+    pub main_number: usize,
+    /// 上下線
+    pub up_down_code: usize,
+    /// キロポスト（キロ）
+    pub kilopost_km: usize,
+    /// キロポスト（メートル）
+    pub kilopost_m: usize,
+    /// 道路構造
+    pub road_structure_code: usize,
+    /// 車線（当事者A）
+    pub lane_a_code: usize,
+    /// 車線（当事者B）
+    pub lane_b_code: usize,
+}
+
+impl HighwayColumns {
+    /// 高速票の列の位置を決定する。
+    ///
+    /// レイアウトが指定された場合はそのレイアウトの列の位置を、指定されなかった場合は
+    /// ヘッダ行から列の位置を決定する。
+    ///
+    /// # 引数
+    ///
+    /// * `layout` - レイアウト
+    /// * `headers` - 高速票のヘッダ行
+    ///
+    /// # 戻り値
+    ///
+    /// 高速票の列の位置
+    pub fn resolve(layout: Option<Layout>, headers: &csv::StringRecord) -> anyhow::Result<Self> {
+        match layout {
+            Some(layout) => Ok(layout.highway_columns()),
+            None => Self::from_headers(headers),
+        }
+    }
+
+    /// 高速票のヘッダ行から列の位置を決定する。
+    ///
+    /// # 引数
+    ///
+    /// * `headers` - 高速票のヘッダ行
+    ///
+    /// # 戻り値
+    ///
+    /// 高速票の列の位置
+    pub fn from_headers(headers: &csv::StringRecord) -> anyhow::Result<Self> {
+        let headers = HeaderIndexes::new(headers);
+
+        Ok(Self {
+            prefecture_code: headers.find(&["都道府県コード"])?,
+            police_station_code: headers.find(&["警察署等コード", "警察署コード"])?,
+            main_number: headers.find(&["本票番号"])?,
+            up_down_code: headers.find(&["上下線"])?,
+            kilopost_km: headers.find(&["キロポスト（キロ）", "キロポストキロ"])?,
+            kilopost_m: headers.find(&["キロポスト（メートル）", "キロポストメートル"])?,
+            road_structure_code: headers.find(&["道路構造"])?,
+            lane_a_code: headers.find(&["車線（当事者A）"])?,
+            lane_b_code: headers.find(&["車線（当事者B）"])?,
+        })
+    }
+}
+
+/// 正規化したヘッダ名と列の位置の対応
+struct HeaderIndexes(HashMap<String, usize>);
+
+impl HeaderIndexes {
+    fn new(headers: &csv::StringRecord) -> Self {
+        let indexes = headers
+            .iter()
+            .enumerate()
+            .map(|(index, header)| (normalize_header(header), index))
+            .collect();
+
+        Self(indexes)
+    }
+
+    fn find_optional(&self, names: &[&str]) -> Option<usize> {
+        names
+            .iter()
+            .find_map(|name| self.0.get(&normalize_header(name)).copied())
+    }
+
+    fn find(&self, names: &[&str]) -> anyhow::Result<usize> {
+        self.find_optional(names).ok_or(anyhow::anyhow!(
+            "ヘッダ行に列({})が見つかりません。",
+            names[0]
+        ))
+    }
+}
+
+/// ヘッダ名を正規化する。
+///
+/// 年によって空白（全角空白を含む）の有無や括弧の全角、半角が異なるため、空白を取り除き、
+/// 半角括弧を全角括弧に置き換える。
+fn normalize_header(header: &str) -> String {
+    header
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '(' => '（',
+            ')' => '）',
+            _ => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIN_HEADERS_2022: &str = "資料区分,都道府県コード,警察署等コード,本票番号,事故内容,死者数,負傷者数,路線コード,地点コード,市区町村コード,発生日時　　年,発生日時　　月,発生日時　　日,発生日時　　時,発生日時　　分,昼夜,日の出時刻　　時,日の出時刻　　分,日の入り時刻　　時,日の入り時刻　　分,天候,地形,路面状態,道路形状,信号機,一時停止規制　標識（当事者A）,一時停止規制　表示（当事者A）,一時停止規制　標識（当事者B）,一時停止規制　表示（当事者B）,車道幅員,道路線形,衝突地点,ゾーン規制,中央分離帯施設等,歩車道区分,事故類型,年齢（当事者A）,年齢（当事者B）,当事者種別（当事者A）,当事者種別（当事者B）,用途別（当事者A）,用途別（当事者B）,車両形状（当事者A）,車両形状（当事者B）,オートマチック車（当事者A）,オートマチック車（当事者B）,サポカー（当事者A）,サポカー（当事者B）,速度規制（指定のみ）（当事者A）,速度規制（指定のみ）（当事者B）,車両の衝突部位（当事者A）,車両の衝突部位（当事者B）,車両の損壊程度（当事者A）,車両の損壊程度（当事者B）,エアバッグの装備（当事者A）,エアバッグの装備（当事者B）,サイドエアバッグの装備（当事者A）,サイドエアバッグの装備（当事者B）,人身損傷程度（当事者A）,人身損傷程度（当事者B）,地点　緯度（北緯）,地点　経度（東経）,曜日(発生年月日),祝日(発生年月日),認知機能検査経過日数（当事者A）,認知機能検査経過日数（当事者B）,運転練習の方法（当事者A）,運転練習の方法（当事者B）";
+
+    const SUPPORT_HEADERS_2022: &str = "資料区分,都道府県コード,警察署等コード,本票番号,補充票番号,当事者種別,用途別,車両形状,乗車別,乗車等の区分,サポカー,エアバッグの装備,サイドエアバッグの装備,人身損傷程度,車両の衝突部位,車両の損壊程度";
+
+    fn headers(line: &str) -> csv::StringRecord {
+        csv::StringRecord::from(line.split(',').collect::<Vec<&str>>())
+    }
+
+    #[test]
+    fn main_columns_from_headers_2022_ok() {
+        let columns = MainColumns::from_headers(&headers(MAIN_HEADERS_2022)).unwrap();
+        assert_eq!(columns, Layout::Y2022.main_columns());
+    }
+
+    #[test]
+    fn main_columns_from_headers_without_driver_columns_ok() {
+        let line = MAIN_HEADERS_2022.split(',').take(64).collect::<Vec<_>>();
+        let columns = MainColumns::from_headers(&csv::StringRecord::from(line)).unwrap();
+        assert_eq!(columns, Layout::Y2019.main_columns());
+        assert!(columns.cognitive_days_a.is_none());
+        assert!(columns.driving_practice_b_code.is_none());
+    }
+
+    #[test]
+    fn main_columns_from_headers_reordered_ok() {
+        let mut line = MAIN_HEADERS_2022.split(',').collect::<Vec<_>>();
+        line.swap(20, 22);
+        let columns = MainColumns::from_headers(&csv::StringRecord::from(line)).unwrap();
+        assert_eq!(columns.weather_code, 22);
+        assert_eq!(columns.surface_condition_code, 20);
+    }
+
+    #[test]
+    fn main_columns_from_headers_missing_column_fail() {
+        let line = MAIN_HEADERS_2022.replace("天候,", "");
+        assert!(MainColumns::from_headers(&headers(&line)).is_err());
+    }
+
+    #[test]
+    fn support_columns_from_headers_2022_ok() {
+        let columns = SupportColumns::from_headers(&headers(SUPPORT_HEADERS_2022)).unwrap();
+        assert_eq!(columns, Layout::Y2022.support_columns());
+    }
+
+    #[test]
+    fn highway_columns_from_headers_ok() {
+        let line = "資料区分,都道府県コード,警察署等コード,本票番号,上下線,キロポスト（キロ）,キロポスト（メートル）,道路構造,車線（当事者A）,車線（当事者B）";
+        let columns = HighwayColumns::from_headers(&headers(line)).unwrap();
+        assert_eq!(columns, Layout::Y2022.highway_columns());
+
+        let mut line = line.split(',').collect::<Vec<_>>();
+        line.swap(8, 9);
+        let columns = HighwayColumns::from_headers(&csv::StringRecord::from(line)).unwrap();
+        assert_eq!(columns.lane_a_code, 9);
+        assert_eq!(columns.lane_b_code, 8);
+    }
+
+    #[test]
+    fn layout_from_year_ok() {
+        use clap::ValueEnum;
+
+        for (year, layout) in [
+            ("2019", Layout::Y2019),
+            ("2020", Layout::Y2019),
+            ("2021", Layout::Y2019),
+            ("2022", Layout::Y2022),
+            ("2023", Layout::Y2022),
+        ] {
+            assert_eq!(Layout::from_str(year, false).unwrap(), layout);
+        }
+        assert!(Layout::from_str("2018", false).is_err());
+    }
+
+    #[test]
+    fn resolve_prefers_layout() {
+        let columns = MainColumns::resolve(Some(Layout::Y2019), &headers("")).unwrap();
+        assert_eq!(columns, Layout::Y2019.main_columns());
+    }
+}
@@ -1,3 +1,12 @@
+pub mod check;
+pub mod convert;
+pub mod datasets;
 pub mod db;
+pub mod export;
+pub mod fetch;
 pub mod files;
 pub mod insert;
+pub mod layouts;
+pub mod sheet;
+#[cfg(test)]
+mod test_utils;
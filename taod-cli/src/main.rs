@@ -1,6 +1,18 @@
+use std::path::PathBuf;
+
 use clap::{Parser, Subcommand};
+use uuid::Uuid;
 
-use taod_cli::insert;
+use code_tables::Language;
+use geometries::Srid;
+use taod_cli::check::{self, ReportFormat};
+use taod_cli::convert::{self, ConvertFormat};
+use taod_cli::datasets;
+use taod_cli::db::OnConflict;
+use taod_cli::export::{self, ExportFormat, ExportOptions};
+use taod_cli::fetch::{self, FetchOptions, NPA_OPEN_DATA_BASE_URL};
+use taod_cli::insert::{self, InsertOptions};
+use taod_cli::layouts::Layout;
 
 /// コマンドライン引数
 #[derive(Debug, Parser)]
@@ -31,12 +43,140 @@ struct Cli {
 enum Commands {
     /// データベースに交通事故データを登録
     ///
-    /// cargo run -- insert <file>
+    /// cargo run -- insert <main-file> <support-file> [highway-file]
     Insert {
         /// 本票ファイル(cp932エンコーディング)
         main_file: String,
         /// 補充票ファイル（cp932エンコーディング）
         support_file: String,
+        /// 高速票ファイル（cp932エンコーディング）
+        highway_file: Option<String>,
+        /// 本票及び補充票のレイアウト（省略した場合はヘッダ行から判定）
+        #[arg(long, value_enum)]
+        layout: Option<Layout>,
+        /// 登録済みの交通事故と重複した場合の動作
+        #[arg(long, value_enum, default_value_t = OnConflict::Fail)]
+        on_conflict: OnConflict,
+        /// COPY文で一括登録
+        #[arg(long)]
+        copy: bool,
+        /// データセットの年（省略した場合は交通事故の発生日時から推定）
+        #[arg(long)]
+        year: Option<i32>,
+        /// 登録できない行を登録除外行テーブル(import_rejects)に記録して、残りの行を登録
+        #[arg(long)]
+        lenient: bool,
+    },
+    /// 交通事故統計情報オープンデータをダウンロード
+    ///
+    /// cargo run -- fetch --year <year>
+    ///
+    /// ダウンロードした本票、補充票及び高速票のファイルパスを標準出力に出力する。
+    Fetch {
+        /// ダウンロードする年
+        #[arg(long)]
+        year: i32,
+        /// オープンデータを公開しているURL（ローカルのHTTPサーバーなど）
+        #[arg(long, default_value = NPA_OPEN_DATA_BASE_URL)]
+        base_url: String,
+        /// ダウンロードしたファイルを保存するディレクトリ
+        #[arg(long, default_value = "cache")]
+        cache_dir: PathBuf,
+        /// ファイル名と期待するSHA-256ハッシュを`sha256sum`形式で記録したファイル
+        #[arg(long)]
+        checksums: Option<PathBuf>,
+        /// キャッシュしたファイルを使用せずにダウンロード
+        #[arg(long)]
+        force: bool,
+    },
+    /// データベースに登録せずに本票と補充票を検査
+    ///
+    /// cargo run -- check <main-file> <support-file> [--format text|json]
+    Check {
+        /// 本票ファイル(cp932エンコーディング)
+        main_file: String,
+        /// 補充票ファイル（cp932エンコーディング）
+        support_file: String,
+        /// 本票及び補充票のレイアウト（省略した場合はヘッダ行から判定）
+        #[arg(long, value_enum)]
+        layout: Option<Layout>,
+        /// 検査結果の出力形式
+        #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+    /// データベースに接続せずに本票と補充票をGeoJSONまたはGeoParquetに変換
+    ///
+    /// cargo run -- convert <main-file> <support-file> -o <output.geojson|output.parquet>
+    Convert {
+        /// 本票ファイル(cp932エンコーディング)
+        main_file: PathBuf,
+        /// 補充票ファイル（cp932エンコーディング）
+        support_file: PathBuf,
+        /// 出力ファイルパス
+        #[arg(short, long)]
+        output: PathBuf,
+        /// 変換形式（省略した場合は出力ファイルの拡張子から判定）
+        #[arg(long, value_enum)]
+        format: Option<ConvertFormat>,
+        /// 本票及び補充票のレイアウト（省略した場合はヘッダ行から判定）
+        #[arg(long, value_enum)]
+        layout: Option<Layout>,
+        /// コードの名前の言語（ja: 日本語、en: 英語）
+        #[arg(long, default_value_t = Language::Ja)]
+        labels: Language,
+    },
+    /// 交通事故と交通事故当事者以外の関与者をGISデータとしてエクスポート
+    ///
+    /// cargo run -- export <output> --format gpkg|fgb|shp|geojsonseq [--where <condition>]
+    ///
+    /// 本票ファイルと補充票ファイルを指定した場合は、データベースに接続せずにファイルから出力する。
+    Export {
+        /// 出力ファイルパス
+        output: PathBuf,
+        /// 出力形式
+        #[arg(long, value_enum)]
+        format: ExportFormat,
+        /// 交通事故を絞り込むSQLの式（交通事故テーブルの別名は`a`）
+        #[arg(long = "where", conflicts_with = "main_file")]
+        where_clause: Option<String>,
+        /// 本票ファイル(cp932エンコーディング)
+        #[arg(long, requires = "support_file")]
+        main_file: Option<PathBuf>,
+        /// 補充票ファイル（cp932エンコーディング）
+        #[arg(long, requires = "main_file")]
+        support_file: Option<PathBuf>,
+        /// 本票及び補充票のレイアウト（省略した場合はヘッダ行から判定）
+        #[arg(long, value_enum)]
+        layout: Option<Layout>,
+        /// コードの名前の言語（ja: 日本語、en: 英語）
+        #[arg(long, default_value_t = Language::Ja)]
+        labels: Language,
+        /// 出力する座標のSRID（4326、3857、6668または平面直角座標系の6669から6687）
+        #[arg(long, default_value_t = Srid::JGD2011, conflicts_with = "main_file")]
+        srid: Srid,
+    },
+    /// データベースに登録したデータセットを管理
+    ///
+    /// cargo run -- datasets list|show <id>|delete <id>
+    Datasets {
+        #[clap(subcommand)]
+        command: DatasetCommands,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum DatasetCommands {
+    /// データセットを一覧表示
+    List,
+    /// データセットの詳細を表示
+    Show {
+        /// データセットID
+        id: Uuid,
+    },
+    /// データセットと、データセットに含まれる交通事故などを削除
+    Delete {
+        /// データセットID
+        id: Uuid,
     },
 }
 
@@ -50,9 +190,76 @@ async fn main() -> anyhow::Result<()> {
         Commands::Insert {
             main_file,
             support_file,
+            highway_file,
+            layout,
+            on_conflict,
+            copy,
+            year,
+            lenient,
+        } => {
+            let options = InsertOptions {
+                layout: *layout,
+                on_conflict: *on_conflict,
+                copy: *copy,
+                year: *year,
+                lenient: *lenient,
+            };
+            insert::insert(main_file, support_file, highway_file.as_ref(), options).await?;
+        }
+        Commands::Fetch {
+            year,
+            base_url,
+            cache_dir,
+            checksums,
+            force,
+        } => {
+            let options = FetchOptions {
+                base_url: base_url.clone(),
+                cache_dir: cache_dir.clone(),
+                checksums: checksums.clone(),
+                force: *force,
+            };
+            fetch::fetch(*year, options).await?;
+        }
+        Commands::Check {
+            main_file,
+            support_file,
+            layout,
+            format,
+        } => check::check(main_file, support_file, *layout, *format)?,
+        Commands::Convert {
+            main_file,
+            support_file,
+            output,
+            format,
+            layout,
+            labels,
+        } => convert::convert(main_file, support_file, output, *format, *layout, *labels).await?,
+        Commands::Export {
+            output,
+            format,
+            where_clause,
+            main_file,
+            support_file,
+            layout,
+            labels,
+            srid,
         } => {
-            insert::insert(main_file, support_file).await?;
+            let options = ExportOptions {
+                format: *format,
+                where_clause: where_clause.clone(),
+                files: main_file.clone().zip(support_file.clone()),
+                layout: *layout,
+                labels: *labels,
+                srid: *srid,
+            };
+            export::export(output, options).await?;
         }
+        Commands::Datasets { command } => match command {
+            DatasetCommands::List => datasets::list().await?,
+            DatasetCommands::Show { id } => datasets::show(*id).await?,
+            DatasetCommands::Delete { id } => datasets::delete(*id).await?,
+        },
     }
 
     Ok(())
@@ -0,0 +1,312 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use serde::Serialize;
+use time::macros::offset;
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time};
+
+/// 交通事故識別子
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RawAccidentIdentifier {
+    /// 都道府県コード
+    pub prefecture_code: String,
+    /// 警察署コード
+    pub police_station_code: String,
+    /// 本票番号
+    pub main_number: i32,
+}
+
+/// SHIFT-JISで符号化されたデータを、UTF-8に逐次変換しながら読み込むリーダー
+///
+/// ファイル全体をメモリに読み込まず、入力バッファに読み込んだ分だけをUTF-8に変換する。
+pub struct ShiftJisReader<R: Read> {
+    /// SHIFT-JISで符号化されたデータを読み込むリーダー
+    inner: R,
+    /// SHIFT-JISをUTF-8に変換するデコーダー
+    decoder: encoding_rs::Decoder,
+    /// 入力バッファ
+    input: Vec<u8>,
+    /// 入力バッファ内で変換していないデータの範囲
+    input_range: (usize, usize),
+    /// 出力バッファ
+    output: Vec<u8>,
+    /// 出力バッファ内で読み出していないデータの範囲
+    output_range: (usize, usize),
+    /// `inner`の終端まで読み込んだかを示すフラグ
+    eof: bool,
+    /// すべてのデータを変換したかを示すフラグ
+    finished: bool,
+}
+
+/// `ShiftJisReader`の入力バッファの大きさ
+const SHIFT_JIS_INPUT_BUFFER_SIZE: usize = 8 * 1024;
+
+impl<R: Read> ShiftJisReader<R> {
+    /// SHIFT-JISリーダーを構築する。
+    ///
+    /// # 引数
+    ///
+    /// * `inner` - SHIFT-JISで符号化されたデータを読み込むリーダー
+    ///
+    /// # 戻り値
+    ///
+    /// SHIFT-JISリーダー
+    pub fn new(inner: R) -> Self {
+        let decoder = encoding_rs::SHIFT_JIS.new_decoder();
+        // SHIFT-JISの1バイトはUTF-8で最大3バイトになる
+        let output_size = decoder
+            .max_utf8_buffer_length(SHIFT_JIS_INPUT_BUFFER_SIZE)
+            .unwrap_or(SHIFT_JIS_INPUT_BUFFER_SIZE * 3);
+
+        Self {
+            inner,
+            decoder,
+            input: vec![0; SHIFT_JIS_INPUT_BUFFER_SIZE],
+            input_range: (0, 0),
+            output: vec![0; output_size],
+            output_range: (0, 0),
+            eof: false,
+            finished: false,
+        }
+    }
+}
+
+impl<R: Read> Read for ShiftJisReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            // 変換済みのデータがあれば返す
+            let (start, end) = self.output_range;
+            if start < end {
+                let size = buf.len().min(end - start);
+                buf[..size].copy_from_slice(&self.output[start..start + size]);
+                self.output_range.0 += size;
+                return Ok(size);
+            }
+            if self.finished {
+                return Ok(0);
+            }
+            // 入力バッファを変換し終えていれば、次のデータを読み込む
+            if self.input_range.0 == self.input_range.1 && !self.eof {
+                let size = self.inner.read(&mut self.input)?;
+                self.input_range = (0, size);
+                self.eof = size == 0;
+            }
+            let (start, end) = self.input_range;
+            let (result, read, written, _) =
+                self.decoder
+                    .decode_to_utf8(&self.input[start..end], &mut self.output, self.eof);
+            self.input_range.0 += read;
+            self.output_range = (0, written);
+            if self.eof && result == encoding_rs::CoderResult::InputEmpty {
+                self.finished = true;
+            }
+        }
+    }
+}
+
+/// SHIFT-JISで符号化されたCSVファイルを開く。
+///
+/// # 引数
+///
+/// * `path` - CSVファイルのパス
+///
+/// # 戻り値
+///
+/// CSVリーダー
+pub(crate) fn open_sheet<P: AsRef<Path>>(
+    path: P,
+) -> anyhow::Result<csv::Reader<ShiftJisReader<File>>> {
+    let path = path.as_ref();
+    let file =
+        File::open(path).map_err(|e| anyhow::anyhow!("{}を開けません。{}", path.display(), e))?;
+
+    Ok(csv::ReaderBuilder::new()
+        .has_headers(true)
+        .trim(csv::Trim::All)
+        .from_reader(ShiftJisReader::new(file)))
+}
+
+/// 票の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sheet {
+    /// 本票
+    Main,
+    /// 補充票
+    Support,
+    /// 高速票
+    Highway,
+}
+
+impl Sheet {
+    /// 票の名前を返す。
+    ///
+    /// # 戻り値
+    ///
+    /// 票の名前
+    pub fn name(&self) -> &'static str {
+        match self {
+            Sheet::Main => "本票",
+            Sheet::Support => "補充票",
+            Sheet::Highway => "高速票",
+        }
+    }
+
+    /// 票の種類を表すコードを返す。
+    ///
+    /// # 戻り値
+    ///
+    /// 票の種類を表すコード
+    pub fn code(&self) -> &'static str {
+        match self {
+            Sheet::Main => "main",
+            Sheet::Support => "support",
+            Sheet::Highway => "highway",
+        }
+    }
+}
+
+/// 路線コードが、4桁の路線コードと1桁の路線区分コードを連結した形式か確認する。
+pub(crate) fn is_route_format(route: &str) -> bool {
+    route.len() == 5 && route.bytes().all(|b| b.is_ascii_digit())
+}
+
+pub(crate) fn offset_datetime(
+    year: i32,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+) -> anyhow::Result<OffsetDateTime> {
+    let month =
+        Month::try_from(month).map_err(|_| anyhow::anyhow!("月({})が範囲外です。", month))?;
+    let date = Date::from_calendar_date(year, month as time::Month, day)?;
+    let time = Time::from_hms(hour, minute, 0u8)
+        .map_err(|_| anyhow::anyhow!("時刻({}:{})が範囲外です。", hour, minute))?;
+    let datetime = PrimitiveDateTime::new(date, time);
+
+    Ok(datetime.assume_offset(offset!(+9)))
+}
+
+pub(crate) fn time(hour: u8, minute: u8) -> anyhow::Result<Time> {
+    Time::from_hms(hour, minute, 0u8)
+        .map_err(|_| anyhow::anyhow!("時刻({}:{})が範囲外です。", hour, minute))
+}
+
+pub(crate) fn dms_to_latitude(hms: &str) -> anyhow::Result<f64> {
+    dms_to_degree(hms, 2)
+}
+
+pub(crate) fn dms_to_longitude(hms: &str) -> anyhow::Result<f64> {
+    dms_to_degree(hms, 3)
+}
+
+/// 度の桁数が`degree_digits`桁、分が2桁、秒が1000倍された度分秒を度に変換する。
+fn dms_to_degree(hms: &str, degree_digits: usize) -> anyhow::Result<f64> {
+    if hms.len() <= degree_digits + 2 || !hms.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(anyhow::anyhow!("度分秒({})の形式が不正です。", hms));
+    }
+    let degree = hms[..degree_digits].parse::<f64>()?;
+    let minute = hms[degree_digits..degree_digits + 2].parse::<f64>()?;
+    let second = hms[degree_digits + 2..].parse::<f64>()? / 1000.0;
+
+    Ok(degree + minute / 60.0 + second / 3600.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_datetime_ok() {
+        let datetime = offset_datetime(2021, 1, 1, 10, 30).unwrap();
+        assert_eq!(datetime.year(), 2021);
+        assert_eq!(datetime.month(), Month::January);
+        assert_eq!(datetime.day(), 1);
+        assert_eq!(datetime.hour(), 10);
+        assert_eq!(datetime.minute(), 30);
+        assert_eq!(datetime.second(), 0);
+        assert_eq!(datetime.offset(), offset!(+9));
+    }
+
+    #[test]
+    fn offset_datetime_fail() {
+        assert!(offset_datetime(2021, 13, 1, 10, 30).is_err());
+        assert!(offset_datetime(2021, 1, 32, 10, 30).is_err());
+        assert!(offset_datetime(2021, 1, 1, 24, 30).is_err());
+        assert!(offset_datetime(2021, 1, 1, 10, 60).is_err());
+    }
+
+    #[test]
+    fn time_ok() {
+        let time = time(10, 30).unwrap();
+        assert_eq!(time.hour(), 10);
+        assert_eq!(time.minute(), 30);
+        assert_eq!(time.second(), 0);
+    }
+
+    #[test]
+    fn time_fail() {
+        assert!(time(24, 30).is_err());
+        assert!(time(10, 60).is_err());
+    }
+
+    #[test]
+    fn dms_to_latitude_ok() {
+        assert_eq!(dms_to_latitude("350000000").unwrap(), 35.0);
+        assert_eq!(dms_to_latitude("353000000").unwrap(), 35.0 + 30.0 / 60.0);
+        assert!(dms_to_latitude("35").is_err());
+        assert!(dms_to_latitude("35３00000").is_err());
+        let result = dms_to_latitude("353030123").unwrap();
+        let expected = 35.0 + 30.0 / 60.0 + 30.123 / 3600.0;
+        assert!(
+            (result - expected).abs() < 1e-11,
+            "expected: {}, result: {}",
+            expected,
+            result
+        );
+    }
+
+    #[test]
+    fn dms_to_longitude_ok() {
+        assert_eq!(dms_to_longitude("1350000000").unwrap(), 135.0);
+        assert_eq!(dms_to_longitude("1353000000").unwrap(), 135.0 + 30.0 / 60.0);
+        let result = dms_to_longitude("1353030123").unwrap();
+        let expected = 135.0 + 30.0 / 60.0 + 30.123 / 3600.0;
+        assert!(
+            (result - expected).abs() < 1e-11,
+            "expected: {}, result: {}",
+            expected,
+            result
+        );
+    }
+
+    /// 1バイトずつ返すリーダー
+    struct OneByteReader<'a>(&'a [u8]);
+
+    impl<'a> Read for OneByteReader<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            match self.0.split_first() {
+                Some((byte, rest)) if !buf.is_empty() => {
+                    buf[0] = *byte;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    #[test]
+    fn shift_jis_reader_ok() {
+        let text = "都道府県コード,警察署等コード\n10,101\n".repeat(1000);
+        let (encoded, _, _) = encoding_rs::SHIFT_JIS.encode(&text);
+        let mut decoded = String::new();
+        ShiftJisReader::new(OneByteReader(&encoded))
+            .read_to_string(&mut decoded)
+            .unwrap();
+
+        assert_eq!(decoded, text);
+    }
+}
@@ -0,0 +1,18 @@
+use std::path::PathBuf;
+
+/// テストごとに空の一時ディレクトリを作成する。
+///
+/// # 引数
+///
+/// * `name` - テストごとに一意なディレクトリの名前
+///
+/// # 戻り値
+///
+/// 一時ディレクトリのパス
+pub(crate) fn temp_dir_for_test(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("taod-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    dir
+}
@@ -0,0 +1,357 @@
+use code_tables::Language;
+
+use crate::language::name_column;
+
+/// コード表
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct CodeTable {
+    /// テーブル名
+    pub table: &'static str,
+    /// コード表の名前
+    pub name: &'static str,
+    /// コード表の英語の名前
+    #[serde(skip)]
+    pub name_en: &'static str,
+    /// コード表の行を取得するSQL
+    ///
+    /// `{table}`はテーブル名、`{name}`は言語に対応する名前の列に置き換える。
+    #[serde(skip)]
+    sql: &'static str,
+}
+
+/// 言語に対応した名前を持つコード表
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct LocalizedCodeTable {
+    /// テーブル名
+    pub table: &'static str,
+    /// コード表の名前
+    pub name: &'static str,
+}
+
+impl CodeTable {
+    /// コード表の行を取得するSQLを返す。
+    ///
+    /// # 引数
+    ///
+    /// * `language` - 行の名前の言語
+    ///
+    /// # 戻り値
+    ///
+    /// コード表の行を取得するSQL
+    pub fn sql(&self, language: Language) -> String {
+        self.sql
+            .replace("{table}", self.table)
+            .replace("{name}", &name_column("name", language))
+    }
+
+    /// 言語に対応した名前を持つコード表を返す。
+    ///
+    /// # 引数
+    ///
+    /// * `language` - コード表の名前の言語
+    ///
+    /// # 戻り値
+    ///
+    /// 言語に対応した名前を持つコード表
+    pub fn localize(&self, language: Language) -> LocalizedCodeTable {
+        let name = match language {
+            Language::Ja => self.name,
+            Language::En => self.name_en,
+        };
+        LocalizedCodeTable {
+            table: self.table,
+            name,
+        }
+    }
+}
+
+/// コードと名前のみを記録するコード表の行を取得するSQL
+const SIMPLE_CODE_TABLE_SQL: &str = "SELECT code::text code, {name} name, \
+    NULL::text jis_code, NULL::text prefecture_code, NULL::text upper_code \
+    FROM {table} ORDER BY code";
+
+/// コード表の一覧
+pub const CODE_TABLES: &[CodeTable] = &[
+    CodeTable {
+        table: "prefectures",
+        name: "都道府県",
+        name_en: "Prefectures",
+        sql: "SELECT code::text code, {name} name, jis_code::text jis_code, NULL::text prefecture_code, NULL::text upper_code FROM prefectures ORDER BY code",
+    },
+    CodeTable {
+        table: "police_stations",
+        name: "警察署",
+        name_en: "Police stations",
+        sql: "SELECT CONCAT(prefecture_code, police_station_code) code, police_station_name name, NULL::text jis_code, prefecture_code::text prefecture_code, NULL::text upper_code FROM police_stations ORDER BY 1",
+    },
+    CodeTable {
+        table: "accident_details",
+        name: "事故内容",
+        name_en: "Accident details",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "routes",
+        name: "路線",
+        name_en: "Routes",
+        sql: "SELECT lower_code::text code, {name} name, NULL::text jis_code, NULL::text prefecture_code, upper_code::text upper_code FROM routes ORDER BY code",
+    },
+    CodeTable {
+        table: "route_classes",
+        name: "路線区分",
+        name_en: "Route classes",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "cities",
+        name: "市区町村",
+        name_en: "Cities",
+        sql: "SELECT city_jis_code::text code, city_name name, NULL::text jis_code, prefecture_jis_code::text prefecture_code, NULL::text upper_code FROM cities ORDER BY code",
+    },
+    CodeTable {
+        table: "day_nights",
+        name: "昼夜",
+        name_en: "Day/night",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "weathers",
+        name: "天候",
+        name_en: "Weather",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "districts",
+        name: "地区（地形）",
+        name_en: "Districts (terrain)",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "surface_conditions",
+        name: "路面状態",
+        name_en: "Road surface conditions",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "road_models",
+        name: "道路形状",
+        name_en: "Road shapes",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "traffic_signals",
+        name: "信号機",
+        name_en: "Traffic signals",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "stop_regulation_signs",
+        name: "一時停止規制標識",
+        name_en: "Stop regulation signs",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "stop_regulation_displays",
+        name: "一時停止規制表示",
+        name_en: "Stop regulation markings",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "road_widths",
+        name: "車道幅員",
+        name_en: "Roadway widths",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "road_alignments",
+        name: "道路線形",
+        name_en: "Road alignments",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "collision_points",
+        name: "衝突地点",
+        name_en: "Collision points",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "zone_regulations",
+        name: "ゾーン規制",
+        name_en: "Zone regulations",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "central_separations",
+        name: "中央分離帯施設",
+        name_en: "Central separation facilities",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "road_segmentations",
+        name: "歩車道区分",
+        name_en: "Pedestrian and roadway separation",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "accident_types",
+        name: "事故類型",
+        name_en: "Accident types",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "ages",
+        name: "年齢",
+        name_en: "Ages",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "parties",
+        name: "当事者種別",
+        name_en: "Party types",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "purposes",
+        name: "用途",
+        name_en: "Purposes",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "vehicle_types",
+        name: "車両形状",
+        name_en: "Vehicle types",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "automatics",
+        name: "オートマチック車",
+        name_en: "Automatic vehicles",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "support_cars",
+        name: "サポカー",
+        name_en: "Support cars",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "speed_regulations",
+        name: "速度規制（指定のみ）",
+        name_en: "Speed regulations (designated only)",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "vehicle_damages",
+        name: "車両の損壊程度",
+        name_en: "Vehicle damage levels",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "airbags",
+        name: "エアバッグの装備",
+        name_en: "Airbags",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "side_airbags",
+        name: "サイドエアバッグの装備",
+        name_en: "Side airbags",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "injuries",
+        name: "人身損傷程度",
+        name_en: "Injury levels",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "weeks",
+        name: "曜日",
+        name_en: "Days of the week",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "holidays",
+        name: "祝日",
+        name_en: "Holidays",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "driving_practices",
+        name: "運転練習の方法",
+        name_en: "Driving practice methods",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "riding_types",
+        name: "乗車別",
+        name_en: "Riding types",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "riding_classes",
+        name: "乗車等の区分",
+        name_en: "Riding classes",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "up_downs",
+        name: "上下線",
+        name_en: "Inbound/outbound",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "road_structures",
+        name: "道路構造",
+        name_en: "Road structures",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+    CodeTable {
+        table: "lanes",
+        name: "車線",
+        name_en: "Lanes",
+        sql: SIMPLE_CODE_TABLE_SQL,
+    },
+];
+
+/// テーブル名からコード表を検索する。
+///
+/// # 引数
+///
+/// * `table` - テーブル名
+///
+/// # 戻り値
+///
+/// コード表、存在しない場合は`None`
+pub fn find_code_table(table: &str) -> Option<&'static CodeTable> {
+    CODE_TABLES
+        .iter()
+        .find(|code_table| code_table.table == table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_code_table_ok() {
+        let weathers = find_code_table("weathers").unwrap();
+        assert_eq!(weathers.name, "天候");
+        assert_eq!(
+            weathers.sql(Language::Ja),
+            "SELECT code::text code, name name, \
+            NULL::text jis_code, NULL::text prefecture_code, NULL::text upper_code \
+            FROM weathers ORDER BY code"
+        );
+        assert_eq!(
+            weathers.sql(Language::En),
+            "SELECT code::text code, COALESCE(name_en, name) name, \
+            NULL::text jis_code, NULL::text prefecture_code, NULL::text upper_code \
+            FROM weathers ORDER BY code"
+        );
+        assert_eq!(weathers.localize(Language::En).name, "Weather");
+        assert!(find_code_table("police_stations").is_some());
+        assert!(find_code_table("accidents").is_none());
+        assert!(find_code_table("weathers; DROP TABLE accidents").is_none());
+    }
+}
@@ -0,0 +1,379 @@
+use time::macros::{format_description, offset};
+
+use code_tables::Language;
+use geometries::Srid;
+
+use crate::models::ExportAccident;
+
+/// UTF-8のBOM
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// 出力バッファの内容をクライアントに送信するサイズ
+pub const EXPORT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// 交通事故のエクスポートの文字エンコーディング
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+pub enum ExportEncoding {
+    /// UTF-8
+    #[default]
+    #[serde(rename = "utf-8", alias = "utf8")]
+    Utf8,
+    /// BOM付きUTF-8
+    #[serde(rename = "utf-8-bom", alias = "utf8bom")]
+    Utf8Bom,
+    /// CP932（交通事故統計情報オープンデータと同じ文字エンコーディング）
+    #[serde(rename = "cp932", alias = "shift_jis")]
+    Cp932,
+}
+
+impl ExportEncoding {
+    /// レスポンスの`Content-Type`ヘッダに指定する値を返す。
+    ///
+    /// # 戻り値
+    ///
+    /// `Content-Type`ヘッダの値
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Utf8 | Self::Utf8Bom => "text/csv; charset=utf-8",
+            Self::Cp932 => "text/csv; charset=Shift_JIS",
+        }
+    }
+
+    /// 出力の先頭に付与するバイト列を返す。
+    ///
+    /// # 戻り値
+    ///
+    /// 出力の先頭に付与するバイト列
+    pub fn preamble(&self) -> &'static [u8] {
+        match self {
+            Self::Utf8Bom => UTF8_BOM,
+            Self::Utf8 | Self::Cp932 => b"",
+        }
+    }
+
+    /// UTF-8で符号化されたバイト列を、文字エンコーディングに従って変換する。
+    ///
+    /// CP932で表現できない文字は、数値文字参照に置き換える。
+    ///
+    /// # 引数
+    ///
+    /// * `data` - UTF-8で符号化されたバイト列
+    ///
+    /// # 戻り値
+    ///
+    /// 変換したバイト列
+    pub fn encode(&self, data: Vec<u8>) -> Result<Vec<u8>, std::string::FromUtf8Error> {
+        match self {
+            Self::Utf8 | Self::Utf8Bom => Ok(data),
+            Self::Cp932 => {
+                let text = String::from_utf8(data)?;
+                Ok(encoding_rs::SHIFT_JIS.encode(&text).0.into_owned())
+            }
+        }
+    }
+}
+
+/// 交通事故エクスポートクエリパラメータ
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize)]
+pub struct ExportParams {
+    /// 文字エンコーディング
+    pub encoding: Option<ExportEncoding>,
+}
+
+/// エクスポートする交通事故に追加する列
+///
+/// 交通事故を取得するSELECT句に続けて、`ACCIDENT_FROM_SQL`の前に追加する。
+pub const EXPORT_COLUMNS_SQL: &str = r#",
+        a.accident_detail_code,
+        a.day_night_code,
+        a.road_model_code,
+        a.accident_type_code,
+        a.party_a_code,
+        a.age_a_code,
+        a.party_b_code,
+        a.age_b_code"#;
+
+/// CSVの列数
+pub const CSV_COLUMNS: usize = 32;
+
+/// CSVのヘッダ行
+pub const CSV_HEADERS: [&str; CSV_COLUMNS] = [
+    "事故ID",
+    "発生日時",
+    "都道府県コード",
+    "都道府県名",
+    "警察署コード",
+    "警察署名",
+    "市区町村コード",
+    "市区町村名",
+    "死者数",
+    "負傷者数",
+    "事故内容コード",
+    "事故内容",
+    "昼夜コード",
+    "昼夜",
+    "天候コード",
+    "天候",
+    "路面状態コード",
+    "路面状態",
+    "道路形状コード",
+    "道路形状",
+    "事故類型コード",
+    "事故類型",
+    "当事者種別コード（当事者A）",
+    "当事者種別（当事者A）",
+    "年齢コード（当事者A）",
+    "年齢（当事者A）",
+    "当事者種別コード（当事者B）",
+    "当事者種別（当事者B）",
+    "年齢コード（当事者B）",
+    "年齢（当事者B）",
+    "緯度",
+    "経度",
+];
+
+/// 英語のCSVのヘッダ行
+pub const CSV_HEADERS_EN: [&str; CSV_COLUMNS] = [
+    "Accident ID",
+    "Occurred at",
+    "Prefecture code",
+    "Prefecture",
+    "Police station code",
+    "Police station",
+    "City code",
+    "City",
+    "Number of deaths",
+    "Number of injuries",
+    "Accident detail code",
+    "Accident detail",
+    "Day/night code",
+    "Day/night",
+    "Weather code",
+    "Weather",
+    "Surface condition code",
+    "Surface condition",
+    "Road model code",
+    "Road model",
+    "Accident type code",
+    "Accident type",
+    "Party code (party A)",
+    "Party (party A)",
+    "Age code (party A)",
+    "Age (party A)",
+    "Party code (party B)",
+    "Party (party B)",
+    "Age code (party B)",
+    "Age (party B)",
+    "Latitude",
+    "Longitude",
+];
+
+/// 言語と空間参照系に対応するCSVのヘッダ行を返す。
+///
+/// 投影座標系の場合は、緯度と経度の列を東距（東方向の座標）と北距（北方向の座標）の列に置き換える。
+/// 測量の平面直角座標系のX座標（北方向）とY座標（東方向）と取り違えないように、列名にX、Yを使用しない。
+///
+/// # 引数
+///
+/// * `language` - ヘッダ行の言語
+/// * `srid` - 発生場所の空間参照系
+///
+/// # 戻り値
+///
+/// CSVのヘッダ行
+pub fn csv_headers(language: Language, srid: Srid) -> [&'static str; CSV_COLUMNS] {
+    let mut headers = match language {
+        Language::Ja => CSV_HEADERS,
+        Language::En => CSV_HEADERS_EN,
+    };
+    if !srid.is_geographic() {
+        let projected = match language {
+            Language::Ja => ["東距", "北距"],
+            Language::En => ["Easting", "Northing"],
+        };
+        headers[CSV_COLUMNS - 2..].copy_from_slice(&projected);
+    }
+
+    headers
+}
+
+/// 表計算ソフトで開くことを想定して、改行をCRLFとするCSVライターを作成する。
+///
+/// # 引数
+///
+/// * `buffer` - CSVを書き込むバッファ
+///
+/// # 戻り値
+///
+/// CSVライター
+pub fn csv_writer(buffer: Vec<u8>) -> csv::Writer<Vec<u8>> {
+    csv::WriterBuilder::new()
+        .terminator(csv::Terminator::CRLF)
+        .from_writer(buffer)
+}
+
+/// 交通事故をCSVのレコードに変換する。
+///
+/// 発生日時は、表計算ソフトで日時として扱えるように日本標準時の`YYYY-MM-DD HH:MM`形式とする。
+/// 事故内容や当事者などのコードの名前は、コード表から言語に対応する名前を出力する。
+///
+/// # 引数
+///
+/// * `export` - エクスポートする交通事故
+/// * `language` - コードの名前の言語
+/// * `srid` - 発生場所の空間参照系
+///
+/// # 戻り値
+///
+/// CSVのレコード
+pub fn accident_csv_record(
+    export: &ExportAccident,
+    language: Language,
+    srid: Srid,
+) -> [String; CSV_COLUMNS] {
+    let accident = &export.accident;
+    let (x, y) = match &accident.location.geometry {
+        Some(geo_types::Geometry::Point(point)) => (point.x().to_string(), point.y().to_string()),
+        _ => (String::new(), String::new()),
+    };
+    // 地理座標系は緯度、経度の順、投影座標系は東距、北距の順とする
+    let (first, second) = if srid.is_geographic() { (y, x) } else { (x, y) };
+    let label = |name: Option<&str>| name.unwrap_or_default().to_string();
+
+    [
+        accident.id.to_string(),
+        accident
+            .occurred_at
+            .to_offset(offset!(+9))
+            .format(format_description!("[year]-[month]-[day] [hour]:[minute]"))
+            .unwrap(),
+        accident.prefecture_code.to_string(),
+        accident.prefecture_name.clone(),
+        accident.police_station_code.to_string(),
+        accident.police_station_name.clone(),
+        accident.city_code.to_string(),
+        accident.city_name.clone(),
+        accident.number_of_deaths.to_string(),
+        accident.number_of_injuries.to_string(),
+        export.accident_detail_code.to_string(),
+        label(export.accident_detail_code.label(language)),
+        export.day_night_code.to_string(),
+        label(export.day_night_code.label(language)),
+        accident.weather_code.to_string(),
+        accident.weather_name.clone(),
+        accident.surface_condition_code.to_string(),
+        accident.surface_condition_name.clone(),
+        export.road_model_code.to_string(),
+        label(export.road_model_code.label(language)),
+        export.accident_type_code.to_string(),
+        label(export.accident_type_code.label(language)),
+        export.party_a_code.to_string(),
+        label(export.party_a_code.label(language)),
+        export.age_a_code.to_string(),
+        label(export.age_a_code.label(language)),
+        export.party_b_code.to_string(),
+        label(export.party_b_code.label(language)),
+        export.age_b_code.to_string(),
+        label(export.age_b_code.label(language)),
+        first,
+        second,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use code_tables::{
+        AccidentDetailCode, AccidentTypeCode, AgeCode, CityCode, DayNightCode, PartyCode,
+        PoliceStationCode, PrefectureJisCode, RoadModelCode, SurfaceConditionCode, WeatherCode,
+    };
+
+    use crate::models::Accident;
+
+    use super::*;
+
+    #[test]
+    fn export_params_ok() {
+        let params: ExportParams = serde_json::from_str(r#"{"encoding": "utf-8-bom"}"#).unwrap();
+        assert_eq!(params.encoding, Some(ExportEncoding::Utf8Bom));
+        let params: ExportParams = serde_json::from_str(r#"{"encoding": "cp932"}"#).unwrap();
+        assert_eq!(params.encoding, Some(ExportEncoding::Cp932));
+        assert!(serde_json::from_str::<ExportParams>(r#"{"encoding": "utf-16"}"#).is_err());
+    }
+
+    #[test]
+    fn export_encoding_ok() {
+        assert_eq!(ExportEncoding::Utf8Bom.preamble(), UTF8_BOM);
+        let data = "天候".as_bytes().to_vec();
+        assert_eq!(ExportEncoding::Utf8.encode(data.clone()).unwrap(), data);
+        // 「天候」のCP932表現
+        assert_eq!(
+            ExportEncoding::Cp932.encode(data).unwrap(),
+            vec![0x93, 0x56, 0x8C, 0xF3]
+        );
+        assert!(ExportEncoding::Cp932.encode(vec![0xFF]).is_err());
+    }
+
+    #[test]
+    fn csv_headers_ok() {
+        assert_eq!(csv_headers(Language::Ja, Srid::JGD2011)[15], "天候");
+        assert_eq!(csv_headers(Language::En, Srid::JGD2011)[15], "Weather");
+        assert_eq!(
+            csv_headers(Language::Ja, Srid::WGS84)[CSV_COLUMNS - 2..],
+            ["緯度", "経度"]
+        );
+        let srid = Srid::try_from(6677).unwrap();
+        assert_eq!(
+            csv_headers(Language::Ja, srid)[CSV_COLUMNS - 2..],
+            ["東距", "北距"]
+        );
+        assert_eq!(
+            csv_headers(Language::En, srid)[CSV_COLUMNS - 2..],
+            ["Easting", "Northing"]
+        );
+    }
+
+    #[test]
+    fn accident_csv_record_ok() {
+        let export = ExportAccident {
+            accident: Accident {
+                id: uuid::Uuid::nil(),
+                prefecture_code: PrefectureJisCode::try_from("01").unwrap(),
+                prefecture_name: String::from("北海道（札幌方面）"),
+                police_station_code: PoliceStationCode::try_from("10101").unwrap(),
+                police_station_name: String::from("中央"),
+                city_code: CityCode::try_from("01101").unwrap(),
+                city_name: String::from("札幌市中央区"),
+                occurred_at: time::macros::datetime!(2022-04-01 00:30 UTC),
+                number_of_deaths: 0,
+                number_of_injuries: 1,
+                weather_code: WeatherCode::try_from("1").unwrap(),
+                weather_name: String::from("晴"),
+                surface_condition_code: SurfaceConditionCode::try_from("1").unwrap(),
+                surface_condition_name: String::from("舗装－乾燥"),
+                location: geozero::wkb::Decode {
+                    geometry: Some(geo_types::Geometry::Point(geo_types::Point::new(
+                        141.35, 43.06,
+                    ))),
+                },
+            },
+            accident_detail_code: AccidentDetailCode::try_from("2").unwrap(),
+            day_night_code: DayNightCode::try_from("12").unwrap(),
+            road_model_code: RoadModelCode::try_from("01").unwrap(),
+            accident_type_code: AccidentTypeCode::try_from("21").unwrap(),
+            party_a_code: PartyCode::try_from("03").unwrap(),
+            age_a_code: AgeCode::try_from("45").unwrap(),
+            party_b_code: PartyCode::try_from("61").unwrap(),
+            age_b_code: AgeCode::try_from("65").unwrap(),
+        };
+
+        let record = accident_csv_record(&export, Language::Ja, Srid::JGD2011);
+        assert_eq!(record[1], "2022-04-01 09:30");
+        assert_eq!(record[10..12], ["2", "負傷"]);
+        assert_eq!(record[26..28], ["61", "歩行者"]);
+        assert_eq!(record[CSV_COLUMNS - 2..], ["43.06", "141.35"]);
+
+        let record = accident_csv_record(&export, Language::En, Srid::JGD2011);
+        assert_eq!(record[11], "Injury");
+        assert_eq!(record[27], "Pedestrian");
+    }
+}
@@ -0,0 +1,629 @@
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use code_tables::{
+    AccidentDetailCode, AccidentTypeCode, CityCode, CodeError, DayNightCode, PartyCode,
+    PoliceStationCode, PrefectureJisCode, SurfaceConditionCode, WeatherCode,
+};
+use geometries::Srid;
+use sqlx::{Postgres, QueryBuilder};
+use time::macros::{format_description, offset};
+use time::{Date, Duration, OffsetDateTime, Time};
+
+use crate::handlers::{AppError, AppErrorContent, AppErrorResponse};
+
+/// 1ページあたりの既定の件数
+pub const DEFAULT_PER_PAGE: u32 = 100;
+
+/// 1ページあたりの最大の件数
+pub const MAX_PER_PAGE: u32 = 1000;
+
+/// 交通事故の警察署コード（本票の都道府県コードと警察署コードを連結したコード）を返すSQLの式
+///
+/// 警察署で絞り込む条件と、警察署ごとの統計のコードで同じ式を使用する。
+pub(crate) const POLICE_STATION_CODE_SQL: &str = "CONCAT(a.prefecture_code, a.police_station_code)";
+
+/// 交通事故絞り込みクエリパラメータ
+///
+/// コードを指定するパラメータは、カンマで区切って複数のコードを指定できる。
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccidentFilterParams {
+    /// 発生日の開始日（YYYY-MM-DD）
+    pub from: Option<String>,
+    /// 発生日の終了日（YYYY-MM-DD、終了日を含む）
+    pub to: Option<String>,
+    /// 都道府県コード（JIS規格）
+    pub prefecture_code: Option<String>,
+    /// 市区町村コード（JIS規格）
+    pub city_code: Option<String>,
+    /// 警察署コード（本票の都道府県コードと警察署コードを連結したコード）
+    pub police_station_code: Option<String>,
+    /// 天候コード
+    #[serde(alias = "weather")]
+    pub weather_code: Option<String>,
+    /// 路面状態コード
+    pub surface_condition_code: Option<String>,
+    /// 事故内容コード（1: 死亡、2: 負傷）
+    #[serde(alias = "detail")]
+    pub accident_detail_code: Option<String>,
+    /// 事故類型コード
+    pub accident_type_code: Option<String>,
+    /// 当事者種別コード（当事者A）
+    pub party_a_code: Option<String>,
+    /// 当事者種別コード（当事者B）
+    pub party_b_code: Option<String>,
+    /// 当事者種別コード（当事者Aまたは当事者B）
+    #[serde(alias = "party")]
+    pub party_code: Option<String>,
+    /// 昼夜コード
+    pub day_night_code: Option<String>,
+    /// 発生時の開始（0から23）
+    pub hour_from: Option<u8>,
+    /// 発生時の終了（0から23、終了時を含む）
+    pub hour_to: Option<u8>,
+}
+
+/// 交通事故の絞り込み条件
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AccidentFilter {
+    /// 発生日時の下限（下限を含む）
+    pub occurred_from: Option<OffsetDateTime>,
+    /// 発生日時の上限（上限を含まない）
+    pub occurred_to: Option<OffsetDateTime>,
+    /// 都道府県コード（JIS規格）
+    pub prefecture_codes: Option<Vec<PrefectureJisCode>>,
+    /// 市区町村コード
+    pub city_codes: Option<Vec<CityCode>>,
+    /// 警察署コード
+    pub police_station_codes: Option<Vec<PoliceStationCode>>,
+    /// 天候コード
+    pub weather_codes: Option<Vec<WeatherCode>>,
+    /// 路面状態コード
+    pub surface_condition_codes: Option<Vec<SurfaceConditionCode>>,
+    /// 事故内容コード
+    pub accident_detail_codes: Option<Vec<AccidentDetailCode>>,
+    /// 事故類型コード
+    pub accident_type_codes: Option<Vec<AccidentTypeCode>>,
+    /// 当事者種別コード（当事者A）
+    pub party_a_codes: Option<Vec<PartyCode>>,
+    /// 当事者種別コード（当事者B）
+    pub party_b_codes: Option<Vec<PartyCode>>,
+    /// 当事者種別コード（当事者Aまたは当事者B）
+    pub party_codes: Option<Vec<PartyCode>>,
+    /// 昼夜コード
+    pub day_night_codes: Option<Vec<DayNightCode>>,
+    /// 発生時の開始
+    pub hour_from: Option<u8>,
+    /// 発生時の終了
+    pub hour_to: Option<u8>,
+}
+
+impl TryFrom<AccidentFilterParams> for AccidentFilter {
+    type Error = AppErrorResponse;
+
+    fn try_from(params: AccidentFilterParams) -> Result<Self, Self::Error> {
+        let occurred_from = params
+            .from
+            .as_deref()
+            .map(|value| parse_date("from", value))
+            .transpose()?;
+        let occurred_to = params
+            .to
+            .as_deref()
+            .map(|value| parse_date("to", value).map(|date| date + Duration::days(1)))
+            .transpose()?;
+        if let (Some(from), Some(to)) = (occurred_from, occurred_to) {
+            if to <= from {
+                return Err(invalid_query(
+                    "toにはfrom以降の日付を指定してください。".into(),
+                    "to must be on or after from.".into(),
+                ));
+            }
+        }
+        for (name, hour) in [("hourFrom", params.hour_from), ("hourTo", params.hour_to)] {
+            if hour.is_some_and(|hour| 23 < hour) {
+                return Err(invalid_query(
+                    format!("{}には0から23までの値を指定してください。", name).into(),
+                    format!("{} must be between 0 and 23.", name).into(),
+                ));
+            }
+        }
+
+        Ok(Self {
+            occurred_from,
+            occurred_to,
+            prefecture_codes: parse_codes("prefectureCode", params.prefecture_code)?,
+            city_codes: parse_codes("cityCode", params.city_code)?,
+            police_station_codes: parse_codes("policeStationCode", params.police_station_code)?,
+            weather_codes: parse_codes("weatherCode", params.weather_code)?,
+            surface_condition_codes: parse_codes(
+                "surfaceConditionCode",
+                params.surface_condition_code,
+            )?,
+            accident_detail_codes: parse_codes("accidentDetailCode", params.accident_detail_code)?,
+            accident_type_codes: parse_codes("accidentTypeCode", params.accident_type_code)?,
+            party_a_codes: parse_codes("partyACode", params.party_a_code)?,
+            party_b_codes: parse_codes("partyBCode", params.party_b_code)?,
+            party_codes: parse_codes("partyCode", params.party_code)?,
+            day_night_codes: parse_codes("dayNightCode", params.day_night_code)?,
+            hour_from: params.hour_from,
+            hour_to: params.hour_to,
+        })
+    }
+}
+
+impl AccidentFilter {
+    /// 絞り込み条件をクエリビルダーに追加する。
+    ///
+    /// 交通事故テーブルの別名を`a`とした`WHERE`句に続けて、`AND`で連結した条件を追加する。
+    ///
+    /// # 引数
+    ///
+    /// * `builder` - クエリビルダー
+    pub fn push_conditions(&self, builder: &mut QueryBuilder<'_, Postgres>) {
+        if let Some(occurred_from) = self.occurred_from {
+            builder.push(" AND a.occurred_at >= ");
+            builder.push_bind(occurred_from);
+        }
+        if let Some(occurred_to) = self.occurred_to {
+            builder.push(" AND a.occurred_at < ");
+            builder.push_bind(occurred_to);
+        }
+        if let Some(codes) = &self.prefecture_codes {
+            builder.push(
+                " AND a.prefecture_code IN (SELECT code FROM prefectures WHERE jis_code = ANY(",
+            );
+            builder.push_bind(codes.clone());
+            builder.push("))");
+        }
+        if let Some(codes) = &self.police_station_codes {
+            builder.push(format!(" AND {} = ANY(", POLICE_STATION_CODE_SQL));
+            builder.push_bind(codes.clone());
+            builder.push(")");
+        }
+        // 型の異なるコードを同じ条件で比較するため、コードを文字列の配列に変換
+        let columns = [
+            ("a.city_jis_code", code_strings(&self.city_codes)),
+            ("a.weather_code", code_strings(&self.weather_codes)),
+            (
+                "a.surface_condition_code",
+                code_strings(&self.surface_condition_codes),
+            ),
+            (
+                "a.accident_detail_code",
+                code_strings(&self.accident_detail_codes),
+            ),
+            (
+                "a.accident_type_code",
+                code_strings(&self.accident_type_codes),
+            ),
+            ("a.party_a_code", code_strings(&self.party_a_codes)),
+            ("a.party_b_code", code_strings(&self.party_b_codes)),
+            ("a.day_night_code", code_strings(&self.day_night_codes)),
+        ];
+        for (column, codes) in columns {
+            if let Some(codes) = codes {
+                builder.push(format!(" AND {}::text = ANY(", column));
+                builder.push_bind(codes);
+                builder.push(")");
+            }
+        }
+        if let Some(codes) = &self.party_codes {
+            builder.push(" AND ARRAY[a.party_a_code::text, a.party_b_code::text] && ");
+            builder.push_bind(codes.clone());
+        }
+        // 発生時は日本標準時で比較して、開始が終了より大きい場合は日付をまたぐ範囲とする
+        let hour = "EXTRACT(HOUR FROM a.occurred_at AT TIME ZONE 'Asia/Tokyo')";
+        match (self.hour_from, self.hour_to) {
+            (Some(from), Some(to)) => {
+                let operator = if from <= to { "AND" } else { "OR" };
+                builder.push(format!(" AND ({} >= ", hour));
+                builder.push_bind(from as i32);
+                builder.push(format!(" {} {} <= ", operator, hour));
+                builder.push_bind(to as i32);
+                builder.push(")");
+            }
+            (Some(from), None) => {
+                builder.push(format!(" AND {} >= ", hour));
+                builder.push_bind(from as i32);
+            }
+            (None, Some(to)) => {
+                builder.push(format!(" AND {} <= ", hour));
+                builder.push_bind(to as i32);
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// ページ指定クエリパラメータ
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageParams {
+    /// ページ番号（1から開始）
+    pub page: Option<u32>,
+    /// 1ページあたりの件数
+    pub per_page: Option<u32>,
+}
+
+/// ページ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    /// ページ番号（1から開始）
+    pub page: u32,
+    /// 1ページあたりの件数
+    pub per_page: u32,
+}
+
+impl TryFrom<PageParams> for Page {
+    type Error = AppErrorResponse;
+
+    fn try_from(params: PageParams) -> Result<Self, Self::Error> {
+        let page = params.page.unwrap_or(1);
+        if page == 0 {
+            return Err(invalid_query(
+                "pageには1以上の値を指定してください。".into(),
+                "page must be 1 or greater.".into(),
+            ));
+        }
+        let per_page = params.per_page.unwrap_or(DEFAULT_PER_PAGE);
+        if per_page == 0 || MAX_PER_PAGE < per_page {
+            return Err(invalid_query(
+                format!(
+                    "perPageには1から{}までの値を指定してください。",
+                    MAX_PER_PAGE
+                )
+                .into(),
+                format!("perPage must be between 1 and {}.", MAX_PER_PAGE).into(),
+            ));
+        }
+
+        Ok(Self { page, per_page })
+    }
+}
+
+impl Page {
+    /// 取得を開始する位置を返す。
+    ///
+    /// # 戻り値
+    ///
+    /// 取得を開始する位置
+    pub fn offset(&self) -> i64 {
+        (self.page as i64 - 1) * self.per_page as i64
+    }
+
+    /// 交通事故を発生日時の順に並べて、ページの範囲を取得する句をクエリビルダーに追加する。
+    ///
+    /// # 引数
+    ///
+    /// * `builder` - クエリビルダー
+    pub fn push_order_and_limit(&self, builder: &mut QueryBuilder<'_, Postgres>) {
+        builder.push(" ORDER BY a.occurred_at, a.id LIMIT ");
+        builder.push_bind(self.per_page as i64);
+        builder.push(" OFFSET ");
+        builder.push_bind(self.offset());
+    }
+
+    /// ページ番号、1ページあたりの件数及び条件に一致した件数を記録したJSONのメンバーを返す。
+    ///
+    /// # 引数
+    ///
+    /// * `total` - 条件に一致した件数
+    ///
+    /// # 戻り値
+    ///
+    /// `page`、`perPage`及び`total`を記録したJSONのメンバー
+    pub fn members(&self, total: i64) -> serde_json::Map<String, serde_json::Value> {
+        let mut members = serde_json::Map::new();
+        members.insert("page".to_string(), serde_json::json!(self.page));
+        members.insert("perPage".to_string(), serde_json::json!(self.per_page));
+        members.insert("total".to_string(), serde_json::json!(total));
+
+        members
+    }
+}
+
+/// 座標の空間参照系クエリパラメータ
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct SridParams {
+    /// 出力する座標のSRID（指定しない場合は交通事故を記録しているJGD2011）
+    pub srid: Option<String>,
+}
+
+impl TryFrom<SridParams> for Srid {
+    type Error = AppErrorResponse;
+
+    fn try_from(params: SridParams) -> Result<Self, Self::Error> {
+        match params.srid {
+            Some(srid) => srid.parse::<Srid>().map_err(|e| {
+                invalid_query(
+                    e.to_string().into(),
+                    format!(
+                        "srid must be 4326, 3857, 6668 or between 6669 and 6687 ({}).",
+                        srid
+                    )
+                    .into(),
+                )
+            }),
+            None => Ok(Srid::default()),
+        }
+    }
+}
+
+/// クエリパラメータエラーを返す。
+///
+/// # 引数
+///
+/// * `message` - エラーメッセージ
+/// * `message_en` - 英語のエラーメッセージ
+///
+/// # 戻り値
+///
+/// クエリパラメータエラー
+pub fn invalid_query(
+    message: Cow<'static, str>,
+    message_en: Cow<'static, str>,
+) -> AppErrorResponse {
+    AppErrorResponse::BadRequest(AppErrorContent {
+        app_error: AppError::InvalidQuery,
+        message,
+        message_en,
+    })
+}
+
+/// コードを文字列の配列に変換する。
+fn code_strings<T: AsRef<str>>(codes: &Option<Vec<T>>) -> Option<Vec<String>> {
+    codes
+        .as_ref()
+        .map(|codes| codes.iter().map(|code| code.as_ref().to_string()).collect())
+}
+
+/// `YYYY-MM-DD`形式の日付を、日本標準時のその日の0時に変換する。
+fn parse_date(name: &str, value: &str) -> Result<OffsetDateTime, AppErrorResponse> {
+    let date = Date::parse(value, format_description!("[year]-[month]-[day]")).map_err(|_| {
+        invalid_query(
+            format!(
+                "{}にはYYYY-MM-DD形式の日付を指定してください({})。",
+                name, value
+            )
+            .into(),
+            format!("{} must be a date in YYYY-MM-DD format ({}).", name, value).into(),
+        )
+    })?;
+
+    Ok(date.with_time(Time::MIDNIGHT).assume_offset(offset!(+9)))
+}
+
+/// カンマで区切られたコードを解析して、コード表のコードに変換する。
+fn parse_codes<T>(name: &str, value: Option<String>) -> Result<Option<Vec<T>>, AppErrorResponse>
+where
+    T: FromStr<Err = CodeError>,
+{
+    let value = match value {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    let codes = value
+        .split(',')
+        .map(|code| code.trim().to_string())
+        .collect::<Vec<_>>();
+    if codes
+        .iter()
+        .any(|code| code.is_empty() || !code.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return Err(invalid_query(
+            format!(
+                "{}には数字のコードをカンマで区切って指定してください({})。",
+                name, value
+            )
+            .into(),
+            format!(
+                "{} must be numeric codes separated by commas ({}).",
+                name, value
+            )
+            .into(),
+        ));
+    }
+    let codes = codes
+        .iter()
+        .map(|code| code.parse::<T>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            invalid_query(
+                format!("{}に存在しないコードが指定されました。{}", name, e).into(),
+                format!("{} contains an unknown code ({}).", name, e.code).into(),
+            )
+        })?;
+
+    Ok(Some(codes))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::web;
+
+    use super::*;
+
+    #[test]
+    fn accident_filter_ok() {
+        let params = AccidentFilterParams {
+            from: Some(String::from("2022-01-01")),
+            to: Some(String::from("2022-01-31")),
+            weather_code: Some(String::from("1, 2")),
+            hour_from: Some(22),
+            hour_to: Some(4),
+            ..Default::default()
+        };
+        let filter = AccidentFilter::try_from(params).unwrap();
+
+        assert_eq!(
+            filter.occurred_from.unwrap().to_string(),
+            "2022-01-01 0:00:00.0 +09:00:00"
+        );
+        assert_eq!(
+            filter.occurred_to.unwrap().to_string(),
+            "2022-02-01 0:00:00.0 +09:00:00"
+        );
+        assert_eq!(
+            filter.weather_codes,
+            Some(vec![
+                WeatherCode::try_from("1").unwrap(),
+                WeatherCode::try_from("2").unwrap()
+            ])
+        );
+        assert_eq!(filter.city_codes, None);
+    }
+
+    #[test]
+    fn accident_filter_fail() {
+        let invalid_params = [
+            AccidentFilterParams {
+                from: Some(String::from("2022/01/01")),
+                ..Default::default()
+            },
+            AccidentFilterParams {
+                from: Some(String::from("2022-02-01")),
+                to: Some(String::from("2022-01-01")),
+                ..Default::default()
+            },
+            AccidentFilterParams {
+                weather_code: Some(String::from("1,")),
+                ..Default::default()
+            },
+            AccidentFilterParams {
+                city_code: Some(String::from("0110a")),
+                ..Default::default()
+            },
+            // コード表に存在しないコード
+            AccidentFilterParams {
+                weather_code: Some(String::from("1,9")),
+                ..Default::default()
+            },
+            AccidentFilterParams {
+                police_station_code: Some(String::from("059")),
+                ..Default::default()
+            },
+            AccidentFilterParams {
+                hour_to: Some(24),
+                ..Default::default()
+            },
+        ];
+        for params in invalid_params {
+            let error = AccidentFilter::try_from(params).unwrap_err();
+            assert!(matches!(
+                error,
+                AppErrorResponse::BadRequest(AppErrorContent {
+                    app_error: AppError::InvalidQuery,
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn accident_filter_params_alias_ok() {
+        let params = web::Query::<AccidentFilterParams>::from_query(
+            "detail=1&weather=2,3&party=61&dayNightCode=21",
+        )
+        .unwrap()
+        .into_inner();
+        assert_eq!(params.accident_detail_code.as_deref(), Some("1"));
+        assert_eq!(params.weather_code.as_deref(), Some("2,3"));
+        assert_eq!(params.party_code.as_deref(), Some("61"));
+        assert_eq!(params.day_night_code.as_deref(), Some("21"));
+
+        let params = web::Query::<AccidentFilterParams>::from_query(
+            "accidentDetailCode=1&weatherCode=2&partyCode=61",
+        )
+        .unwrap()
+        .into_inner();
+        assert_eq!(params.accident_detail_code.as_deref(), Some("1"));
+        assert_eq!(params.weather_code.as_deref(), Some("2"));
+        assert_eq!(params.party_code.as_deref(), Some("61"));
+    }
+
+    #[test]
+    fn push_conditions_ok() {
+        let filter = AccidentFilter {
+            accident_detail_codes: Some(vec![AccidentDetailCode::try_from("1").unwrap()]),
+            party_codes: Some(vec![PartyCode::try_from("61").unwrap()]),
+            hour_from: Some(22),
+            hour_to: Some(4),
+            ..Default::default()
+        };
+        let mut builder = QueryBuilder::new("SELECT a.id FROM accidents a WHERE TRUE");
+        filter.push_conditions(&mut builder);
+
+        assert_eq!(
+            builder.sql(),
+            "SELECT a.id FROM accidents a WHERE TRUE \
+            AND a.accident_detail_code::text = ANY($1) \
+            AND ARRAY[a.party_a_code::text, a.party_b_code::text] && $2 \
+            AND (EXTRACT(HOUR FROM a.occurred_at AT TIME ZONE 'Asia/Tokyo') >= $3 \
+            OR EXTRACT(HOUR FROM a.occurred_at AT TIME ZONE 'Asia/Tokyo') <= $4)"
+        );
+    }
+
+    #[test]
+    fn page_ok() {
+        let page = Page::try_from(PageParams {
+            page: None,
+            per_page: None,
+        })
+        .unwrap();
+        assert_eq!(
+            page,
+            Page {
+                page: 1,
+                per_page: DEFAULT_PER_PAGE
+            }
+        );
+        assert_eq!(page.offset(), 0);
+
+        let page = Page::try_from(PageParams {
+            page: Some(3),
+            per_page: Some(20),
+        })
+        .unwrap();
+        assert_eq!(page.offset(), 40);
+        let mut builder = QueryBuilder::new("SELECT a.id FROM accidents a");
+        page.push_order_and_limit(&mut builder);
+        assert_eq!(
+            builder.sql(),
+            "SELECT a.id FROM accidents a ORDER BY a.occurred_at, a.id LIMIT $1 OFFSET $2"
+        );
+        assert_eq!(
+            serde_json::Value::Object(page.members(45)),
+            serde_json::json!({"page": 3, "perPage": 20, "total": 45})
+        );
+
+        assert!(Page::try_from(PageParams {
+            page: Some(0),
+            per_page: None
+        })
+        .is_err());
+        assert!(Page::try_from(PageParams {
+            page: None,
+            per_page: Some(MAX_PER_PAGE + 1)
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn srid_params_ok() {
+        assert_eq!(
+            Srid::try_from(SridParams::default()).unwrap(),
+            Srid::JGD2011
+        );
+        let srid = Srid::try_from(SridParams {
+            srid: Some(String::from("6677")),
+        })
+        .unwrap();
+        assert_eq!(srid.code(), 6677);
+        assert!(Srid::try_from(SridParams {
+            srid: Some(String::from("4612")),
+        })
+        .is_err());
+    }
+}
@@ -1,26 +1,57 @@
 use std::borrow::Cow;
 use std::fmt::{Debug, Display};
 
-use actix_web::http::header::ContentType;
+use actix_web::http::header::{ContentType, CONTENT_DISPOSITION};
 use actix_web::http::StatusCode;
+use actix_web::web::Bytes;
 use actix_web::{web, HttpResponse, HttpResponseBuilder, Responder, ResponseError};
+use futures::channel::mpsc;
+use futures::{SinkExt, TryStreamExt};
 use geojson::{FeatureCollection, GeoJson};
 use serde_json::to_string_pretty;
 use serde_json::value::Value::{Number as SerdeNumber, String as SerdeString};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
 
-use geometries::WkbGeometryF64;
+use code_tables::{
+    AccidentDetailCode, AccidentTypeCode, AgeCode, AirbagCode, AutomaticCode,
+    CentralSeparationCode, CityCode, CollisionPointCode, DayNightCode, DistrictCode,
+    DrivingPracticeCode, HolidayCode, InjuryCode, LaneCode, Language, PartyCode, PoliceStationCode,
+    PrefectureJisCode, PurposeCode, RidingClassCode, RidingTypeCode, RoadAlignmentCode,
+    RoadModelCode, RoadSegmentationCode, RoadStructureCode, RoadWidthCode, RouteClassCode,
+    RouteCode, SideAirbagCode, SpeedRegulationCode, StopRegulationDisplayCode,
+    StopRegulationSignCode, SupportCarCode, SurfaceConditionCode, TrafficSignalCode, UpDownCode,
+    VehicleDamageCode, VehicleTypeCode, WeatherCode, WeekCode, ZoneRegulationCode,
+};
+use geometries::{Srid, WkbGeometryF64};
 
-use crate::map::{tile_bbox, TileCoordinate};
-use crate::map::{BBox, SRID_JGD2001};
-use crate::models::Accident;
+use crate::codes::{find_code_table, CODE_TABLES};
+use crate::export::{
+    accident_csv_record, csv_headers, csv_writer, ExportEncoding, ExportParams, EXPORT_CHUNK_SIZE,
+    EXPORT_COLUMNS_SQL,
+};
+use crate::filters::{
+    invalid_query, AccidentFilter, AccidentFilterParams, Page, PageParams, SridParams,
+};
+use crate::language::{name_column, RequestLanguage};
+use crate::map::{tile_bbox, tile_size, TileCoordinate, MAX_TILE_ZOOM, WEB_MERCATOR_HALF_EXTENT};
+use crate::map::{BBox, MVT_BUFFER, MVT_EXTENT};
+use crate::models::{
+    Accident, AccidentCluster, AccidentDetail, AccidentStats, CodeRow, ExportAccident,
+    HighwayDetail, InvolvedPerson, NearAccident, PartyDetail, Position,
+};
+use crate::near::{near_query, Near, NearParams};
 use crate::settings::Settings;
+use crate::stats::{stats_query, StatsParams};
+use crate::within::{WithinArea, WithinParams};
 
 /// アプリケーションエラーレスポンス
 #[derive(Debug, serde::Serialize, thiserror::Error)]
 pub enum AppErrorResponse {
     /// リクエストが不正
     BadRequest(AppErrorContent),
+    /// リソースが存在しない
+    NotFound(AppErrorContent),
     /// サーバー内部エラー
     InternalServerError(AppErrorContent),
 }
@@ -35,21 +66,40 @@ impl ResponseError for AppErrorResponse {
     fn status_code(&self) -> StatusCode {
         match self {
             AppErrorResponse::BadRequest(_) => StatusCode::BAD_REQUEST,
+            AppErrorResponse::NotFound(_) => StatusCode::NOT_FOUND,
             AppErrorResponse::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 
     fn error_response(&self) -> HttpResponse {
         let status_code = self.status_code();
-        match self {
-            AppErrorResponse::BadRequest(content) => HttpResponse::build(status_code)
-                .json(AppResponseErrorBody::new(status_code, content)),
-            AppErrorResponse::InternalServerError(content) => HttpResponse::build(status_code)
-                .json(AppResponseErrorBody::new(status_code, content)),
-        }
+        let content = match self {
+            AppErrorResponse::BadRequest(content) => content,
+            AppErrorResponse::NotFound(content) => content,
+            AppErrorResponse::InternalServerError(content) => content,
+        };
+        let mut response = HttpResponse::build(status_code).json(AppResponseErrorBody::new(
+            status_code,
+            content,
+            Language::Ja,
+        ));
+        // リクエストの言語が英語の場合にミドルウェアで置き換えるため、英語のボディを記録
+        let body = AppResponseErrorBody::new(status_code, content, Language::En);
+        response
+            .extensions_mut()
+            .insert(EnglishErrorBody(serde_json::to_string(&body).unwrap()));
+
+        response
     }
 }
 
+/// 英語のエラーレスポンスボディ
+///
+/// エラーレスポンスは日本語のメッセージで作成して、リクエストの言語が英語の場合は、
+/// ミドルウェアがレスポンスの拡張に記録したこのボディに置き換える。
+#[derive(Debug, Clone)]
+pub struct EnglishErrorBody(pub String);
+
 /// アプリケーションエラーコンテンツ
 #[derive(Debug, serde::Serialize)]
 pub struct AppErrorContent {
@@ -57,6 +107,45 @@ pub struct AppErrorContent {
     pub app_error: AppError,
     /// エラーメッセージ
     pub message: Cow<'static, str>,
+    /// 英語のエラーメッセージ
+    pub message_en: Cow<'static, str>,
+}
+
+impl AppErrorContent {
+    /// 言語に対応するエラーメッセージを返す。
+    ///
+    /// # 引数
+    ///
+    /// * `language` - エラーメッセージの言語
+    ///
+    /// # 戻り値
+    ///
+    /// エラーメッセージ
+    pub fn message(&self, language: Language) -> &str {
+        match language {
+            Language::Ja => &self.message,
+            Language::En => &self.message_en,
+        }
+    }
+}
+
+/// データベースエラーを返す。
+///
+/// データベースのエラーメッセージは、言語によらずそのまま返す。
+///
+/// # 引数
+///
+/// * `e` - データベースエラー
+///
+/// # 戻り値
+///
+/// データベースエラー
+pub fn database_error(e: sqlx::Error) -> AppErrorResponse {
+    AppErrorResponse::InternalServerError(AppErrorContent {
+        app_error: AppError::Database,
+        message: format!("データベースでエラーが発生しました。{}", e).into(),
+        message_en: format!("A database error occurred. {}", e).into(),
+    })
 }
 
 /// アプリケーションエラー
@@ -67,8 +156,17 @@ pub enum AppError {
     None = 0,
     /// データベースエラー
     Database = 1,
-    /// 交通事故ズームレベルエラー
-    AccidentZoomLevel = 2,
+    /// クエリパラメータエラー
+    ///
+    /// `2`は、交通事故表示最小ズームレベル未満のタイルを拒否していた交通事故ズームレベルエラーの
+    /// コードであったため欠番とする。
+    InvalidQuery = 3,
+    /// 交通事故が存在しないエラー
+    AccidentNotFound = 4,
+    /// コード表が存在しないエラー
+    CodeTableNotFound = 5,
+    /// 範囲を表現するジオメトリエラー
+    InvalidGeometry = 6,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -92,11 +190,11 @@ fn serialize_status_code<S: serde::Serializer>(
 }
 
 impl AppResponseErrorBody {
-    fn new(status_code: StatusCode, content: &AppErrorContent) -> Self {
+    fn new(status_code: StatusCode, content: &AppErrorContent, language: Language) -> Self {
         Self {
             status_code,
             app_error: content.app_error,
-            message: content.message.to_string(),
+            message: content.message(language).to_string(),
         }
     }
 }
@@ -106,86 +204,76 @@ pub async fn health_check() -> impl Responder {
     HttpResponse::Ok().body("Hello world!")
 }
 
+/// コード表一覧ハンドラ
+pub async fn code_table_list(RequestLanguage(language): RequestLanguage) -> impl Responder {
+    let code_tables = CODE_TABLES
+        .iter()
+        .map(|code_table| code_table.localize(language))
+        .collect::<Vec<_>>();
+
+    HttpResponse::Ok().json(code_tables)
+}
+
+/// コード表ハンドラ
+///
+/// 指定されたコード表のコードと名前の組を返す。
+pub async fn code_table_rows(
+    pool: web::Data<PgPool>,
+    table: web::Path<String>,
+    RequestLanguage(language): RequestLanguage,
+) -> actix_web::Result<HttpResponse> {
+    let table = table.into_inner();
+    let code_table =
+        find_code_table(&table).ok_or(AppErrorResponse::NotFound(AppErrorContent {
+            app_error: AppError::CodeTableNotFound,
+            message: format!("コード表({})が見つかりません。", table).into(),
+            message_en: format!("Code table ({}) not found.", table).into(),
+        }))?;
+    let rows = sqlx::query_as::<_, CodeRow>(&code_table.sql(language))
+        .fetch_all(pool.as_ref())
+        .await
+        .map_err(database_error)?;
+
+    Ok(HttpResponse::Ok().json(rows))
+}
+
 /// 交通事故リストハンドラ
+///
+/// タイルの範囲に含まれる交通事故のうち、クエリパラメータで指定された条件に一致する交通事故を返す。
 pub async fn accident_list(
     settings: web::Data<Settings>,
     pool: web::Data<PgPool>,
     tile_coordinate: web::Path<TileCoordinate>,
+    filter_params: web::Query<AccidentFilterParams>,
+    RequestLanguage(language): RequestLanguage,
 ) -> actix_web::Result<HttpResponse> {
-    let bbox = calculate_extend_accident_bbox(
-        tile_coordinate.into_inner(),
-        settings.web_app.accident_zoom_level,
-        settings.web_app.accident_buffer_ratio,
-    )?;
-    let accidents = sqlx::query_as!(
-        Accident,
-        r#"
-        SELECT
-            a.id,
-            ci.prefecture_jis_code prefecture_code,
-            pr.name prefecture_name,
-            CONCAT(a.prefecture_code, a.police_station_code) police_station_code,
-            po.police_station_name,
-            a.city_jis_code city_code,
-            ci.city_name,
-            a.occurred_at,
-            a.number_of_deaths,
-            a.number_of_injuries,
-            a.weather_code,
-            we.name weather_name,
-            a.surface_condition_code,
-            su.name surface_condition_name,
-            a.location as "location!: WkbGeometryF64"
-        FROM accidents a
-        INNER JOIN prefectures pr ON a.prefecture_code = pr.code
-        INNER JOIN police_stations po ON a.prefecture_code = po.prefecture_code
-            AND a.police_station_code = po.police_station_code
-        INNER JOIN cities ci ON a.city_jis_code = ci.city_jis_code
-        INNER JOIN weathers we ON a.weather_code = we.code
-        INNER JOIN surface_conditions su ON a.surface_condition_code = su.code
-        WHERE
-            ST_CONTAINS(
-                ST_MakeEnvelope(
-                    $1,
-                    $2,
-                    $3,
-                    $4,
-                    $5
-                ),
-                a.location
-            )
-        "#,
-        bbox.x_min,
-        bbox.y_min,
-        bbox.x_max,
-        bbox.y_max,
-        SRID_JGD2001 as i32,
-    )
-    .fetch_all(pool.as_ref())
-    .await
-    .map_err(|e| {
-        AppErrorResponse::InternalServerError(AppErrorContent {
-            app_error: AppError::Database,
-            message: e.to_string().into(),
-        })
-    })?;
+    let tile_coordinate = valid_tile_coordinate(tile_coordinate.into_inner())?;
+    let filter = AccidentFilter::try_from(filter_params.into_inner())?;
+    // 交通事故表示最小ズームレベル未満の場合は、集計した交通事故を返す
+    if tile_coordinate.z < settings.web_app.accident_zoom_level {
+        return accident_cluster_list(
+            pool.as_ref(),
+            tile_coordinate,
+            settings.web_app.accident_cluster_division,
+            &filter,
+        )
+        .await;
+    }
+    let bbox =
+        calculate_extend_accident_bbox(tile_coordinate, settings.web_app.accident_buffer_ratio);
+
+    let mut builder = QueryBuilder::new(accident_select_sql(language, Srid::JGD2011));
+    push_tile_conditions(&mut builder, &bbox, &filter);
+    let accidents = builder
+        .build_query_as::<Accident>()
+        .fetch_all(pool.as_ref())
+        .await
+        .map_err(database_error)?;
 
     // GeoJSONに変換
     let features = accidents
         .into_iter()
-        .map(|accident| {
-            let properties = Some(accident_properties(&accident));
-            let geometry: Option<geojson::Geometry> =
-                Some(geojson::Value::from(&accident.location.geometry.unwrap()).into());
-            let id = Some(geojson::feature::Id::String(accident.id.to_string()));
-            geojson::Feature {
-                bbox: None,
-                geometry,
-                id,
-                properties,
-                foreign_members: None,
-            }
-        })
+        .map(accident_feature)
         .collect::<Vec<_>>();
     let feature_collection = FeatureCollection {
         bbox: None,
@@ -207,14 +295,25 @@ pub async fn accident_list_geojson(
     settings: web::Data<Settings>,
     pool: web::Data<PgPool>,
     tile_coordinate: web::Path<TileCoordinate>,
+    filter_params: web::Query<AccidentFilterParams>,
+    RequestLanguage(language): RequestLanguage,
 ) -> actix_web::Result<HttpResponse> {
-    let bbox = calculate_extend_accident_bbox(
-        tile_coordinate.into_inner(),
-        settings.web_app.accident_zoom_level,
-        settings.web_app.accident_buffer_ratio,
-    )?;
+    let tile_coordinate = valid_tile_coordinate(tile_coordinate.into_inner())?;
+    let filter = AccidentFilter::try_from(filter_params.into_inner())?;
+    // 交通事故表示最小ズームレベル未満の場合は、集計した交通事故を返す
+    if tile_coordinate.z < settings.web_app.accident_zoom_level {
+        return accident_cluster_list(
+            pool.as_ref(),
+            tile_coordinate,
+            settings.web_app.accident_cluster_division,
+            &filter,
+        )
+        .await;
+    }
+    let bbox =
+        calculate_extend_accident_bbox(tile_coordinate, settings.web_app.accident_buffer_ratio);
 
-    let record = sqlx::query!(
+    let mut builder = QueryBuilder::new(
         r#"
         SELECT json_build_object(
             'type', 'FeatureCollection',
@@ -240,90 +339,1115 @@ pub async fn accident_list_geojson(
                     )
                 )
             )
-        ) as "features!: sqlx::types::Json<serde_json::Value>"
-        FROM (
-            SELECT
-                a.id,
-                ci.prefecture_jis_code prefecture_code,
-                pr.name prefecture_name,
-                CONCAT(a.prefecture_code, a.police_station_code) police_station_code,
-                po.police_station_name,
-                a.city_jis_code city_code,
-                ci.city_name,
-                a.occurred_at,
-                a.number_of_deaths,
-                a.number_of_injuries,
-                a.weather_code,
-                we.name weather_name,
-                a.surface_condition_code,
-                su.name surface_condition_name,
-                a.location
-            FROM accidents a
-            INNER JOIN prefectures pr ON a.prefecture_code = pr.code
-            INNER JOIN police_stations po ON a.prefecture_code = po.prefecture_code
-                AND a.police_station_code = po.police_station_code
-            INNER JOIN cities ci ON a.city_jis_code = ci.city_jis_code
-            INNER JOIN weathers we ON a.weather_code = we.code
-            INNER JOIN surface_conditions su ON a.surface_condition_code = su.code
-            WHERE
-                ST_CONTAINS(
-                    ST_MakeEnvelope(
-                        $1,
-                        $2,
-                        $3,
-                        $4,
-                        $5
-                    ),
-                    a.location
-                )
         )
-        "#,
-        bbox.x_min,
-        bbox.y_min,
-        bbox.x_max,
-        bbox.y_max,
-        SRID_JGD2001 as i32,
-    )
-    .fetch_one(pool.as_ref())
-    .await
-    .map_err(|e| {
-        AppErrorResponse::InternalServerError(AppErrorContent {
-            app_error: AppError::Database,
-            message: e.to_string().into(),
-        })
-    })?;
+        FROM ("#,
+    );
+    builder.push(accident_select_sql(language, Srid::JGD2011));
+    push_tile_conditions(&mut builder, &bbox, &filter);
+    builder.push(") accidents");
+    let features: sqlx::types::Json<serde_json::Value> = builder
+        .build_query_scalar()
+        .fetch_one(pool.as_ref())
+        .await
+        .map_err(database_error)?;
 
-    match record.features.get("features") {
+    match features.get("features") {
         Some(serde_json::Value::Array(_)) => Ok(HttpResponseBuilder::new(StatusCode::OK)
             .content_type(ContentType::json())
-            .body(record.features.to_string())),
+            .body(features.to_string())),
         _ => Ok(HttpResponseBuilder::new(StatusCode::OK)
             .content_type(ContentType::json())
             .body(r#"{"features": [], "type": "FeatureCollection"}"#)),
     }
 }
 
+/// 交通事故統計ハンドラ
+///
+/// クエリパラメータで指定された条件で交通事故を絞り込み、指定された集計単位ごとに
+/// 交通事故の件数、死者数及び負傷者数を返す。
+pub async fn accident_stats(
+    pool: web::Data<PgPool>,
+    stats_params: web::Query<StatsParams>,
+    filter_params: web::Query<AccidentFilterParams>,
+    RequestLanguage(language): RequestLanguage,
+) -> actix_web::Result<HttpResponse> {
+    let group_by = stats_params.group_by;
+    let filter = AccidentFilter::try_from(filter_params.into_inner())?;
+
+    let mut builder = stats_query(group_by, &filter, language);
+    let stats = builder
+        .build_query_as::<AccidentStats>()
+        .fetch_all(pool.as_ref())
+        .await
+        .map_err(database_error)?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "groupBy": group_by,
+        "stats": stats,
+    })))
+}
+
+/// 交通事故ベクトルタイルのレイヤー名
+const ACCIDENT_MVT_LAYER: &str = "accidents";
+
+/// 交通事故ベクトルタイルハンドラ
+///
+/// タイルの範囲に含まれる交通事故を、PostGISでMapbox Vector Tile形式に変換して返す。
+/// 地物の属性は、GeoJSONで返す交通事故のプロパティと同じである。
+pub async fn accident_tile(
+    settings: web::Data<Settings>,
+    pool: web::Data<PgPool>,
+    tile_coordinate: web::Path<TileCoordinate>,
+    filter_params: web::Query<AccidentFilterParams>,
+    RequestLanguage(language): RequestLanguage,
+) -> actix_web::Result<HttpResponse> {
+    let tile_coordinate = valid_tile_coordinate(tile_coordinate.into_inner())?;
+    let bbox =
+        calculate_extend_accident_bbox(tile_coordinate, settings.web_app.accident_buffer_ratio);
+    let filter = AccidentFilter::try_from(filter_params.into_inner())?;
+
+    let mut builder = QueryBuilder::new("SELECT ST_AsMVT(tile, ");
+    builder.push_bind(ACCIDENT_MVT_LAYER);
+    builder.push(", ");
+    builder.push_bind(MVT_EXTENT);
+    builder.push(", 'geom') FROM (SELECT ST_AsMVTGeom(ST_Transform(location, ");
+    builder.push_bind(Srid::WEB_MERCATOR.code() as i32);
+    builder.push("), ST_TileEnvelope(");
+    let mut separated = builder.separated(", ");
+    separated.push_bind(tile_coordinate.z as i32);
+    separated.push_bind(tile_coordinate.x as i32);
+    separated.push_bind(tile_coordinate.y as i32);
+    builder.push("), ");
+    builder.push_bind(MVT_EXTENT);
+    builder.push(", ");
+    builder.push_bind(MVT_BUFFER);
+    builder.push(
+        r#", true) geom,
+            id::text "id",
+            prefecture_code "prefectureCode",
+            prefecture_name "prefectureName",
+            city_code "cityCode",
+            city_name "cityName",
+            police_station_code "policeStationCode",
+            police_station_name "policeStationName",
+            to_char(occurred_at AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS"Z"') "occurredAt",
+            number_of_deaths "numberOfDeaths",
+            number_of_injuries "numberOfInjuries",
+            weather_code "weatherCode",
+            weather_name "weatherName",
+            surface_condition_code "surfaceConditionCode",
+            surface_condition_name "surfaceConditionName"
+        FROM ("#,
+    );
+    builder.push(accident_select_sql(language, Srid::JGD2011));
+    push_tile_conditions(&mut builder, &bbox, &filter);
+    builder.push(") accidents) tile");
+    let tile: Option<Vec<u8>> = builder
+        .build_query_scalar()
+        .fetch_one(pool.as_ref())
+        .await
+        .map_err(database_error)?;
+
+    Ok(HttpResponseBuilder::new(StatusCode::OK)
+        .content_type("application/vnd.mapbox-vector-tile")
+        .body(tile.unwrap_or_default()))
+}
+
+/// 交通事故詳細ハンドラ
+///
+/// 交通事故のすべての項目を、コード表の名前と当事者以外関与者を含めて返す。
+pub async fn accident_detail(
+    pool: web::Data<PgPool>,
+    accident_id: web::Path<Uuid>,
+    srid_params: web::Query<SridParams>,
+    RequestLanguage(language): RequestLanguage,
+) -> actix_web::Result<HttpResponse> {
+    let accident_id = accident_id.into_inner();
+    let srid = Srid::try_from(srid_params.into_inner())?;
+    let mut connection = pool.acquire().await.map_err(database_error)?;
+    let accident = fetch_accident_detail(&mut connection, accident_id, language, srid).await?;
+
+    Ok(HttpResponse::Ok().json(accident))
+}
+
+/// 交通事故のすべての項目を、コード表の名前と当事者以外関与者を含めて取得する。
+///
+/// # 引数
+///
+/// * `connection` - データベースコネクション
+/// * `accident_id` - 交通事故ID
+/// * `language` - コード表の名前の言語
+/// * `srid` - 発生場所の座標の空間参照系
+///
+/// # 戻り値
+///
+/// 交通事故の詳細、交通事故が存在しない場合は`AccidentNotFound`エラー
+async fn fetch_accident_detail(
+    connection: &mut PgConnection,
+    accident_id: Uuid,
+    language: Language,
+    srid: Srid,
+) -> actix_web::Result<AccidentDetail> {
+    let record = sqlx::query!(
+        r#"
+        SELECT
+            a.id,
+            ci.prefecture_jis_code "prefecture_code: PrefectureJisCode",
+            COALESCE(CASE WHEN $2 THEN pr.name_en END, pr.name) "prefecture_name!",
+            CONCAT(a.prefecture_code, a.police_station_code) "police_station_code!: PoliceStationCode",
+            po.police_station_name,
+            a.main_number,
+            a.number_of_deaths,
+            a.number_of_injuries,
+            a.route_code "route_code: RouteCode",
+            (
+                SELECT COALESCE(CASE WHEN $2 THEN ro.name_en END, ro.name)
+                FROM routes ro
+                WHERE a.route_code BETWEEN ro.lower_code AND ro.upper_code
+                LIMIT 1
+            ) route_name,
+            a.location_code,
+            a.city_jis_code "city_code: CityCode",
+            ci.city_name,
+            a.occurred_at,
+            a.sunrise_time,
+            a.sunset_time,
+            a.accident_detail_code "accident_detail_code: AccidentDetailCode", COALESCE(CASE WHEN $2 THEN accident_detail.name_en END, accident_detail.name) "accident_detail_name!",
+            a.route_class_code "route_class_code: RouteClassCode", COALESCE(CASE WHEN $2 THEN route_class.name_en END, route_class.name) "route_class_name!",
+            a.day_night_code "day_night_code: DayNightCode", COALESCE(CASE WHEN $2 THEN day_night.name_en END, day_night.name) "day_night_name!",
+            a.weather_code "weather_code: WeatherCode", COALESCE(CASE WHEN $2 THEN weather.name_en END, weather.name) "weather_name!",
+            a.district_code "district_code: DistrictCode", COALESCE(CASE WHEN $2 THEN district.name_en END, district.name) "district_name!",
+            a.surface_condition_code "surface_condition_code: SurfaceConditionCode", COALESCE(CASE WHEN $2 THEN surface_condition.name_en END, surface_condition.name) "surface_condition_name!",
+            a.road_model_code "road_model_code: RoadModelCode", COALESCE(CASE WHEN $2 THEN road_model.name_en END, road_model.name) "road_model_name!",
+            a.traffic_signal_code "traffic_signal_code: TrafficSignalCode", COALESCE(CASE WHEN $2 THEN traffic_signal.name_en END, traffic_signal.name) "traffic_signal_name!",
+            a.road_width_code "road_width_code: RoadWidthCode", COALESCE(CASE WHEN $2 THEN road_width.name_en END, road_width.name) "road_width_name!",
+            a.road_alignment_code "road_alignment_code: RoadAlignmentCode", COALESCE(CASE WHEN $2 THEN road_alignment.name_en END, road_alignment.name) "road_alignment_name!",
+            a.collision_point_code "collision_point_code: CollisionPointCode", COALESCE(CASE WHEN $2 THEN collision_point.name_en END, collision_point.name) "collision_point_name!",
+            a.zone_regulation_code "zone_regulation_code: ZoneRegulationCode", COALESCE(CASE WHEN $2 THEN zone_regulation.name_en END, zone_regulation.name) "zone_regulation_name!",
+            a.central_separation_code "central_separation_code: CentralSeparationCode", COALESCE(CASE WHEN $2 THEN central_separation.name_en END, central_separation.name) "central_separation_name!",
+            a.road_segmentation_code "road_segmentation_code: RoadSegmentationCode", COALESCE(CASE WHEN $2 THEN road_segmentation.name_en END, road_segmentation.name) "road_segmentation_name!",
+            a.accident_type_code "accident_type_code: AccidentTypeCode", COALESCE(CASE WHEN $2 THEN accident_type.name_en END, accident_type.name) "accident_type_name!",
+            a.week_code "week_code: WeekCode", COALESCE(CASE WHEN $2 THEN week.name_en END, week.name) "week_name!",
+            a.holiday_code "holiday_code: HolidayCode", COALESCE(CASE WHEN $2 THEN holiday.name_en END, holiday.name) "holiday_name!",
+            a.party_a_code "party_a_code: PartyCode", COALESCE(CASE WHEN $2 THEN party_a.name_en END, party_a.name) "party_a_name!",
+            a.age_a_code "age_a_code: AgeCode", COALESCE(CASE WHEN $2 THEN age_a.name_en END, age_a.name) "age_a_name!",
+            a.purpose_a_code "purpose_a_code: PurposeCode", COALESCE(CASE WHEN $2 THEN purpose_a.name_en END, purpose_a.name) "purpose_a_name!",
+            a.vehicle_type_a_code "vehicle_type_a_code: VehicleTypeCode", COALESCE(CASE WHEN $2 THEN vehicle_type_a.name_en END, vehicle_type_a.name) "vehicle_type_a_name!",
+            a.stop_regulation_sign_a_code "stop_regulation_sign_a_code: StopRegulationSignCode", COALESCE(CASE WHEN $2 THEN stop_regulation_sign_a.name_en END, stop_regulation_sign_a.name) "stop_regulation_sign_a_name!",
+            a.stop_regulation_display_a_code "stop_regulation_display_a_code: StopRegulationDisplayCode", COALESCE(CASE WHEN $2 THEN stop_regulation_display_a.name_en END, stop_regulation_display_a.name) "stop_regulation_display_a_name!",
+            a.automatic_a_code "automatic_a_code: AutomaticCode", COALESCE(CASE WHEN $2 THEN automatic_a.name_en END, automatic_a.name) "automatic_a_name!",
+            a.support_car_a_code "support_car_a_code: SupportCarCode", COALESCE(CASE WHEN $2 THEN support_car_a.name_en END, support_car_a.name) "support_car_a_name!",
+            a.speed_regulation_a_code "speed_regulation_a_code: SpeedRegulationCode", COALESCE(CASE WHEN $2 THEN speed_regulation_a.name_en END, speed_regulation_a.name) "speed_regulation_a_name!",
+            a.vehicle_damage_a_code "vehicle_damage_a_code: VehicleDamageCode", COALESCE(CASE WHEN $2 THEN vehicle_damage_a.name_en END, vehicle_damage_a.name) "vehicle_damage_a_name!",
+            a.airbag_a_code "airbag_a_code: AirbagCode", COALESCE(CASE WHEN $2 THEN airbag_a.name_en END, airbag_a.name) "airbag_a_name!",
+            a.side_airbag_a_code "side_airbag_a_code: SideAirbagCode", COALESCE(CASE WHEN $2 THEN side_airbag_a.name_en END, side_airbag_a.name) "side_airbag_a_name!",
+            a.injury_a_code "injury_a_code: InjuryCode", COALESCE(CASE WHEN $2 THEN injury_a.name_en END, injury_a.name) "injury_a_name!",
+            a.driving_practice_a_code "driving_practice_a_code: DrivingPracticeCode", COALESCE(CASE WHEN $2 THEN driving_practice_a.name_en END, driving_practice_a.name) "driving_practice_a_name!",
+            a.collision_part_a,
+            a.cognitive_days_a,
+            a.party_b_code "party_b_code: PartyCode", COALESCE(CASE WHEN $2 THEN party_b.name_en END, party_b.name) "party_b_name!",
+            a.age_b_code "age_b_code: AgeCode", COALESCE(CASE WHEN $2 THEN age_b.name_en END, age_b.name) "age_b_name!",
+            a.purpose_b_code "purpose_b_code: PurposeCode", COALESCE(CASE WHEN $2 THEN purpose_b.name_en END, purpose_b.name) "purpose_b_name!",
+            a.vehicle_type_b_code "vehicle_type_b_code: VehicleTypeCode", COALESCE(CASE WHEN $2 THEN vehicle_type_b.name_en END, vehicle_type_b.name) "vehicle_type_b_name!",
+            a.stop_regulation_sign_b_code "stop_regulation_sign_b_code: StopRegulationSignCode", COALESCE(CASE WHEN $2 THEN stop_regulation_sign_b.name_en END, stop_regulation_sign_b.name) "stop_regulation_sign_b_name!",
+            a.stop_regulation_display_b_code "stop_regulation_display_b_code: StopRegulationDisplayCode", COALESCE(CASE WHEN $2 THEN stop_regulation_display_b.name_en END, stop_regulation_display_b.name) "stop_regulation_display_b_name!",
+            a.automatic_b_code "automatic_b_code: AutomaticCode", COALESCE(CASE WHEN $2 THEN automatic_b.name_en END, automatic_b.name) "automatic_b_name!",
+            a.support_car_b_code "support_car_b_code: SupportCarCode", COALESCE(CASE WHEN $2 THEN support_car_b.name_en END, support_car_b.name) "support_car_b_name!",
+            a.speed_regulation_b_code "speed_regulation_b_code: SpeedRegulationCode", COALESCE(CASE WHEN $2 THEN speed_regulation_b.name_en END, speed_regulation_b.name) "speed_regulation_b_name!",
+            a.vehicle_damage_b_code "vehicle_damage_b_code: VehicleDamageCode", COALESCE(CASE WHEN $2 THEN vehicle_damage_b.name_en END, vehicle_damage_b.name) "vehicle_damage_b_name!",
+            a.airbag_b_code "airbag_b_code: AirbagCode", COALESCE(CASE WHEN $2 THEN airbag_b.name_en END, airbag_b.name) "airbag_b_name!",
+            a.side_airbag_b_code "side_airbag_b_code: SideAirbagCode", COALESCE(CASE WHEN $2 THEN side_airbag_b.name_en END, side_airbag_b.name) "side_airbag_b_name!",
+            a.injury_b_code "injury_b_code: InjuryCode", COALESCE(CASE WHEN $2 THEN injury_b.name_en END, injury_b.name) "injury_b_name!",
+            a.driving_practice_b_code "driving_practice_b_code: DrivingPracticeCode", COALESCE(CASE WHEN $2 THEN driving_practice_b.name_en END, driving_practice_b.name) "driving_practice_b_name!",
+            a.collision_part_b,
+            a.cognitive_days_b,
+            ST_Transform(a.location, $3) as "location!: WkbGeometryF64"
+        FROM accidents a
+        INNER JOIN prefectures pr ON a.prefecture_code = pr.code
+        INNER JOIN police_stations po ON a.prefecture_code = po.prefecture_code
+            AND a.police_station_code = po.police_station_code
+        INNER JOIN cities ci ON a.city_jis_code = ci.city_jis_code
+        INNER JOIN accident_details accident_detail ON a.accident_detail_code = accident_detail.code
+        INNER JOIN route_classes route_class ON a.route_class_code = route_class.code
+        INNER JOIN day_nights day_night ON a.day_night_code = day_night.code
+        INNER JOIN weathers weather ON a.weather_code = weather.code
+        INNER JOIN districts district ON a.district_code = district.code
+        INNER JOIN surface_conditions surface_condition ON a.surface_condition_code = surface_condition.code
+        INNER JOIN road_models road_model ON a.road_model_code = road_model.code
+        INNER JOIN traffic_signals traffic_signal ON a.traffic_signal_code = traffic_signal.code
+        INNER JOIN road_widths road_width ON a.road_width_code = road_width.code
+        INNER JOIN road_alignments road_alignment ON a.road_alignment_code = road_alignment.code
+        INNER JOIN collision_points collision_point ON a.collision_point_code = collision_point.code
+        INNER JOIN zone_regulations zone_regulation ON a.zone_regulation_code = zone_regulation.code
+        INNER JOIN central_separations central_separation ON a.central_separation_code = central_separation.code
+        INNER JOIN road_segmentations road_segmentation ON a.road_segmentation_code = road_segmentation.code
+        INNER JOIN accident_types accident_type ON a.accident_type_code = accident_type.code
+        INNER JOIN weeks week ON a.week_code = week.code
+        INNER JOIN holidays holiday ON a.holiday_code = holiday.code
+        INNER JOIN parties party_a ON a.party_a_code = party_a.code
+        INNER JOIN ages age_a ON a.age_a_code = age_a.code
+        INNER JOIN purposes purpose_a ON a.purpose_a_code = purpose_a.code
+        INNER JOIN vehicle_types vehicle_type_a ON a.vehicle_type_a_code = vehicle_type_a.code
+        INNER JOIN stop_regulation_signs stop_regulation_sign_a ON a.stop_regulation_sign_a_code = stop_regulation_sign_a.code
+        INNER JOIN stop_regulation_displays stop_regulation_display_a ON a.stop_regulation_display_a_code = stop_regulation_display_a.code
+        INNER JOIN automatics automatic_a ON a.automatic_a_code = automatic_a.code
+        INNER JOIN support_cars support_car_a ON a.support_car_a_code = support_car_a.code
+        INNER JOIN speed_regulations speed_regulation_a ON a.speed_regulation_a_code = speed_regulation_a.code
+        INNER JOIN vehicle_damages vehicle_damage_a ON a.vehicle_damage_a_code = vehicle_damage_a.code
+        INNER JOIN airbags airbag_a ON a.airbag_a_code = airbag_a.code
+        INNER JOIN side_airbags side_airbag_a ON a.side_airbag_a_code = side_airbag_a.code
+        INNER JOIN injuries injury_a ON a.injury_a_code = injury_a.code
+        INNER JOIN driving_practices driving_practice_a ON a.driving_practice_a_code = driving_practice_a.code
+        INNER JOIN parties party_b ON a.party_b_code = party_b.code
+        INNER JOIN ages age_b ON a.age_b_code = age_b.code
+        INNER JOIN purposes purpose_b ON a.purpose_b_code = purpose_b.code
+        INNER JOIN vehicle_types vehicle_type_b ON a.vehicle_type_b_code = vehicle_type_b.code
+        INNER JOIN stop_regulation_signs stop_regulation_sign_b ON a.stop_regulation_sign_b_code = stop_regulation_sign_b.code
+        INNER JOIN stop_regulation_displays stop_regulation_display_b ON a.stop_regulation_display_b_code = stop_regulation_display_b.code
+        INNER JOIN automatics automatic_b ON a.automatic_b_code = automatic_b.code
+        INNER JOIN support_cars support_car_b ON a.support_car_b_code = support_car_b.code
+        INNER JOIN speed_regulations speed_regulation_b ON a.speed_regulation_b_code = speed_regulation_b.code
+        INNER JOIN vehicle_damages vehicle_damage_b ON a.vehicle_damage_b_code = vehicle_damage_b.code
+        INNER JOIN airbags airbag_b ON a.airbag_b_code = airbag_b.code
+        INNER JOIN side_airbags side_airbag_b ON a.side_airbag_b_code = side_airbag_b.code
+        INNER JOIN injuries injury_b ON a.injury_b_code = injury_b.code
+        INNER JOIN driving_practices driving_practice_b ON a.driving_practice_b_code = driving_practice_b.code
+        WHERE a.id = $1
+        "#,
+        accident_id,
+        language == Language::En,
+        srid.code() as i32,
+    )
+    .fetch_optional(&mut *connection)
+    .await
+    .map_err(database_error)?
+    .ok_or(AppErrorResponse::NotFound(AppErrorContent {
+        app_error: AppError::AccidentNotFound,
+        message: format!("交通事故({})が見つかりません。", accident_id).into(),
+        message_en: format!("Accident ({}) not found.", accident_id).into(),
+    }))?;
+
+    let involved_persons = sqlx::query_as!(
+        InvolvedPerson,
+        r#"
+        SELECT
+            ip.id,
+            ip.sub_number,
+            ip.party_code "party_code: PartyCode",
+            COALESCE(CASE WHEN $2 THEN pa.name_en END, pa.name) "party_name!",
+            ip.purpose_code "purpose_code: PurposeCode",
+            COALESCE(CASE WHEN $2 THEN pu.name_en END, pu.name) "purpose_name?",
+            ip.vehicle_type_code "vehicle_type_code: VehicleTypeCode",
+            COALESCE(CASE WHEN $2 THEN vt.name_en END, vt.name) "vehicle_type_name?",
+            ip.riding_type_code "riding_type_code: RidingTypeCode",
+            COALESCE(CASE WHEN $2 THEN rt.name_en END, rt.name) "riding_type_name!",
+            ip.riding_class_code "riding_class_code: RidingClassCode",
+            COALESCE(CASE WHEN $2 THEN rc.name_en END, rc.name) "riding_class_name!",
+            ip.support_car_code "support_car_code: SupportCarCode",
+            COALESCE(CASE WHEN $2 THEN sc.name_en END, sc.name) "support_car_name!",
+            ip.airbag_code "airbag_code: AirbagCode",
+            COALESCE(CASE WHEN $2 THEN ab.name_en END, ab.name) "airbag_name!",
+            ip.side_airbag_code "side_airbag_code: SideAirbagCode",
+            COALESCE(CASE WHEN $2 THEN sa.name_en END, sa.name) "side_airbag_name!",
+            ip.injury_code "injury_code: InjuryCode",
+            COALESCE(CASE WHEN $2 THEN ij.name_en END, ij.name) "injury_name!",
+            ip.collision_part,
+            ip.vehicle_damage_code "vehicle_damage_code: VehicleDamageCode",
+            COALESCE(CASE WHEN $2 THEN vd.name_en END, vd.name) "vehicle_damage_name?"
+        FROM involved_persons ip
+        INNER JOIN parties pa ON ip.party_code = pa.code
+        LEFT JOIN purposes pu ON ip.purpose_code = pu.code
+        LEFT JOIN vehicle_types vt ON ip.vehicle_type_code = vt.code
+        INNER JOIN riding_types rt ON ip.riding_type_code = rt.code
+        INNER JOIN riding_classes rc ON ip.riding_class_code = rc.code
+        INNER JOIN support_cars sc ON ip.support_car_code = sc.code
+        INNER JOIN airbags ab ON ip.airbag_code = ab.code
+        INNER JOIN side_airbags sa ON ip.side_airbag_code = sa.code
+        INNER JOIN injuries ij ON ip.injury_code = ij.code
+        LEFT JOIN vehicle_damages vd ON ip.vehicle_damage_code = vd.code
+        WHERE ip.accident_id = $1
+        ORDER BY ip.sub_number
+        "#,
+        accident_id,
+        language == Language::En,
+    )
+    .fetch_all(&mut *connection)
+    .await
+    .map_err(database_error)?;
+
+    let highway = sqlx::query_as!(
+        HighwayDetail,
+        r#"
+        SELECT
+            h.up_down_code "up_down_code: UpDownCode",
+            COALESCE(CASE WHEN $2 THEN ud.name_en END, ud.name) "up_down_name?",
+            h.kilopost_km,
+            h.kilopost_m,
+            h.road_structure_code "road_structure_code: RoadStructureCode",
+            COALESCE(CASE WHEN $2 THEN rs.name_en END, rs.name) "road_structure_name?",
+            h.lane_a_code "lane_a_code: LaneCode",
+            COALESCE(CASE WHEN $2 THEN lane_a.name_en END, lane_a.name) "lane_a_name?",
+            h.lane_b_code "lane_b_code: LaneCode",
+            COALESCE(CASE WHEN $2 THEN lane_b.name_en END, lane_b.name) "lane_b_name?"
+        FROM highway_details h
+        LEFT JOIN up_downs ud ON h.up_down_code = ud.code
+        LEFT JOIN road_structures rs ON h.road_structure_code = rs.code
+        LEFT JOIN lanes lane_a ON h.lane_a_code = lane_a.code
+        LEFT JOIN lanes lane_b ON h.lane_b_code = lane_b.code
+        WHERE h.accident_id = $1
+        "#,
+        accident_id,
+        language == Language::En,
+    )
+    .fetch_optional(&mut *connection)
+    .await
+    .map_err(database_error)?;
+
+    let party_a = PartyDetail {
+        party_code: record.party_a_code,
+        party_name: record.party_a_name,
+        age_code: record.age_a_code,
+        age_name: record.age_a_name,
+        purpose_code: record.purpose_a_code,
+        purpose_name: record.purpose_a_name,
+        vehicle_type_code: record.vehicle_type_a_code,
+        vehicle_type_name: record.vehicle_type_a_name,
+        stop_regulation_sign_code: record.stop_regulation_sign_a_code,
+        stop_regulation_sign_name: record.stop_regulation_sign_a_name,
+        stop_regulation_display_code: record.stop_regulation_display_a_code,
+        stop_regulation_display_name: record.stop_regulation_display_a_name,
+        automatic_code: record.automatic_a_code,
+        automatic_name: record.automatic_a_name,
+        support_car_code: record.support_car_a_code,
+        support_car_name: record.support_car_a_name,
+        speed_regulation_code: record.speed_regulation_a_code,
+        speed_regulation_name: record.speed_regulation_a_name,
+        vehicle_damage_code: record.vehicle_damage_a_code,
+        vehicle_damage_name: record.vehicle_damage_a_name,
+        airbag_code: record.airbag_a_code,
+        airbag_name: record.airbag_a_name,
+        side_airbag_code: record.side_airbag_a_code,
+        side_airbag_name: record.side_airbag_a_name,
+        injury_code: record.injury_a_code,
+        injury_name: record.injury_a_name,
+        driving_practice_code: record.driving_practice_a_code,
+        driving_practice_name: record.driving_practice_a_name,
+        collision_part: record.collision_part_a,
+        cognitive_days: record.cognitive_days_a,
+    };
+    let party_b = PartyDetail {
+        party_code: record.party_b_code,
+        party_name: record.party_b_name,
+        age_code: record.age_b_code,
+        age_name: record.age_b_name,
+        purpose_code: record.purpose_b_code,
+        purpose_name: record.purpose_b_name,
+        vehicle_type_code: record.vehicle_type_b_code,
+        vehicle_type_name: record.vehicle_type_b_name,
+        stop_regulation_sign_code: record.stop_regulation_sign_b_code,
+        stop_regulation_sign_name: record.stop_regulation_sign_b_name,
+        stop_regulation_display_code: record.stop_regulation_display_b_code,
+        stop_regulation_display_name: record.stop_regulation_display_b_name,
+        automatic_code: record.automatic_b_code,
+        automatic_name: record.automatic_b_name,
+        support_car_code: record.support_car_b_code,
+        support_car_name: record.support_car_b_name,
+        speed_regulation_code: record.speed_regulation_b_code,
+        speed_regulation_name: record.speed_regulation_b_name,
+        vehicle_damage_code: record.vehicle_damage_b_code,
+        vehicle_damage_name: record.vehicle_damage_b_name,
+        airbag_code: record.airbag_b_code,
+        airbag_name: record.airbag_b_name,
+        side_airbag_code: record.side_airbag_b_code,
+        side_airbag_name: record.side_airbag_b_name,
+        injury_code: record.injury_b_code,
+        injury_name: record.injury_b_name,
+        driving_practice_code: record.driving_practice_b_code,
+        driving_practice_name: record.driving_practice_b_name,
+        collision_part: record.collision_part_b,
+        cognitive_days: record.cognitive_days_b,
+    };
+    let position = match record.location.geometry {
+        Some(geo_types::Geometry::Point(point)) => Some(Position::new(point, srid)),
+        _ => None,
+    };
+    let accident = AccidentDetail {
+        id: record.id,
+        prefecture_code: record.prefecture_code,
+        prefecture_name: record.prefecture_name,
+        police_station_code: record.police_station_code,
+        police_station_name: record.police_station_name,
+        main_number: record.main_number,
+        number_of_deaths: record.number_of_deaths,
+        number_of_injuries: record.number_of_injuries,
+        route_code: record.route_code,
+        route_name: record.route_name,
+        location_code: record.location_code,
+        city_code: record.city_code,
+        city_name: record.city_name,
+        occurred_at: record.occurred_at,
+        sunrise_time: format_time(record.sunrise_time),
+        sunset_time: format_time(record.sunset_time),
+        accident_detail_code: record.accident_detail_code,
+        accident_detail_name: record.accident_detail_name,
+        route_class_code: record.route_class_code,
+        route_class_name: record.route_class_name,
+        day_night_code: record.day_night_code,
+        day_night_name: record.day_night_name,
+        weather_code: record.weather_code,
+        weather_name: record.weather_name,
+        district_code: record.district_code,
+        district_name: record.district_name,
+        surface_condition_code: record.surface_condition_code,
+        surface_condition_name: record.surface_condition_name,
+        road_model_code: record.road_model_code,
+        road_model_name: record.road_model_name,
+        traffic_signal_code: record.traffic_signal_code,
+        traffic_signal_name: record.traffic_signal_name,
+        road_width_code: record.road_width_code,
+        road_width_name: record.road_width_name,
+        road_alignment_code: record.road_alignment_code,
+        road_alignment_name: record.road_alignment_name,
+        collision_point_code: record.collision_point_code,
+        collision_point_name: record.collision_point_name,
+        zone_regulation_code: record.zone_regulation_code,
+        zone_regulation_name: record.zone_regulation_name,
+        central_separation_code: record.central_separation_code,
+        central_separation_name: record.central_separation_name,
+        road_segmentation_code: record.road_segmentation_code,
+        road_segmentation_name: record.road_segmentation_name,
+        accident_type_code: record.accident_type_code,
+        accident_type_name: record.accident_type_name,
+        week_code: record.week_code,
+        week_name: record.week_name,
+        holiday_code: record.holiday_code,
+        holiday_name: record.holiday_name,
+        position,
+        party_a,
+        party_b,
+        involved_persons,
+        highway,
+    };
+
+    Ok(accident)
+}
+
+/// 交通事故検索結果の形式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchFormat {
+    /// JSON
+    #[default]
+    Json,
+    /// GeoJSON
+    GeoJson,
+}
+
+/// 交通事故検索結果の形式クエリパラメータ
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+pub struct SearchFormatParams {
+    /// 検索結果の形式
+    pub format: Option<SearchFormat>,
+}
+
+/// 交通事故を取得するFROM句（交通事故テーブルの別名は`a`）
+pub(crate) const ACCIDENT_FROM_SQL: &str = r#"
+    FROM accidents a
+    INNER JOIN prefectures pr ON a.prefecture_code = pr.code
+    INNER JOIN police_stations po ON a.prefecture_code = po.prefecture_code
+        AND a.police_station_code = po.police_station_code
+    INNER JOIN cities ci ON a.city_jis_code = ci.city_jis_code
+    INNER JOIN weathers we ON a.weather_code = we.code
+    INNER JOIN surface_conditions su ON a.surface_condition_code = su.code
+"#;
+
+/// 交通事故を取得するSELECT句とFROM句を返す。
+///
+/// 交通事故テーブルの別名は`a`である。
+/// 発生場所（`location`）は、指定された空間参照系に変換して返す。
+///
+/// # 引数
+///
+/// * `language` - コード表の名前の言語
+/// * `srid` - 発生場所の空間参照系
+///
+/// # 戻り値
+///
+/// 交通事故を取得するSELECT句とFROM句
+pub(crate) fn accident_select_sql(language: Language, srid: Srid) -> String {
+    format!(
+        "{}{}",
+        accident_columns_sql(language, srid),
+        ACCIDENT_FROM_SQL
+    )
+}
+
+/// 交通事故を取得するSELECT句を返す。
+///
+/// 列を追加する場合は、SELECT句に列を追加した後に`ACCIDENT_FROM_SQL`を続ける。
+///
+/// # 引数
+///
+/// * `language` - コード表の名前の言語
+/// * `srid` - 発生場所の空間参照系
+///
+/// # 戻り値
+///
+/// 交通事故を取得するSELECT句
+pub(crate) fn accident_columns_sql(language: Language, srid: Srid) -> String {
+    format!(
+        r#"
+    SELECT
+        a.id,
+        ci.prefecture_jis_code prefecture_code,
+        {} prefecture_name,
+        CONCAT(a.prefecture_code, a.police_station_code) police_station_code,
+        po.police_station_name,
+        a.city_jis_code city_code,
+        ci.city_name,
+        a.occurred_at,
+        a.number_of_deaths,
+        a.number_of_injuries,
+        a.weather_code,
+        {} weather_name,
+        a.surface_condition_code,
+        {} surface_condition_name,
+        {} location"#,
+        name_column("pr.name", language),
+        name_column("we.name", language),
+        name_column("su.name", language),
+        location_column("a.location", srid),
+    )
+}
+
+/// 交通事故の発生場所の列を、空間参照系を変換するSQLの式に変換する。
+///
+/// 交通事故を記録している空間参照系（JGD2011）の場合は、列をそのまま返す。
+///
+/// # 引数
+///
+/// * `column` - 交通事故の発生場所の列（`a.location`など）
+/// * `srid` - 変換後の空間参照系
+///
+/// # 戻り値
+///
+/// 空間参照系を変換した発生場所を返すSQLの式
+pub(crate) fn location_column(column: &str, srid: Srid) -> String {
+    if srid == Srid::JGD2011 {
+        column.to_string()
+    } else {
+        format!("ST_Transform({}, {})", column, srid)
+    }
+}
+
+/// 交通事故検索ハンドラ
+///
+/// クエリパラメータで指定された条件で交通事故を絞り込み、発生日時の順にページ単位で返す。
+pub async fn accident_search(
+    pool: web::Data<PgPool>,
+    filter_params: web::Query<AccidentFilterParams>,
+    page_params: web::Query<PageParams>,
+    format_params: web::Query<SearchFormatParams>,
+    srid_params: web::Query<SridParams>,
+    RequestLanguage(language): RequestLanguage,
+) -> actix_web::Result<HttpResponse> {
+    let filter = AccidentFilter::try_from(filter_params.into_inner())?;
+    let page = Page::try_from(page_params.into_inner())?;
+    let srid = Srid::try_from(srid_params.into_inner())?;
+
+    // 条件に一致する交通事故の件数を取得
+    let mut builder = QueryBuilder::new("SELECT COUNT(*) FROM accidents a WHERE TRUE");
+    filter.push_conditions(&mut builder);
+    let total: i64 = builder
+        .build_query_scalar()
+        .fetch_one(pool.as_ref())
+        .await
+        .map_err(database_error)?;
+
+    // 条件に一致する交通事故を取得
+    let mut builder = QueryBuilder::new(accident_select_sql(language, srid));
+    builder.push(" WHERE TRUE");
+    filter.push_conditions(&mut builder);
+    page.push_order_and_limit(&mut builder);
+    let accidents = builder
+        .build_query_as::<Accident>()
+        .fetch_all(pool.as_ref())
+        .await
+        .map_err(database_error)?;
+
+    let mut page_members = page.members(total);
+    let body = match format_params.format.unwrap_or_default() {
+        SearchFormat::Json => {
+            let accidents = accidents
+                .into_iter()
+                .map(|accident| serde_json::Value::Object(accident_object(accident, srid)))
+                .collect::<Vec<_>>();
+            page_members.insert("accidents".to_string(), serde_json::Value::Array(accidents));
+            serde_json::Value::Object(page_members).to_string()
+        }
+        SearchFormat::GeoJson => {
+            insert_crs_member(&mut page_members, srid);
+            let feature_collection = FeatureCollection {
+                bbox: None,
+                features: accidents.into_iter().map(accident_feature).collect(),
+                foreign_members: Some(page_members),
+            };
+            GeoJson::from(feature_collection).to_string()
+        }
+    };
+
+    Ok(HttpResponseBuilder::new(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(body))
+}
+
+/// 地点周辺の交通事故検索ハンドラ
+///
+/// 指定された地点から半径内で発生した交通事故を、地点から近い順に返す。
+/// 交通事故には、地点からの距離（メートル）を`distance`として追加する。
+pub async fn accident_near(
+    pool: web::Data<PgPool>,
+    near_params: web::Query<NearParams>,
+    filter_params: web::Query<AccidentFilterParams>,
+    format_params: web::Query<SearchFormatParams>,
+    srid_params: web::Query<SridParams>,
+    RequestLanguage(language): RequestLanguage,
+) -> actix_web::Result<HttpResponse> {
+    let near = Near::try_from(near_params.into_inner())?;
+    let filter = AccidentFilter::try_from(filter_params.into_inner())?;
+    let srid = Srid::try_from(srid_params.into_inner())?;
+
+    let accidents = near_query(&near, &filter, language, srid)
+        .build_query_as::<NearAccident>()
+        .fetch_all(pool.as_ref())
+        .await
+        .map_err(database_error)?;
+
+    let mut members = serde_json::Map::new();
+    members.insert("latitude".to_string(), serde_json::json!(near.lat));
+    members.insert("longitude".to_string(), serde_json::json!(near.lon));
+    members.insert("radius".to_string(), serde_json::json!(near.radius));
+    let body = match format_params.format.unwrap_or_default() {
+        SearchFormat::Json => {
+            let accidents = accidents
+                .into_iter()
+                .map(|near_accident| {
+                    let mut object = accident_object(near_accident.accident, srid);
+                    object.insert(
+                        "distance".to_string(),
+                        serde_json::json!(near_accident.distance),
+                    );
+                    serde_json::Value::Object(object)
+                })
+                .collect::<Vec<_>>();
+            members.insert("accidents".to_string(), serde_json::Value::Array(accidents));
+            serde_json::Value::Object(members).to_string()
+        }
+        SearchFormat::GeoJson => {
+            let features = accidents
+                .into_iter()
+                .map(|near_accident| {
+                    let mut feature = accident_feature(near_accident.accident);
+                    feature.set_property("distance", near_accident.distance);
+                    feature
+                })
+                .collect();
+            insert_crs_member(&mut members, srid);
+            let feature_collection = FeatureCollection {
+                bbox: None,
+                features,
+                foreign_members: Some(members),
+            };
+            GeoJson::from(feature_collection).to_string()
+        }
+    };
+
+    Ok(HttpResponseBuilder::new(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(body))
+}
+
+/// 範囲内の交通事故検索ハンドラ
+///
+/// リクエストボディのGeoJSONで指定された範囲に含まれる交通事故のうち、クエリパラメータで
+/// 指定された条件に一致する交通事故を返す。
+/// 結果の形式は、既定でタイルの交通事故リストと同じGeoJSONのFeatureCollectionである。
+#[allow(clippy::too_many_arguments)]
+pub async fn accident_within(
+    pool: web::Data<PgPool>,
+    within_params: web::Query<WithinParams>,
+    filter_params: web::Query<AccidentFilterParams>,
+    page_params: web::Query<PageParams>,
+    format_params: web::Query<SearchFormatParams>,
+    srid_params: web::Query<SridParams>,
+    RequestLanguage(language): RequestLanguage,
+    body: String,
+) -> actix_web::Result<HttpResponse> {
+    let area = WithinArea::new(&body, within_params.into_inner())?;
+    let filter = AccidentFilter::try_from(filter_params.into_inner())?;
+    let page = Page::try_from(page_params.into_inner())?;
+    let srid = Srid::try_from(srid_params.into_inner())?;
+
+    // 範囲に含まれる交通事故の件数を取得
+    let mut builder = QueryBuilder::new("SELECT COUNT(*) FROM accidents a");
+    area.push_conditions(&mut builder, &filter);
+    let total: i64 = builder
+        .build_query_scalar()
+        .fetch_one(pool.as_ref())
+        .await
+        .map_err(database_error)?;
+
+    // 範囲に含まれる交通事故を、広い範囲でもすべてを読み込まないようにページ単位で取得
+    let mut builder = QueryBuilder::new(accident_select_sql(language, srid));
+    area.push_conditions(&mut builder, &filter);
+    page.push_order_and_limit(&mut builder);
+    let accidents = builder
+        .build_query_as::<Accident>()
+        .fetch_all(pool.as_ref())
+        .await
+        .map_err(database_error)?;
+
+    let mut page_members = page.members(total);
+    let body = match format_params.format.unwrap_or(SearchFormat::GeoJson) {
+        SearchFormat::Json => {
+            let accidents = accidents
+                .into_iter()
+                .map(|accident| serde_json::Value::Object(accident_object(accident, srid)))
+                .collect::<Vec<_>>();
+            page_members.insert("accidents".to_string(), serde_json::Value::Array(accidents));
+            serde_json::Value::Object(page_members).to_string()
+        }
+        SearchFormat::GeoJson => {
+            insert_crs_member(&mut page_members, srid);
+            let feature_collection = FeatureCollection {
+                bbox: None,
+                features: accidents.into_iter().map(accident_feature).collect(),
+                foreign_members: Some(page_members),
+            };
+            GeoJson::from(feature_collection).to_string()
+        }
+    };
+
+    Ok(HttpResponseBuilder::new(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(body))
+}
+
+/// エクスポートしたデータをクライアントに送信するチャネルの容量
+const EXPORT_CHANNEL_CAPACITY: usize = 4;
+
+/// 交通事故エクスポートハンドラ
+///
+/// クエリパラメータで指定された条件で交通事故を絞り込み、発生日時の順にCSVで返す。
+/// 交通事故をすべてメモリに読み込まないように、データベースから取得した交通事故を
+/// 一定のサイズごとにクライアントへストリーミングする。
+pub async fn accident_export(
+    pool: web::Data<PgPool>,
+    export_params: web::Query<ExportParams>,
+    filter_params: web::Query<AccidentFilterParams>,
+    srid_params: web::Query<SridParams>,
+    RequestLanguage(language): RequestLanguage,
+) -> actix_web::Result<HttpResponse> {
+    let filter = AccidentFilter::try_from(filter_params.into_inner())?;
+    let srid = Srid::try_from(srid_params.into_inner())?;
+    let encoding = export_params.encoding.unwrap_or_default();
+
+    let mut builder = QueryBuilder::new(accident_columns_sql(language, srid));
+    builder.push(EXPORT_COLUMNS_SQL);
+    builder.push(ACCIDENT_FROM_SQL);
+    builder.push(" WHERE TRUE");
+    filter.push_conditions(&mut builder);
+    builder.push(" ORDER BY a.occurred_at, a.id");
+
+    let pool = pool.into_inner();
+    let (mut sender, receiver) = mpsc::channel(EXPORT_CHANNEL_CAPACITY);
+    actix_web::rt::spawn(async move {
+        // エクスポートの途中でエラーが発生した場合は、レスポンスを中断する
+        if let Err(e) =
+            write_accidents_csv(&pool, builder, encoding, language, srid, &mut sender).await
+        {
+            let _ = sender
+                .send(Err(actix_web::error::ErrorInternalServerError(e)))
+                .await;
+        }
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type(encoding.content_type())
+        .insert_header((
+            CONTENT_DISPOSITION,
+            r#"attachment; filename="accidents.csv""#,
+        ))
+        .streaming(receiver))
+}
+
+/// 交通事故をCSVに変換して、チャネルに送信する。
+///
+/// クライアントが切断してチャネルに送信できなくなった場合は、エクスポートを終了する。
+async fn write_accidents_csv(
+    pool: &PgPool,
+    mut builder: QueryBuilder<'_, Postgres>,
+    encoding: ExportEncoding,
+    language: Language,
+    srid: Srid,
+    sender: &mut mpsc::Sender<actix_web::Result<Bytes>>,
+) -> anyhow::Result<()> {
+    let mut writer = csv_writer(encoding.preamble().to_vec());
+    writer.write_record(csv_headers(language, srid))?;
+
+    let mut accidents = builder.build_query_as::<ExportAccident>().fetch(pool);
+    while let Some(accident) = accidents.try_next().await? {
+        writer.write_record(accident_csv_record(&accident, language, srid))?;
+        if EXPORT_CHUNK_SIZE <= writer.get_ref().len() {
+            let chunk = std::mem::replace(&mut writer, csv_writer(vec![]));
+            let chunk = encoding.encode(chunk.into_inner()?)?;
+            if sender.send(Ok(Bytes::from(chunk))).await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+    let chunk = encoding.encode(writer.into_inner()?)?;
+    let _ = sender.send(Ok(Bytes::from(chunk))).await;
+
+    Ok(())
+}
+
+/// タイル座標を検証する。
+///
+/// # 引数
+///
+/// * `tile_coordinate` - タイル座標
+///
+/// # 戻り値
+///
+/// 有効なタイル座標、無効な場合はクエリパラメータエラー
+fn valid_tile_coordinate(
+    tile_coordinate: TileCoordinate,
+) -> Result<TileCoordinate, AppErrorResponse> {
+    if tile_coordinate.is_valid() {
+        return Ok(tile_coordinate);
+    }
+    let TileCoordinate { z, x, y } = tile_coordinate;
+
+    Err(invalid_query(
+        format!(
+            "タイル座標({}/{}/{})が不正です。ズームレベルは0から{}、X座標とY座標は0から2のズームレベル乗未満を指定してください。",
+            z, x, y, MAX_TILE_ZOOM
+        )
+        .into(),
+        format!(
+            "Tile coordinate ({}/{}/{}) is invalid. The zoom level must be between 0 and {}, and x and y must be between 0 and 2^zoom - 1.",
+            z, x, y, MAX_TILE_ZOOM
+        )
+        .into(),
+    ))
+}
+
+/// タイルの範囲と絞り込み条件を`WHERE`句としてクエリビルダーに追加する。
+fn push_tile_conditions(
+    builder: &mut QueryBuilder<'_, Postgres>,
+    bbox: &BBox,
+    filter: &AccidentFilter,
+) {
+    builder.push(" WHERE ST_CONTAINS(ST_MakeEnvelope(");
+    let mut separated = builder.separated(", ");
+    separated.push_bind(bbox.x_min);
+    separated.push_bind(bbox.y_min);
+    separated.push_bind(bbox.x_max);
+    separated.push_bind(bbox.y_max);
+    separated.push_bind(Srid::JGD2011.code() as i32);
+    builder.push("), a.location)");
+    filter.push_conditions(builder);
+}
+
+/// 集計した交通事故のGeoJSONを返す。
+///
+/// タイルを縦横に`division`個に分割したグリッドに交通事故を集計して、グリッドごとに
+/// 交通事故の件数、死者数及び負傷者数を、交通事故の平均の位置を地点とする地物として返す。
+///
+/// # 引数
+///
+/// * `pool` - データベースコネクションプール
+/// * `tile_coordinate` - タイル座標
+/// * `division` - タイルの1辺を分割する数
+/// * `filter` - 交通事故の絞り込み条件
+///
+/// # 戻り値
+///
+/// 集計した交通事故を記録したGeoJSONのFeatureCollectionを返すレスポンス
+async fn accident_cluster_list(
+    pool: &PgPool,
+    tile_coordinate: TileCoordinate,
+    division: u32,
+    filter: &AccidentFilter,
+) -> actix_web::Result<HttpResponse> {
+    let mut builder = cluster_query(tile_coordinate, division, filter);
+    let clusters = builder
+        .build_query_as::<AccidentCluster>()
+        .fetch_all(pool)
+        .await
+        .map_err(database_error)?;
+
+    let features = clusters
+        .into_iter()
+        .map(|cluster| {
+            let mut properties = geojson::JsonObject::new();
+            properties.insert("cluster".to_string(), serde_json::Value::Bool(true));
+            properties.insert("count".to_string(), serde_json::json!(cluster.count));
+            properties.insert(
+                "numberOfDeaths".to_string(),
+                serde_json::json!(cluster.number_of_deaths),
+            );
+            properties.insert(
+                "numberOfInjuries".to_string(),
+                serde_json::json!(cluster.number_of_injuries),
+            );
+            geojson::Feature {
+                bbox: None,
+                geometry: Some(geojson::Geometry::new(geojson::Value::Point(vec![
+                    cluster.longitude,
+                    cluster.latitude,
+                ]))),
+                id: None,
+                properties: Some(properties),
+                foreign_members: None,
+            }
+        })
+        .collect::<Vec<_>>();
+    let feature_collection = FeatureCollection {
+        bbox: None,
+        features,
+        foreign_members: None,
+    };
+
+    Ok(HttpResponseBuilder::new(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(GeoJson::from(feature_collection).to_string()))
+}
+
+/// タイルに含まれる交通事故をグリッドごとに集計するクエリを作成する。
+///
+/// グリッドはWebメルカトル（EPSG:3857）の座標で計算するため、タイルの境界とグリッドの境界が
+/// 一致して、地図に表示したタイルを縦横に`division`個に分割した正方形となる。
+///
+/// # 引数
+///
+/// * `tile_coordinate` - タイル座標
+/// * `division` - タイルの1辺を分割する数
+/// * `filter` - 交通事故の絞り込み条件
+///
+/// # 戻り値
+///
+/// 交通事故を集計するクエリ
+fn cluster_query(
+    tile_coordinate: TileCoordinate,
+    division: u32,
+    filter: &AccidentFilter,
+) -> QueryBuilder<'static, Postgres> {
+    // 隣接するタイルで同じ交通事故を集計しないように、タイルの範囲を拡張しない
+    let bbox = tile_bbox(tile_coordinate);
+    let grid_size = cluster_grid_size(tile_coordinate.z, division);
+
+    let mut builder = QueryBuilder::new(
+        r#"
+        SELECT
+            COUNT(*) count,
+            SUM(a.number_of_deaths) number_of_deaths,
+            SUM(a.number_of_injuries) number_of_injuries,
+            AVG(ST_X(a.location)) longitude,
+            AVG(ST_Y(a.location)) latitude
+        FROM accidents a"#,
+    );
+    push_tile_conditions(&mut builder, &bbox, filter);
+    // Webメルカトルの座標の範囲の左下からの距離をグリッドの1辺の長さで除した値の整数部分が
+    // 同じ交通事故を集計
+    builder.push(" GROUP BY");
+    for (index, function) in ["ST_X", "ST_Y"].into_iter().enumerate() {
+        builder.push(if index == 0 { " " } else { ", " });
+        builder.push(format!("FLOOR(({}(ST_Transform(a.location, ", function));
+        builder.push_bind(Srid::WEB_MERCATOR.code() as i32);
+        builder.push(")) + ");
+        builder.push_bind(WEB_MERCATOR_HALF_EXTENT);
+        builder.push(") / ");
+        builder.push_bind(grid_size);
+        builder.push(")");
+    }
+
+    builder
+}
+
+/// 交通事故を集計するグリッドの1辺の長さを計算する。
+///
+/// # 引数
+///
+/// * `zoom` - タイルのズームレベル
+/// * `division` - タイルの1辺を分割する数
+///
+/// # 戻り値
+///
+/// Webメルカトルの座標でのグリッドの1辺の長さ(m)
+fn cluster_grid_size(zoom: u8, division: u32) -> f64 {
+    tile_size(zoom) / division.max(1) as f64
+}
+
 fn calculate_extend_accident_bbox(
     tile_coordinate: TileCoordinate,
-    accident_zoom_level: u8,
     accident_buffer_ratio: f64,
-) -> Result<BBox, AppErrorResponse> {
-    // ズームレベルを確認
-    if tile_coordinate.z < accident_zoom_level {
-        return Err(AppErrorResponse::BadRequest(AppErrorContent {
-            app_error: AppError::AccidentZoomLevel,
-            message: format!(
-                "交通事故はズームレベル{}以上から取得できます。",
-                accident_zoom_level
-            )
-            .into(),
-        }));
-    }
+) -> BBox {
     // タイルのバウンディングボックスを計算
     let bbox = tile_bbox(tile_coordinate);
 
     // バウンディングボックスを拡張
-    Ok(bbox.extend(accident_buffer_ratio))
+    bbox.extend(accident_buffer_ratio)
+}
+
+/// 時刻を`HH:MM`形式の文字列に変換する。
+fn format_time(time: time::Time) -> String {
+    format!("{:02}:{:02}", time.hour(), time.minute())
+}
+
+/// 投影座標系の場合に、GeoJSONのFeatureCollectionに座標参照系（`crs`）を追加する。
+///
+/// RFC 7946は座標参照系を経度と緯度に限定しているため、GDALなどが解釈できる
+/// 旧仕様（GeoJSON 2008）の名前付き座標参照系で空間参照系を示す。
+fn insert_crs_member(members: &mut geojson::JsonObject, srid: Srid) {
+    if srid.is_geographic() {
+        return;
+    }
+    members.insert(
+        "crs".to_string(),
+        serde_json::json!({
+            "type": "name",
+            "properties": { "name": format!("urn:ogc:def:crs:EPSG::{}", srid) },
+        }),
+    );
+}
+
+fn accident_feature(accident: Accident) -> geojson::Feature {
+    let properties = Some(accident_properties(&accident));
+    let geometry: Option<geojson::Geometry> =
+        Some(geojson::Value::from(&accident.location.geometry.unwrap()).into());
+    let id = Some(geojson::feature::Id::String(accident.id.to_string()));
+    geojson::Feature {
+        bbox: None,
+        geometry,
+        id,
+        properties,
+        foreign_members: None,
+    }
+}
+
+fn accident_object(accident: Accident, srid: Srid) -> geojson::JsonObject {
+    let mut object = geojson::JsonObject::new();
+    object.insert("id".to_string(), SerdeString(accident.id.to_string()));
+    object.extend(accident_properties(&accident));
+    if let Some(geo_types::Geometry::Point(point)) = accident.location.geometry {
+        if let serde_json::Value::Object(position) = serde_json::json!(Position::new(point, srid)) {
+            object.extend(position);
+        }
+    }
+
+    object
 }
 
 fn accident_properties(accident: &Accident) -> geojson::JsonObject {
@@ -346,7 +1470,7 @@ fn accident_properties(accident: &Accident) -> geojson::JsonObject {
     );
     props.insert(
         "policeStationCode".to_string(),
-        SerdeString(accident.police_station_code.as_ref().unwrap().to_string()),
+        SerdeString(accident.police_station_code.to_string()),
     );
     props.insert(
         "policeStationName".to_string(),
@@ -388,3 +1512,324 @@ fn accident_properties(accident: &Accident) -> geojson::JsonObject {
 
     props
 }
+
+#[cfg(test)]
+mod tests {
+    use db::connection_pool;
+    use geozero::wkb;
+
+    use crate::map::{degree_to_tile, Coordinate};
+
+    use super::*;
+
+    /// 交通事故の列と、列に登録するコードを記録したコード表の組
+    const ACCIDENT_CODE_COLUMNS_FOR_TEST: &[(&str, &str)] = &[
+        ("accident_detail_code", "accident_details"),
+        ("route_class_code", "route_classes"),
+        ("day_night_code", "day_nights"),
+        ("weather_code", "weathers"),
+        ("district_code", "districts"),
+        ("surface_condition_code", "surface_conditions"),
+        ("road_model_code", "road_models"),
+        ("traffic_signal_code", "traffic_signals"),
+        ("stop_regulation_sign_a_code", "stop_regulation_signs"),
+        ("stop_regulation_display_a_code", "stop_regulation_displays"),
+        ("stop_regulation_sign_b_code", "stop_regulation_signs"),
+        ("stop_regulation_display_b_code", "stop_regulation_displays"),
+        ("road_width_code", "road_widths"),
+        ("road_alignment_code", "road_alignments"),
+        ("collision_point_code", "collision_points"),
+        ("zone_regulation_code", "zone_regulations"),
+        ("central_separation_code", "central_separations"),
+        ("road_segmentation_code", "road_segmentations"),
+        ("accident_type_code", "accident_types"),
+        ("age_a_code", "ages"),
+        ("age_b_code", "ages"),
+        ("party_a_code", "parties"),
+        ("party_b_code", "parties"),
+        ("purpose_a_code", "purposes"),
+        ("purpose_b_code", "purposes"),
+        ("vehicle_type_a_code", "vehicle_types"),
+        ("vehicle_type_b_code", "vehicle_types"),
+        ("automatic_a_code", "automatics"),
+        ("automatic_b_code", "automatics"),
+        ("support_car_a_code", "support_cars"),
+        ("support_car_b_code", "support_cars"),
+        ("speed_regulation_a_code", "speed_regulations"),
+        ("speed_regulation_b_code", "speed_regulations"),
+        ("vehicle_damage_a_code", "vehicle_damages"),
+        ("vehicle_damage_b_code", "vehicle_damages"),
+        ("airbag_a_code", "airbags"),
+        ("airbag_b_code", "airbags"),
+        ("side_airbag_a_code", "side_airbags"),
+        ("side_airbag_b_code", "side_airbags"),
+        ("injury_a_code", "injuries"),
+        ("injury_b_code", "injuries"),
+        ("week_code", "weeks"),
+        ("holiday_code", "holidays"),
+        ("driving_practice_a_code", "driving_practices"),
+        ("driving_practice_b_code", "driving_practices"),
+    ];
+
+    /// 各コード表の最小のコードを記録した交通事故を、高速票と当事者以外関与者を含めて登録する。
+    async fn insert_accident_for_test(connection: &mut PgConnection) -> Uuid {
+        let accident_id = Uuid::new_v4();
+        let mut builder = QueryBuilder::<Postgres>::new(
+            "INSERT INTO accidents (id, prefecture_code, police_station_code, main_number, \
+            number_of_deaths, number_of_injuries, route_code, location_code, city_jis_code, \
+            occurred_at, sunrise_time, sunset_time, collision_part_a, collision_part_b, \
+            cognitive_days_a, cognitive_days_b, location",
+        );
+        for (column, _) in ACCIDENT_CODE_COLUMNS_FOR_TEST {
+            builder.push(format!(", {}", column));
+        }
+        builder.push(") VALUES (");
+        builder.push_bind(accident_id);
+        builder.push(
+            ", '10', '059', 99001, 0, 1, '1010', 1, (SELECT MIN(city_jis_code) FROM cities), \
+            '2022-04-01T09:30:00+09:00', '05:20', '18:00', '01', '00', 1, 1, ST_SetSRID(",
+        );
+        builder.push_bind(wkb::Encode(geo_types::Geometry::Point(
+            geo_types::Point::new(141.35, 43.06),
+        )));
+        builder.push(", ");
+        builder.push_bind(Srid::JGD2011.code() as i32);
+        builder.push(")");
+        for (_, table) in ACCIDENT_CODE_COLUMNS_FOR_TEST {
+            builder.push(format!(", (SELECT MIN(code) FROM {})", table));
+        }
+        builder.push(")");
+        builder.build().execute(&mut *connection).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO highway_details (id, accident_id, up_down_code, kilopost_km, kilopost_m, \
+            road_structure_code, lane_a_code, lane_b_code) \
+            VALUES ($1, $2, '1', 123, 400, '2', '1', '5')",
+        )
+        .bind(Uuid::new_v4())
+        .bind(accident_id)
+        .execute(&mut *connection)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO involved_persons (id, accident_id, sub_number, party_code, \
+            riding_type_code, riding_class_code, support_car_code, airbag_code, side_airbag_code, \
+            injury_code) \
+            SELECT $1, $2, 1, (SELECT MIN(code) FROM parties), \
+            (SELECT MIN(code) FROM riding_types), (SELECT MIN(code) FROM riding_classes), \
+            (SELECT MIN(code) FROM support_cars), (SELECT MIN(code) FROM airbags), \
+            (SELECT MIN(code) FROM side_airbags), (SELECT MIN(code) FROM injuries)",
+        )
+        .bind(Uuid::new_v4())
+        .bind(accident_id)
+        .execute(&mut *connection)
+        .await
+        .unwrap();
+
+        accident_id
+    }
+
+    #[tokio::test]
+    #[ignore = "PostgreSQLのデータベースが必要"]
+    async fn fetch_accident_detail_ok() {
+        let pool = connection_pool().await.unwrap();
+        let mut tx = pool.begin().await.unwrap();
+        let accident_id = insert_accident_for_test(&mut tx).await;
+
+        let accident = fetch_accident_detail(&mut tx, accident_id, Language::En, Srid::JGD2011)
+            .await
+            .unwrap();
+        assert_eq!(accident.id, accident_id);
+        assert_eq!(accident.prefecture_name, "Hokkaido (Sapporo)");
+        assert_eq!(accident.police_station_code, "10059");
+        assert_eq!(accident.involved_persons.len(), 1);
+        let highway = accident.highway.unwrap();
+        assert_eq!(highway.up_down_name.as_deref(), Some("Inbound"));
+        assert_eq!(highway.road_structure_name.as_deref(), Some("Ramp"));
+        assert_eq!(highway.lane_a_name.as_deref(), Some("Lane 1"));
+        assert_eq!(highway.lane_b_code, "5");
+        assert_eq!(highway.lane_b_name.as_deref(), Some("Shoulder"));
+
+        let accident = fetch_accident_detail(&mut tx, accident_id, Language::Ja, Srid::JGD2011)
+            .await
+            .unwrap();
+        let highway = accident.highway.unwrap();
+        assert_eq!(highway.up_down_name.as_deref(), Some("上り"));
+        assert_eq!(highway.road_structure_name.as_deref(), Some("ランプ"));
+
+        tx.rollback().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "PostgreSQLのデータベースが必要"]
+    async fn fetch_accident_detail_fail() {
+        let pool = connection_pool().await.unwrap();
+        let mut connection = pool.acquire().await.unwrap();
+        let error =
+            fetch_accident_detail(&mut connection, Uuid::new_v4(), Language::Ja, Srid::JGD2011)
+                .await
+                .unwrap_err();
+        assert_eq!(
+            error.as_response_error().status_code(),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[test]
+    fn cluster_grid_size_ok() {
+        assert_eq!(cluster_grid_size(0, 4), WEB_MERCATOR_HALF_EXTENT / 2.0);
+        assert_eq!(cluster_grid_size(0, 0), WEB_MERCATOR_HALF_EXTENT * 2.0);
+        // ズームレベルが1増えるとグリッドの1辺の長さは半分になる
+        assert_eq!(cluster_grid_size(10, 8) / 2.0, cluster_grid_size(11, 8));
+    }
+
+    #[test]
+    fn cluster_query_ok() {
+        let tile_coordinate = TileCoordinate {
+            z: 8,
+            x: 227,
+            y: 100,
+        };
+        let filter = AccidentFilter {
+            accident_detail_codes: Some(vec![AccidentDetailCode::try_from("1").unwrap()]),
+            ..Default::default()
+        };
+        let builder = cluster_query(tile_coordinate, 4, &filter);
+
+        assert_eq!(
+            builder.sql(),
+            r#"
+        SELECT
+            COUNT(*) count,
+            SUM(a.number_of_deaths) number_of_deaths,
+            SUM(a.number_of_injuries) number_of_injuries,
+            AVG(ST_X(a.location)) longitude,
+            AVG(ST_Y(a.location)) latitude
+        FROM accidents a WHERE ST_CONTAINS(ST_MakeEnvelope($1, $2, $3, $4, $5), a.location) AND a.accident_detail_code::text = ANY($6) GROUP BY FLOOR((ST_X(ST_Transform(a.location, $7)) + $8) / $9), FLOOR((ST_Y(ST_Transform(a.location, $10)) + $11) / $12)"#
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "PostgreSQLのデータベースが必要"]
+    async fn cluster_query_execute_ok() {
+        let pool = connection_pool().await.unwrap();
+        let mut tx = pool.begin().await.unwrap();
+        insert_accident_for_test(&mut tx).await;
+        let tile_coordinate = degree_to_tile(
+            Coordinate {
+                lon: 141.35,
+                lat: 43.06,
+            },
+            8,
+        );
+        // 登録した交通事故のみを集計するように、発生日時と警察署で絞り込む
+        let filter = AccidentFilter {
+            occurred_from: Some(time::macros::datetime!(2022-04-01 09:30 +09:00)),
+            occurred_to: Some(time::macros::datetime!(2022-04-01 09:31 +09:00)),
+            police_station_codes: Some(vec![PoliceStationCode::try_from("10059").unwrap()]),
+            ..Default::default()
+        };
+
+        let clusters = cluster_query(tile_coordinate, 4, &filter)
+            .build_query_as::<AccidentCluster>()
+            .fetch_all(&mut *tx)
+            .await
+            .unwrap();
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].count, 1);
+        assert_eq!(clusters[0].number_of_injuries, 1);
+
+        tx.rollback().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "PostgreSQLのデータベースが必要"]
+    async fn within_area_buffer_execute_ok() {
+        let pool = connection_pool().await.unwrap();
+        let mut tx = pool.begin().await.unwrap();
+        let accident_id = insert_accident_for_test(&mut tx).await;
+
+        // 交通事故の発生場所の約80メートル西を通るLineStringを、200メートルのバッファで検索
+        let line_string =
+            r#"{"type": "LineString", "coordinates": [[141.349, 43.05], [141.349, 43.07]]}"#;
+        let area = WithinArea::new(
+            line_string,
+            WithinParams {
+                buffer: Some(200.0),
+            },
+        )
+        .unwrap();
+        let mut builder = QueryBuilder::new("SELECT a.id FROM accidents a");
+        area.push_conditions(&mut builder, &AccidentFilter::default());
+        builder.push(" AND a.id = ");
+        builder.push_bind(accident_id);
+        let ids: Vec<Uuid> = builder
+            .build_query_scalar()
+            .fetch_all(&mut *tx)
+            .await
+            .unwrap();
+        assert_eq!(ids, [accident_id]);
+
+        tx.rollback().await.unwrap();
+    }
+
+    #[test]
+    fn valid_tile_coordinate_ok() {
+        let tile_coordinate = TileCoordinate {
+            z: 14,
+            x: 14414,
+            y: 6467,
+        };
+        assert!(valid_tile_coordinate(tile_coordinate).is_ok());
+    }
+
+    #[test]
+    fn valid_tile_coordinate_fail() {
+        for (z, x, y) in [(14, 16384, 6467), (14, 14414, 16384), (0, 0, 1), (25, 0, 0)] {
+            let error = valid_tile_coordinate(TileCoordinate { z, x, y }).unwrap_err();
+            assert_eq!(error.status_code(), StatusCode::BAD_REQUEST);
+            match error {
+                AppErrorResponse::BadRequest(content) => {
+                    assert!(matches!(content.app_error, AppError::InvalidQuery))
+                }
+                _ => panic!("クエリパラメータエラーではありません。"),
+            }
+        }
+    }
+
+    #[test]
+    fn push_tile_conditions_ok() {
+        let bbox = BBox {
+            x_min: 139.0,
+            y_min: 35.0,
+            x_max: 140.0,
+            y_max: 35.5,
+        };
+        let filter = AccidentFilter::try_from(
+            web::Query::<AccidentFilterParams>::from_query("detail=1&weather=1,2&party=61")
+                .unwrap()
+                .into_inner(),
+        )
+        .unwrap();
+        let mut builder = QueryBuilder::new("SELECT a.id FROM accidents a");
+        push_tile_conditions(&mut builder, &bbox, &filter);
+
+        assert_eq!(
+            builder.sql(),
+            "SELECT a.id FROM accidents a \
+            WHERE ST_CONTAINS(ST_MakeEnvelope($1, $2, $3, $4, $5), a.location) \
+            AND a.weather_code::text = ANY($6) \
+            AND a.accident_detail_code::text = ANY($7) \
+            AND ARRAY[a.party_a_code::text, a.party_b_code::text] && $8"
+        );
+
+        // 絞り込み条件がない場合は、タイルの範囲のみで絞り込む
+        let mut builder = QueryBuilder::new("SELECT a.id FROM accidents a");
+        push_tile_conditions(&mut builder, &bbox, &AccidentFilter::default());
+        assert_eq!(
+            builder.sql(),
+            "SELECT a.id FROM accidents a \
+            WHERE ST_CONTAINS(ST_MakeEnvelope($1, $2, $3, $4, $5), a.location)"
+        );
+    }
+}
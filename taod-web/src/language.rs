@@ -0,0 +1,169 @@
+use std::future::{ready, Ready};
+
+use actix_web::dev::Payload;
+use actix_web::http::header::{AcceptLanguage, Header, Preference};
+use actix_web::{web, FromRequest, HttpRequest};
+
+use code_tables::Language;
+
+use crate::filters::invalid_query;
+use crate::handlers::AppErrorResponse;
+
+/// 言語クエリパラメータ
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct LanguageParams {
+    /// 言語（`ja`または`en`）
+    pub lang: Option<String>,
+}
+
+/// リクエストの言語
+///
+/// `lang`クエリパラメータ、`Accept-Language`ヘッダの順に言語を決定して、
+/// いずれからも決定できない場合は日本語とする。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestLanguage(pub Language);
+
+impl FromRequest for RequestLanguage {
+    type Error = AppErrorResponse;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let language = query_language(req).map(|language| {
+            Self(
+                language
+                    .or_else(|| accept_language(req))
+                    .unwrap_or_default(),
+            )
+        });
+
+        ready(language)
+    }
+}
+
+/// `lang`クエリパラメータから言語を返す。
+///
+/// # 引数
+///
+/// * `req` - リクエスト
+///
+/// # 戻り値
+///
+/// 言語、`lang`クエリパラメータがない場合は`None`
+pub fn query_language(req: &HttpRequest) -> Result<Option<Language>, AppErrorResponse> {
+    let params = web::Query::<LanguageParams>::from_query(req.query_string())
+        .map(|params| params.into_inner())
+        .unwrap_or_default();
+    params
+        .lang
+        .map(|lang| {
+            lang.parse::<Language>().map_err(|_| {
+                invalid_query(
+                    format!("langにはjaまたはenを指定してください({})。", lang).into(),
+                    format!("lang must be ja or en ({}).", lang).into(),
+                )
+            })
+        })
+        .transpose()
+}
+
+/// `Accept-Language`ヘッダから、品質値が最も高い対応している言語を返す。
+///
+/// # 引数
+///
+/// * `req` - リクエスト
+///
+/// # 戻り値
+///
+/// 言語、対応している言語がない場合は`None`
+pub fn accept_language(req: &HttpRequest) -> Option<Language> {
+    let accept_language = AcceptLanguage::parse(req).ok()?;
+    accept_language
+        .ranked()
+        .into_iter()
+        .find_map(|preference| match preference {
+            Preference::Specific(tag) => tag.primary_language().parse().ok(),
+            Preference::Any => None,
+        })
+}
+
+/// リクエストの言語を返す。
+///
+/// `lang`クエリパラメータに対応していない言語が指定された場合は、`Accept-Language`ヘッダから
+/// 言語を決定する。
+///
+/// # 引数
+///
+/// * `req` - リクエスト
+///
+/// # 戻り値
+///
+/// 言語
+pub fn request_language(req: &HttpRequest) -> Language {
+    query_language(req)
+        .ok()
+        .flatten()
+        .or_else(|| accept_language(req))
+        .unwrap_or_default()
+}
+
+/// コード表の名前の列を、言語に対応するSQLの式に変換する。
+///
+/// 英語の場合は英語の名前の列（`<列名>_en`）を返し、英語の名前がない場合は日本語の名前を返す。
+/// 英語の名前の列を持たない市区町村テーブルと警察署テーブルには使用しない。
+///
+/// # 引数
+///
+/// * `column` - コード表の名前の列（`we.name`など）
+/// * `language` - 言語
+///
+/// # 戻り値
+///
+/// 言語に対応する名前を返すSQLの式
+pub fn name_column(column: &str, language: Language) -> String {
+    match language {
+        Language::Ja => column.to_string(),
+        Language::En => format!("COALESCE({}_en, {})", column, column),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn request_language_ok() {
+        let req = TestRequest::default().to_http_request();
+        assert_eq!(request_language(&req), Language::Ja);
+
+        let req = TestRequest::with_uri("/api/accidents?lang=en").to_http_request();
+        assert_eq!(request_language(&req), Language::En);
+
+        let req = TestRequest::default()
+            .insert_header(("Accept-Language", "fr-CH, en-US;q=0.8, ja;q=0.7"))
+            .to_http_request();
+        assert_eq!(request_language(&req), Language::En);
+
+        // langクエリパラメータを優先
+        let req = TestRequest::with_uri("/api/accidents?lang=ja")
+            .insert_header(("Accept-Language", "en"))
+            .to_http_request();
+        assert_eq!(request_language(&req), Language::Ja);
+
+        // 対応していない言語は無視
+        let req = TestRequest::with_uri("/api/accidents?lang=fr")
+            .insert_header(("Accept-Language", "en"))
+            .to_http_request();
+        assert_eq!(request_language(&req), Language::En);
+        assert!(query_language(&req).is_err());
+    }
+
+    #[test]
+    fn name_column_ok() {
+        assert_eq!(name_column("we.name", Language::Ja), "we.name");
+        assert_eq!(
+            name_column("we.name", Language::En),
+            "COALESCE(we.name_en, we.name)"
+        );
+    }
+}
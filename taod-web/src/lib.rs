@@ -1,5 +1,12 @@
+pub mod codes;
+pub mod export;
+pub mod filters;
 pub mod handlers;
+pub mod language;
 pub mod map;
 pub mod middleware;
 pub mod models;
+pub mod near;
 pub mod settings;
+pub mod stats;
+pub mod within;
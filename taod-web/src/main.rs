@@ -1,12 +1,15 @@
 use std::net::TcpListener;
 
-use actix_web::middleware::ErrorHandlers;
 use actix_web::{web, App, HttpServer};
 
 use db::connection_pool;
 
-use taod_web::handlers::{accident_list, accident_list_geojson, health_check};
-use taod_web::middleware::default_error_handler;
+use taod_web::handlers::{
+    accident_detail, accident_export, accident_list, accident_list_geojson, accident_near,
+    accident_search, accident_stats, accident_tile, accident_within, code_table_list,
+    code_table_rows, health_check,
+};
+use taod_web::middleware::error_handlers;
 use taod_web::settings::get_settings;
 
 #[tokio::main]
@@ -21,16 +24,28 @@ async fn main() -> anyhow::Result<()> {
 
     HttpServer::new(move || {
         App::new()
-            .wrap(ErrorHandlers::new().default_handler_client(default_error_handler))
+            .wrap(error_handlers())
             .app_data(web::Data::new(settings.clone()))
             .app_data(web::Data::new(pool.clone()))
             .service(
                 web::scope("/api")
                     .route("/health-check", web::get().to(health_check))
+                    .route("/accidents", web::get().to(accident_search))
+                    .route("/accidents/near", web::get().to(accident_near))
+                    .route("/accidents/export", web::get().to(accident_export))
+                    .route("/accidents/within", web::post().to(accident_within))
+                    .route("/accidents/{id}", web::get().to(accident_detail))
                     .route("/accidents/{z}/{x}/{y}", web::get().to(accident_list))
                     .route(
                         "/accidents-geojson/{z}/{x}/{y}",
                         web::get().to(accident_list_geojson),
+                    )
+                    .route("/codes", web::get().to(code_table_list))
+                    .route("/codes/{table}", web::get().to(code_table_rows))
+                    .route("/stats/accidents", web::get().to(accident_stats))
+                    .route(
+                        "/tiles/accidents/{z}/{x}/{y}.pbf",
+                        web::get().to(accident_tile),
                     ),
             )
     })
@@ -1,12 +1,21 @@
 use std::f64::consts::PI;
 
-/// 日本測地系2011
-pub const SRID_JGD2001: u32 = 6668;
+/// ベクトルタイルのタイル1辺あたりの座標の範囲
+pub const MVT_EXTENT: i32 = 4096;
+
+/// ベクトルタイルのタイルの外側に含める範囲（タイル座標の単位）
+pub const MVT_BUFFER: i32 = 256;
+
+/// タイル座標の最大ズームレベル
+pub const MAX_TILE_ZOOM: u8 = 24;
 
 /// 測地基準系1980(GRS80)楕円体長半径(m)
 /// 日本測地型2011の楕円体における長半径
 pub const GRS80_MAJOR_AXIS: f64 = 6378137.0;
 
+/// Webメルカトルの原点から座標の範囲の端までの距離(m)
+pub const WEB_MERCATOR_HALF_EXTENT: f64 = PI * GRS80_MAJOR_AXIS;
+
 /// タイル座標
 #[derive(Debug, Clone, Copy, serde::Deserialize)]
 pub struct TileCoordinate {
@@ -18,6 +27,25 @@ pub struct TileCoordinate {
     pub y: u32,
 }
 
+impl TileCoordinate {
+    /// タイル座標が有効であるかを返す。
+    ///
+    /// ズームレベルが`MAX_TILE_ZOOM`以下で、X座標とY座標が0以上2のズームレベル乗未満の場合に
+    /// 有効とする。
+    ///
+    /// # 戻り値
+    ///
+    /// 有効な場合は`true`、無効な場合は`false`
+    pub fn is_valid(&self) -> bool {
+        if MAX_TILE_ZOOM < self.z {
+            return false;
+        }
+        let n = 1_u32 << self.z;
+
+        self.x < n && self.y < n
+    }
+}
+
 /// 経度、緯度
 #[derive(Debug, Clone, Copy)]
 pub struct Coordinate {
@@ -166,6 +194,19 @@ pub fn tile_longitude_range(tile_x: u32, zoom: u8) -> Range {
     Range { min, max }
 }
 
+/// タイルの1辺の長さをWebメルカトルの座標で計算する。
+///
+/// # 引数
+///
+/// * `zoom` - ズームレベル
+///
+/// # 戻り値
+///
+/// タイルの1辺の長さ(m)
+pub fn tile_size(zoom: u8) -> f64 {
+    2.0 * WEB_MERCATOR_HALF_EXTENT / (1_u64 << zoom) as f64
+}
+
 /// タイル座標からバウンダリーボックスを計算する。
 ///
 /// # 引数
@@ -197,6 +238,36 @@ pub fn tile_bbox(tc: TileCoordinate) -> BBox {
 mod tests {
     use super::*;
 
+    #[test]
+    fn tile_coordinate_is_valid_ok() {
+        assert!(TileCoordinate { z: 0, x: 0, y: 0 }.is_valid());
+        assert!(TileCoordinate {
+            z: 14,
+            x: 16383,
+            y: 16383
+        }
+        .is_valid());
+        assert!(!TileCoordinate { z: 0, x: 1, y: 0 }.is_valid());
+        assert!(!TileCoordinate {
+            z: 14,
+            x: 14414,
+            y: 16384
+        }
+        .is_valid());
+        assert!(!TileCoordinate {
+            z: 14,
+            x: 16384,
+            y: 6467
+        }
+        .is_valid());
+        assert!(!TileCoordinate {
+            z: MAX_TILE_ZOOM + 1,
+            x: 0,
+            y: 0
+        }
+        .is_valid());
+    }
+
     /// 富士山三角点
     const FUJI_LON: f64 = 138.0 + 43.0 / 60.0 + 39.0 / 3600.0;
     const FUJI_LAT: f64 = 35.0 + 21.0 / 60.0 + 39.0 / 3600.0;
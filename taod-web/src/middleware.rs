@@ -1,12 +1,30 @@
 use actix_web::body::MessageBody;
 use actix_web::dev::ServiceResponse;
 use actix_web::http::header;
-use actix_web::middleware::ErrorHandlerResponse;
+use actix_web::middleware::{ErrorHandlerResponse, ErrorHandlers};
 use actix_web::HttpResponse;
 
-use crate::handlers::{AppError, AppResponseErrorBody};
+use code_tables::Language;
+
+use crate::handlers::{AppError, AppResponseErrorBody, EnglishErrorBody};
+use crate::language::request_language;
+
+/// クライアントエラーとサーバーエラーのレスポンスを`default_error_handler`で処理するミドルウェアを返す。
+///
+/// # 戻り値
+///
+/// エラーレスポンスを処理するミドルウェア
+pub fn error_handlers<B>() -> ErrorHandlers<B>
+where
+    B: actix_web::body::MessageBody + 'static,
+    <B as MessageBody>::Error: std::fmt::Debug,
+{
+    ErrorHandlers::new().default_handler(default_error_handler)
+}
 
 /// actix-webが、ハンドラがない場合やエクストラクタで発生したエラーなどをJSON形式に変換するミドルウェア
+///
+/// アプリケーションエラーのレスポンスは、リクエストの言語が英語の場合に英語のボディに置き換える。
 pub fn default_error_handler<B>(
     mut service_response: ServiceResponse<B>,
 ) -> actix_web::Result<ErrorHandlerResponse<B>>
@@ -14,12 +32,28 @@ where
     B: actix_web::body::MessageBody,
     <B as MessageBody>::Error: std::fmt::Debug,
 {
-    // Content-Typeがapplication/jsonの場合はそのまま返す
+    // Content-Typeがapplication/jsonの場合は、リクエストの言語が英語の場合のみボディを置き換える
     let content_type = service_response.headers().get(header::CONTENT_TYPE);
     if content_type.is_some() && content_type.unwrap() == "application/json" {
-        return Ok(ErrorHandlerResponse::Response(
-            service_response.map_into_left_body(),
-        ));
+        let english_body = service_response
+            .response()
+            .extensions()
+            .get::<EnglishErrorBody>()
+            .cloned();
+        return match english_body {
+            Some(EnglishErrorBody(body))
+                if request_language(service_response.request()) == Language::En =>
+            {
+                let (request, response) = service_response.into_parts();
+                let response = ServiceResponse::new(request, response.set_body(body))
+                    .map_into_boxed_body()
+                    .map_into_right_body();
+                Ok(ErrorHandlerResponse::Response(response))
+            }
+            _ => Ok(ErrorHandlerResponse::Response(
+                service_response.map_into_left_body(),
+            )),
+        };
     }
 
     // レスポンスヘッダーにContent-Typeを追加して、リクエストとレスポンスに分離
@@ -47,3 +81,73 @@ where
 
     Ok(ErrorHandlerResponse::Response(response))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use actix_web::http::StatusCode;
+    use actix_web::{test, web, App};
+    use sqlx::postgres::PgPoolOptions;
+
+    use super::*;
+    use crate::handlers::accident_detail;
+
+    /// 接続できないデータベースのURL
+    const UNREACHABLE_DATABASE_URL: &str = "postgres://taod@127.0.0.1:1/taod";
+
+    /// エラーレスポンスのメッセージを返す。
+    async fn error_message_for_test(request: test::TestRequest) -> (StatusCode, String) {
+        let pool = PgPoolOptions::new()
+            .acquire_timeout(Duration::from_secs(1))
+            .connect_lazy(UNREACHABLE_DATABASE_URL)
+            .unwrap();
+        let app = test::init_service(
+            App::new()
+                .wrap(error_handlers())
+                .app_data(web::Data::new(pool))
+                .route("/api/accidents/{id}", web::get().to(accident_detail)),
+        )
+        .await;
+        let response = test::call_service(&app, request.to_request()).await;
+        let status_code = response.status();
+        let body: serde_json::Value = test::read_body_json(response).await;
+
+        (status_code, body["message"].as_str().unwrap().to_string())
+    }
+
+    #[actix_web::test]
+    async fn default_error_handler_ok() {
+        let uri = "/api/accidents/0b41e48b-8d7b-41e7-a847-d76d4da11168";
+
+        // サーバーエラー
+        let (status_code, message) =
+            error_message_for_test(test::TestRequest::get().uri(uri)).await;
+        assert_eq!(status_code, StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(message.starts_with("データベースでエラーが発生しました。"));
+        let (status_code, message) =
+            error_message_for_test(test::TestRequest::get().uri(&format!("{}?lang=en", uri))).await;
+        assert_eq!(status_code, StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(message.starts_with("A database error occurred."));
+        let (_, message) = error_message_for_test(
+            test::TestRequest::get()
+                .uri(uri)
+                .insert_header(("Accept-Language", "en")),
+        )
+        .await;
+        assert!(message.starts_with("A database error occurred."));
+
+        // クライアントエラー
+        let (status_code, message) = error_message_for_test(
+            test::TestRequest::get()
+                .uri(&format!("{}?srid=4612", uri))
+                .insert_header(("Accept-Language", "en")),
+        )
+        .await;
+        assert_eq!(status_code, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            message,
+            "srid must be 4326, 3857, 6668 or between 6669 and 6687 (4612)."
+        );
+    }
+}
@@ -1,22 +1,32 @@
-use geometries::WkbGeometryF64;
+use code_tables::{
+    AccidentDetailCode, AccidentTypeCode, AgeCode, AirbagCode, AutomaticCode,
+    CentralSeparationCode, CityCode, CollisionPointCode, DayNightCode, DistrictCode,
+    DrivingPracticeCode, HolidayCode, InjuryCode, LaneCode, PartyCode, PoliceStationCode,
+    PrefectureJisCode, PurposeCode, RidingClassCode, RidingTypeCode, RoadAlignmentCode,
+    RoadModelCode, RoadSegmentationCode, RoadStructureCode, RoadWidthCode, RouteClassCode,
+    RouteCode, SideAirbagCode, SpeedRegulationCode, StopRegulationDisplayCode,
+    StopRegulationSignCode, SupportCarCode, SurfaceConditionCode, TrafficSignalCode, UpDownCode,
+    VehicleDamageCode, VehicleTypeCode, WeatherCode, WeekCode, ZoneRegulationCode,
+};
+use geometries::{Srid, WkbGeometryF64};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
 /// 交通事故
-#[derive(Debug)]
+#[derive(Debug, sqlx::FromRow)]
 pub struct Accident {
     /// 交通事故ID
     pub id: Uuid,
     /// 都道府県コード
-    pub prefecture_code: String,
+    pub prefecture_code: PrefectureJisCode,
     /// 都道府県名
     pub prefecture_name: String,
     /// 警察署コード
-    pub police_station_code: Option<String>,
+    pub police_station_code: PoliceStationCode,
     /// 警察署名
     pub police_station_name: String,
     /// 市区町村コード
-    pub city_code: String,
+    pub city_code: CityCode,
     /// 市区町村名
     pub city_name: String,
     /// 発生日時
@@ -26,13 +36,417 @@ pub struct Accident {
     /// 負傷者数
     pub number_of_injuries: i32,
     /// 天候コード
-    pub weather_code: String,
+    pub weather_code: WeatherCode,
     /// 天候名
     pub weather_name: String,
     /// 路面状態コード
-    pub surface_condition_code: String,
+    pub surface_condition_code: SurfaceConditionCode,
     /// 路面状態名
     pub surface_condition_name: String,
     /// 発生箇所
     pub location: WkbGeometryF64,
 }
+
+/// 発生箇所の座標
+///
+/// 地理座標系の場合は経度（`longitude`）と緯度（`latitude`）、投影座標系の場合は
+/// 東距（`easting`）と北距（`northing`）で表現する。
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(untagged)]
+pub enum Position {
+    /// 地理座標系の座標
+    Geographic {
+        /// 経度
+        longitude: f64,
+        /// 緯度
+        latitude: f64,
+    },
+    /// 投影座標系の座標
+    Projected {
+        /// 東距（東方向の座標、メートル）
+        easting: f64,
+        /// 北距（北方向の座標、メートル）
+        northing: f64,
+    },
+}
+
+impl Position {
+    /// 空間参照系に対応する座標を作成する。
+    ///
+    /// # 引数
+    ///
+    /// * `point` - 空間参照系で表現したポイント
+    /// * `srid` - ポイントの空間参照系
+    ///
+    /// # 戻り値
+    ///
+    /// 発生箇所の座標
+    pub fn new(point: geo_types::Point, srid: Srid) -> Self {
+        if srid.is_geographic() {
+            Self::Geographic {
+                longitude: point.x(),
+                latitude: point.y(),
+            }
+        } else {
+            Self::Projected {
+                easting: point.x(),
+                northing: point.y(),
+            }
+        }
+    }
+}
+
+/// 地点周辺の交通事故
+#[derive(Debug, sqlx::FromRow)]
+pub struct NearAccident {
+    /// 交通事故
+    #[sqlx(flatten)]
+    pub accident: Accident,
+    /// 地点からの距離（メートル）
+    pub distance: f64,
+}
+
+/// エクスポートする交通事故
+///
+/// 交通事故に、エクスポートで出力する事故内容や当事者などのコードを加える。
+#[derive(Debug, sqlx::FromRow)]
+pub struct ExportAccident {
+    /// 交通事故
+    #[sqlx(flatten)]
+    pub accident: Accident,
+    /// 事故内容コード
+    pub accident_detail_code: AccidentDetailCode,
+    /// 昼夜コード
+    pub day_night_code: DayNightCode,
+    /// 道路形状コード
+    pub road_model_code: RoadModelCode,
+    /// 事故類型コード
+    pub accident_type_code: AccidentTypeCode,
+    /// 当事者種別コード（当事者A）
+    pub party_a_code: PartyCode,
+    /// 年齢コード（当事者A）
+    pub age_a_code: AgeCode,
+    /// 当事者種別コード（当事者B）
+    pub party_b_code: PartyCode,
+    /// 年齢コード（当事者B）
+    pub age_b_code: AgeCode,
+}
+
+/// 集計した交通事故
+#[derive(Debug, sqlx::FromRow)]
+pub struct AccidentCluster {
+    /// 交通事故の件数
+    pub count: i64,
+    /// 死者数の合計
+    pub number_of_deaths: i64,
+    /// 負傷者数の合計
+    pub number_of_injuries: i64,
+    /// 交通事故の平均の経度
+    pub longitude: f64,
+    /// 交通事故の平均の緯度
+    pub latitude: f64,
+}
+
+/// 交通事故統計
+#[derive(Debug, sqlx::FromRow, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccidentStats {
+    /// 集計単位のコード
+    pub code: String,
+    /// 集計単位の名前
+    pub name: Option<String>,
+    /// 交通事故の件数
+    pub number_of_accidents: i64,
+    /// 死者数の合計
+    pub number_of_deaths: i64,
+    /// 負傷者数の合計
+    pub number_of_injuries: i64,
+}
+
+/// 交通事故詳細
+///
+/// 交通事故のすべての項目と、コードに対応するコード表の名前を記録する。
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccidentDetail {
+    /// 交通事故ID
+    pub id: Uuid,
+    /// 都道府県コード
+    pub prefecture_code: PrefectureJisCode,
+    /// 都道府県名
+    pub prefecture_name: String,
+    /// 警察署コード
+    pub police_station_code: PoliceStationCode,
+    /// 警察署名
+    pub police_station_name: String,
+    /// 本票番号
+    pub main_number: i32,
+    /// 死者数
+    pub number_of_deaths: i32,
+    /// 負傷者数
+    pub number_of_injuries: i32,
+    /// 路線コード
+    pub route_code: RouteCode,
+    /// 路線名
+    pub route_name: Option<String>,
+    /// 地点コード
+    pub location_code: i32,
+    /// 市区町村コード
+    pub city_code: CityCode,
+    /// 市区町村名
+    pub city_name: String,
+    /// 発生日時
+    #[serde(with = "time::serde::rfc3339")]
+    pub occurred_at: OffsetDateTime,
+    /// 日の出時刻（HH:MM）
+    pub sunrise_time: String,
+    /// 日の入時刻（HH:MM）
+    pub sunset_time: String,
+    /// 事故内容コード
+    pub accident_detail_code: AccidentDetailCode,
+    /// 事故内容名
+    pub accident_detail_name: String,
+    /// 路線区分コード
+    pub route_class_code: RouteClassCode,
+    /// 路線区分名
+    pub route_class_name: String,
+    /// 昼夜コード
+    pub day_night_code: DayNightCode,
+    /// 昼夜名
+    pub day_night_name: String,
+    /// 天候コード
+    pub weather_code: WeatherCode,
+    /// 天候名
+    pub weather_name: String,
+    /// 地形（地区）コード
+    pub district_code: DistrictCode,
+    /// 地形（地区）名
+    pub district_name: String,
+    /// 路面状態コード
+    pub surface_condition_code: SurfaceConditionCode,
+    /// 路面状態名
+    pub surface_condition_name: String,
+    /// 道路形状コード
+    pub road_model_code: RoadModelCode,
+    /// 道路形状名
+    pub road_model_name: String,
+    /// 信号機コード
+    pub traffic_signal_code: TrafficSignalCode,
+    /// 信号機名
+    pub traffic_signal_name: String,
+    /// 車道幅員コード
+    pub road_width_code: RoadWidthCode,
+    /// 車道幅員名
+    pub road_width_name: String,
+    /// 道路線形コード
+    pub road_alignment_code: RoadAlignmentCode,
+    /// 道路線形名
+    pub road_alignment_name: String,
+    /// 衝突地点コード
+    pub collision_point_code: CollisionPointCode,
+    /// 衝突地点名
+    pub collision_point_name: String,
+    /// ゾーン規制コード
+    pub zone_regulation_code: ZoneRegulationCode,
+    /// ゾーン規制名
+    pub zone_regulation_name: String,
+    /// 中央分離帯施設コード
+    pub central_separation_code: CentralSeparationCode,
+    /// 中央分離帯施設名
+    pub central_separation_name: String,
+    /// 歩車道区分コード
+    pub road_segmentation_code: RoadSegmentationCode,
+    /// 歩車道区分名
+    pub road_segmentation_name: String,
+    /// 事故類型コード
+    pub accident_type_code: AccidentTypeCode,
+    /// 事故類型名
+    pub accident_type_name: String,
+    /// 曜日コード
+    pub week_code: WeekCode,
+    /// 曜日名
+    pub week_name: String,
+    /// 祝日コード
+    pub holiday_code: HolidayCode,
+    /// 祝日名
+    pub holiday_name: String,
+    /// 発生箇所の座標
+    #[serde(flatten)]
+    pub position: Option<Position>,
+    /// 当事者A
+    pub party_a: PartyDetail,
+    /// 当事者B
+    pub party_b: PartyDetail,
+    /// 当事者以外関与者
+    pub involved_persons: Vec<InvolvedPerson>,
+    /// 高速道路交通事故詳細
+    pub highway: Option<HighwayDetail>,
+}
+
+/// 交通事故の当事者の詳細
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartyDetail {
+    /// 当事者種別コード
+    pub party_code: PartyCode,
+    /// 当事者種別名
+    pub party_name: String,
+    /// 年齢コード
+    pub age_code: AgeCode,
+    /// 年齢名
+    pub age_name: String,
+    /// 用途コード
+    pub purpose_code: PurposeCode,
+    /// 用途名
+    pub purpose_name: String,
+    /// 車両形状コード
+    pub vehicle_type_code: VehicleTypeCode,
+    /// 車両形状名
+    pub vehicle_type_name: String,
+    /// 一時停止規制標識コード
+    pub stop_regulation_sign_code: StopRegulationSignCode,
+    /// 一時停止規制標識名
+    pub stop_regulation_sign_name: String,
+    /// 一時停止規制表示コード
+    pub stop_regulation_display_code: StopRegulationDisplayCode,
+    /// 一時停止規制表示名
+    pub stop_regulation_display_name: String,
+    /// オートマチック車コード
+    pub automatic_code: AutomaticCode,
+    /// オートマチック車名
+    pub automatic_name: String,
+    /// サポカーコード
+    pub support_car_code: SupportCarCode,
+    /// サポカー名
+    pub support_car_name: String,
+    /// 速度規制（指定のみ）コード
+    pub speed_regulation_code: SpeedRegulationCode,
+    /// 速度規制（指定のみ）名
+    pub speed_regulation_name: String,
+    /// 車両の損壊程度コード
+    pub vehicle_damage_code: VehicleDamageCode,
+    /// 車両の損壊程度名
+    pub vehicle_damage_name: String,
+    /// エアバッグの装備コード
+    pub airbag_code: AirbagCode,
+    /// エアバッグの装備名
+    pub airbag_name: String,
+    /// サイドエアバッグの装備コード
+    pub side_airbag_code: SideAirbagCode,
+    /// サイドエアバッグの装備名
+    pub side_airbag_name: String,
+    /// 人身損傷程度コード
+    pub injury_code: InjuryCode,
+    /// 人身損傷程度名
+    pub injury_name: String,
+    /// 運転練習の方法コード
+    pub driving_practice_code: DrivingPracticeCode,
+    /// 運転練習の方法名
+    pub driving_practice_name: String,
+    /// 車両の衝突部位
+    pub collision_part: String,
+    /// 認知機能検査経過日数
+    pub cognitive_days: i32,
+}
+
+/// 交通事故当事者以外関与者
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvolvedPerson {
+    /// 交通事故当事者以外関与者ID
+    pub id: Uuid,
+    /// 補充票番号
+    pub sub_number: i32,
+    /// 当事者種別コード
+    pub party_code: PartyCode,
+    /// 当事者種別名
+    pub party_name: String,
+    /// 用途別コード
+    pub purpose_code: Option<PurposeCode>,
+    /// 用途別名
+    pub purpose_name: Option<String>,
+    /// 車両形状コード
+    pub vehicle_type_code: Option<VehicleTypeCode>,
+    /// 車両形状名
+    pub vehicle_type_name: Option<String>,
+    /// 乗車別コード
+    pub riding_type_code: RidingTypeCode,
+    /// 乗車別名
+    pub riding_type_name: String,
+    /// 乗車等区分コード
+    pub riding_class_code: RidingClassCode,
+    /// 乗車等区分名
+    pub riding_class_name: String,
+    /// サポカーコード
+    pub support_car_code: SupportCarCode,
+    /// サポカー名
+    pub support_car_name: String,
+    /// エアバッグの装備コード
+    pub airbag_code: AirbagCode,
+    /// エアバッグの装備名
+    pub airbag_name: String,
+    /// サイドエアバッグの装備コード
+    pub side_airbag_code: SideAirbagCode,
+    /// サイドエアバッグの装備名
+    pub side_airbag_name: String,
+    /// 人身損傷程度コード
+    pub injury_code: InjuryCode,
+    /// 人身損傷程度名
+    pub injury_name: String,
+    /// 車両の衝突部位
+    pub collision_part: Option<String>,
+    /// 車両の損壊程度コード
+    pub vehicle_damage_code: Option<VehicleDamageCode>,
+    /// 車両の損壊程度名
+    pub vehicle_damage_name: Option<String>,
+}
+
+/// 高速道路交通事故詳細
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HighwayDetail {
+    /// 上下線コード
+    pub up_down_code: UpDownCode,
+    /// 上下線名
+    pub up_down_name: Option<String>,
+    /// キロポスト（キロメートル）
+    pub kilopost_km: i32,
+    /// キロポスト（メートル）
+    pub kilopost_m: i32,
+    /// 道路構造コード
+    pub road_structure_code: RoadStructureCode,
+    /// 道路構造名
+    pub road_structure_name: Option<String>,
+    /// 車線コード（当事者A）
+    pub lane_a_code: LaneCode,
+    /// 車線名（当事者A）
+    pub lane_a_name: Option<String>,
+    /// 車線コード（当事者B）
+    pub lane_b_code: LaneCode,
+    /// 車線名（当事者B）
+    pub lane_b_name: Option<String>,
+}
+
+/// コード表の行
+#[derive(Debug, sqlx::FromRow, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeRow {
+    /// コード
+    ///
+    /// 警察署テーブルの場合は本票の都道府県コードと警察署コードを連結したコード、
+    /// 路線テーブルの場合は路線コードの範囲の下限とする。
+    pub code: String,
+    /// 名前
+    pub name: String,
+    /// JIS規格の都道府県コード（都道府県テーブルのみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jis_code: Option<String>,
+    /// 都道府県コード
+    ///
+    /// 警察署テーブルの場合は本票の都道府県コード、市区町村テーブルの場合はJIS規格の都道府県コードとする。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefecture_code: Option<String>,
+    /// 路線コードの範囲の上限（路線テーブルのみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upper_code: Option<String>,
+}
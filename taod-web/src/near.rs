@@ -0,0 +1,265 @@
+use sqlx::{Postgres, QueryBuilder};
+
+use code_tables::Language;
+use geometries::Srid;
+
+use crate::filters::{invalid_query, AccidentFilter, DEFAULT_PER_PAGE, MAX_PER_PAGE};
+use crate::handlers::{accident_columns_sql, AppErrorResponse, ACCIDENT_FROM_SQL};
+
+/// 既定の検索半径（メートル）
+pub const DEFAULT_NEAR_RADIUS: f64 = 300.0;
+
+/// 最大の検索半径（メートル）
+pub const MAX_NEAR_RADIUS: f64 = 10_000.0;
+
+/// 緯度1度あたりの距離の下限（メートル）
+///
+/// 赤道付近の子午線方向の1度の長さ（約110,574m）より小さい値とすることで、
+/// 度単位に換算した検索半径が、メートル単位の検索半径より必ず大きくなるようにする。
+const MIN_METERS_PER_DEGREE: f64 = 110_000.0;
+
+/// 度単位の検索半径を計算するときに使用する緯度の上限
+const MAX_SEARCH_LATITUDE: f64 = 89.0;
+
+/// 地点周辺の交通事故検索クエリパラメータ
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize)]
+pub struct NearParams {
+    /// 緯度
+    pub lat: Option<f64>,
+    /// 経度
+    pub lon: Option<f64>,
+    /// 検索半径（メートル）
+    pub radius: Option<f64>,
+    /// 最大の取得件数
+    pub limit: Option<u32>,
+}
+
+/// 地点周辺の交通事故の検索条件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Near {
+    /// 緯度
+    pub lat: f64,
+    /// 経度
+    pub lon: f64,
+    /// 検索半径（メートル）
+    pub radius: f64,
+    /// 最大の取得件数
+    pub limit: u32,
+}
+
+impl TryFrom<NearParams> for Near {
+    type Error = AppErrorResponse;
+
+    fn try_from(params: NearParams) -> Result<Self, Self::Error> {
+        let lat = params.lat.ok_or_else(|| {
+            invalid_query("latを指定してください。".into(), "lat is required.".into())
+        })?;
+        if !(-90.0..=90.0).contains(&lat) {
+            return Err(invalid_query(
+                format!("latには-90から90までの値を指定してください({})。", lat).into(),
+                format!("lat must be between -90 and 90 ({}).", lat).into(),
+            ));
+        }
+        let lon = params.lon.ok_or_else(|| {
+            invalid_query("lonを指定してください。".into(), "lon is required.".into())
+        })?;
+        if !(-180.0..=180.0).contains(&lon) {
+            return Err(invalid_query(
+                format!("lonには-180から180までの値を指定してください({})。", lon).into(),
+                format!("lon must be between -180 and 180 ({}).", lon).into(),
+            ));
+        }
+        let radius = params.radius.unwrap_or(DEFAULT_NEAR_RADIUS);
+        if !(radius > 0.0 && radius <= MAX_NEAR_RADIUS) {
+            return Err(invalid_query(
+                format!(
+                    "radiusには0より大きく{}以下の値をメートル単位で指定してください({})。",
+                    MAX_NEAR_RADIUS, radius
+                )
+                .into(),
+                format!(
+                    "radius must be greater than 0 and at most {} meters ({}).",
+                    MAX_NEAR_RADIUS, radius
+                )
+                .into(),
+            ));
+        }
+        let limit = params.limit.unwrap_or(DEFAULT_PER_PAGE);
+        if limit == 0 || MAX_PER_PAGE < limit {
+            return Err(invalid_query(
+                format!("limitには1から{}までの値を指定してください。", MAX_PER_PAGE).into(),
+                format!("limit must be between 1 and {}.", MAX_PER_PAGE).into(),
+            ));
+        }
+
+        Ok(Self {
+            lat,
+            lon,
+            radius,
+            limit,
+        })
+    }
+}
+
+impl Near {
+    /// 検索半径を、空間インデックスで絞り込むための度単位の半径に換算する。
+    ///
+    /// 検索範囲の最も極に近い緯度で経度1度あたりの距離を計算するため、換算した半径の
+    /// 範囲は、メートル単位の検索半径の範囲を必ず含む。
+    ///
+    /// # 戻り値
+    ///
+    /// 度単位の検索半径
+    pub fn radius_degrees(&self) -> f64 {
+        let lat_degrees = self.radius / MIN_METERS_PER_DEGREE;
+        let lat = (self.lat.abs() + lat_degrees).min(MAX_SEARCH_LATITUDE);
+
+        lat_degrees / lat.to_radians().cos()
+    }
+}
+
+/// 地点周辺の交通事故を、地点から近い順に取得するクエリを構築する。
+///
+/// 交通事故の発生場所の空間インデックス（`idx_accidents_location`）を使用するため、
+/// 度単位の検索半径で発生場所の矩形を絞り込んだ後に、測地線距離で絞り込む。
+///
+/// # 引数
+///
+/// * `near` - 地点周辺の交通事故の検索条件
+/// * `filter` - 交通事故の絞り込み条件
+/// * `language` - コード表の名前の言語
+/// * `srid` - 交通事故の発生場所の空間参照系
+///
+/// # 戻り値
+///
+/// 交通事故と地点からの距離（`distance`、メートル）を返すクエリビルダー
+pub fn near_query(
+    near: &Near,
+    filter: &AccidentFilter,
+    language: Language,
+    srid: Srid,
+) -> QueryBuilder<'static, Postgres> {
+    // 距離は、空間参照系を変換する前のJGD2011の発生場所で計算し、出力する発生場所のみを変換する
+    let mut builder = QueryBuilder::new(accident_columns_sql(language, srid));
+    builder.push(", ST_Distance(a.location::geography, ");
+    push_point(&mut builder, near);
+    builder.push("::geography) distance");
+    builder.push(ACCIDENT_FROM_SQL);
+    builder.push(" WHERE a.location && ST_Expand(");
+    push_point(&mut builder, near);
+    builder.push(", ");
+    builder.push_bind(near.radius_degrees());
+    builder.push(") AND ST_DWithin(a.location::geography, ");
+    push_point(&mut builder, near);
+    builder.push("::geography, ");
+    builder.push_bind(near.radius);
+    builder.push(")");
+    filter.push_conditions(&mut builder);
+    builder.push(" ORDER BY distance, a.id LIMIT ");
+    builder.push_bind(near.limit as i64);
+
+    builder
+}
+
+/// 地点のジオメトリをクエリビルダーに追加する。
+fn push_point(builder: &mut QueryBuilder<'static, Postgres>, near: &Near) {
+    builder.push("ST_SetSRID(ST_MakePoint(");
+    builder.push_bind(near.lon);
+    builder.push(", ");
+    builder.push_bind(near.lat);
+    builder.push(format!("), {})", Srid::JGD2011));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn near_ok() {
+        let params = NearParams {
+            lat: Some(35.681),
+            lon: Some(139.767),
+            ..Default::default()
+        };
+        let near = Near::try_from(params).unwrap();
+        assert_eq!(near.radius, DEFAULT_NEAR_RADIUS);
+        assert_eq!(near.limit, DEFAULT_PER_PAGE);
+
+        // 度単位の検索半径は、緯度方向及び経度方向の検索半径を含む
+        let degrees = near.radius_degrees();
+        assert!(300.0 / 110_574.0 < degrees);
+        assert!(300.0 / (111_320.0 * 35.681_f64.to_radians().cos()) < degrees);
+        assert!(degrees < 0.01);
+    }
+
+    #[test]
+    fn near_fail() {
+        let params = [
+            NearParams {
+                lon: Some(139.767),
+                ..Default::default()
+            },
+            NearParams {
+                lat: Some(91.0),
+                lon: Some(139.767),
+                ..Default::default()
+            },
+            NearParams {
+                lat: Some(35.681),
+                lon: Some(139.767),
+                radius: Some(0.0),
+                ..Default::default()
+            },
+            NearParams {
+                lat: Some(35.681),
+                lon: Some(139.767),
+                radius: Some(MAX_NEAR_RADIUS + 1.0),
+                ..Default::default()
+            },
+            NearParams {
+                lat: Some(35.681),
+                lon: Some(139.767),
+                limit: Some(0),
+                ..Default::default()
+            },
+        ];
+        for params in params {
+            assert!(Near::try_from(params).is_err(), "{:?}", params);
+        }
+    }
+
+    #[test]
+    fn near_query_ok() {
+        let near = Near {
+            lat: 35.681,
+            lon: 139.767,
+            radius: 300.0,
+            limit: 10,
+        };
+        let builder = near_query(
+            &near,
+            &AccidentFilter::default(),
+            Language::Ja,
+            Srid::JGD2011,
+        );
+        let sql = builder.sql();
+        assert!(sql.contains(
+            "a.location location, ST_Distance(a.location::geography, \
+            ST_SetSRID(ST_MakePoint($1, $2), 6668)::geography) distance\n    FROM accidents a"
+        ));
+        assert!(sql.ends_with(
+            " WHERE a.location && ST_Expand(ST_SetSRID(ST_MakePoint($3, $4), 6668), $5) \
+            AND ST_DWithin(a.location::geography, ST_SetSRID(ST_MakePoint($6, $7), 6668)::geography, $8) \
+            ORDER BY distance, a.id LIMIT $9"
+        ));
+
+        // 距離は空間参照系を変換する前の発生場所で計算し、出力する発生場所のみを変換
+        let srid = Srid::try_from(6677).unwrap();
+        let builder = near_query(&near, &AccidentFilter::default(), Language::Ja, srid);
+        let sql = builder.sql();
+        assert!(sql.contains(
+            "ST_Transform(a.location, 6677) location, ST_Distance(a.location::geography, "
+        ));
+        assert_eq!(sql.matches("ST_Transform").count(), 1);
+    }
+}
@@ -30,6 +30,11 @@ pub struct WebAppSettings {
     /// 緯度1度は、約111,120mである。この場合、111,120 * 0.000143304 = 15.92mとなり、これだけ当該タイルを
     /// 上下左右に広げた範囲で交通事故を取得する。
     pub accident_buffer_ratio: f64,
+
+    /// 交通事故集計分割数
+    /// 交通事故表示最小ズームレベル未満のタイルでは、タイルの1辺をこの数で分割したグリッドごとに
+    /// 交通事故を集計した結果を返す。
+    pub accident_cluster_division: u32,
 }
 
 /// Webアプリ運用環境
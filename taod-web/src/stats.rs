@@ -0,0 +1,245 @@
+use sqlx::{Postgres, QueryBuilder};
+
+use code_tables::Language;
+
+use crate::filters::{AccidentFilter, POLICE_STATION_CODE_SQL};
+use crate::language::name_column;
+
+/// 交通事故統計の集計単位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsGroupBy {
+    /// 都道府県（JIS規格の都道府県コード）
+    Prefecture,
+    /// 市区町村
+    City,
+    /// 警察署
+    PoliceStation,
+    /// 発生年月（YYYY-MM）
+    Month,
+    /// 曜日
+    Weekday,
+    /// 発生時（00から23）
+    Hour,
+    /// 昼夜
+    DayNight,
+    /// 天候
+    Weather,
+    /// 路面状態
+    SurfaceCondition,
+    /// 事故内容
+    AccidentDetail,
+    /// 事故類型
+    AccidentType,
+    /// 道路形状
+    RoadModel,
+}
+
+impl StatsGroupBy {
+    /// 集計単位のコードを返すSQLの式を返す。
+    ///
+    /// # 戻り値
+    ///
+    /// 集計単位のコードを返すSQLの式
+    fn code_expression(&self) -> &'static str {
+        match self {
+            Self::Prefecture => "n.jis_code::text",
+            Self::City => "a.city_jis_code::text",
+            Self::PoliceStation => POLICE_STATION_CODE_SQL,
+            Self::Month => "to_char(a.occurred_at AT TIME ZONE 'Asia/Tokyo', 'YYYY-MM')",
+            Self::Weekday => "a.week_code::text",
+            Self::Hour => "to_char(a.occurred_at AT TIME ZONE 'Asia/Tokyo', 'HH24')",
+            Self::DayNight => "a.day_night_code::text",
+            Self::Weather => "a.weather_code::text",
+            Self::SurfaceCondition => "a.surface_condition_code::text",
+            Self::AccidentDetail => "a.accident_detail_code::text",
+            Self::AccidentType => "a.accident_type_code::text",
+            Self::RoadModel => "a.road_model_code::text",
+        }
+    }
+
+    /// 集計単位の名前を返すSQLの式と、名前を取得するために結合するテーブルを返す。
+    ///
+    /// # 戻り値
+    ///
+    /// 名前を返すSQLの式と結合句、名前がない集計単位の場合は`None`
+    fn name_expression(&self) -> Option<(&'static str, &'static str)> {
+        match self {
+            // 本票の都道府県コードは北海道を方面ごとに分けているため、方面を除いた名前で
+            // JIS規格の都道府県コードごとに集計
+            Self::Prefecture => Some((
+                "n.name",
+                "INNER JOIN (SELECT code, jis_code, \
+                regexp_replace(name, '（.+方面）$', '') name, \
+                regexp_replace(name_en, ' \\(.+\\)$', '') name_en \
+                FROM prefectures) n ON a.prefecture_code = n.code",
+            )),
+            Self::City => Some((
+                "n.city_name",
+                "INNER JOIN cities n ON a.city_jis_code = n.city_jis_code",
+            )),
+            Self::PoliceStation => Some((
+                "n.police_station_name",
+                "INNER JOIN police_stations n ON a.prefecture_code = n.prefecture_code \
+                AND a.police_station_code = n.police_station_code",
+            )),
+            Self::Month | Self::Hour => None,
+            Self::Weekday => Some(("n.name", "INNER JOIN weeks n ON a.week_code = n.code")),
+            Self::DayNight => Some((
+                "n.name",
+                "INNER JOIN day_nights n ON a.day_night_code = n.code",
+            )),
+            Self::Weather => Some(("n.name", "INNER JOIN weathers n ON a.weather_code = n.code")),
+            Self::SurfaceCondition => Some((
+                "n.name",
+                "INNER JOIN surface_conditions n ON a.surface_condition_code = n.code",
+            )),
+            Self::AccidentDetail => Some((
+                "n.name",
+                "INNER JOIN accident_details n ON a.accident_detail_code = n.code",
+            )),
+            Self::AccidentType => Some((
+                "n.name",
+                "INNER JOIN accident_types n ON a.accident_type_code = n.code",
+            )),
+            Self::RoadModel => Some((
+                "n.name",
+                "INNER JOIN road_models n ON a.road_model_code = n.code",
+            )),
+        }
+    }
+}
+
+/// 交通事故統計クエリパラメータ
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+pub struct StatsParams {
+    /// 集計単位
+    #[serde(rename = "groupBy", alias = "group_by")]
+    pub group_by: StatsGroupBy,
+}
+
+/// 交通事故を集計するクエリを構築する。
+///
+/// # 引数
+///
+/// * `group_by` - 集計単位
+/// * `filter` - 交通事故の絞り込み条件
+/// * `language` - 集計単位の名前の言語
+///
+/// # 戻り値
+///
+/// 集計単位のコード（`code`）、名前（`name`）、交通事故の件数（`number_of_accidents`）、
+/// 死者数（`number_of_deaths`）及び負傷者数（`number_of_injuries`）を返すクエリビルダー
+pub fn stats_query(
+    group_by: StatsGroupBy,
+    filter: &AccidentFilter,
+    language: Language,
+) -> QueryBuilder<'static, Postgres> {
+    let (name, join) = match group_by.name_expression() {
+        // 市区町村と警察署は英語の名前を持たない
+        Some((name, join))
+            if matches!(group_by, StatsGroupBy::City | StatsGroupBy::PoliceStation) =>
+        {
+            (name.to_string(), join)
+        }
+        Some((name, join)) => (name_column(name, language), join),
+        None => (String::from("NULL::text"), ""),
+    };
+    let mut builder = QueryBuilder::new(format!(
+        "SELECT {} code, {} name, \
+        COUNT(*) number_of_accidents, \
+        SUM(a.number_of_deaths) number_of_deaths, \
+        SUM(a.number_of_injuries) number_of_injuries \
+        FROM accidents a {} WHERE TRUE",
+        group_by.code_expression(),
+        name,
+        join
+    ));
+    filter.push_conditions(&mut builder);
+    builder.push(" GROUP BY 1, 2 ORDER BY 1");
+
+    builder
+}
+
+#[cfg(test)]
+mod tests {
+    use code_tables::{PoliceStationCode, WeatherCode};
+
+    use super::*;
+
+    #[test]
+    fn stats_params_ok() {
+        let params: StatsParams = serde_json::from_str(r#"{"groupBy": "police_station"}"#).unwrap();
+        assert_eq!(params.group_by, StatsGroupBy::PoliceStation);
+        let params: StatsParams = serde_json::from_str(r#"{"group_by": "hour"}"#).unwrap();
+        assert_eq!(params.group_by, StatsGroupBy::Hour);
+        assert!(serde_json::from_str::<StatsParams>(r#"{"groupBy": "unknown"}"#).is_err());
+    }
+
+    #[test]
+    fn stats_query_ok() {
+        let filter = AccidentFilter {
+            weather_codes: Some(vec![WeatherCode::try_from("1").unwrap()]),
+            ..Default::default()
+        };
+        let builder = stats_query(StatsGroupBy::Weather, &filter, Language::Ja);
+        assert_eq!(
+            builder.sql(),
+            "SELECT a.weather_code::text code, n.name name, \
+            COUNT(*) number_of_accidents, \
+            SUM(a.number_of_deaths) number_of_deaths, \
+            SUM(a.number_of_injuries) number_of_injuries \
+            FROM accidents a INNER JOIN weathers n ON a.weather_code = n.code WHERE TRUE \
+            AND a.weather_code::text = ANY($1) \
+            GROUP BY 1, 2 ORDER BY 1"
+        );
+
+        let builder = stats_query(
+            StatsGroupBy::Month,
+            &AccidentFilter::default(),
+            Language::En,
+        );
+        assert!(builder.sql().contains("NULL::text name"));
+
+        let builder = stats_query(StatsGroupBy::Weather, &filter, Language::En);
+        assert!(builder.sql().contains("COALESCE(n.name_en, n.name) name"));
+        let builder = stats_query(StatsGroupBy::City, &filter, Language::En);
+        assert!(builder.sql().contains("n.city_name name"));
+    }
+
+    #[test]
+    fn stats_query_code_ok() {
+        // 都道府県はJIS規格の都道府県コードごとに集計
+        let builder = stats_query(
+            StatsGroupBy::Prefecture,
+            &AccidentFilter::default(),
+            Language::En,
+        );
+        assert_eq!(
+            builder.sql(),
+            "SELECT n.jis_code::text code, COALESCE(n.name_en, n.name) name, \
+            COUNT(*) number_of_accidents, \
+            SUM(a.number_of_deaths) number_of_deaths, \
+            SUM(a.number_of_injuries) number_of_injuries \
+            FROM accidents a INNER JOIN (SELECT code, jis_code, \
+            regexp_replace(name, '（.+方面）$', '') name, \
+            regexp_replace(name_en, ' \\(.+\\)$', '') name_en \
+            FROM prefectures) n ON a.prefecture_code = n.code WHERE TRUE \
+            GROUP BY 1, 2 ORDER BY 1"
+        );
+
+        // 警察署は絞り込み条件と同じ警察署コードごとに集計
+        let filter = AccidentFilter {
+            police_station_codes: Some(vec![PoliceStationCode::try_from("10059").unwrap()]),
+            ..Default::default()
+        };
+        let builder = stats_query(StatsGroupBy::PoliceStation, &filter, Language::Ja);
+        assert!(builder.sql().starts_with(&format!(
+            "SELECT {} code, n.police_station_name name",
+            POLICE_STATION_CODE_SQL
+        )));
+        assert!(builder
+            .sql()
+            .contains(&format!(" AND {} = ANY($1)", POLICE_STATION_CODE_SQL)));
+    }
+}
@@ -0,0 +1,225 @@
+use std::borrow::Cow;
+
+use geojson::{GeoJson, Geometry, Value};
+use sqlx::{Postgres, QueryBuilder};
+
+use geometries::Srid;
+
+use crate::filters::AccidentFilter;
+use crate::handlers::{AppError, AppErrorContent, AppErrorResponse};
+
+/// 最大のバッファ距離（メートル）
+pub const MAX_WITHIN_BUFFER: f64 = 10_000.0;
+
+/// 範囲内の交通事故検索クエリパラメータ
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize)]
+pub struct WithinParams {
+    /// バッファ距離（メートル）
+    pub buffer: Option<f64>,
+}
+
+/// 交通事故を検索する範囲
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithinArea {
+    /// 範囲を表現するGeoJSONのジオメトリ
+    geometry: String,
+    /// バッファ距離（メートル）
+    buffer: Option<f64>,
+}
+
+impl WithinArea {
+    /// リクエストボディのGeoJSONから、交通事故を検索する範囲を構築する。
+    ///
+    /// GeoJSONには、ジオメトリまたはジオメトリを持つフィーチャーを指定できる。
+    /// ジオメトリの種類は、`Polygon`、`MultiPolygon`、`LineString`及び`MultiLineString`で、
+    /// `LineString`と`MultiLineString`の場合はバッファ距離の指定を必須とする。
+    ///
+    /// # 引数
+    ///
+    /// * `body` - リクエストボディ
+    /// * `params` - 範囲内の交通事故検索クエリパラメータ
+    ///
+    /// # 戻り値
+    ///
+    /// 交通事故を検索する範囲
+    pub fn new(body: &str, params: WithinParams) -> Result<Self, AppErrorResponse> {
+        let geojson = body.parse::<GeoJson>().map_err(|e| {
+            invalid_geometry(
+                format!("GeoJSONを解析できません。{}", e).into(),
+                format!("Failed to parse GeoJSON. {}", e).into(),
+            )
+        })?;
+        let geometry = match geojson {
+            GeoJson::Geometry(geometry) => geometry,
+            GeoJson::Feature(feature) => feature.geometry.ok_or_else(|| {
+                invalid_geometry(
+                    "フィーチャーにジオメトリがありません。".into(),
+                    "The feature has no geometry.".into(),
+                )
+            })?,
+            GeoJson::FeatureCollection(_) => {
+                return Err(invalid_geometry(
+                    "ジオメトリまたはフィーチャーを指定してください。".into(),
+                    "Specify a geometry or a feature.".into(),
+                ))
+            }
+        };
+        if let Some(buffer) = params.buffer {
+            if !(buffer > 0.0 && buffer <= MAX_WITHIN_BUFFER) {
+                return Err(invalid_geometry(
+                    format!(
+                        "bufferには0より大きく{}以下の値をメートル単位で指定してください({})。",
+                        MAX_WITHIN_BUFFER, buffer
+                    )
+                    .into(),
+                    format!(
+                        "buffer must be greater than 0 and at most {} meters ({}).",
+                        MAX_WITHIN_BUFFER, buffer
+                    )
+                    .into(),
+                ));
+            }
+        }
+        match geometry.value {
+            Value::Polygon(_) | Value::MultiPolygon(_) => {}
+            Value::LineString(_) | Value::MultiLineString(_) => {
+                if params.buffer.is_none() {
+                    return Err(invalid_geometry(
+                        "LineStringまたはMultiLineStringを指定した場合は、bufferを指定してください。"
+                            .into(),
+                        "buffer is required for LineString and MultiLineString.".into(),
+                    ));
+                }
+            }
+            _ => {
+                return Err(invalid_geometry(
+                    format!(
+                        "Polygon、MultiPolygon、LineStringまたはMultiLineStringを指定してください({})。",
+                        geometry.value.type_name()
+                    )
+                    .into(),
+                    format!(
+                        "Specify a Polygon, MultiPolygon, LineString or MultiLineString ({}).",
+                        geometry.value.type_name()
+                    )
+                    .into(),
+                ))
+            }
+        }
+
+        Ok(Self {
+            geometry: Geometry::new(geometry.value).to_string(),
+            buffer: params.buffer,
+        })
+    }
+
+    /// 範囲に交通事故の発生場所が含まれる条件を、`WHERE`句としてクエリビルダーに追加する。
+    ///
+    /// 交通事故の発生場所の空間インデックスを使用するため、`ST_Intersects`で判定する。
+    ///
+    /// # 引数
+    ///
+    /// * `builder` - クエリビルダー
+    /// * `filter` - 交通事故の絞り込み条件
+    pub fn push_conditions(
+        &self,
+        builder: &mut QueryBuilder<'_, Postgres>,
+        filter: &AccidentFilter,
+    ) {
+        builder.push(" WHERE ST_Intersects(");
+        match self.buffer {
+            // 地理型からジオメトリ型に戻すとSRIDがWGS84（4326）になるため、交通事故の発生場所と
+            // 同じ空間参照系（JGD2011）を設定する
+            Some(buffer) => {
+                builder.push("ST_SetSRID(ST_Buffer(");
+                self.push_geometry(builder);
+                builder.push("::geography, ");
+                builder.push_bind(buffer);
+                builder.push(format!(")::geometry, {})", Srid::JGD2011));
+            }
+            None => self.push_geometry(builder),
+        }
+        builder.push(", a.location)");
+        filter.push_conditions(builder);
+    }
+
+    /// 範囲のジオメトリをクエリビルダーに追加する。
+    fn push_geometry(&self, builder: &mut QueryBuilder<'_, Postgres>) {
+        builder.push("ST_SetSRID(ST_GeomFromGeoJSON(");
+        builder.push_bind(self.geometry.clone());
+        builder.push(format!("), {})", Srid::JGD2011));
+    }
+}
+
+/// ジオメトリエラーを返す。
+fn invalid_geometry(message: Cow<'static, str>, message_en: Cow<'static, str>) -> AppErrorResponse {
+    AppErrorResponse::BadRequest(AppErrorContent {
+        app_error: AppError::InvalidGeometry,
+        message,
+        message_en,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLYGON: &str = r#"{"type": "Polygon", "coordinates": [[[139.0, 35.0], [140.0, 35.0], [140.0, 36.0], [139.0, 35.0]]]}"#;
+
+    const LINE_STRING: &str =
+        r#"{"type": "LineString", "coordinates": [[139.0, 35.0], [140.0, 36.0]]}"#;
+
+    #[test]
+    fn within_area_ok() {
+        let area = WithinArea::new(POLYGON, WithinParams::default()).unwrap();
+        assert_eq!(area.buffer, None);
+        assert!(area.geometry.contains(r#""type":"Polygon""#));
+
+        let feature = format!(
+            r#"{{"type": "Feature", "properties": {{"name": "学区"}}, "geometry": {}}}"#,
+            LINE_STRING
+        );
+        let params = WithinParams { buffer: Some(50.0) };
+        let area = WithinArea::new(&feature, params).unwrap();
+        assert_eq!(area.buffer, Some(50.0));
+        assert!(area.geometry.contains(r#""type":"LineString""#));
+    }
+
+    #[test]
+    fn within_area_fail() {
+        let bodies = [
+            (r#"{"type": "Polygon"}"#, None),
+            (r#"{"type": "Point", "coordinates": [139.0, 35.0]}"#, None),
+            (r#"{"type": "FeatureCollection", "features": []}"#, None),
+            (
+                r#"{"type": "Feature", "properties": null, "geometry": null}"#,
+                None,
+            ),
+            (LINE_STRING, None),
+            (LINE_STRING, Some(0.0)),
+            (POLYGON, Some(MAX_WITHIN_BUFFER + 1.0)),
+        ];
+        for (body, buffer) in bodies {
+            assert!(
+                WithinArea::new(body, WithinParams { buffer }).is_err(),
+                "{} {:?}",
+                body,
+                buffer
+            );
+        }
+    }
+
+    #[test]
+    fn push_conditions_ok() {
+        let params = WithinParams { buffer: Some(50.0) };
+        let area = WithinArea::new(LINE_STRING, params).unwrap();
+        let mut builder = QueryBuilder::<Postgres>::new("SELECT a.id FROM accidents a");
+        area.push_conditions(&mut builder, &AccidentFilter::default());
+        assert_eq!(
+            builder.sql(),
+            "SELECT a.id FROM accidents a WHERE ST_Intersects(\
+            ST_SetSRID(ST_Buffer(ST_SetSRID(ST_GeomFromGeoJSON($1), 6668)::geography, $2)::geometry, 6668), \
+            a.location)"
+        );
+    }
+}